@@ -0,0 +1,258 @@
+use super::{write_file, AgentProject};
+use crate::core::error::{FridaMgrError, Result};
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Directory next to the agent entry that receives generated hook modules.
+pub const HOOKS_DIR: &str = "hooks";
+
+/// A hook request parsed from `--java Class!m1,m2` or `--native module!sym1,sym2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookTarget {
+    Java {
+        class: String,
+        methods: Vec<String>,
+    },
+    Native {
+        module: String,
+        symbols: Vec<String>,
+    },
+}
+
+impl HookTarget {
+    pub fn parse_java(spec: &str) -> Result<Self> {
+        let (class, methods) = split_spec(spec, "--java com.example.Crypto!encrypt,decrypt")?;
+        Ok(HookTarget::Java { class, methods })
+    }
+
+    pub fn parse_native(spec: &str) -> Result<Self> {
+        let (module, symbols) = split_spec(spec, "--native libfoo.so!sub_1234")?;
+        Ok(HookTarget::Native { module, symbols })
+    }
+
+    /// Default module name for the generated file: the Java class's simple name or the
+    /// native module's stem, lowercased.
+    pub fn file_stem(&self) -> String {
+        let raw = match self {
+            HookTarget::Java { class, .. } => class.rsplit('.').next().unwrap_or(class),
+            HookTarget::Native { module, .. } => module.split('.').next().unwrap_or(module),
+        };
+        sanitize_stem(raw)
+    }
+}
+
+fn split_spec(spec: &str, example: &str) -> Result<(String, Vec<String>)> {
+    let invalid = || {
+        FridaMgrError::Config(format!(
+            "Invalid hook target '{}' (expected e.g. {})",
+            spec, example
+        ))
+    };
+
+    let (owner, names) = spec.split_once('!').ok_or_else(invalid)?;
+    let owner = owner.trim();
+    let names: Vec<String> = names
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .collect();
+    if owner.is_empty() || names.is_empty() {
+        return Err(invalid());
+    }
+    Ok((owner.to_string(), names))
+}
+
+fn sanitize_stem(raw: &str) -> String {
+    let stem: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string();
+    if stem.is_empty() {
+        "hooks".to_string()
+    } else {
+        stem
+    }
+}
+
+/// `sub_1234` (IDA-style) and `0x1234` name module offsets; anything else is an export.
+fn native_offset(symbol: &str) -> Option<String> {
+    let hex = symbol
+        .strip_prefix("sub_")
+        .or_else(|| symbol.strip_prefix("0x"))?;
+    if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("0x{}", hex.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// TypeScript module with logging interceptors for every target.
+pub fn render_hooks(targets: &[HookTarget]) -> String {
+    let specs: Vec<String> = targets
+        .iter()
+        .map(|t| match t {
+            HookTarget::Java { class, methods } => {
+                format!("--java {}!{}", class, methods.join(","))
+            }
+            HookTarget::Native { module, symbols } => {
+                format!("--native {}!{}", module, symbols.join(","))
+            }
+        })
+        .collect();
+
+    let mut out = format!(
+        "/// <reference path=\"../env.d.ts\" />\n// Generated by `frida-mgr codegen {}`.\n// Edit freely; rerun with --force to regenerate.\n",
+        specs.join(" ")
+    );
+
+    if targets.iter().any(|t| matches!(t, HookTarget::Java { .. })) {
+        out.push_str("\nconst JavaApi = (globalThis as any).Java;\n");
+    }
+
+    for target in targets {
+        out.push('\n');
+        match target {
+            HookTarget::Java { class, methods } => render_java(&mut out, class, methods),
+            HookTarget::Native { module, symbols } => render_native(&mut out, module, symbols),
+        }
+    }
+
+    out.push_str("\nexport {};\n");
+    out
+}
+
+fn render_java(out: &mut String, class: &str, methods: &[String]) {
+    let short = class.rsplit('.').next().unwrap_or(class);
+    out.push_str(&format!(
+        r#"if (JavaApi && JavaApi.available) {{
+  JavaApi.perform(() => {{
+    const clazz = JavaApi.use("{class}");
+"#
+    ));
+    for method in methods {
+        out.push_str(&format!(
+            r#"
+    clazz["{method}"].overloads.forEach((overload: any) => {{
+      overload.implementation = function (this: any, ...args: any[]) {{
+        const tag = `[{short}.{method}(${{overload.argumentTypes.map((t: any) => t.className).join(", ")}})]`;
+        console.log(`${{tag}} args: ${{args.map((a) => String(a)).join(", ")}}`);
+        const retval = overload.apply(this, args);
+        console.log(`${{tag}} retval: ${{retval}}`);
+        return retval;
+      }};
+    }});
+"#
+        ));
+    }
+    out.push_str("  });\n}\n");
+}
+
+fn render_native(out: &mut String, module: &str, symbols: &[String]) {
+    let stem = module.split('.').next().unwrap_or(module);
+    let var = format!("{}Module", sanitize_stem(stem).replace('_', ""));
+    out.push_str(&format!(
+        "// The module must already be loaded when the agent starts.\nconst {var} = Process.getModuleByName(\"{module}\");\n"
+    ));
+    for symbol in symbols {
+        let address = match native_offset(symbol) {
+            Some(offset) => format!("{var}.base.add({offset})"),
+            None => format!("{var}.getExportByName(\"{symbol}\")"),
+        };
+        out.push_str(&format!(
+            r#"
+Interceptor.attach({address}, {{
+  onEnter(args) {{
+    console.log(`[{module}!{symbol}] onEnter args: ${{args[0]}}, ${{args[1]}}, ${{args[2]}}, ${{args[3]}}`);
+  }},
+  onLeave(retval) {{
+    console.log(`[{module}!{symbol}] onLeave retval: ${{retval}}`);
+  }},
+}});
+"#
+        ));
+    }
+}
+
+/// Write `hooks/<stem>.ts` next to the agent entry and import it from the entry so the
+/// next `agent build` picks it up. Returns the written path.
+pub async fn write_hooks(
+    agent: &AgentProject,
+    stem: &str,
+    content: String,
+    force: bool,
+) -> Result<PathBuf> {
+    let src_dir = agent.entry_path.parent().unwrap_or(&agent.agent_dir);
+    let path = src_dir.join(HOOKS_DIR).join(format!("{}.ts", stem));
+    write_file(&path, content, force).await?;
+
+    if agent.entry_path.is_file() {
+        let import = format!("import \"./{}/{}\";", HOOKS_DIR, stem);
+        let entry = fs::read_to_string(&agent.entry_path).await?;
+        if !entry.lines().any(|line| line.trim() == import) {
+            let mut updated = entry;
+            if !updated.is_empty() && !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            updated.push_str(&import);
+            updated.push('\n');
+            fs::write(&agent.entry_path, updated).await?;
+        }
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hook_specs() {
+        assert_eq!(
+            HookTarget::parse_java("com.example.Crypto!encrypt, decrypt").unwrap(),
+            HookTarget::Java {
+                class: "com.example.Crypto".to_string(),
+                methods: vec!["encrypt".to_string(), "decrypt".to_string()],
+            }
+        );
+        assert!(HookTarget::parse_java("com.example.Crypto").is_err());
+        assert!(HookTarget::parse_native("libfoo.so!").is_err());
+
+        let native = HookTarget::parse_native("libfoo.so!sub_1234,open").unwrap();
+        assert_eq!(native.file_stem(), "libfoo");
+        assert_eq!(
+            HookTarget::parse_java("com.example.Crypto!a")
+                .unwrap()
+                .file_stem(),
+            "crypto"
+        );
+    }
+
+    #[test]
+    fn native_offsets_and_exports() {
+        assert_eq!(native_offset("sub_1A2B").as_deref(), Some("0x1a2b"));
+        assert_eq!(native_offset("0x40").as_deref(), Some("0x40"));
+        assert_eq!(native_offset("sub_init"), None);
+
+        let ts = render_hooks(&[HookTarget::parse_native("libfoo.so!sub_1234,open").unwrap()]);
+        assert!(ts.contains("libfooModule.base.add(0x1234)"));
+        assert!(ts.contains("libfooModule.getExportByName(\"open\")"));
+    }
+
+    #[test]
+    fn java_stub_hooks_every_overload() {
+        let ts = render_hooks(&[HookTarget::parse_java("com.example.Crypto!encrypt").unwrap()]);
+        assert!(ts.contains("JavaApi.use(\"com.example.Crypto\")"));
+        assert!(ts.contains("clazz[\"encrypt\"].overloads.forEach"));
+        assert!(ts.contains("[Crypto.encrypt("));
+    }
+}
@@ -1,6 +1,9 @@
+pub mod codegen;
+pub mod sourcemap;
+
 use crate::config::schema::{AgentBuildTool, AgentConfig, ProjectConfig};
 use crate::core::error::{FridaMgrError, Result};
-use crate::core::{ensure_dir_exists, resolve_path};
+use crate::core::{ensure_dir_exists, interrupt, resolve_path, ProcessExecutor};
 use colored::Colorize;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
@@ -14,6 +17,7 @@ pub struct AgentProject {
     pub entry_path: PathBuf,
     pub out_path: PathBuf,
     pub tool: AgentBuildTool,
+    pub sourcemap: bool,
 }
 
 impl AgentProject {
@@ -27,6 +31,7 @@ impl AgentProject {
             entry_path,
             out_path,
             tool: config.agent.tool.clone(),
+            sourcemap: config.agent.sourcemap,
         }
     }
 
@@ -40,6 +45,7 @@ impl AgentProject {
             entry_path,
             out_path,
             tool: config.tool.clone(),
+            sourcemap: config.sourcemap,
         }
     }
 
@@ -162,7 +168,7 @@ pub async fn build_agent(agent: &AgentProject) -> Result<PathBuf> {
         .ok_or_else(|| FridaMgrError::Config("Invalid agent.out path".to_string()))?;
     ensure_dir_exists(out_parent).await?;
 
-    let (bin_name, args) = match agent.tool {
+    let (bin_name, mut args) = match agent.tool {
         AgentBuildTool::FridaCompile => (
             "frida-compile",
             vec![
@@ -183,6 +189,10 @@ pub async fn build_agent(agent: &AgentProject) -> Result<PathBuf> {
             ],
         ),
     };
+    // frida-compile already embeds source maps in its bundle; esbuild writes `<out>.map`.
+    if agent.sourcemap && agent.tool == AgentBuildTool::Esbuild {
+        args.push("--sourcemap".to_string());
+    }
 
     let bin_path = local_node_bin(&agent.agent_dir, bin_name);
     if !bin_path.exists() {
@@ -200,13 +210,17 @@ pub async fn build_agent(agent: &AgentProject) -> Result<PathBuf> {
         agent.tool.as_str().cyan()
     );
 
-    let status = Command::new(&bin_path)
+    let mut child = Command::new(&bin_path)
         .args(&args)
         .current_dir(&agent.agent_dir)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .status()
+        .spawn()
+        .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run {}: {}", bin_name, e)))?;
+    let _guard = child.id().map(interrupt::kill_on_interrupt);
+    let status = child
+        .wait()
         .await
         .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run {}: {}", bin_name, e)))?;
 
@@ -395,6 +409,227 @@ frida-mgr spawn --agent {dir} -- --no-pause
     )
 }
 
+/// A dependency declared in the agent's package.json and the version npm installed for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentDependency {
+    pub name: String,
+    pub spec: String,
+    pub installed: Option<String>,
+}
+
+impl AgentDependency {
+    /// Whether the installed version satisfies the declared spec. Exact versions must match;
+    /// tags (`latest`) and non-semver specs (git URLs, `file:`) only require an install.
+    pub fn satisfied(&self) -> bool {
+        let Some(installed) = self.installed.as_deref() else {
+            return false;
+        };
+        let Ok(version) = semver::Version::parse(installed) else {
+            return true;
+        };
+        if let Ok(exact) = semver::Version::parse(&self.spec) {
+            return exact == version;
+        }
+        match semver::VersionReq::parse(&self.spec) {
+            Ok(req) => req.matches(&version),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Dependencies and devDependencies of the agent with their installed versions from
+/// `node_modules`. Empty when the agent has no package.json.
+pub async fn agent_dependencies(agent: &AgentProject) -> Result<Vec<AgentDependency>> {
+    let manifest = agent.agent_dir.join("package.json");
+    if !manifest.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&manifest).await?;
+    let package: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| anyhow::anyhow!(e))?;
+
+    let mut deps = Vec::new();
+    for section in ["dependencies", "devDependencies"] {
+        let Some(entries) = package.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, spec) in entries {
+            let installed_manifest = agent
+                .agent_dir
+                .join("node_modules")
+                .join(name)
+                .join("package.json");
+            let installed = match fs::read_to_string(&installed_manifest).await {
+                Ok(content) => serde_json::from_str::<serde_json::Value>(&content)
+                    .ok()
+                    .and_then(|v| v.get("version")?.as_str().map(str::to_string)),
+                Err(_) => None,
+            };
+            deps.push(AgentDependency {
+                name: name.clone(),
+                spec: spec.as_str().unwrap_or_default().to_string(),
+                installed,
+            });
+        }
+    }
+    Ok(deps)
+}
+
+/// Agent packages whose major version follows Frida's, with their first major for Frida 17
+/// (which moved to @types/frida-gum 19 and frida-compile 17).
+const FRIDA_TRACKED_PACKAGES: [(&str, u64); 2] = [("@types/frida-gum", 19), ("frida-compile", 17)];
+
+/// The pin `package` needs for Frida `frida` when `spec` (a package.json range or an
+/// installed version) resolves to a major that does not suit it. `None` when it suits, the
+/// package does not follow Frida, or either side is not semver (git URLs, `file:`).
+pub fn frida_pin_mismatch(package: &str, frida: &str, spec: &str) -> Option<String> {
+    let (_, first_for_17) = FRIDA_TRACKED_PACKAGES
+        .iter()
+        .find(|(name, _)| *name == package)?;
+    let frida = semver::Version::parse(frida).ok()?;
+    let major = highest_major(spec)?;
+    if (frida.major >= 17) == (major >= *first_for_17) {
+        return None;
+    }
+    Some(if frida.major >= 17 {
+        format!("^{}.0.0", first_for_17)
+    } else {
+        format!("^{}.0.0", first_for_17 - 1)
+    })
+}
+
+/// The newest major version npm may install for `spec`; `u64::MAX` when it is unbounded.
+fn highest_major(spec: &str) -> Option<u64> {
+    let spec = spec.trim();
+    if matches!(spec, "" | "*" | "x" | "latest") {
+        return Some(u64::MAX);
+    }
+    if let Ok(version) = semver::Version::parse(spec) {
+        return Some(version.major);
+    }
+    let req = semver::VersionReq::parse(spec).ok()?;
+    let mut highest = u64::MAX;
+    for comparator in &req.comparators {
+        let bound = match comparator.op {
+            semver::Op::Greater | semver::Op::GreaterEq => continue,
+            semver::Op::Less
+                if comparator.minor.unwrap_or(0) == 0 && comparator.patch.unwrap_or(0) == 0 =>
+            {
+                comparator.major.checked_sub(1)?
+            }
+            _ => comparator.major,
+        };
+        highest = highest.min(bound);
+    }
+    Some(highest)
+}
+
+/// A package.json pin rewritten for the project's Frida release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinUpdate {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Rewrite the agent's package.json pins of packages that follow Frida so they suit
+/// `frida`, leaving the rest of the file as written. Empty when nothing needed changing.
+pub async fn update_frida_pins(agent: &AgentProject, frida: &str) -> Result<Vec<PinUpdate>> {
+    let manifest = agent.agent_dir.join("package.json");
+    if !manifest.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&manifest).await?;
+    let (updated, updates) = rewrite_frida_pins(&content, frida);
+    if !updates.is_empty() {
+        fs::write(&manifest, updated).await?;
+    }
+    Ok(updates)
+}
+
+fn rewrite_frida_pins(package_json: &str, frida: &str) -> (String, Vec<PinUpdate>) {
+    let mut content = package_json.to_string();
+    let mut updates = Vec::new();
+    for (name, _) in FRIDA_TRACKED_PACKAGES {
+        let pattern =
+            regex::Regex::new(&format!(r#"("{}"\s*:\s*")([^"]*)(")"#, regex::escape(name)))
+                .expect("valid regex");
+        content = pattern
+            .replace_all(&content, |caps: &regex::Captures| {
+                let spec = &caps[2];
+                match frida_pin_mismatch(name, frida, spec) {
+                    Some(pin) => {
+                        updates.push(PinUpdate {
+                            name: name.to_string(),
+                            from: spec.to_string(),
+                            to: pin.clone(),
+                        });
+                        format!("{}{}{}", &caps[1], pin, &caps[3])
+                    }
+                    None => caps[0].to_string(),
+                }
+            })
+            .into_owned();
+    }
+    (content, updates)
+}
+
+/// The package manager the agent uses, judged by its lockfile; npm when there is none.
+pub fn agent_package_manager(agent: &AgentProject) -> &'static str {
+    let lockfiles = [
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lockb", "bun"),
+        ("bun.lock", "bun"),
+    ];
+    lockfiles
+        .iter()
+        .find(|(lockfile, _)| agent.agent_dir.join(lockfile).is_file())
+        .map(|(_, manager)| *manager)
+        .unwrap_or("npm")
+}
+
+/// Run `<package manager> install` in the agent directory.
+pub async fn install_agent_dependencies(agent: &AgentProject) -> Result<()> {
+    let manager = agent_package_manager(agent);
+    let program = ProcessExecutor::find_command(manager).ok_or_else(|| {
+        FridaMgrError::CommandFailed(format!(
+            "{} not found on PATH; install it or run the install in {} yourself",
+            manager,
+            agent.agent_dir.display()
+        ))
+    })?;
+
+    println!(
+        "{} Installing agent dependencies with {}...",
+        "⚙".blue().bold(),
+        manager.cyan()
+    );
+    let mut child = Command::new(&program)
+        .arg("install")
+        .current_dir(&agent.agent_dir)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run {}: {}", manager, e)))?;
+    let _guard = child.id().map(interrupt::kill_on_interrupt);
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run {}: {}", manager, e)))?;
+
+    if !status.success() {
+        return Err(FridaMgrError::CommandFailed(format!(
+            "{} install failed with exit code {:?}",
+            manager,
+            status.code()
+        )));
+    }
+    println!("{} Agent dependencies installed", "✓".green().bold());
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -415,6 +650,97 @@ mod tests {
         assert!(p.contains("\"esbuild\""));
     }
 
+    #[test]
+    fn dependency_specs_are_checked_against_installed() {
+        let dep = |spec: &str, installed: Option<&str>| AgentDependency {
+            name: "@types/frida-gum".to_string(),
+            spec: spec.to_string(),
+            installed: installed.map(str::to_string),
+        };
+        assert!(dep("latest", Some("19.0.1")).satisfied());
+        assert!(!dep("latest", None).satisfied());
+        assert!(dep("^18.7.0", Some("18.8.1")).satisfied());
+        assert!(!dep("^18.7.0", Some("19.0.1")).satisfied());
+        assert!(!dep("18.7.0", Some("18.8.1")).satisfied());
+    }
+
+    #[test]
+    fn frida_tracked_pins_follow_the_frida_major() {
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "16.6.6", "18.8.1"),
+            None
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "16.6.6", "^18.7.0"),
+            None
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "16.6.6", "<19"),
+            None
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "16.6.6", "latest").as_deref(),
+            Some("^18.0.0")
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "16.6.6", "19.0.1").as_deref(),
+            Some("^18.0.0")
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "17.2.0", "18.8.1").as_deref(),
+            Some("^19.0.0")
+        );
+        assert_eq!(
+            frida_pin_mismatch("@types/frida-gum", "17.2.0", ">=18"),
+            None
+        );
+        assert_eq!(
+            frida_pin_mismatch("frida-compile", "16.6.6", "^10.2.5"),
+            None
+        );
+        assert_eq!(
+            frida_pin_mismatch("frida-compile", "17.0.0", "^16.4.1").as_deref(),
+            Some("^17.0.0")
+        );
+        assert_eq!(frida_pin_mismatch("typescript", "17.0.0", "4.0.0"), None);
+        assert_eq!(
+            frida_pin_mismatch("frida-compile", "17.0.0", "github:frida/frida-compile"),
+            None
+        );
+    }
+
+    #[test]
+    fn rewrites_only_mismatched_pins() {
+        let package_json = r#"{
+  "devDependencies": {
+    "frida-compile": "^16.4.1",
+    "@types/frida-gum": "latest",
+    "typescript": "latest"
+  }
+}
+"#;
+        let (updated, updates) = rewrite_frida_pins(package_json, "16.6.6");
+        assert_eq!(
+            updates,
+            vec![PinUpdate {
+                name: "@types/frida-gum".to_string(),
+                from: "latest".to_string(),
+                to: "^18.0.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            updated,
+            package_json.replace(
+                r#""@types/frida-gum": "latest""#,
+                r#""@types/frida-gum": "^18.0.0""#
+            )
+        );
+        let (_, updates) = rewrite_frida_pins(&updated, "16.6.6");
+        assert!(updates.is_empty());
+        let (_, updates) = rewrite_frida_pins(&updated, "17.2.0");
+        assert_eq!(updates.len(), 2);
+    }
+
     #[test]
     fn default_config_paths_are_relative() {
         let cfg = AgentConfig::default();
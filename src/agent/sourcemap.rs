@@ -0,0 +1,414 @@
+//! Source Map v3 decoding for agent bundles, used to point frida's stack traces at the
+//! TypeScript the agent was built from instead of the generated bundle.
+
+use crate::agent::AgentProject;
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::path::{Component, Path, PathBuf};
+
+/// First line of a frida-compile bundle; the module table follows until [`BUNDLE_SEPARATOR`].
+const BUNDLE_MAGIC: &str = "📦";
+const BUNDLE_SEPARATOR: &str = "✄";
+
+/// `file:line` or `file:line:column`, as frida prints them in stack frames.
+static POSITION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([^\s()]+?):(\d+)(?::(\d+))?").expect("valid regex"));
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSourceMap {
+    #[serde(default)]
+    source_root: Option<String>,
+    #[serde(default)]
+    sources: Vec<Option<String>>,
+    mappings: String,
+}
+
+/// One mapped span of a generated line. Lines and columns are 0-based, as in the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    generated_column: u32,
+    source: usize,
+    line: u32,
+    column: u32,
+}
+
+/// A decoded source map: for every generated line, its segments ordered by column.
+#[derive(Debug)]
+pub struct SourceMap {
+    sources: Vec<String>,
+    lines: Vec<Vec<Segment>>,
+}
+
+/// Where a generated position came from. `line` and `column` are 1-based.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OriginalPosition<'a> {
+    pub source: &'a str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl SourceMap {
+    pub fn parse(json: &str) -> Result<Self> {
+        let raw: RawSourceMap = serde_json::from_str(json)
+            .map_err(|e| FridaMgrError::Config(format!("Invalid source map: {}", e)))?;
+        let root = raw.source_root.unwrap_or_default();
+        let sources = raw
+            .sources
+            .into_iter()
+            .map(|source| {
+                let source = source.unwrap_or_default();
+                if root.is_empty() || source.contains("://") {
+                    source
+                } else {
+                    format!("{}/{}", root.trim_end_matches('/'), source)
+                }
+            })
+            .collect();
+        Ok(Self {
+            sources,
+            lines: decode_mappings(&raw.mappings)?,
+        })
+    }
+
+    /// The original position of 1-based `line` (and `column`, when frida printed one). Without
+    /// a column the first mapped segment of the line is used.
+    pub fn lookup(&self, line: u32, column: Option<u32>) -> Option<OriginalPosition<'_>> {
+        let segments = self.lines.get(line.checked_sub(1)? as usize)?;
+        let segment = match column {
+            Some(column) => {
+                let column = column.saturating_sub(1);
+                segments
+                    .iter()
+                    .rev()
+                    .find(|s| s.generated_column <= column)
+                    .or_else(|| segments.first())?
+            }
+            None => segments.first()?,
+        };
+        Some(OriginalPosition {
+            source: self.sources.get(segment.source)?,
+            line: segment.line + 1,
+            column: segment.column + 1,
+        })
+    }
+}
+
+fn decode_mappings(mappings: &str) -> Result<Vec<Vec<Segment>>> {
+    let invalid = || FridaMgrError::Config("Invalid source map: malformed mappings".to_string());
+    let (mut source, mut line, mut column) = (0i64, 0i64, 0i64);
+    let mut lines = Vec::new();
+    for generated in mappings.split(';') {
+        let mut generated_column = 0i64;
+        let mut segments = Vec::new();
+        for field in generated.split(',').filter(|f| !f.is_empty()) {
+            let values = decode_vlq(field).ok_or_else(invalid)?;
+            generated_column += values.first().copied().ok_or_else(invalid)?;
+            // One-field segments map generated text to nothing.
+            if values.len() < 4 {
+                continue;
+            }
+            source += values[1];
+            line += values[2];
+            column += values[3];
+            if generated_column < 0 || source < 0 || line < 0 || column < 0 {
+                return Err(invalid());
+            }
+            segments.push(Segment {
+                generated_column: generated_column as u32,
+                source: source as usize,
+                line: line as u32,
+                column: column as u32,
+            });
+        }
+        segments.sort_by_key(|s| s.generated_column);
+        lines.push(segments);
+    }
+    Ok(lines)
+}
+
+/// The base64 VLQ values of one segment.
+fn decode_vlq(field: &str) -> Option<Vec<i64>> {
+    let mut values = Vec::new();
+    let (mut value, mut shift) = (0i64, 0u32);
+    for c in field.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as i64;
+        if shift > 60 {
+            return None;
+        }
+        value += (digit & 31) << shift;
+        if digit & 32 != 0 {
+            shift += 5;
+            continue;
+        }
+        values.push(if value & 1 == 1 {
+            -(value >> 1)
+        } else {
+            value >> 1
+        });
+        value = 0;
+        shift = 0;
+    }
+    (shift == 0).then_some(values)
+}
+
+/// The source maps of a built agent, keyed by the script name frida reports in stack frames.
+#[derive(Debug)]
+pub struct AgentSourceMaps {
+    project_dir: PathBuf,
+    /// (script name, directory its map's sources are relative to, map)
+    scripts: Vec<(String, PathBuf, SourceMap)>,
+}
+
+impl AgentSourceMaps {
+    /// Read the maps of `agent`'s bundle: the modules of a frida-compile bundle or the
+    /// `<out>.map` esbuild writes next to it. `None` when the bundle carries no maps.
+    pub async fn load(agent: &AgentProject) -> Result<Option<Self>> {
+        let bundle = tokio::fs::read_to_string(&agent.out_path).await?;
+        let mut scripts = Vec::new();
+
+        if let Some(modules) = parse_bundle(&bundle) {
+            for (name, content) in &modules {
+                let Some(script) = name.strip_suffix(".map") else {
+                    continue;
+                };
+                let relative = script.trim_start_matches('/');
+                let dir = Path::new(relative).parent().unwrap_or(Path::new(""));
+                scripts.push((
+                    script.to_string(),
+                    agent.agent_dir.join(dir),
+                    SourceMap::parse(content)?,
+                ));
+            }
+        } else {
+            let map_path = PathBuf::from(format!("{}.map", agent.out_path.display()));
+            if map_path.is_file() {
+                let name = agent
+                    .out_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let dir = agent.out_path.parent().unwrap_or(&agent.agent_dir);
+                scripts.push((
+                    name,
+                    dir.to_path_buf(),
+                    SourceMap::parse(&tokio::fs::read_to_string(&map_path).await?)?,
+                ));
+            }
+        }
+
+        Ok((!scripts.is_empty()).then(|| Self {
+            project_dir: agent.project_dir.clone(),
+            scripts,
+        }))
+    }
+
+    /// `line` with every bundle position rewritten to its original source, or `None` when it
+    /// mentions none.
+    pub fn translate_line(&self, line: &str) -> Option<String> {
+        let mut changed = false;
+        let translated = POSITION.replace_all(line, |caps: &Captures| {
+            match self.translate_position(caps) {
+                Some(position) => {
+                    changed = true;
+                    position
+                }
+                None => caps[0].to_string(),
+            }
+        });
+        changed.then(|| translated.into_owned())
+    }
+
+    fn translate_position(&self, caps: &Captures) -> Option<String> {
+        let file = &caps[1];
+        let line = caps[2].parse().ok()?;
+        let column = caps.get(3).and_then(|c| c.as_str().parse().ok());
+        let (_, dir, map) = self
+            .scripts
+            .iter()
+            .find(|(name, _, _)| script_matches(file, name))?;
+        let original = map.lookup(line, column)?;
+        let source = self.display_source(dir, original.source);
+        Some(match column {
+            Some(_) => format!("{}:{}:{}", source, original.line, original.column),
+            None => format!("{}:{}", source, original.line),
+        })
+    }
+
+    /// `source` relative to the project root when it lies inside it.
+    fn display_source(&self, dir: &Path, source: &str) -> String {
+        if source.contains("://") {
+            return source.to_string();
+        }
+        let path = normalize(&dir.join(source.trim_start_matches('/')));
+        path.strip_prefix(&self.project_dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string()
+    }
+}
+
+/// The maps `top`/`spawn` translate frida's output with when `agent.sourcemap` is set.
+/// Problems reading them are reported and leave the output untranslated.
+pub async fn session_source_maps(agent: &AgentProject) -> Option<AgentSourceMaps> {
+    if !agent.sourcemap {
+        return None;
+    }
+    match AgentSourceMaps::load(agent).await {
+        Ok(Some(maps)) => Some(maps),
+        Ok(None) => {
+            eprintln!(
+                "{} agent.sourcemap is set but {} carries no source maps; stack traces are shown as-is",
+                "⚠".yellow().bold(),
+                agent.out_path.display()
+            );
+            None
+        }
+        Err(e) => {
+            eprintln!(
+                "{} Could not read the agent's source maps: {}",
+                "⚠".yellow().bold(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Whether frida's script name `file` is the bundle (module) `name`.
+fn script_matches(file: &str, name: &str) -> bool {
+    let file = file.strip_prefix("file://").unwrap_or(file);
+    if name.starts_with('/') {
+        return file == name;
+    }
+    file == name || file.ends_with(&format!("/{}", name))
+}
+
+/// The (name, content) modules of a frida-compile bundle, or `None` for a plain script.
+fn parse_bundle(bundle: &str) -> Option<Vec<(String, String)>> {
+    let rest = bundle.strip_prefix(BUNDLE_MAGIC)?.strip_prefix('\n')?;
+    let (header, mut body) = rest.split_once(&format!("\n{}\n", BUNDLE_SEPARATOR))?;
+    let separator = format!("\n{}\n", BUNDLE_SEPARATOR);
+
+    let mut modules = Vec::new();
+    for entry in header.lines() {
+        let (size, name) = entry.split_once(' ')?;
+        let size: usize = size.parse().ok()?;
+        let content = body.get(..size)?;
+        modules.push((name.to_string(), content.to_string()));
+        body = body[size..].strip_prefix(separator.as_str()).unwrap_or("");
+    }
+    Some(modules)
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // index.ts: line 1 -> generated line 1, line 3 col 2 -> generated line 2 col 4.
+    const MAP: &str =
+        r#"{"version":3,"sources":["../src/index.ts"],"names":[],"mappings":"AAAA;IAEE,OAAO"}"#;
+
+    fn agent_maps(name: &str, dir: &str) -> AgentSourceMaps {
+        AgentSourceMaps {
+            project_dir: PathBuf::from("/work/demo"),
+            scripts: vec![(
+                name.to_string(),
+                PathBuf::from(dir),
+                SourceMap::parse(MAP).unwrap(),
+            )],
+        }
+    }
+
+    #[test]
+    fn decodes_vlq_values() {
+        assert_eq!(decode_vlq("AAAA"), Some(vec![0, 0, 0, 0]));
+        assert_eq!(decode_vlq("IAEE"), Some(vec![4, 0, 2, 2]));
+        assert_eq!(decode_vlq("D"), Some(vec![-1]));
+        assert_eq!(decode_vlq("gB"), Some(vec![16]));
+        assert_eq!(decode_vlq("g"), None);
+        assert_eq!(decode_vlq("A!"), None);
+    }
+
+    #[test]
+    fn looks_up_original_positions() {
+        let map = SourceMap::parse(MAP).unwrap();
+        let at = |line, column| map.lookup(line, column).map(|p| (p.line, p.column));
+        assert_eq!(at(1, None), Some((1, 1)));
+        assert_eq!(at(2, None), Some((3, 3)));
+        assert_eq!(at(2, Some(5)), Some((3, 3)));
+        assert_eq!(at(2, Some(12)), Some((3, 10)));
+        assert_eq!(at(3, None), None);
+        assert_eq!(map.lookup(2, None).unwrap().source, "../src/index.ts");
+    }
+
+    #[test]
+    fn translates_stack_frames_of_the_bundle() {
+        let maps = agent_maps("agent.js", "/work/demo/agent/dist");
+        assert_eq!(
+            maps.translate_line("    at onEnter (/agent.js:2:12)")
+                .as_deref(),
+            Some("    at onEnter (agent/src/index.ts:3:10)")
+        );
+        assert_eq!(
+            maps.translate_line("    at /work/demo/agent/dist/agent.js:2")
+                .as_deref(),
+            Some("    at agent/src/index.ts:3")
+        );
+        assert_eq!(maps.translate_line("    at call (native)"), None);
+        assert_eq!(maps.translate_line("    at f (/other.js:2:12)"), None);
+        assert_eq!(maps.translate_line("    at f (/notagent.js:2)"), None);
+    }
+
+    #[test]
+    fn reads_modules_of_frida_compile_bundles() {
+        let js = "console.log(1);";
+        let bundle = format!(
+            "📦\n{} /src/index.js.map\n{} /src/index.js\n✄\n{}\n✄\n{}",
+            MAP.len(),
+            js.len(),
+            MAP,
+            js
+        );
+        let modules = parse_bundle(&bundle).unwrap();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(
+            modules[0],
+            ("/src/index.js.map".to_string(), MAP.to_string())
+        );
+        assert_eq!(modules[1], ("/src/index.js".to_string(), js.to_string()));
+        assert!(parse_bundle(js).is_none());
+
+        let maps = agent_maps("/src/index.js", "/work/demo/agent/src");
+        assert_eq!(
+            maps.translate_line("Error: boom\n    at <anonymous> (/src/index.js:2)")
+                .as_deref(),
+            Some("Error: boom\n    at <anonymous> (agent/src/index.ts:3)")
+        );
+        assert_eq!(maps.translate_line("at f (/lib/src/index.js:2)"), None);
+    }
+}
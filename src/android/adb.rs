@@ -1,32 +1,127 @@
-use crate::android::foreground;
-use crate::config::ArchType;
+use crate::android::device_info::DeviceInfo;
+use crate::android::{artifacts, foreground, gadget, network, sdk, shell};
+use crate::config::{
+    AndroidServerTarget, ArchType, SelinuxPolicy, ServerLogConfig, SERVER_LOG_DISABLED,
+};
 use crate::core::error::{FridaMgrError, Result};
-use crate::core::ProcessExecutor;
+use crate::core::{
+    compute_md5, compute_sha256, interrupt, CommandRunner, Event, EventBus, ProcessExecutor,
+};
 use colored::Colorize;
+use std::collections::HashMap;
 use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Device {
     pub id: String,
     pub model: String,
     pub state: String,
+    pub transport: Transport,
+    /// Filled in by `AdbClient::list_devices_detailed`.
+    pub info: Option<DeviceInfo>,
+}
+
+/// How adb reaches a device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transport {
+    #[default]
+    Usb,
+    Tcp,
+    Emulator,
+}
+
+impl Transport {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Transport::Usb => "usb",
+            Transport::Tcp => "tcp",
+            Transport::Emulator => "emulator",
+        }
+    }
 }
 
 pub struct AdbClient {
     adb_path: String,
+    status_on_stderr: bool,
+    runner: Arc<dyn CommandRunner>,
+    /// Index into `PS_COMMANDS` of the listing each device answered, once probed.
+    ps_commands: Mutex<HashMap<String, usize>>,
+    /// Root command that got a root shell per device and configured `root_command`, once
+    /// probed (`None` when nothing did).
+    root_commands: Mutex<HashMap<(String, String), Option<String>>>,
+    /// `ro.build.version.sdk` per device, once read.
+    api_levels: Mutex<HashMap<String, Option<u32>>>,
+    /// How many times `push_file` pushes before giving up on a failed or corrupted copy.
+    push_attempts: usize,
 }
 
+/// Ways to list every process, newest first: toybox `ps` (Android 8+) needs `-A`, the
+/// toolbox `ps` of Android 7 and older lists everything by default (and treats `-A` as a
+/// name filter), and `toolbox ps` gets past a busybox `ps` shadowing it.
+const PS_COMMANDS: [&[&str]; 3] = [&["ps", "-A"], &["ps"], &["toolbox", "ps"]];
+
+/// Root invocations tried when the configured `root_command` gets no root shell: Magisk,
+/// KernelSU and SuperSU `su -c`, the AOSP userdebug `su <uid> <command>`, and an `su`
+/// installed in /sbin but missing from the shell's `PATH`.
+const ROOT_COMMANDS: [&str; 3] = ["su", "su 0 {cmd}", "/sbin/su"];
+
+/// Checksum tools tried on the device after a push, with their digest length in hex:
+/// toybox has `sha256sum`; the toolbox shells of older Android releases only `md5sum`.
+const CHECKSUM_TOOLS: [(&str, usize); 2] = [("sha256sum", 64), ("md5sum", 32)];
+
 impl AdbClient {
+    /// Falls back to an adb from an Android SDK install when `adb_path` is not runnable.
     pub fn new(adb_path: Option<String>) -> Self {
         Self {
-            adb_path: adb_path.unwrap_or_else(|| "adb".to_string()),
+            adb_path: sdk::resolve_adb_path(adb_path.as_deref().unwrap_or("adb")),
+            status_on_stderr: false,
+            runner: Arc::new(ProcessExecutor),
+            ps_commands: Mutex::new(HashMap::new()),
+            root_commands: Mutex::new(HashMap::new()),
+            api_levels: Mutex::new(HashMap::new()),
+            push_attempts: 1,
+        }
+    }
+
+    /// Run adb through `runner` instead of spawning it (interactive sessions and pushes with
+    /// progress still spawn adb directly).
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Push up to `attempts` times (`network.max_retries`) when adb fails or the copy's
+    /// checksum on the device does not match the local file.
+    pub fn with_push_attempts(mut self, attempts: u32) -> Self {
+        self.push_attempts = (attempts as usize).max(1);
+        self
+    }
+
+    /// Print server start/stop progress on stderr, keeping stdout for `--output jsonl`.
+    pub fn with_status_on_stderr(mut self, enabled: bool) -> Self {
+        self.status_on_stderr = enabled;
+        self
+    }
+
+    fn status(&self, line: String) {
+        if self.status_on_stderr {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
         }
     }
 
+    /// The adb binary commands actually run.
+    pub fn adb_path(&self) -> &str {
+        &self.adb_path
+    }
+
     pub fn check_installed(&self) -> Result<()> {
-        if !ProcessExecutor::check_command_exists(&self.adb_path) {
+        if !self.runner.exists(&self.adb_path) {
             return Err(FridaMgrError::Adb(
-                "ADB is not installed or not in PATH. Please install Android SDK Platform Tools."
+                "ADB is not installed or not in PATH (ANDROID_HOME, ANDROID_SDK_ROOT and default SDK locations were searched too). Please install Android SDK Platform Tools, or run 'frida-mgr adb install'."
                     .to_string(),
             ));
         }
@@ -36,105 +131,929 @@ impl AdbClient {
     pub async fn list_devices(&self) -> Result<Vec<Device>> {
         self.check_installed()?;
 
-        let output =
-            ProcessExecutor::execute_with_output(&self.adb_path, &["devices", "-l"]).await?;
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["devices", "-l"])
+            .await?;
+
+        Ok(parse_devices(&output))
+    }
 
-        let mut devices = Vec::new();
+    pub async fn get_first_device(&self) -> Result<Device> {
+        let devices = self.list_devices().await?;
+
+        if devices.is_empty() {
+            return Err(FridaMgrError::NoDevice);
+        }
+
+        Ok(devices[0].clone())
+    }
+
+    /// The connected device with serial `serial` (reconnecting a network device once), or
+    /// the first connected device.
+    pub async fn get_device(&self, serial: Option<&str>) -> Result<Device> {
+        let Some(serial) = serial else {
+            return self.get_first_device().await;
+        };
+
+        let mut devices = self.list_devices().await?;
+        // Network devices drop off after a reboot or Wi-Fi change; reconnect once.
+        if !devices.iter().any(|d| d.id == serial)
+            && network::is_network_serial(serial)
+            && self.connect(serial).await.is_ok()
+        {
+            devices = self.list_devices().await?;
+        }
+        devices
+            .into_iter()
+            .find(|d| d.id == serial)
+            .ok_or_else(|| FridaMgrError::DeviceNotFound(serial.to_string()))
+    }
+
+    /// Reconnect wireless `endpoints` (those `frida-mgr connect` remembered). Returns
+    /// whether any came back.
+    pub async fn reconnect_wireless_devices(&self, endpoints: &[String]) -> bool {
+        let mut reconnected = false;
+        for endpoint in endpoints {
+            if self.connect(endpoint).await.is_ok() {
+                self.status(format!(
+                    "{} Reconnected to {}",
+                    "ℹ".blue().bold(),
+                    endpoint.cyan()
+                ));
+                reconnected = true;
+            }
+        }
+        reconnected
+    }
+
+    /// Restart adbd on the device listening for TCP connections on `port`.
+    pub async fn tcpip(&self, device_id: &str, port: u16) -> Result<()> {
+        self.check_installed()?;
+
+        let port = port.to_string();
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "tcpip", &port])
+            .await?;
+
+        if !output.status.success() {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to switch {} to TCP/IP mode: {}",
+                device_id,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// `adb connect <endpoint>`.
+    pub async fn connect(&self, endpoint: &str) -> Result<()> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .run(&self.adb_path, &["connect", endpoint])
+            .await?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        if !output.status.success() || !network::connect_succeeded(&text) {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to connect to {}: {}",
+                endpoint,
+                text.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// `adb disconnect <endpoint>`, or every network device when `endpoint` is `None`.
+    pub async fn disconnect(&self, endpoint: Option<&str>) -> Result<()> {
+        self.check_installed()?;
+
+        let mut args = vec!["disconnect"];
+        args.extend(endpoint);
+        let output = self.runner.run(&self.adb_path, &args).await?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        if !output.status.success() || text.to_ascii_lowercase().contains("error") {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to disconnect {}: {}",
+                endpoint.unwrap_or("network devices"),
+                text.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Pair with a wireless-debugging device using the code shown on its screen.
+    pub async fn pair(&self, endpoint: &str, code: &str) -> Result<()> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .run(&self.adb_path, &["pair", endpoint, code])
+            .await?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        if !output.status.success() || !network::pair_succeeded(&text) {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to pair with {}: {}",
+                endpoint,
+                text.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Wireless-debugging services found by adb's mDNS browser.
+    pub async fn mdns_services(&self) -> Result<Vec<network::MdnsService>> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["mdns", "services"])
+            .await?;
+        Ok(network::parse_mdns_services(&output))
+    }
 
-        for line in output.lines().skip(1) {
-            if line.trim().is_empty() {
+    /// `list_devices` plus `get_device_info` for every usable device.
+    pub async fn list_devices_detailed(&self, root_command: &str) -> Result<Vec<Device>> {
+        let mut devices = self.list_devices().await?;
+        for device in devices.iter_mut().filter(|d| d.state == "device") {
+            // A device that stops answering mid-listing just shows no details.
+            device.info = self.get_device_info(&device.id, root_command).await.ok();
+        }
+        Ok(devices)
+    }
+
+    /// Model, manufacturer, Android version, API level and ABIs from one `getprop`, plus
+    /// how root is reached and the SELinux mode. Only the `getprop` read has to succeed.
+    pub async fn get_device_info(&self, device_id: &str, root_command: &str) -> Result<DeviceInfo> {
+        self.check_installed()?;
+
+        let props = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", "getprop"])
+            .await?;
+        let mut info = DeviceInfo::from_getprop(&props);
+        if let Some(api_level) = info.api_level {
+            self.api_levels
+                .lock()
+                .unwrap()
+                .insert(device_id.to_string(), Some(api_level));
+        }
+        info.root = self
+            .root_method(device_id, root_command)
+            .await
+            .ok()
+            .flatten();
+        info.selinux = self.selinux_mode(device_id).await.ok();
+        Ok(info)
+    }
+
+    /// The device's Wi-Fi IPv4 address, if it has one.
+    pub async fn wifi_ip(&self, device_id: &str) -> Result<Option<String>> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .stdout(
+                &self.adb_path,
+                &["-s", device_id, "shell", "ip", "-f", "inet", "addr", "show"],
+            )
+            .await?;
+
+        Ok(network::parse_wifi_ipv4(&output))
+    }
+
+    pub async fn get_arch(&self, device_id: &str) -> Result<ArchType> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .stdout(
+                &self.adb_path,
+                &["-s", device_id, "shell", "getprop", "ro.product.cpu.abi"],
+            )
+            .await?;
+
+        let abi = output.trim();
+        Ok(ArchType::from_abi(abi))
+    }
+
+    /// The device's API level (`ro.build.version.sdk`), `None` when it does not parse.
+    pub async fn api_level(&self, device_id: &str) -> Result<Option<u32>> {
+        if let Some(level) = self.api_levels.lock().unwrap().get(device_id) {
+            return Ok(*level);
+        }
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .stdout(
+                &self.adb_path,
+                &["-s", device_id, "shell", "getprop", "ro.build.version.sdk"],
+            )
+            .await?;
+        let level = output.trim().parse().ok();
+        self.api_levels
+            .lock()
+            .unwrap()
+            .insert(device_id.to_string(), level);
+        Ok(level)
+    }
+
+    /// Architecture an app's process runs as: from `/proc/<pid>/exe` when readable, else
+    /// the package's `primaryCpuAbi`. `None` means unknown, or an app without native code,
+    /// which runs as the device's primary ABI.
+    pub async fn app_arch(
+        &self,
+        device_id: &str,
+        package: &str,
+        pid: Option<u32>,
+        device_arch: &ArchType,
+    ) -> Result<Option<ArchType>> {
+        self.check_installed()?;
+
+        if let Some(pid) = pid {
+            let cmd = shell::command(["readlink", &format!("/proc/{}/exe", pid)]);
+            let output = self
+                .runner
+                .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                .await?;
+            if let Some(arch) = arch_from_exe(&String::from_utf8_lossy(&output.stdout), device_arch)
+            {
+                return Ok(Some(arch));
+            }
+        }
+
+        Ok(parse_primary_cpu_abi(
+            &self.dumpsys_package(device_id, package).await?,
+        ))
+    }
+
+    /// Push `local` to `remote` and compare checksums on both ends (SHA-256, or MD5 on
+    /// shells without `sha256sum`), pushing again (see `with_push_attempts`) when adb fails
+    /// or the copy arrived corrupted.
+    pub async fn push_file(&self, device_id: &str, local: &Path, remote: &str) -> Result<()> {
+        self.check_installed()?;
+
+        println!(
+            "{} Pushing {} to device...",
+            "↑".blue().bold(),
+            local.file_name().unwrap().to_str().unwrap().yellow()
+        );
+
+        let mut attempt = 1;
+        loop {
+            let failure = match self.push_once(device_id, local, remote).await? {
+                false => "adb push failed".to_string(),
+                true => match self.remote_checksum(device_id, remote).await? {
+                    Some((tool, actual)) => {
+                        let expected = match tool {
+                            "md5sum" => compute_md5(local).await?,
+                            _ => compute_sha256(local).await?,
+                        };
+                        if actual == expected {
+                            break;
+                        }
+                        format!(
+                            "checksum mismatch ({}: expected {}, device has {})",
+                            tool, expected, actual
+                        )
+                    }
+                    None => {
+                        println!(
+                            "{} No sha256sum or md5sum on the device; skipping checksum verification",
+                            "ℹ".blue().bold()
+                        );
+                        break;
+                    }
+                },
+            };
+
+            if attempt >= self.push_attempts {
+                return Err(FridaMgrError::Adb(format!(
+                    "Failed to push {} to device {} after {} attempt(s): {}",
+                    remote, device_id, attempt, failure
+                )));
+            }
+            eprintln!(
+                "{} Push {}/{}: {}; retrying",
+                "⚠".yellow().bold(),
+                attempt,
+                self.push_attempts,
+                failure
+            );
+            attempt += 1;
+        }
+
+        println!("{} File pushed successfully", "✓".green().bold());
+
+        Ok(())
+    }
+
+    async fn push_once(&self, device_id: &str, local: &Path, remote: &str) -> Result<bool> {
+        let events = EventBus::global();
+        events.emit(Event::PushStarted {
+            device: device_id.to_string(),
+            local: local.to_path_buf(),
+            remote: remote.to_string(),
+        });
+        let success = self
+            .runner
+            .status(
+                &self.adb_path,
+                &["-s", device_id, "push", local.to_str().unwrap(), remote],
+            )
+            .await;
+        events.emit(Event::PushFinished {
+            device: device_id.to_string(),
+            remote: remote.to_string(),
+            ok: matches!(success, Ok(true)),
+        });
+        success
+    }
+
+    /// Checksum of `remote` on the device with the first of `CHECKSUM_TOOLS` its shell has,
+    /// and the tool used; `None` when it has neither.
+    async fn remote_checksum(
+        &self,
+        device_id: &str,
+        remote: &str,
+    ) -> Result<Option<(&'static str, String)>> {
+        for (tool, hex_len) in CHECKSUM_TOOLS {
+            let cmd = shell::command([tool, remote]);
+            let output = self
+                .runner
+                .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                .await?;
+            if !output.status.success() {
                 continue;
             }
+            if let Some(digest) = parse_checksum(&String::from_utf8_lossy(&output.stdout), hex_len)
+            {
+                return Ok(Some((tool, digest)));
+            }
+        }
+        Ok(None)
+    }
 
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let id = parts[0].to_string();
-                let state = parts[1].to_string();
+    /// Installed package names (`pm list packages`), for one Android user when given.
+    pub async fn list_packages(&self, device_id: &str, user: Option<u32>) -> Result<Vec<String>> {
+        self.check_installed()?;
 
-                let model = parts
-                    .iter()
-                    .find(|p| p.starts_with("model:"))
-                    .map(|p| p.strip_prefix("model:").unwrap_or("unknown"))
-                    .unwrap_or("unknown")
-                    .to_string();
+        let mut args = vec!["-s", device_id, "shell", "pm", "list", "packages"];
+        let user = user.map(|user| user.to_string());
+        if let Some(user) = user.as_deref() {
+            args.extend(["--user", user]);
+        }
+        let output = self.runner.stdout(&self.adb_path, &args).await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("package:"))
+            .map(str::to_string)
+            .collect())
+    }
 
-                devices.push(Device { id, model, state });
+    /// `dumpsys package <package>`: resolver tables, install state and ABI.
+    pub async fn dumpsys_package(&self, device_id: &str, package: &str) -> Result<String> {
+        self.check_installed()?;
+
+        let cmd = shell::command(["dumpsys", "package", package]);
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// On-device paths of `package`'s base and split APKs (`pm path`).
+    pub async fn package_apk_paths(&self, device_id: &str, package: &str) -> Result<Vec<String>> {
+        self.check_installed()?;
+
+        let cmd = shell::command(["pm", "path", package]);
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        let paths: Vec<String> = output
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("package:"))
+            .map(str::to_string)
+            .collect();
+        if paths.is_empty() {
+            return Err(FridaMgrError::Adb(format!(
+                "Package {} is not installed on {}",
+                package, device_id
+            )));
+        }
+        Ok(paths)
+    }
+
+    pub async fn pull_file(&self, device_id: &str, remote: &str, local: &Path) -> Result<()> {
+        self.check_installed()?;
+
+        let local_str = local.to_string_lossy();
+        let status = self
+            .runner
+            .run_streaming(
+                &self.adb_path,
+                &["-s", device_id, "pull", remote, &local_str],
+                &mut |line| println!("  {}", line.dimmed()),
+            )
+            .await?;
+        if !status.success() {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to pull {} from {}",
+                remote, device_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// `run-as` only works for apps built with `android:debuggable="true"`.
+    pub async fn is_debuggable(&self, device_id: &str, package: &str) -> Result<bool> {
+        self.check_installed()?;
+
+        let cmd = shell::command(["run-as", package, "id"]);
+        self.runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await
+    }
+
+    /// Copy frida-gadget and its config into the app's `code_cache` through `run-as` (no
+    /// root needed) and return the on-device library path.
+    pub async fn install_run_as_gadget(
+        &self,
+        device_id: &str,
+        package: &str,
+        gadget: &Path,
+        config: &str,
+    ) -> Result<String> {
+        let staging = format!("/data/local/tmp/{}", gadget::GADGET_LIB);
+        self.push_file(device_id, gadget, &staging).await?;
+
+        let copy_lib = format!(
+            "mkdir -p code_cache && cat > code_cache/{lib} && chmod 700 code_cache/{lib}",
+            lib = gadget::GADGET_LIB,
+        );
+        let copy_config = format!("cat > code_cache/{}", gadget::GADGET_CONFIG);
+        let cmd = format!(
+            "cat {staging} | {run_as} sh -c {copy_lib} && echo {config} | {run_as} sh -c {copy_config}; \
+             r=$?; rm -f {staging}; exit $r",
+            staging = shell::quote(&staging),
+            run_as = shell::command(["run-as", package]),
+            copy_lib = shell::quote(&copy_lib),
+            copy_config = shell::quote(&copy_config),
+            config = shell::quote(config),
+        );
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to copy frida-gadget into {} with run-as",
+                package
+            )));
+        }
+
+        Ok(format!(
+            "/data/data/{}/code_cache/{}",
+            package,
+            gadget::GADGET_LIB
+        ))
+    }
+
+    /// Move the frida-server pushed to `staged` into `package`'s `code_cache` through
+    /// `run-as` (debuggable apps, no root) and return its path in the app's data dir.
+    pub async fn install_run_as_server(
+        &self,
+        device_id: &str,
+        package: &str,
+        staged: &str,
+        server_process_name: &str,
+    ) -> Result<String> {
+        self.check_installed()?;
+
+        let copy = format!(
+            "mkdir -p code_cache && cat > code_cache/{name} && chmod 700 code_cache/{name}",
+            name = shell::quote(server_process_name),
+        );
+        let cmd = format!(
+            "cat {staged} | {run_as} sh -c {copy}; r=$?; rm -f {staged}; exit $r",
+            staged = shell::quote(staged),
+            run_as = shell::command(["run-as", package]),
+            copy = shell::quote(&copy),
+        );
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to copy {} into {} with run-as",
+                server_process_name, package
+            )));
+        }
+
+        Ok(format!(
+            "/data/data/{}/code_cache/{}",
+            package, server_process_name
+        ))
+    }
+
+    /// Start the frida-server `install_run_as_server` installed as `package`'s user. An app
+    /// may not listen on every interface, so it binds the device's loopback and is reached
+    /// through `adb forward`.
+    pub async fn start_run_as_server(
+        &self,
+        device_id: &str,
+        package: &str,
+        server_process_name: &str,
+        port: u16,
+    ) -> Result<()> {
+        self.check_installed()?;
+
+        let _ = self
+            .kill_run_as_server(device_id, package, server_process_name)
+            .await;
+
+        self.status(format!(
+            "{} Starting {} on 127.0.0.1:{} (with run-as {})...",
+            "⚙".blue().bold(),
+            server_process_name.cyan(),
+            port.to_string().cyan(),
+            package.yellow()
+        ));
+
+        let binary = format!("code_cache/{}", server_process_name);
+        let log = format!("{}.log", binary);
+        let listen = format!("127.0.0.1:{}", port);
+        let script = format!(
+            "{} > {} 2>&1 &",
+            shell::command(["nohup", &binary, "-l", &listen]),
+            shell::quote(&log)
+        );
+        let cmd = shell::command(["run-as", package, "sh", "-c", &script]);
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to start {} with run-as {}",
+                server_process_name, package
+            )));
+        }
+
+        for _ in 0..10 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(400)).await;
+            if self
+                .check_server_running(device_id, server_process_name)
+                .await
+                .unwrap_or(false)
+            {
+                return Ok(());
             }
         }
 
-        Ok(devices)
+        let cmd = shell::command(["run-as", package, "cat", &log]);
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await
+            .unwrap_or_default();
+        if !output.is_empty() {
+            eprintln!("\n{}", "Error output:".yellow().bold());
+            eprintln!("{}", output);
+        }
+        Err(FridaMgrError::Adb(format!(
+            "{} exited right after starting as {}; on Android 10+ apps targeting API 29 or later \
+             cannot execute files from their data dir",
+            server_process_name, package
+        )))
     }
 
-    pub async fn get_first_device(&self) -> Result<Device> {
-        let devices = self.list_devices().await?;
+    /// Stop a frida-server started with `start_run_as_server`.
+    pub async fn kill_run_as_server(
+        &self,
+        device_id: &str,
+        package: &str,
+        server_process_name: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
 
-        if devices.is_empty() {
-            return Err(FridaMgrError::NoDevice);
+        let cmd = shell::command(["run-as", package, "killall", server_process_name]);
+        self.runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        if self
+            .check_server_running(device_id, server_process_name)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to stop {} with run-as {}",
+                server_process_name, package
+            )));
         }
+        Ok(())
+    }
 
-        Ok(devices[0].clone())
+    /// Delete a frida-server `install_run_as_server` put in `package`'s `code_cache`, with
+    /// the log `start_run_as_server` writes next to it.
+    pub async fn remove_run_as_server(
+        &self,
+        device_id: &str,
+        package: &str,
+        server_process_name: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
+
+        let binary = format!("code_cache/{}", server_process_name);
+        let log = format!("{}.log", binary);
+        let cmd = shell::command(["run-as", package, "rm", "-f", &binary, &log]);
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to remove {} with run-as {}",
+                server_process_name, package
+            )));
+        }
+        Ok(())
     }
 
-    pub async fn get_device(&self, device_id: Option<&str>) -> Result<Device> {
-        if let Some(id) = device_id {
-            let devices = self.list_devices().await?;
-            devices
-                .into_iter()
-                .find(|d| d.id == id)
-                .ok_or_else(|| FridaMgrError::DeviceNotFound(id.to_string()))
-        } else {
-            self.get_first_device().await
+    /// Restart `package`'s launcher activity with `agent` loaded as a JVMTI agent
+    /// (Android 9+, debuggable apps only).
+    pub async fn start_with_agent(
+        &self,
+        device_id: &str,
+        package: &str,
+        agent: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
+
+        let cmd = shell::command(["cmd", "package", "resolve-activity", "--brief", package]);
+        let resolved = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        let component = gadget::parse_resolved_activity(&resolved).ok_or_else(|| {
+            FridaMgrError::Adb(format!("No launcher activity found for {}", package))
+        })?;
+
+        let cmd = shell::command([
+            "am",
+            "start",
+            "-S",
+            "--attach-agent",
+            agent,
+            "-n",
+            &component,
+        ]);
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if !output.status.success() || text.contains("Error") {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to start {} with an attached agent: {}",
+                component,
+                text.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Fire a VIEW intent for `uri` at `package`, the way a tapped deep link would.
+    pub async fn open_uri(&self, device_id: &str, package: &str, uri: &str) -> Result<()> {
+        self.check_installed()?;
+
+        let cmd = shell::command([
+            "am",
+            "start",
+            "-W",
+            "-a",
+            "android.intent.action.VIEW",
+            "-d",
+            uri,
+            package,
+        ]);
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if !output.status.success() || text.contains("Error") {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to open {} in {}: {}",
+                uri,
+                package,
+                text.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// `adb forward tcp:<port> tcp:<port>` for the rest of this command; it is removed
+    /// again if the command is interrupted.
+    pub async fn forward(&self, device_id: &str, port: u16) -> Result<()> {
+        self.forward_local(device_id, port, port).await
+    }
+
+    /// `adb forward tcp:<local> tcp:<remote>` for the rest of this command; it is removed
+    /// again if the command is interrupted.
+    pub async fn forward_local(&self, device_id: &str, local: u16, remote: u16) -> Result<()> {
+        let spec = self.add_forward(device_id, local, remote).await?;
+        interrupt::remove_forward_on_interrupt(&self.adb_path, device_id, &spec);
+        Ok(())
+    }
+
+    /// `adb forward tcp:<port> tcp:<port>` meant to outlive this command.
+    pub async fn forward_persistent(&self, device_id: &str, port: u16) -> Result<()> {
+        self.add_forward(device_id, port, port).await.map(|_| ())
+    }
+
+    /// Returns the local spec, which `adb forward --remove` takes.
+    async fn add_forward(&self, device_id: &str, local: u16, remote: u16) -> Result<String> {
+        self.check_installed()?;
+
+        let local_spec = format!("tcp:{}", local);
+        let remote_spec = format!("tcp:{}", remote);
+        let success = self
+            .runner
+            .status(
+                &self.adb_path,
+                &["-s", device_id, "forward", &local_spec, &remote_spec],
+            )
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to forward port {} from {}",
+                remote, device_id
+            )));
+        }
+
+        Ok(local_spec)
+    }
+
+    /// Every forward adb currently holds, across devices (`adb forward --list`).
+    pub async fn list_forwards(&self) -> Result<Vec<Forward>> {
+        self.check_installed()?;
+
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["forward", "--list"])
+            .await?;
+        Ok(parse_forwards(&output))
+    }
+
+    /// `adb forward --remove tcp:<port>`.
+    pub async fn remove_forward(&self, device_id: &str, port: u16) -> Result<()> {
+        self.check_installed()?;
+
+        let spec = format!("tcp:{}", port);
+        let success = self
+            .runner
+            .status(
+                &self.adb_path,
+                &["-s", device_id, "forward", "--remove", &spec],
+            )
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to remove the forward of port {} from {}",
+                port, device_id
+            )));
         }
+        Ok(())
     }
 
-    pub async fn get_arch(&self, device_id: &str) -> Result<ArchType> {
+    /// Whether a file created in `dir` can be made executable and run (catches read-only
+    /// and noexec locations before pushing).
+    pub async fn probe_exec_dir(&self, device_id: &str, dir: &str) -> Result<bool> {
         self.check_installed()?;
 
-        let output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "getprop", "ro.product.cpu.abi"],
-        )
-        .await?;
+        let probe = shell::quote(&format!("{}/.frida-mgr-probe", dir.trim_end_matches('/')));
+        let cmd = format!(
+            "echo '#!/system/bin/sh' > {p} && chmod 755 {p} && {p}; r=$?; rm -f {p}; exit $r",
+            p = probe
+        );
+        self.runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await
+    }
 
-        let abi = output.trim();
-        Ok(ArchType::from_abi(abi))
+    pub async fn file_exists(&self, device_id: &str, path: &str) -> bool {
+        self.runner
+            .status(
+                &self.adb_path,
+                &["-s", device_id, "shell", &shell::command(["ls", path])],
+            )
+            .await
+            .unwrap_or(false)
     }
 
-    pub async fn push_file(&self, device_id: &str, local: &Path, remote: &str) -> Result<()> {
+    /// The version the frida-server binary at `server_path` reports (`--version`), or `None`
+    /// when it is missing or prints something else.
+    pub async fn server_version(
+        &self,
+        device_id: &str,
+        server_path: &str,
+    ) -> Result<Option<String>> {
         self.check_installed()?;
 
-        println!(
-            "{} Pushing {} to device...",
-            "↑".blue().bold(),
-            local.file_name().unwrap().to_str().unwrap().yellow()
-        );
-
-        let success = ProcessExecutor::execute_with_status(
-            &self.adb_path,
-            &["-s", device_id, "push", local.to_str().unwrap(), remote],
-        )
-        .await?;
-
-        if !success {
-            return Err(FridaMgrError::Adb(format!(
-                "Failed to push file to device {}",
-                device_id
-            )));
+        let output = self
+            .runner
+            .run(
+                &self.adb_path,
+                &[
+                    "-s",
+                    device_id,
+                    "shell",
+                    &shell::command([server_path, "--version"]),
+                ],
+            )
+            .await?;
+        if !output.status.success() {
+            return Ok(None);
         }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find(|line| semver::Version::parse(line).is_ok())
+            .map(str::to_string))
+    }
 
-        println!("{} File pushed successfully", "✓".green().bold());
-
-        Ok(())
+    /// The candidate the server was actually pushed to, or the first one if none exist.
+    pub async fn locate_server(
+        &self,
+        device_id: &str,
+        candidates: Vec<AndroidServerTarget>,
+    ) -> AndroidServerTarget {
+        if candidates.len() > 1 {
+            for candidate in &candidates {
+                if self.file_exists(device_id, &candidate.remote_path).await {
+                    return candidate.clone();
+                }
+            }
+        }
+        candidates
+            .into_iter()
+            .next()
+            .expect("candidates always include the default target")
     }
 
     pub async fn make_executable(&self, device_id: &str, path: &str) -> Result<()> {
         self.check_installed()?;
 
-        let success = ProcessExecutor::execute_with_status(
-            &self.adb_path,
-            &["-s", device_id, "shell", "chmod", "755", path],
-        )
-        .await?;
+        let success = self
+            .runner
+            .status(
+                &self.adb_path,
+                &[
+                    "-s",
+                    device_id,
+                    "shell",
+                    &shell::command(["chmod", "755", path]),
+                ],
+            )
+            .await?;
 
         if !success {
             return Err(FridaMgrError::Adb(format!(
@@ -146,6 +1065,7 @@ impl AdbClient {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn start_server(
         &self,
         device_id: &str,
@@ -153,6 +1073,8 @@ impl AdbClient {
         server_process_name: &str,
         port: u16,
         root_command: &str,
+        selinux: SelinuxPolicy,
+        log: &ServerLogConfig,
     ) -> Result<()> {
         self.check_installed()?;
 
@@ -161,33 +1083,42 @@ impl AdbClient {
             .kill_server(device_id, server_process_name, root_command)
             .await;
 
-        println!(
+        let enforcing = self.prepare_selinux(device_id, root_command, selinux).await;
+
+        self.status(format!(
             "{} Starting {} on port {} (with {})...",
             "⚙".blue().bold(),
             server_process_name.cyan(),
             port.to_string().cyan(),
             root_command.yellow()
-        );
+        ));
 
-        // Use nohup to properly daemonize and redirect output to log
-        let log_path = format!("{}.log", server_path);
-
-        // Clear old log
-        let _ = ProcessExecutor::execute_with_status(
-            &self.adb_path,
-            &["-s", device_id, "shell", "rm", "-f", &log_path],
-        )
-        .await;
+        // Use nohup to properly daemonize and append output to the (rotated) log; only
+        // what this start writes after `log_offset` is inspected below.
+        let log_path = log.path_for(server_path);
+        let log_offset = self
+            .rotate_server_log(device_id, &log_path, log.max_size_kb, root_command)
+            .await
+            .unwrap_or(0);
 
         // Use configured root command (su, sudo, laotie, etc.)
-        let cmd = format!(
-            "{} -c 'nohup {} -l 0.0.0.0:{} > {} 2>&1 &'",
-            root_command, server_path, port, log_path
-        );
+        let listen = format!("0.0.0.0:{}", port);
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &format!(
+                    "{} >> {} 2>&1 &",
+                    shell::command(["nohup", server_path, "-l", &listen]),
+                    shell::quote(&log_path)
+                ),
+            )
+            .await;
 
-        let success =
-            ProcessExecutor::execute_with_status(&self.adb_path, &["-s", device_id, "shell", &cmd])
-                .await?;
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
 
         if !success {
             return Err(FridaMgrError::Adb(format!(
@@ -196,11 +1127,11 @@ impl AdbClient {
             )));
         }
 
-        println!(
+        self.status(format!(
             "{} Verifying {}...",
             "⚙".blue().bold(),
             server_process_name.cyan()
-        );
+        ));
 
         // Wait and check multiple times
         for attempt in 0..15 {
@@ -215,7 +1146,7 @@ impl AdbClient {
             if !running {
                 // Process died - definitely an error
                 let logs = self
-                    .get_server_logs(device_id, &log_path)
+                    .get_server_logs(device_id, &log_path, log_offset)
                     .await
                     .unwrap_or_default();
 
@@ -237,7 +1168,13 @@ impl AdbClient {
                         "  - Root command '{}' not working (try 'su', 'sudo', or custom)",
                         root_command
                     );
-                    eprintln!("  - SELinux blocking execution");
+                    if enforcing {
+                        eprintln!(
+                            "  - SELinux is enforcing; set android.selinux = \"permissive\" in frida.toml"
+                        );
+                    } else {
+                        eprintln!("  - SELinux blocking execution");
+                    }
                     eprintln!("  - Incompatible Frida server version");
                 }
 
@@ -250,7 +1187,7 @@ impl AdbClient {
             // Check logs for errors every few attempts
             if attempt % 3 == 2 {
                 let logs = self
-                    .get_server_logs(device_id, &log_path)
+                    .get_server_logs(device_id, &log_path, log_offset)
                     .await
                     .unwrap_or_default();
 
@@ -282,7 +1219,16 @@ impl AdbClient {
                     );
                     eprintln!("  2. Try a different root command in frida.toml:");
                     eprintln!("     root_command = \"su\" or \"sudo\" or \"laotie\"");
+                    eprintln!(
+                        "     or a template for su without -c: root_command = \"su 0 {{cmd}}\""
+                    );
                     eprintln!("  3. Try a different frida version: frida-mgr install <version>");
+                    eprintln!("  4. Read earlier server output: frida-mgr logs -n 200");
+                    if enforcing {
+                        eprintln!(
+                            "  5. SELinux is enforcing; try android.selinux = \"permissive\" in frida.toml"
+                        );
+                    }
 
                     return Err(FridaMgrError::Adb(format!(
                         "{} started but encountered errors. See output above.",
@@ -299,7 +1245,7 @@ impl AdbClient {
             .unwrap_or(false)
         {
             let logs = self
-                .get_server_logs(device_id, &log_path)
+                .get_server_logs(device_id, &log_path, log_offset)
                 .await
                 .unwrap_or_default();
 
@@ -322,7 +1268,7 @@ impl AdbClient {
 
         // Check for any warning/error logs
         let logs = self
-            .get_server_logs(device_id, &log_path)
+            .get_server_logs(device_id, &log_path, log_offset)
             .await
             .unwrap_or_default();
         if !logs.trim().is_empty() {
@@ -352,19 +1298,236 @@ impl AdbClient {
             }
         }
 
-        println!(
+        self.status(format!(
             "{} {} started",
             "✓".green().bold(),
             server_process_name.cyan()
-        );
-        println!(
+        ));
+        self.status(format!(
             "  Note: Run {} to verify it's working",
             "frida-mgr ps -U".cyan()
-        );
+        ));
 
         Ok(())
     }
 
+    /// Run frida-server attached to an adb shell session, streaming its output until it
+    /// exits or Ctrl-C is pressed (which also kills the remote process).
+    pub async fn run_server_foreground(
+        &self,
+        device_id: &str,
+        server_path: &str,
+        server_process_name: &str,
+        port: u16,
+        root_command: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
+
+        let _ = self
+            .kill_server(device_id, server_process_name, root_command)
+            .await;
+
+        println!(
+            "{} Running {} on port {} in the foreground (with {}); press Ctrl-C to stop",
+            "⚙".blue().bold(),
+            server_process_name.cyan(),
+            port.to_string().cyan(),
+            root_command.yellow()
+        );
+
+        let listen = format!("0.0.0.0:{}", port);
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &format!("{} 2>&1", shell::command([server_path, "-l", &listen])),
+            )
+            .await;
+        let mut command = tokio::process::Command::new(&self.adb_path);
+        command
+            .args(["-s", device_id, "shell", &cmd])
+            .stdin(Stdio::null())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+        // Keep the terminal's Ctrl-C away from adb so the remote process is stopped
+        // deliberately below rather than left orphaned.
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut child = command.spawn().map_err(|e| {
+            FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", self.adb_path, e))
+        })?;
+
+        tokio::select! {
+            status = child.wait() => {
+                let status = status.map_err(|e| {
+                    FridaMgrError::CommandFailed(format!("{}: {}", self.adb_path, e))
+                })?;
+                Err(FridaMgrError::Adb(format!(
+                    "{} exited ({})",
+                    server_process_name,
+                    status
+                        .code()
+                        .map(|c| format!("code {}", c))
+                        .unwrap_or_else(|| "terminated".to_string())
+                )))
+            }
+            _ = interrupt::ctrl_c() => {
+                println!();
+                println!(
+                    "{} Stopping {}...",
+                    "⚙".blue().bold(),
+                    server_process_name.cyan()
+                );
+                let _ = child.kill().await;
+                self.kill_server(device_id, server_process_name, root_command)
+                    .await
+            }
+        }
+    }
+
+    /// The device's SELinux mode as `getenforce` prints it (`Enforcing`, `Permissive` or
+    /// `Disabled`).
+    pub async fn selinux_mode(&self, device_id: &str) -> Result<String> {
+        self.check_installed()?;
+
+        self.runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", "getenforce"])
+            .await
+    }
+
+    /// With `SelinuxPolicy::Permissive`, switch an enforcing device to permissive before a
+    /// server start. Returns whether SELinux is (still) enforcing; never fails the start.
+    async fn prepare_selinux(
+        &self,
+        device_id: &str,
+        root_command: &str,
+        selinux: SelinuxPolicy,
+    ) -> bool {
+        let enforcing = self
+            .selinux_mode(device_id)
+            .await
+            .is_ok_and(|mode| mode == "Enforcing");
+        if !enforcing || selinux != SelinuxPolicy::Permissive {
+            return enforcing;
+        }
+
+        self.status(format!(
+            "{} SELinux is enforcing; running {} (android.selinux = \"permissive\")",
+            "⚙".blue().bold(),
+            "setenforce 0".cyan()
+        ));
+        let cmd = self
+            .root_shell(device_id, root_command, "setenforce 0")
+            .await;
+        let switched = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await
+            .unwrap_or(false)
+            && self
+                .selinux_mode(device_id)
+                .await
+                .is_ok_and(|mode| mode != "Enforcing");
+        if !switched {
+            eprintln!(
+                "{} Could not switch SELinux to permissive with {}; starting anyway",
+                "⚠".yellow().bold(),
+                root_command.yellow()
+            );
+        }
+        !switched
+    }
+
+    /// How the device grants root: adbd itself running as root, or the su implementation
+    /// behind the root command that works (see `root_command_for`). `None` when no root
+    /// command gets a root shell.
+    pub async fn root_method(&self, device_id: &str, root_command: &str) -> Result<Option<String>> {
+        self.check_installed()?;
+
+        let uid = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", "id", "-u"])
+            .await?;
+        if uid == "0" {
+            return Ok(Some("adbd running as root (adb root)".to_string()));
+        }
+
+        let Some(root_command) = self.detect_root_command(device_id, root_command).await else {
+            return Ok(None);
+        };
+        let su = root_command.split_whitespace().next().unwrap_or("su");
+        let cmd = shell::command([su, "-v"]);
+        let version = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await
+            .unwrap_or_default();
+        Ok(Some(match su_implementation(&version) {
+            Some(implementation) => format!("{} via {}", implementation, root_command),
+            None => root_command,
+        }))
+    }
+
+    /// The root command to use on `device_id`: `configured` when it gets a root shell,
+    /// otherwise the first of `ROOT_COMMANDS` that does (with a warning), otherwise
+    /// `configured` so commands fail with its own error.
+    pub async fn root_command_for(&self, device_id: &str, configured: &str) -> String {
+        self.detect_root_command(device_id, configured)
+            .await
+            .unwrap_or_else(|| configured.to_string())
+    }
+
+    /// `script` wrapped in the root command `root_command_for` picks.
+    async fn root_shell(&self, device_id: &str, root_command: &str, script: &str) -> String {
+        shell::as_root(
+            &self.root_command_for(device_id, root_command).await,
+            script,
+        )
+    }
+
+    /// Probe `configured`, then `ROOT_COMMANDS`, for one that runs `id` as uid 0; the
+    /// answer is remembered per device.
+    async fn detect_root_command(&self, device_id: &str, configured: &str) -> Option<String> {
+        let key = (device_id.to_string(), configured.to_string());
+        if let Some(known) = self.root_commands.lock().unwrap().get(&key) {
+            return known.clone();
+        }
+
+        let mut found = None;
+        for (index, candidate) in std::iter::once(configured)
+            .chain(ROOT_COMMANDS.into_iter().filter(|c| *c != configured))
+            .enumerate()
+        {
+            let cmd = shell::as_root(candidate, "id");
+            let output = self
+                .runner
+                .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                .await
+                .unwrap_or_default();
+            if output.contains("uid=0(") {
+                if index > 0 {
+                    eprintln!(
+                        "{} root_command '{}' got no root shell on {}; using '{}'; set android.root_command = \"{}\" in frida.toml to use it directly",
+                        "⚠".yellow().bold(),
+                        configured,
+                        device_id,
+                        candidate,
+                        candidate
+                    );
+                }
+                found = Some(candidate.to_string());
+                break;
+            }
+        }
+
+        self.root_commands
+            .lock()
+            .unwrap()
+            .insert(key, found.clone());
+        found
+    }
+
     pub async fn kill_server(
         &self,
         device_id: &str,
@@ -380,27 +1543,34 @@ impl AdbClient {
             .unwrap_or(false);
 
         if !was_running {
-            println!(
+            self.status(format!(
                 "{} {} is not running",
                 "ℹ".blue().bold(),
                 server_process_name.cyan()
-            );
+            ));
             return Ok(());
         }
 
-        println!(
+        self.status(format!(
             "{} Stopping {} (with {})...",
             "⚙".blue().bold(),
             server_process_name.cyan(),
             root_command.yellow()
-        );
+        ));
 
         // Use root command to kill server
-        let cmd = format!("{} -c 'killall {}'", root_command, server_process_name);
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &shell::command(["killall", server_process_name]),
+            )
+            .await;
 
-        let success =
-            ProcessExecutor::execute_with_status(&self.adb_path, &["-s", device_id, "shell", &cmd])
-                .await?;
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
 
         if !success {
             eprintln!(
@@ -410,8 +1580,11 @@ impl AdbClient {
                 root_command.yellow()
             );
             eprintln!(
-                "  Try manually: adb shell \"{} -c 'killall -9 {}'\"",
-                root_command, server_process_name
+                "  Try manually: adb shell \"{}\"",
+                shell::as_root(
+                    &self.root_command_for(device_id, root_command).await,
+                    &shell::command(["killall", "-9", server_process_name])
+                )
             );
             return Err(FridaMgrError::Adb(format!(
                 "Failed to stop {} with root command '{}'",
@@ -442,23 +1615,223 @@ impl AdbClient {
         Ok(())
     }
 
+    /// Delete the log `start_server` appends server output to, and its rotated `.1`.
+    pub async fn remove_server_log(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        root_command: &str,
+    ) -> Result<()> {
+        if log_path == SERVER_LOG_DISABLED {
+            return Ok(());
+        }
+        self.remove_remote_files(
+            device_id,
+            &[log_path.to_string(), format!("{}.1", log_path)],
+            root_command,
+        )
+        .await
+    }
+
+    /// Before a start: move a log over `max_size_kb` to `<log>.1` (or truncate it when the
+    /// limit is 0) and return the size the new server output will be appended after.
+    async fn rotate_server_log(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        max_size_kb: u64,
+        root_command: &str,
+    ) -> Result<u64> {
+        if log_path == SERVER_LOG_DISABLED {
+            return Ok(0);
+        }
+
+        let log = shell::quote(log_path);
+        let rotate = if max_size_kb == 0 {
+            format!("rm -f {}", log)
+        } else {
+            format!(
+                "if [ -f {l} ] && [ $(wc -c < {l}) -gt {max} ]; then mv -f {l} {rotated}; fi",
+                l = log,
+                max = max_size_kb * 1024,
+                rotated = shell::quote(&format!("{}.1", log_path))
+            )
+        };
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &format!("{}; wc -c < {} 2>/dev/null || echo 0", rotate, log),
+            )
+            .await;
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        Ok(output
+            .lines()
+            .last()
+            .and_then(|line| line.trim().parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// `rm -f` files on the device through the root shell (server logs are root-owned).
+    /// The /dev/null a disabled log points at is refused.
+    pub async fn remove_remote_files(
+        &self,
+        device_id: &str,
+        paths: &[String],
+        root_command: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
+        if paths.iter().any(|path| path == SERVER_LOG_DISABLED) {
+            return Err(FridaMgrError::Adb(format!(
+                "Refusing to remove {} on {}",
+                SERVER_LOG_DISABLED, device_id
+            )));
+        }
+
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &shell::command(
+                    ["rm", "-f"]
+                        .into_iter()
+                        .chain(paths.iter().map(String::as_str)),
+                ),
+            )
+            .await;
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to remove {} on {}",
+                paths.join(", "),
+                device_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Regular files in `dir` on the device with their sizes, each marked when its content
+    /// looks frida-related.
+    /// Listed through the root shell so root-owned logs and fallback dirs are visible; a
+    /// missing directory lists as empty.
+    pub async fn list_marked_files(
+        &self,
+        device_id: &str,
+        dir: &str,
+        root_command: &str,
+    ) -> Result<Vec<artifacts::ListedFile>> {
+        self.check_installed()?;
+
+        let script = format!(
+            "cd {dir} 2>/dev/null || exit 0; for f in *; do [ -f \"$f\" ] || continue; \
+             if grep -qE {pattern} \"$f\" 2>/dev/null; then m=1; else m=0; fi; \
+             s=$(wc -c < \"$f\" 2>/dev/null); echo \"$m ${{s:-0}} $f\"; done",
+            dir = shell::quote(dir),
+            pattern = shell::quote(artifacts::FRIDA_MARKER_PATTERN),
+        );
+        let cmd = self.root_shell(device_id, root_command, &script).await;
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        Ok(artifacts::parse_listing(&output))
+    }
+
     pub async fn check_server_running(
         &self,
         device_id: &str,
         server_process_name: &str,
     ) -> Result<bool> {
+        let output = self.list_processes(device_id).await?;
+        Ok(ps_lists_process(&output, server_process_name))
+    }
+
+    /// PID of the running server process, if any.
+    pub async fn server_pid(
+        &self,
+        device_id: &str,
+        server_process_name: &str,
+    ) -> Result<Option<u32>> {
+        let output = self.list_processes(device_id).await?;
+        Ok(ps_server_pid(&output, server_process_name))
+    }
+
+    /// PID of the process named `process` (an app's main process is named after its
+    /// package), if it is running.
+    pub async fn process_pid(&self, device_id: &str, process: &str) -> Result<Option<u32>> {
+        let output = self.list_processes(device_id).await?;
+        Ok(ps_pid(&output, process))
+    }
+
+    /// Stream `logcat` for the process `pid` (Android 7+), handing each line to `on_line`
+    /// until logcat exits or frida-mgr is interrupted.
+    pub async fn logcat(
+        &self,
+        device_id: &str,
+        pid: u32,
+        on_line: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<()> {
         self.check_installed()?;
 
-        let output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "ps", "-A"],
-        )
-        .await?;
+        let pid = pid.to_string();
+        let status = self
+            .runner
+            .run_streaming(
+                &self.adb_path,
+                &["-s", device_id, "logcat", "-v", "threadtime", "--pid", &pid],
+                on_line,
+            )
+            .await?;
+        if !status.success() {
+            return Err(FridaMgrError::Adb(format!(
+                "logcat --pid {} failed on {} (needs Android 7 or later)",
+                pid, device_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// `ps` output covering every process on the device, probing which `PS_COMMANDS` form
+    /// the device understands on first use.
+    pub async fn list_processes(&self, device_id: &str) -> Result<String> {
+        self.check_installed()?;
+
+        let known = self.ps_commands.lock().unwrap().get(device_id).copied();
+        if let Some(index) = known {
+            let cmd = shell::command(PS_COMMANDS[index].iter().copied());
+            return self
+                .runner
+                .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                .await;
+        }
 
-        Ok(output.lines().any(|line| {
-            line.split_whitespace()
-                .any(|token| token == server_process_name)
-                || line.contains(server_process_name)
+        let mut last_error = None;
+        for (index, ps) in PS_COMMANDS.iter().enumerate() {
+            let cmd = shell::command(ps.iter().copied());
+            match self
+                .runner
+                .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                .await
+            {
+                Ok(output) if ps_lists_anything(&output) => {
+                    self.ps_commands
+                        .lock()
+                        .unwrap()
+                        .insert(device_id.to_string(), index);
+                    return Ok(output);
+                }
+                Ok(_) => {}
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            FridaMgrError::Adb(format!("Unable to list processes on {}", device_id))
         }))
     }
 
@@ -467,22 +1840,23 @@ impl AdbClient {
 
         // Use netstat or ss to check if port is listening
         let port_str = port.to_string();
-        let output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "netstat", "-tuln"],
-        )
-        .await;
+        let output = self
+            .runner
+            .stdout(
+                &self.adb_path,
+                &["-s", device_id, "shell", "netstat", "-tuln"],
+            )
+            .await;
 
         if let Ok(netstat_output) = output {
             return Ok(netstat_output.contains(&format!(":{}", port_str)));
         }
 
         // Fallback: try ss command
-        let output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "ss", "-tuln"],
-        )
-        .await;
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", "ss", "-tuln"])
+            .await;
 
         if let Ok(ss_output) = output {
             return Ok(ss_output.contains(&format!(":{}", port_str)));
@@ -492,14 +1866,113 @@ impl AdbClient {
         Ok(false)
     }
 
-    pub async fn get_server_logs(&self, device_id: &str, log_path: &str) -> Result<String> {
+    /// The last `lines` lines of the server log, or `None` when it does not exist.
+    pub async fn tail_server_log(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        lines: usize,
+    ) -> Result<Option<String>> {
         self.check_installed()?;
 
-        let output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "cat", log_path],
-        )
-        .await;
+        let cmd = format!(
+            "[ -f {log} ] || exit 3; {tail}",
+            log = shell::quote(log_path),
+            tail = shell::command(["tail", "-n", &lines.to_string(), log_path])
+        );
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).to_string())),
+            Some(3) => Ok(None),
+            _ => Err(FridaMgrError::Adb(format!(
+                "Failed to read {}: {}",
+                log_path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))),
+        }
+    }
+
+    /// `len` bytes of the server log starting at byte `offset`, untrimmed.
+    pub async fn read_server_log(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        offset: u64,
+        len: u64,
+    ) -> Result<String> {
+        self.check_installed()?;
+
+        let cmd = format!(
+            "{} | {}",
+            shell::command(["tail", "-c", &format!("+{}", offset + 1), log_path]),
+            shell::command(["head", "-c", &len.to_string()])
+        );
+        let output = self
+            .runner
+            .run(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Size of the server log in bytes (0 when it does not exist).
+    pub async fn server_log_size(&self, device_id: &str, log_path: &str) -> Result<u64> {
+        self.check_installed()?;
+
+        let cmd = format!("wc -c < {} 2>/dev/null || echo 0", shell::quote(log_path));
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        Ok(output.trim().parse().unwrap_or(0))
+    }
+
+    /// Empty the server log in place, so a running server keeps appending to it.
+    pub async fn truncate_server_log(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        root_command: &str,
+    ) -> Result<()> {
+        self.check_installed()?;
+
+        let log = shell::quote(log_path);
+        let cmd = self
+            .root_shell(
+                device_id,
+                root_command,
+                &format!("[ ! -f {log} ] || : > {log}", log = log),
+            )
+            .await;
+        let success = self
+            .runner
+            .status(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await?;
+        if !success {
+            return Err(FridaMgrError::Adb(format!(
+                "Failed to clear {} on {}",
+                log_path, device_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Server output written to `log_path` past byte `offset`.
+    pub async fn get_server_logs(
+        &self,
+        device_id: &str,
+        log_path: &str,
+        offset: u64,
+    ) -> Result<String> {
+        self.check_installed()?;
+
+        let cmd = shell::command(["tail", "-c", &format!("+{}", offset + 1), log_path]);
+        let output = self
+            .runner
+            .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+            .await;
 
         match output {
             Ok(logs) => Ok(logs),
@@ -523,65 +1996,107 @@ impl AdbClient {
         }
     }
 
+    /// The app with the focused activity; see `get_foreground_apps` for split-screen.
     pub async fn get_foreground_app(&self, device_id: &str) -> Result<foreground::ForegroundApp> {
+        Ok(self.get_foreground_apps(device_id).await?.remove(0))
+    }
+
+    /// Every app with a resumed or visible activity, the focused one first (never empty).
+    /// Split-screen and freeform windows resume several apps at once.
+    pub async fn get_foreground_apps(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<foreground::ForegroundApp>> {
         self.check_installed()?;
 
-        let activity_output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &[
-                "-s",
-                device_id,
-                "shell",
-                "dumpsys",
-                "activity",
-                "activities",
-            ],
-        )
-        .await?;
+        let api_level = self.api_level(device_id).await.ok().flatten();
+        let sources = foreground::sources_for_api(api_level);
+        let mut found = Vec::new();
+        let mut errors = Vec::new();
+        for source in sources {
+            let args: Vec<&str> = ["-s", device_id, "shell"]
+                .into_iter()
+                .chain(source.command().iter().copied())
+                .collect();
+            match self.runner.stdout(&self.adb_path, &args).await {
+                Ok(output) => {
+                    found = source.parse_all(&output);
+                    if !found.is_empty() {
+                        break;
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        // Only an unreachable device fails every source.
+        if errors.len() == sources.len() {
+            return Err(errors.remove(0));
+        }
+        if found.is_empty() {
+            return Err(FridaMgrError::Adb(
+                "Unable to detect the foreground app (try unlocking the device and opening the target app)."
+                    .to_string(),
+            ));
+        }
 
-        let mut component = foreground::parse_foreground_component_from_dumpsys_activity_activities(
-            &activity_output,
-        );
-        let record_hint = component.as_ref().and_then(|c| {
-            foreground::find_process_record_near_activity_record(
-                &activity_output,
-                c.line_index,
-                &c.package,
-            )
-        });
-        let mut pid = record_hint.as_ref().map(|r| r.pid);
-        let mut process_hint = record_hint.as_ref().map(|r| r.process.clone());
+        let processes_output = self.list_processes(device_id).await?;
+        let mut apps = Vec::with_capacity(found.len());
+        for found in found {
+            apps.push(
+                self.foreground_process(device_id, found, &processes_output)
+                    .await,
+            );
+        }
+        Ok(apps)
+    }
 
-        if component.is_none() {
-            let window_output = ProcessExecutor::execute_with_output(
-                &self.adb_path,
-                &["-s", device_id, "shell", "dumpsys", "window", "windows"],
-            )
-            .await?;
+    /// PID and name of `package`'s process running as Android user `user`.
+    pub async fn user_process(
+        &self,
+        device_id: &str,
+        package: &str,
+        user: u32,
+    ) -> Result<Option<(u32, String)>> {
+        let output = self.list_processes(device_id).await?;
+        Ok(ps_user_process(&output, package, user))
+    }
 
-            component =
-                foreground::parse_foreground_component_from_dumpsys_window_windows(&window_output);
+    /// Fill in the process name and pid of a foreground match from `pidof`, the process'
+    /// cmdline and the `ps` listing. When the match names its user, only that user's
+    /// processes count: `pidof` would also report the app running in another profile.
+    async fn foreground_process(
+        &self,
+        device_id: &str,
+        found: foreground::ForegroundMatch,
+        processes_output: &str,
+    ) -> foreground::ForegroundApp {
+        let mut pid = found.pid;
+        let mut process_hint = found.process;
+        let component = found.component;
+
+        if let (None, Some(user)) = (pid, found.user) {
+            if let Some((user_pid, process)) =
+                ps_user_process(processes_output, &component.package, user)
+            {
+                pid = Some(user_pid);
+                process_hint.get_or_insert(process);
+            }
         }
 
-        let component = component.ok_or_else(|| {
-            FridaMgrError::Adb(
-                "Unable to detect the foreground app (try unlocking the device and opening the target app)."
-                    .to_string(),
-            )
-        })?;
-
         if pid.is_none() {
-            let pidof_output = ProcessExecutor::execute_with_output(
-                &self.adb_path,
-                &[
-                    "-s",
-                    device_id,
-                    "shell",
-                    "pidof",
-                    component.package.as_str(),
-                ],
-            )
-            .await;
+            let pidof_output = self
+                .runner
+                .stdout(
+                    &self.adb_path,
+                    &[
+                        "-s",
+                        device_id,
+                        "shell",
+                        "pidof",
+                        component.package.as_str(),
+                    ],
+                )
+                .await;
 
             if let Ok(pidof_output) = pidof_output {
                 pid = pidof_output
@@ -593,12 +2108,11 @@ impl AdbClient {
 
         if process_hint.is_none() {
             if let Some(pid) = pid {
-                let proc_path = format!("/proc/{}/cmdline", pid);
-                let cmdline_output = ProcessExecutor::execute_with_output(
-                    &self.adb_path,
-                    &["-s", device_id, "shell", "cat", &proc_path],
-                )
-                .await;
+                let cmd = shell::command(["cat", &format!("/proc/{}/cmdline", pid)]);
+                let cmdline_output = self
+                    .runner
+                    .stdout(&self.adb_path, &["-s", device_id, "shell", &cmd])
+                    .await;
 
                 if let Ok(cmdline_output) = cmdline_output {
                     let cmdline = cmdline_output.split('\0').next().unwrap_or("").trim();
@@ -609,12 +2123,6 @@ impl AdbClient {
             }
         }
 
-        let processes_output = ProcessExecutor::execute_with_output(
-            &self.adb_path,
-            &["-s", device_id, "shell", "ps", "-A"],
-        )
-        .await?;
-
         let package = component.package.clone();
         let package_prefix = format!("{}:", package);
         let package_dot = format!("{}.", package);
@@ -640,13 +2148,16 @@ impl AdbClient {
         }
 
         let process = process_hint.unwrap_or_else(|| component.package.clone());
+        // `pidof` only arrived with toybox in Android 6.
+        let pid = pid.or_else(|| ps_pid(processes_output, &process));
 
-        Ok(foreground::ForegroundApp {
+        foreground::ForegroundApp {
             package: component.package,
             activity: Some(component.activity),
             process,
             pid,
-        })
+            user: found.user,
+        }
     }
 
     pub async fn get_foreground_process_name(&self, device_id: &str) -> Result<String> {
@@ -659,3 +2170,742 @@ impl Default for AdbClient {
         Self::new(None)
     }
 }
+
+/// One line of `adb forward --list`: `<serial> <local> <remote>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forward {
+    pub device_id: String,
+    pub local: String,
+    pub remote: String,
+}
+
+impl Forward {
+    /// Whether this forwards local TCP `port` to the same port on the device.
+    pub fn is_tcp_port(&self, port: u16) -> bool {
+        let spec = format!("tcp:{}", port);
+        self.local == spec && self.remote == spec
+    }
+}
+
+/// Forwards from `adb forward --list` output.
+pub fn parse_forwards(output: &str) -> Vec<Forward> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(Forward {
+                device_id: parts.next()?.to_string(),
+                local: parts.next()?.to_string(),
+                remote: parts.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Devices from `adb devices -l` output.
+pub fn parse_devices(output: &str) -> Vec<Device> {
+    let mut devices = Vec::new();
+
+    for line in output.lines().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() >= 2 {
+            let id = parts[0].to_string();
+            let state = parts[1].to_string();
+
+            let model = parts
+                .iter()
+                .find(|p| p.starts_with("model:"))
+                .map(|p| p.strip_prefix("model:").unwrap_or("unknown"))
+                .unwrap_or("unknown")
+                .to_string();
+            let transport = if parts.iter().any(|p| p.starts_with("usb:")) {
+                Transport::Usb
+            } else if id.starts_with("emulator-") {
+                Transport::Emulator
+            } else if id.contains(':') || id.contains("._adb-tls-connect.") {
+                Transport::Tcp
+            } else {
+                Transport::Usb
+            };
+
+            devices.push(Device {
+                id,
+                model,
+                state,
+                transport,
+                ..Device::default()
+            });
+        }
+    }
+
+    devices
+}
+
+/// Whether `ps` output has a process named `name` or started from a path ending in it.
+/// Exact matching keeps `frida-server` from matching a companion `frida-server32`.
+/// Whether `ps` printed at least one process besides its header.
+fn ps_lists_anything(output: &str) -> bool {
+    output.lines().skip(1).any(|line| !line.trim().is_empty())
+}
+
+/// The root solution named in `su -v` output (`27.0:MAGISKSU`, `1.0.1:KernelSU`,
+/// `10933:APatch`), with its version.
+fn su_implementation(output: &str) -> Option<String> {
+    let line = output.lines().next()?.trim();
+    let (version, tag) = line.split_once(':').unwrap_or(("", line));
+    let tag = tag.to_lowercase();
+    let name = if tag.contains("magisk") {
+        "Magisk"
+    } else if tag.contains("kernelsu") {
+        "KernelSU"
+    } else if tag.contains("apatch") {
+        "APatch"
+    } else if tag.contains("supersu") {
+        "SuperSU"
+    } else {
+        return None;
+    };
+    Some(if version.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, version)
+    })
+}
+
+/// PID of the process named `name`, read from the column under the `PID` header (toybox,
+/// toolbox and busybox `ps` put it in different places).
+fn ps_pid(output: &str, name: &str) -> Option<u32> {
+    let mut lines = output.lines();
+    let column = lines.next()?.split_whitespace().position(|h| h == "PID")?;
+    lines
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|tokens| tokens.last() == Some(&name))
+        .and_then(|tokens| tokens.get(column)?.parse().ok())
+}
+
+/// PID and name of `package`'s process (or one of its `:`/`.` sub-processes) owned by
+/// Android user `user`, whose app uids `ps` shows as `u<user>_a<app id>`.
+fn ps_user_process(output: &str, package: &str, user: u32) -> Option<(u32, String)> {
+    let owner = format!("u{}_", user);
+    let mut lines = output.lines();
+    let column = lines.next()?.split_whitespace().position(|h| h == "PID")?;
+    let processes: Vec<(u32, &str)> = lines
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if !tokens.first()?.starts_with(&owner) {
+                return None;
+            }
+            let name = *tokens.last()?;
+            let belongs = name == package
+                || name
+                    .strip_prefix(package)
+                    .is_some_and(|rest| rest.starts_with(':') || rest.starts_with('.'));
+            belongs.then_some((tokens.get(column)?.parse().ok()?, name))
+        })
+        .collect();
+    processes
+        .iter()
+        .find(|(_, name)| *name == package)
+        .or_else(|| processes.first())
+        .map(|(pid, name)| (*pid, name.to_string()))
+}
+
+/// PID of the process `ps_lists_process` finds: `name`, or `name` run by its full path.
+fn ps_server_pid(output: &str, name: &str) -> Option<u32> {
+    let suffix = format!("/{}", name);
+    let mut lines = output.lines();
+    let column = lines.next()?.split_whitespace().position(|h| h == "PID")?;
+    lines
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|tokens| {
+            tokens
+                .last()
+                .is_some_and(|last| *last == name || last.ends_with(&suffix))
+        })
+        .and_then(|tokens| tokens.get(column)?.parse().ok())
+}
+
+fn ps_lists_process(output: &str, name: &str) -> bool {
+    let suffix = format!("/{}", name);
+    output.lines().any(|line| {
+        line.split_whitespace()
+            .any(|token| token == name || token.ends_with(&suffix))
+    })
+}
+
+/// `primaryCpuAbi` from `dumpsys package` output; `null` (no native code) yields `None`.
+pub fn parse_primary_cpu_abi(dumpsys: &str) -> Option<ArchType> {
+    let abi = dumpsys
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("primaryCpuAbi="))?
+        .trim();
+    match abi {
+        "" | "null" => None,
+        abi => Some(ArchType::from_abi(abi)),
+    }
+}
+
+/// Zygote-forked apps run `app_process32` or `app_process64`; map that to an arch in the
+/// device's family.
+fn arch_from_exe(exe: &str, device_arch: &ArchType) -> Option<ArchType> {
+    let exe = exe.trim();
+    if exe.ends_with("app_process64") {
+        return Some(device_arch.clone());
+    }
+    if !exe.ends_with("app_process32") {
+        return None;
+    }
+    match device_arch {
+        ArchType::Arm64 | ArchType::Arm => Some(ArchType::Arm),
+        ArchType::X8664 | ArchType::X86 => Some(ArchType::X86),
+        ArchType::Auto => None,
+    }
+}
+
+/// The `hex_len`-digit digest from `sha256sum`/`md5sum <file>` output (`<hex>  <file>`).
+/// Old adb shells report a missing command only in the output, so anything that is not a
+/// digest yields `None`.
+fn parse_checksum(output: &str, hex_len: usize) -> Option<String> {
+    let digest = output.split_whitespace().next()?;
+    (digest.len() == hex_len && digest.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| digest.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ScriptedRunner;
+
+    #[test]
+    fn ps_match_is_exact() {
+        let output = "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nroot 4242 1 0 0 0 0 S /data/local/tmp/frida-server32\n";
+        assert!(ps_lists_process(output, "frida-server32"));
+        assert!(!ps_lists_process(output, "frida-server"));
+    }
+
+    #[test]
+    fn reads_primary_cpu_abi() {
+        let output = "Packages:\n  Package [com.example] (1a2b):\n    primaryCpuAbi=armeabi-v7a\n    secondaryCpuAbi=null\n";
+        assert_eq!(parse_primary_cpu_abi(output), Some(ArchType::Arm));
+        assert_eq!(parse_primary_cpu_abi("    primaryCpuAbi=null\n"), None);
+        assert_eq!(parse_primary_cpu_abi("Unable to find package"), None);
+    }
+
+    #[test]
+    fn maps_app_process_to_arch() {
+        assert_eq!(
+            arch_from_exe("/system/bin/app_process32\n", &ArchType::Arm64),
+            Some(ArchType::Arm)
+        );
+        assert_eq!(
+            arch_from_exe("/system/bin/app_process64", &ArchType::X8664),
+            Some(ArchType::X8664)
+        );
+        assert_eq!(
+            arch_from_exe("readlink: /proc/1/exe: Permission denied", &ArchType::Arm64),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_device_list() {
+        let output = "List of devices attached\nR58M123ABC             device usb:1-1 product:beyond1 model:SM_G973F device:beyond1 transport_id:1\n192.168.1.20:5555      offline\n\n";
+        let devices = parse_devices(output);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].id, "R58M123ABC");
+        assert_eq!(devices[0].model, "SM_G973F");
+        assert_eq!(devices[1].state, "offline");
+        assert_eq!(devices[1].model, "unknown");
+        assert_eq!(devices[0].transport, Transport::Usb);
+        assert_eq!(devices[1].transport, Transport::Tcp);
+    }
+
+    #[test]
+    fn parses_forward_list() {
+        let output = "emulator-5554 tcp:27042 tcp:27042\nR58M123ABC tcp:9000 localabstract:foo\n\n";
+        let forwards = parse_forwards(output);
+        assert_eq!(forwards.len(), 2);
+        assert_eq!(forwards[0].device_id, "emulator-5554");
+        assert!(forwards[0].is_tcp_port(27042));
+        assert!(!forwards[1].is_tcp_port(9000));
+        assert_eq!(forwards[1].remote, "localabstract:foo");
+    }
+
+    /// A client replaying `steps` (commands written without the adb path) in order.
+    fn scripted(steps: &[(&str, i32, &str)]) -> (AdbClient, Arc<ScriptedRunner>) {
+        let adb = AdbClient::new(None);
+        let runner = steps
+            .iter()
+            .fold(ScriptedRunner::new(), |runner, (command, code, stdout)| {
+                runner.expect(&format!("{} {}", adb.adb_path(), command), *code, stdout)
+            });
+        let runner = Arc::new(runner);
+        (adb.with_runner(runner.clone()), runner)
+    }
+
+    const PS_WITH_SERVER: &str = "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nroot 4242 1 0 0 0 0 S /data/local/tmp/frida-server\n";
+    const PS_WITHOUT_SERVER: &str =
+        "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nroot 1 0 0 0 0 0 S init\n";
+
+    #[tokio::test]
+    async fn status_from_recorded_ps() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell ps -A", 0, PS_WITH_SERVER),
+            ("-s emulator-5554 shell ps -A", 0, PS_WITHOUT_SERVER),
+        ]);
+        assert_eq!(
+            adb.get_server_status("emulator-5554", "frida-server")
+                .await
+                .unwrap(),
+            "running"
+        );
+        assert_eq!(
+            adb.get_server_status("emulator-5554", "frida-server")
+                .await
+                .unwrap(),
+            "stopped"
+        );
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reads_server_version() {
+        let (adb, runner) = scripted(&[
+            (
+                "-s emulator-5554 shell /data/local/tmp/frida-server --version",
+                0,
+                "16.6.6\n",
+            ),
+            (
+                "-s emulator-5554 shell /data/local/tmp/frida-server --version",
+                1,
+                "/system/bin/sh: /data/local/tmp/frida-server: not found\n",
+            ),
+        ]);
+        for expected in [Some("16.6.6"), None] {
+            assert_eq!(
+                adb.server_version("emulator-5554", "/data/local/tmp/frida-server")
+                    .await
+                    .unwrap()
+                    .as_deref(),
+                expected
+            );
+        }
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_plain_ps_on_legacy_devices() {
+        const TOOLBOX_PS: &str = "USER     PID   PPID  VSIZE  RSS     WCHAN    PC         NAME\nroot      1     0     8936   720   ffffffff 00000000 S /init\nroot      3120  1     18284  5472  ffffffff 00000000 S frida-server\n";
+        let (adb, runner) = scripted(&[
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER     PID   PPID  VSIZE  RSS     WCHAN    PC         NAME\n",
+            ),
+            ("-s emulator-5554 shell ps", 0, TOOLBOX_PS),
+            ("-s emulator-5554 shell ps", 0, TOOLBOX_PS),
+        ]);
+        for _ in 0..2 {
+            assert!(adb
+                .check_server_running("emulator-5554", "frida-server")
+                .await
+                .unwrap());
+        }
+        assert!(runner.remaining().is_empty());
+        assert_eq!(ps_pid(TOOLBOX_PS, "frida-server"), Some(3120));
+        assert_eq!(
+            ps_pid(PS_WITH_SERVER, "/data/local/tmp/frida-server"),
+            Some(4242)
+        );
+        assert_eq!(ps_pid(PS_WITHOUT_SERVER, "frida-server"), None);
+        assert_eq!(ps_server_pid(PS_WITH_SERVER, "frida-server"), Some(4242));
+        assert_eq!(ps_server_pid(TOOLBOX_PS, "frida-server"), Some(3120));
+        assert_eq!(ps_server_pid(PS_WITH_SERVER, "frida-server32"), None);
+    }
+
+    fn push_step(local: &Path) -> String {
+        format!(
+            "-s emulator-5554 push {} /data/local/tmp/frida-server",
+            local.display()
+        )
+    }
+
+    const REMOTE_SHA256: &str = "-s emulator-5554 shell sha256sum /data/local/tmp/frida-server";
+    const REMOTE_MD5: &str = "-s emulator-5554 shell md5sum /data/local/tmp/frida-server";
+
+    #[tokio::test]
+    async fn push_reports_adb_failure() {
+        let local = tempfile::NamedTempFile::new().unwrap();
+        let push = push_step(local.path());
+        let (adb, runner) = scripted(&[
+            (&push, 0, ""),
+            (REMOTE_SHA256, 127, "/system/bin/sh: sha256sum: not found"),
+            (REMOTE_MD5, 127, "/system/bin/sh: md5sum: not found"),
+            (&push, 1, ""),
+        ]);
+        adb.push_file(
+            "emulator-5554",
+            local.path(),
+            "/data/local/tmp/frida-server",
+        )
+        .await
+        .unwrap();
+        assert!(adb
+            .push_file(
+                "emulator-5554",
+                local.path(),
+                "/data/local/tmp/frida-server"
+            )
+            .await
+            .is_err());
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn push_retries_until_the_checksum_matches() {
+        let local = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(local.path(), b"frida-server").unwrap();
+        let push = push_step(local.path());
+        let digest = compute_sha256(local.path()).await.unwrap();
+        let good = format!("{}  /data/local/tmp/frida-server\n", digest);
+        let bad = format!("{}  /data/local/tmp/frida-server\n", "0".repeat(64));
+
+        let (adb, runner) = scripted(&[
+            (&push, 1, ""),
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, &bad),
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, &good),
+        ]);
+        adb.with_push_attempts(3)
+            .push_file(
+                "emulator-5554",
+                local.path(),
+                "/data/local/tmp/frida-server",
+            )
+            .await
+            .unwrap();
+        assert!(runner.remaining().is_empty());
+
+        let (adb, runner) = scripted(&[
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, &bad),
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, &bad),
+        ]);
+        let err = adb
+            .with_push_attempts(2)
+            .push_file(
+                "emulator-5554",
+                local.path(),
+                "/data/local/tmp/frida-server",
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("after 2 attempt(s): checksum mismatch"),
+            "{}",
+            err
+        );
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn push_falls_back_to_md5sum() {
+        let local = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(local.path(), b"frida-server").unwrap();
+        let push = push_step(local.path());
+        let digest = compute_md5(local.path()).await.unwrap();
+        let good = format!("{}  /data/local/tmp/frida-server\n", digest);
+        let bad = format!("{}  /data/local/tmp/frida-server\n", "0".repeat(32));
+
+        // Old toolbox shells exit 0 even for a missing command.
+        let (adb, runner) = scripted(&[
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, "sha256sum: not found"),
+            (REMOTE_MD5, 0, &bad),
+            (&push, 0, ""),
+            (REMOTE_SHA256, 0, "sha256sum: not found"),
+            (REMOTE_MD5, 0, &good),
+        ]);
+        adb.with_push_attempts(2)
+            .push_file(
+                "emulator-5554",
+                local.path(),
+                "/data/local/tmp/frida-server",
+            )
+            .await
+            .unwrap();
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[test]
+    fn parses_checksum_output() {
+        let digest = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        assert_eq!(
+            parse_checksum(&format!("{}  /data/local/tmp/frida-server\n", digest), 64),
+            Some(digest.to_ascii_lowercase())
+        );
+        assert_eq!(parse_checksum("sha256sum: not found", 64), None);
+        assert_eq!(parse_checksum("", 64), None);
+        assert_eq!(
+            parse_checksum("d41d8cd98f00b204e9800998ecf8427e  /data/local/tmp/fs", 32),
+            Some("d41d8cd98f00b204e9800998ecf8427e".to_string())
+        );
+        assert_eq!(parse_checksum(digest, 32), None);
+    }
+
+    #[tokio::test]
+    async fn foreground_falls_back_to_am_stack_list_on_api_28() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell getprop ro.build.version.sdk", 0, "28\n"),
+            ("-s emulator-5554 shell dumpsys activity activities", 0, "ACTIVITY MANAGER ACTIVITIES\n"),
+            (
+                "-s emulator-5554 shell am stack list",
+                0,
+                "Stack id=1 bounds=[0,0][1080,1920] displayId=0 userId=0\n  taskId=12: com.example/.MainActivity bounds=[0,0][1080,1920] userId=0 visible=true topActivity=ComponentInfo{com.example/com.example.MainActivity}\n",
+            ),
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nu0_a123 4242 1 0 0 0 0 S com.example\n",
+            ),
+        ]);
+        let app = adb.get_foreground_app("emulator-5554").await.unwrap();
+        assert_eq!(app.package, "com.example");
+        assert_eq!(app.activity.as_deref(), Some("com.example.MainActivity"));
+        assert_eq!(app.process, "com.example");
+        assert_eq!(app.pid, Some(4242));
+        assert_eq!(app.user, Some(0));
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn foreground_in_a_work_profile_skips_the_primary_users_process() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell getprop ro.build.version.sdk", 0, "34\n"),
+            (
+                "-s emulator-5554 shell dumpsys activity activities",
+                0,
+                "  Resumed activities in task display areas (from top to bottom):\n    Resumed: ActivityRecord{3c1f u10 com.example/.MainActivity t42}\n",
+            ),
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nu0_a123 4242 1 0 0 0 0 S com.example\nu10_a123 5150 1 0 0 0 0 S com.example\n",
+            ),
+        ]);
+        let app = adb.get_foreground_app("emulator-5554").await.unwrap();
+        assert_eq!(app.user, Some(10));
+        assert_eq!(app.pid, Some(5150));
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn kill_server_runs_root_killall_and_verifies() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell ps -A", 0, PS_WITH_SERVER),
+            ("-s emulator-5554 shell su -c id", 0, ROOT_ID),
+            ("-s emulator-5554 shell su -c 'killall frida-server'", 0, ""),
+            ("-s emulator-5554 shell ps -A", 0, PS_WITHOUT_SERVER),
+        ]);
+        adb.kill_server("emulator-5554", "frida-server", "su")
+            .await
+            .unwrap();
+        assert!(runner.remaining().is_empty());
+    }
+
+    const ROOT_ID: &str = "uid=0(root) gid=0(root) context=u:r:magisk:s0\n";
+
+    #[tokio::test]
+    async fn falls_back_to_a_root_command_that_works_and_remembers_it() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell su -c id", 0, "uid=2000(shell)\n"),
+            ("-s emulator-5554 shell su 0 sh -c id", 0, ROOT_ID),
+            (
+                "-s emulator-5554 shell su 0 sh -c 'killall frida-server'",
+                0,
+                "",
+            ),
+            ("-s emulator-5554 shell ps -A", 0, PS_WITHOUT_SERVER),
+        ]);
+        assert_eq!(
+            adb.root_command_for("emulator-5554", "su").await,
+            "su 0 {cmd}"
+        );
+        adb.kill_server("emulator-5554", "frida-server", "su")
+            .await
+            .unwrap();
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn permissive_policy_runs_setenforce_only_when_enforcing() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell getenforce", 0, "Permissive\n"),
+            ("-s emulator-5554 shell getenforce", 0, "Enforcing\n"),
+            ("-s emulator-5554 shell getenforce", 0, "Enforcing\n"),
+            ("-s emulator-5554 shell su -c id", 0, ROOT_ID),
+            ("-s emulator-5554 shell su -c 'setenforce 0'", 0, ""),
+            ("-s emulator-5554 shell getenforce", 0, "Permissive\n"),
+        ]);
+        for (policy, enforcing) in [
+            (SelinuxPolicy::Permissive, false),
+            (SelinuxPolicy::Keep, true),
+            (SelinuxPolicy::Permissive, false),
+        ] {
+            assert_eq!(
+                adb.prepare_selinux("emulator-5554", "su", policy).await,
+                enforcing
+            );
+        }
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn root_method_names_the_su_implementation() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell id -u", 0, "2000\n"),
+            ("-s emulator-5554 shell su -c id", 0, ROOT_ID),
+            ("-s emulator-5554 shell su -v", 0, "27.0:MAGISKSU\n"),
+            ("-s emulator-5554 shell id -u", 0, "0\n"),
+        ]);
+        assert_eq!(
+            adb.root_method("emulator-5554", "su").await.unwrap(),
+            Some("Magisk 27.0 via su".to_string())
+        );
+        assert_eq!(
+            adb.root_method("emulator-5554", "su").await.unwrap(),
+            Some("adbd running as root (adb root)".to_string())
+        );
+        assert!(runner.remaining().is_empty());
+        assert_eq!(
+            su_implementation("v1.0.1:KernelSU"),
+            Some("KernelSU v1.0.1".to_string())
+        );
+        assert_eq!(su_implementation("laotie 1.2"), None);
+    }
+
+    #[tokio::test]
+    async fn device_info_collects_props_root_and_selinux_in_one_pass() {
+        let (adb, runner) = scripted(&[
+            (
+                "-s emulator-5554 shell getprop",
+                0,
+                "[ro.product.model]: [sdk_gphone64_x86_64]\n[ro.product.manufacturer]: [Google]\n[ro.build.version.release]: [13]\n[ro.build.version.sdk]: [33]\n[ro.product.cpu.abilist]: [x86_64,arm64-v8a]\n",
+            ),
+            ("-s emulator-5554 shell id -u", 0, "2000\n"),
+            ("-s emulator-5554 shell su -c id", 0, ROOT_ID),
+            ("-s emulator-5554 shell su -v", 0, "27.0:MAGISKSU\n"),
+            ("-s emulator-5554 shell getenforce", 0, "Enforcing\n"),
+        ]);
+        let info = adb.get_device_info("emulator-5554", "su").await.unwrap();
+        assert_eq!(info.model.as_deref(), Some("sdk_gphone64_x86_64"));
+        assert_eq!(info.manufacturer.as_deref(), Some("Google"));
+        assert_eq!(info.version_label(), "Android 13 (API 33)");
+        assert_eq!(info.abis, ["x86_64", "arm64-v8a"]);
+        assert_eq!(info.root.as_deref(), Some("Magisk 27.0 via su"));
+        assert_eq!(info.selinux.as_deref(), Some("Enforcing"));
+        // The API level is cached for foreground detection.
+        assert_eq!(adb.api_level("emulator-5554").await.unwrap(), Some(33));
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_as_server_is_copied_into_code_cache() {
+        let (adb, runner) = scripted(&[(
+            "-s emulator-5554 shell cat /data/local/tmp/frida-server | run-as com.example.app sh -c 'mkdir -p code_cache && cat > code_cache/frida-server && chmod 700 code_cache/frida-server'; r=$?; rm -f /data/local/tmp/frida-server; exit $r",
+            0,
+            "",
+        )]);
+        let path = adb
+            .install_run_as_server(
+                "emulator-5554",
+                "com.example.app",
+                "/data/local/tmp/frida-server",
+                "frida-server",
+            )
+            .await
+            .unwrap();
+        assert_eq!(path, "/data/data/com.example.app/code_cache/frida-server");
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remote_paths_are_quoted_for_the_root_shell() {
+        let (adb, runner) = scripted(&[
+            ("-s emulator-5554 shell su -c id", 0, ROOT_ID),
+            (
+                "-s emulator-5554 shell su -c 'rm -f '\\''/data/local/tmp/my dir/fs'\\'' /data/local/tmp/fs.log'",
+                0,
+                "",
+            ),
+        ]);
+        adb.remove_remote_files(
+            "emulator-5554",
+            &[
+                "/data/local/tmp/my dir/fs".to_string(),
+                "/data/local/tmp/fs.log".to_string(),
+            ],
+            "su",
+        )
+        .await
+        .unwrap();
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dev_null_is_never_removed() {
+        let (adb, runner) = scripted(&[]);
+        let err = adb
+            .remove_remote_files(
+                "emulator-5554",
+                &[
+                    "/data/local/tmp/fs".to_string(),
+                    SERVER_LOG_DISABLED.to_string(),
+                ],
+                "su",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FridaMgrError::Adb(_)));
+        assert!(runner.remaining().is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_device_reconnects_a_dropped_network_serial() {
+        let (adb, runner) = scripted(&[
+            ("devices -l", 0, "List of devices attached\n"),
+            (
+                "connect 192.168.1.20:5555",
+                0,
+                "connected to 192.168.1.20:5555\n",
+            ),
+            (
+                "devices -l",
+                0,
+                "List of devices attached\n192.168.1.20:5555 device product:x model:Pixel_7 device:x\n",
+            ),
+        ]);
+        let device = adb.get_device(Some("192.168.1.20:5555")).await.unwrap();
+        assert_eq!(device.id, "192.168.1.20:5555");
+        assert!(runner.remaining().is_empty());
+
+        let (adb, _runner) = scripted(&[("devices -l", 0, "List of devices attached\n")]);
+        let err = adb.get_device(Some("R58M123ABC")).await.unwrap_err();
+        assert!(matches!(err, FridaMgrError::DeviceNotFound(serial) if serial == "R58M123ABC"));
+    }
+
+    #[tokio::test]
+    async fn unexpected_commands_fail() {
+        let (adb, _runner) = scripted(&[("devices -l", 0, "List of devices attached\n")]);
+        assert!(adb.list_devices().await.unwrap().is_empty());
+        assert!(adb.list_devices().await.is_err());
+    }
+}
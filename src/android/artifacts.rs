@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+/// `grep -E` pattern marking a file as frida-related when its name alone does not.
+pub const FRIDA_MARKER_PATTERN: &str = "frida|Interceptor|Java\\.perform";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Server,
+    Log,
+    Agent,
+}
+
+impl ArtifactKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArtifactKind::Server => "server",
+            ArtifactKind::Log => "log",
+            ArtifactKind::Agent => "agent",
+        }
+    }
+}
+
+/// A regular file in a scanned directory, its size in bytes and whether its content
+/// matched `FRIDA_MARKER_PATTERN`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedFile {
+    pub name: String,
+    pub size: u64,
+    pub marked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub name: String,
+    pub size: u64,
+    pub kind: ArtifactKind,
+}
+
+/// Parse `<0|1> <size> <name>` lines printed by the device-side scan (`wc -c` may pad the
+/// size with spaces).
+pub fn parse_listing(output: &str) -> Vec<ListedFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (marked, rest) = line.trim_end_matches('\r').split_once(' ')?;
+            let (size, name) = rest.trim_start().split_once(' ')?;
+            if name.is_empty() || name == "*" {
+                return None;
+            }
+            Some(ListedFile {
+                name: name.to_string(),
+                size: size.parse().unwrap_or(0),
+                marked: marked == "1",
+            })
+        })
+        .collect()
+}
+
+/// Pick frida artifacts out of a directory listing: server binaries (`frida-*`, one of
+/// `server_names`, or an extension-less file mentioning frida, which covers custom and
+/// `ephemeral` names), their `.log` files and rotated `.log.1` copies, and agent bundles /
+/// gadget libraries.
+pub fn frida_artifacts(files: &[ListedFile], server_names: &[String]) -> Vec<Artifact> {
+    let is_server = |file: &ListedFile| {
+        let name = file.name.as_str();
+        if [".log", ".log.1", ".js", ".so"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            return false;
+        }
+        name.starts_with("frida-")
+            || server_names.iter().any(|n| n == name)
+            || (file.marked && !name.contains('.'))
+    };
+    let servers: HashSet<&str> = files
+        .iter()
+        .filter(|f| is_server(f))
+        .map(|f| f.name.as_str())
+        .collect();
+
+    files
+        .iter()
+        .filter_map(|file| {
+            let name = file.name.as_str();
+            let kind = if servers.contains(name) {
+                ArtifactKind::Server
+            } else if let Some(stem) = name
+                .strip_suffix(".log")
+                .or_else(|| name.strip_suffix(".log.1"))
+            {
+                if servers.contains(stem)
+                    || stem.starts_with("frida-")
+                    || server_names.iter().any(|n| n == stem)
+                    || is_ephemeral_name(stem)
+                {
+                    ArtifactKind::Log
+                } else {
+                    return None;
+                }
+            } else if name.starts_with("libfrida-gadget") || (name.ends_with(".js") && file.marked)
+            {
+                ArtifactKind::Agent
+            } else {
+                return None;
+            };
+            Some(Artifact {
+                name: file.name.clone(),
+                size: file.size,
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Names `ephemeral spawn` gives its server: eight lowercase hex digits.
+fn is_ephemeral_name(name: &str) -> bool {
+    name.len() == 8
+        && name
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, marked: bool) -> ListedFile {
+        ListedFile {
+            name: name.to_string(),
+            size: 0,
+            marked,
+        }
+    }
+
+    #[test]
+    fn parses_device_listing() {
+        assert_eq!(
+            parse_listing("1 52428800 frida-server\r\n0      0 my notes.txt\n0 0 *\n"),
+            vec![
+                ListedFile {
+                    size: 52428800,
+                    ..file("frida-server", true)
+                },
+                file("my notes.txt", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_servers_logs_and_agents() {
+        let files = vec![
+            file("frida-server", true),
+            file("frida-server.log", false),
+            file("frida-server.log.1", false),
+            file("fs", false),
+            file("fs.log", false),
+            file("3fa9c01e", true),
+            file("0badc0de.log", false),
+            file("_agent.js", true),
+            file("libfrida-gadget.so", true),
+            file("app.apk", true),
+            file("notes.txt", false),
+            file("build.log", false),
+            file("helper.js", false),
+        ];
+        let artifacts = frida_artifacts(&files, &["fs".to_string()]);
+        let found: Vec<(&str, ArtifactKind)> = artifacts
+            .iter()
+            .map(|a| (a.name.as_str(), a.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("frida-server", ArtifactKind::Server),
+                ("frida-server.log", ArtifactKind::Log),
+                ("frida-server.log.1", ArtifactKind::Log),
+                ("fs", ArtifactKind::Server),
+                ("fs.log", ArtifactKind::Log),
+                ("3fa9c01e", ArtifactKind::Server),
+                ("0badc0de.log", ArtifactKind::Log),
+                ("_agent.js", ArtifactKind::Agent),
+                ("libfrida-gadget.so", ArtifactKind::Agent),
+            ]
+        );
+    }
+}
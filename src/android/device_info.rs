@@ -0,0 +1,168 @@
+use crate::config::ArchType;
+
+/// ABIs frida-server is published for, as reported in `ro.product.cpu.abilist`.
+const FRIDA_ABIS: [&str; 5] = ["arm64-v8a", "armeabi-v7a", "armeabi", "x86_64", "x86"];
+
+/// The first Frida release supporting each Android API level that needed runtime changes
+/// (Android 12: 15.1.0, Android 14: 16.1.0).
+const MIN_FRIDA_FOR_API: [(u32, &str); 2] = [(31, "15.1.0"), (34, "16.1.0")];
+
+/// Device properties `status` and `devices --long` report, gathered by
+/// `AdbClient::get_device_info`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// `ro.product.model`
+    pub model: Option<String>,
+    /// `ro.product.manufacturer`
+    pub manufacturer: Option<String>,
+    /// `ro.build.version.release`
+    pub android_version: Option<String>,
+    /// `ro.build.version.sdk`
+    pub api_level: Option<u32>,
+    /// `ro.product.cpu.abilist`
+    pub abis: Vec<String>,
+    /// How root is reached (`AdbClient::root_method`); `None` when the device is not rooted.
+    pub root: Option<String>,
+    /// `getenforce` output; `None` when it could not be read.
+    pub selinux: Option<String>,
+}
+
+impl DeviceInfo {
+    /// The properties found in `getprop` output (`[key]: [value]` lines); root and SELinux
+    /// are left unknown.
+    pub fn from_getprop(getprop: &str) -> Self {
+        let mut info = DeviceInfo::default();
+        for line in getprop.lines() {
+            let Some((key, value)) = line.trim().split_once("]: [") else {
+                continue;
+            };
+            let key = key.trim_start_matches('[');
+            let value = value.trim_end_matches(']').trim();
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "ro.product.model" => info.model = Some(value.to_string()),
+                "ro.product.manufacturer" => info.manufacturer = Some(value.to_string()),
+                "ro.build.version.release" => info.android_version = Some(value.to_string()),
+                "ro.build.version.sdk" => info.api_level = value.parse().ok(),
+                "ro.product.cpu.abilist" => {
+                    info.abis = value.split(',').map(|abi| abi.trim().to_string()).collect()
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+
+    /// `Android 14 (API 34)`, or whichever half is known.
+    pub fn version_label(&self) -> String {
+        match (&self.android_version, self.api_level) {
+            (Some(version), Some(api)) => format!("Android {} (API {})", version, api),
+            (Some(version), None) => format!("Android {}", version),
+            (None, Some(api)) => format!("API {}", api),
+            (None, None) => "unknown".to_string(),
+        }
+    }
+
+    /// Why frida `frida_version` (the project's, when there is one) built for `arch` may not
+    /// work on this device; empty when nothing stands out.
+    pub fn compatibility_issues(
+        &self,
+        frida_version: Option<&str>,
+        arch: &ArchType,
+    ) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if !self.abis.is_empty()
+            && !self
+                .abis
+                .iter()
+                .any(|abi| FRIDA_ABIS.contains(&abi.as_str()))
+        {
+            issues.push(format!(
+                "no frida-server build for ABIs {}",
+                self.abis.join(", ")
+            ));
+        } else if *arch != ArchType::Auto
+            && !self.abis.is_empty()
+            && !self.abis.iter().any(|abi| ArchType::from_abi(abi) == *arch)
+        {
+            issues.push(format!(
+                "android.arch = \"{}\" is not among the device's ABIs ({})",
+                arch.to_str(),
+                self.abis.join(", ")
+            ));
+        }
+
+        if let (Some(version), Some(api)) = (frida_version, self.api_level) {
+            let required = MIN_FRIDA_FOR_API
+                .iter()
+                .rev()
+                .find(|(min_api, _)| api >= *min_api)
+                .map(|(_, min_frida)| *min_frida);
+            if let (Some(required), Ok(current)) = (required, semver::Version::parse(version)) {
+                if current < semver::Version::parse(required).unwrap() {
+                    issues.push(format!(
+                        "frida {} predates {} support (needs {} or newer)",
+                        version,
+                        self.version_label(),
+                        required
+                    ));
+                }
+            }
+        }
+
+        if self.root.is_none() {
+            issues.push(
+                "not rooted: frida-server cannot attach to other apps (use `spawn --run-as` for debuggable apps)"
+                    .to_string(),
+            );
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIXEL_GETPROP: &str = "[ro.build.version.release]: [14]\n[ro.build.version.sdk]: [34]\n[ro.product.cpu.abilist]: [arm64-v8a,armeabi-v7a,armeabi]\n[ro.product.manufacturer]: [Google]\n[ro.product.model]: [Pixel 7]\n[ro.secure]: [1]\n[ro.debuggable]: []\n";
+
+    #[test]
+    fn reads_device_properties_from_getprop() {
+        let info = DeviceInfo::from_getprop(PIXEL_GETPROP);
+        assert_eq!(info.model.as_deref(), Some("Pixel 7"));
+        assert_eq!(info.manufacturer.as_deref(), Some("Google"));
+        assert_eq!(info.android_version.as_deref(), Some("14"));
+        assert_eq!(info.api_level, Some(34));
+        assert_eq!(info.abis, ["arm64-v8a", "armeabi-v7a", "armeabi"]);
+        assert_eq!(info.version_label(), "Android 14 (API 34)");
+        assert_eq!(info.root, None);
+    }
+
+    #[test]
+    fn flags_old_frida_wrong_arch_and_missing_root() {
+        let mut info = DeviceInfo::from_getprop(PIXEL_GETPROP);
+        info.root = Some("Magisk 27.0 via su".to_string());
+        assert!(info
+            .compatibility_issues(Some("17.5.0"), &ArchType::Auto)
+            .is_empty());
+        assert!(info
+            .compatibility_issues(Some("not-a-version"), &ArchType::Arm)
+            .is_empty());
+
+        let issues = info.compatibility_issues(Some("16.0.19"), &ArchType::X86);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("android.arch = \"x86\""));
+        assert!(issues[1].contains("needs 16.1.0 or newer"));
+
+        info.root = None;
+        info.abis = vec!["riscv64".to_string()];
+        let issues = info.compatibility_issues(None, &ArchType::Auto);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("no frida-server build for ABIs riscv64"));
+        assert!(issues[1].starts_with("not rooted"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::config::ArchType;
+use crate::core::error::Result;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+/// `e_ident` plus `e_type`, enough to reach `e_machine`.
+const E_MACHINE_OFFSET: usize = 18;
+
+const EM_386: u16 = 0x03;
+const EM_ARM: u16 = 0x28;
+const EM_X86_64: u16 = 0x3e;
+const EM_AARCH64: u16 = 0xb7;
+
+/// How a binary built for one arch fares on a device of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchCompatibility {
+    Match,
+    /// 32-bit binary on the 64-bit variant of the same family: runs, but cannot
+    /// instrument 64-bit processes.
+    Compat32,
+    Mismatch,
+}
+
+/// Architecture from an ELF header, or `None` when the bytes are not an ELF file for
+/// one of the Android architectures.
+pub fn parse_elf_arch(header: &[u8]) -> Option<ArchType> {
+    if header.len() < E_MACHINE_OFFSET + 2 || &header[..4] != ELF_MAGIC {
+        return None;
+    }
+
+    let bytes = [header[E_MACHINE_OFFSET], header[E_MACHINE_OFFSET + 1]];
+    // EI_DATA: 1 = little endian, 2 = big endian
+    let machine = match header[5] {
+        2 => u16::from_be_bytes(bytes),
+        _ => u16::from_le_bytes(bytes),
+    };
+
+    match machine {
+        EM_386 => Some(ArchType::X86),
+        EM_ARM => Some(ArchType::Arm),
+        EM_X86_64 => Some(ArchType::X8664),
+        EM_AARCH64 => Some(ArchType::Arm64),
+        _ => None,
+    }
+}
+
+/// Read just enough of `path` to identify its architecture.
+pub async fn read_elf_arch(path: &Path) -> Result<Option<ArchType>> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut header = [0u8; E_MACHINE_OFFSET + 2];
+    let mut read = 0;
+    while read < header.len() {
+        let n = file.read(&mut header[read..]).await?;
+        if n == 0 {
+            break;
+        }
+        read += n;
+    }
+    Ok(parse_elf_arch(&header[..read]))
+}
+
+pub fn arch_compatibility(binary: &ArchType, device: &ArchType) -> ArchCompatibility {
+    match (binary, device) {
+        _ if binary == device => ArchCompatibility::Match,
+        (ArchType::Arm, ArchType::Arm64) | (ArchType::X86, ArchType::X8664) => {
+            ArchCompatibility::Compat32
+        }
+        _ => ArchCompatibility::Mismatch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(class: u8, data: u8, machine: [u8; 2]) -> Vec<u8> {
+        let mut h = vec![0u8; 20];
+        h[..4].copy_from_slice(ELF_MAGIC);
+        h[4] = class;
+        h[5] = data;
+        h[18..20].copy_from_slice(&machine);
+        h
+    }
+
+    #[test]
+    fn reads_machine_type() {
+        assert_eq!(
+            parse_elf_arch(&header(2, 1, [0xb7, 0x00])),
+            Some(ArchType::Arm64)
+        );
+        assert_eq!(
+            parse_elf_arch(&header(2, 1, [0x3e, 0x00])),
+            Some(ArchType::X8664)
+        );
+        assert_eq!(
+            parse_elf_arch(&header(1, 1, [0x28, 0x00])),
+            Some(ArchType::Arm)
+        );
+        assert_eq!(
+            parse_elf_arch(&header(1, 2, [0x00, 0x03])),
+            Some(ArchType::X86)
+        );
+        assert_eq!(parse_elf_arch(b"#!/bin/sh\necho hi\n\n\n\n"), None);
+        assert_eq!(parse_elf_arch(&header(2, 1, [0x08, 0x00])), None);
+    }
+
+    #[test]
+    fn classifies_compatibility() {
+        assert_eq!(
+            arch_compatibility(&ArchType::Arm64, &ArchType::Arm64),
+            ArchCompatibility::Match
+        );
+        assert_eq!(
+            arch_compatibility(&ArchType::Arm, &ArchType::Arm64),
+            ArchCompatibility::Compat32
+        );
+        assert_eq!(
+            arch_compatibility(&ArchType::Arm64, &ArchType::X8664),
+            ArchCompatibility::Mismatch
+        );
+    }
+}
@@ -7,6 +7,8 @@ pub struct ForegroundApp {
     pub activity: Option<String>,
     pub process: String,
     pub pid: Option<u32>,
+    /// Android user (profile) the activity runs in, when the source reports it.
+    pub user: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,7 +26,7 @@ pub struct ProcessRecord {
 
 static DUMPSYS_ACTIVITY_COMPONENT_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
-        r"(?:mResumedActivity|ResumedActivity|mFocusedActivity):\s+ActivityRecord\{[^\}]*\s(?P<component>[A-Za-z0-9_\.]+/\.*[A-Za-z0-9_\.$]+)\b",
+        r"\b(?:mResumedActivity|ResumedActivity|topResumedActivity|mFocusedActivity|Resumed)[:=]\s*ActivityRecord\{[^\}]*\s(?P<component>[A-Za-z0-9_\.]+/\.*[A-Za-z0-9_\.$]+)\b",
     )
     .expect("valid regex")
 });
@@ -36,11 +38,175 @@ static DUMPSYS_WINDOW_COMPONENT_RE: Lazy<Regex> = Lazy::new(|| {
     .expect("valid regex")
 });
 
+static DUMPSYS_ACTIVITY_TOP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*ACTIVITY\s+(?P<component>[A-Za-z0-9_\.]+/\.*[A-Za-z0-9_\.$]+)\s+\S+\s+pid=(?P<pid>\d+)?",
+    )
+    .expect("valid regex")
+});
+
+static AM_STACK_TASK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*taskId=\d+:\s+(?P<component>[A-Za-z0-9_\.]+/\.*[A-Za-z0-9_\.$]+)\b.*\bvisible=true\b(?:.*\btopActivity=ComponentInfo\{(?P<top>[A-Za-z0-9_\.]+/\.*[A-Za-z0-9_\.$]+)\})?",
+    )
+    .expect("valid regex")
+});
+
+/// `u10` in `ActivityRecord{3c1f u10 ...}` / `Window{8f2c u10 ...}`, or `userId=10` in
+/// `am stack list`.
+static RECORD_USER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{[0-9a-f]+ u(?P<u>\d+) |\buserId=(?P<id>\d+)\b").expect("valid regex")
+});
+
 static PROCESS_RECORD_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\b(?P<pid>\d+):(?P<process>[A-Za-z0-9_\.]+(?:(?::|\.)[A-Za-z0-9_\.]+)*)/")
         .expect("valid regex")
 });
 
+/// A place the foreground activity can be read from. Which ones exist and what they print
+/// depends on the Android version (and some OEM ROMs drop lines), so `sources_for_api`
+/// picks an order per API level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForegroundSource {
+    /// `dumpsys activity activities`: `mFocusedActivity` (API ≤ 25), `mResumedActivity`
+    /// (API 24-30), `ResumedActivity` (API 29+) or `Resumed:` / `topResumedActivity=`
+    /// (API 31+), with the `ProcessRecord` that follows it.
+    ActivityActivities,
+    /// `am stack list` (API 24-30): the first visible task and its `topActivity`.
+    AmStackList,
+    /// `dumpsys window windows`: `mCurrentFocus` or `mFocusedApp`.
+    WindowWindows,
+    /// `dumpsys activity top`: the last `ACTIVITY` block and its pid.
+    ActivityTop,
+}
+
+/// What a source reported about the foreground app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForegroundMatch {
+    pub component: ForegroundComponent,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+    pub user: Option<u32>,
+}
+
+impl ForegroundSource {
+    /// Arguments to `adb shell`.
+    pub fn command(&self) -> &'static [&'static str] {
+        match self {
+            ForegroundSource::ActivityActivities => &["dumpsys", "activity", "activities"],
+            ForegroundSource::AmStackList => &["am", "stack", "list"],
+            ForegroundSource::WindowWindows => &["dumpsys", "window", "windows"],
+            ForegroundSource::ActivityTop => &["dumpsys", "activity", "top"],
+        }
+    }
+
+    /// The app with the focused activity, when the output names one.
+    pub fn parse(&self, output: &str) -> Option<ForegroundMatch> {
+        self.parse_all(output).into_iter().next()
+    }
+
+    /// Every app with a resumed or visible activity, the focused one first: more than one
+    /// in split-screen or freeform windows, for the sources that list them all.
+    pub fn parse_all(&self, output: &str) -> Vec<ForegroundMatch> {
+        let bare = |component| ForegroundMatch {
+            component,
+            pid: None,
+            process: None,
+            user: None,
+        };
+        let matches: Vec<ForegroundMatch> = match self {
+            ForegroundSource::ActivityActivities => {
+                parse_resumed_components_from_dumpsys_activity_activities(output)
+                    .into_iter()
+                    .map(|component| {
+                        let record = find_process_record_near_activity_record(
+                            output,
+                            component.line_index,
+                            &component.package,
+                        );
+                        ForegroundMatch {
+                            component,
+                            pid: record.as_ref().map(|r| r.pid),
+                            process: record.map(|r| r.process),
+                            user: None,
+                        }
+                    })
+                    .collect()
+            }
+            ForegroundSource::AmStackList => parse_visible_from_am_stack_list(output)
+                .into_iter()
+                .map(bare)
+                .collect(),
+            ForegroundSource::WindowWindows => {
+                parse_foreground_component_from_dumpsys_window_windows(output)
+                    .map(bare)
+                    .into_iter()
+                    .collect()
+            }
+            ForegroundSource::ActivityTop => parse_foreground_from_dumpsys_activity_top(output)
+                .map(|(component, pid)| ForegroundMatch {
+                    component,
+                    pid,
+                    process: None,
+                    user: None,
+                })
+                .into_iter()
+                .collect(),
+        };
+
+        let lines: Vec<&str> = output.lines().collect();
+        let mut seen = Vec::new();
+        matches
+            .into_iter()
+            .map(|m| ForegroundMatch {
+                user: lines
+                    .get(m.component.line_index)
+                    .and_then(|line| record_user(line)),
+                ..m
+            })
+            .filter(|m| {
+                let new = !seen.contains(&m.component.package);
+                seen.push(m.component.package.clone());
+                new
+            })
+            .collect()
+    }
+}
+
+/// The user an activity or task line belongs to.
+pub fn record_user(line: &str) -> Option<u32> {
+    let caps = RECORD_USER_RE.captures(line)?;
+    caps.name("u")
+        .or_else(|| caps.name("id"))?
+        .as_str()
+        .parse()
+        .ok()
+}
+
+/// The apps whose package or process name contains `prefer`, ignoring case.
+pub fn preferred<'a>(apps: &'a [ForegroundApp], prefer: &str) -> Vec<&'a ForegroundApp> {
+    let needle = prefer.to_lowercase();
+    apps.iter()
+        .filter(|app| {
+            app.package.to_lowercase().contains(&needle)
+                || app.process.to_lowercase().contains(&needle)
+        })
+        .collect()
+}
+
+/// Sources to try, most reliable first, on a device with API level `api_level` (`None`
+/// when `ro.build.version.sdk` could not be read).
+pub fn sources_for_api(api_level: Option<u32>) -> &'static [ForegroundSource] {
+    use ForegroundSource::*;
+    match api_level {
+        // `am stack` arrived in Android 7 and was removed in Android 12.
+        Some(24..=30) => &[ActivityActivities, AmStackList, WindowWindows, ActivityTop],
+        // Some Android 5-7 builds print no resumed activity or focused window at all;
+        // `dumpsys activity top` has listed the top activity with its pid since 4.x.
+        _ => &[ActivityActivities, WindowWindows, ActivityTop],
+    }
+}
+
 pub fn parse_component(component: &str, line_index: usize) -> Option<ForegroundComponent> {
     let (package, activity) = component.split_once('/')?;
     let activity = activity
@@ -58,13 +224,23 @@ pub fn parse_component(component: &str, line_index: usize) -> Option<ForegroundC
 pub fn parse_foreground_component_from_dumpsys_activity_activities(
     output: &str,
 ) -> Option<ForegroundComponent> {
-    for (idx, line) in output.lines().enumerate() {
-        if let Some(caps) = DUMPSYS_ACTIVITY_COMPONENT_RE.captures(line) {
-            let component = caps.name("component")?.as_str();
-            return parse_component(component, idx);
-        }
-    }
-    None
+    parse_resumed_components_from_dumpsys_activity_activities(output)
+        .into_iter()
+        .next()
+}
+
+/// Every resumed (or focused) activity line, in output order: the top task comes first.
+pub fn parse_resumed_components_from_dumpsys_activity_activities(
+    output: &str,
+) -> Vec<ForegroundComponent> {
+    output
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let caps = DUMPSYS_ACTIVITY_COMPONENT_RE.captures(line)?;
+            parse_component(caps.name("component")?.as_str(), idx)
+        })
+        .collect()
 }
 
 pub fn parse_foreground_component_from_dumpsys_window_windows(
@@ -79,6 +255,37 @@ pub fn parse_foreground_component_from_dumpsys_window_windows(
     None
 }
 
+/// The visible tasks in `am stack list` (stacks are listed top to bottom), each by its
+/// `topActivity` rather than the activity that started the task.
+pub fn parse_visible_from_am_stack_list(output: &str) -> Vec<ForegroundComponent> {
+    output
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let caps = AM_STACK_TASK_RE.captures(line)?;
+            let component = caps.name("top").or_else(|| caps.name("component"))?;
+            parse_component(component.as_str(), idx)
+        })
+        .collect()
+}
+
+/// The last `ACTIVITY` block of `dumpsys activity top` (the focused one) and its pid, if
+/// the process is running.
+pub fn parse_foreground_from_dumpsys_activity_top(
+    output: &str,
+) -> Option<(ForegroundComponent, Option<u32>)> {
+    output
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let caps = DUMPSYS_ACTIVITY_TOP_RE.captures(line)?;
+            let component = parse_component(caps.name("component")?.as_str(), idx)?;
+            let pid = caps.name("pid").and_then(|pid| pid.as_str().parse().ok());
+            Some((component, pid))
+        })
+        .last()
+}
+
 pub fn find_process_name_near_activity_record(
     output: &str,
     start_line: usize,
@@ -165,4 +372,172 @@ mod tests {
         assert_eq!(record.pid, 4242);
         assert_eq!(record.process, "com.example:remote");
     }
+
+    #[test]
+    fn parses_foreground_from_activity_top() {
+        let output = r#"
+TASK com.android.launcher3 id=1
+  ACTIVITY com.android.launcher3/.Launcher 41a2c8e8 pid=912
+TASK com.example id=7
+  ACTIVITY com.example/.MainActivity 41d0f2a0 pid=4242
+    Local Activity 41c3 State:
+        "#;
+        let (fg, pid) = parse_foreground_from_dumpsys_activity_top(output).unwrap();
+        assert_eq!(fg.package, "com.example");
+        assert_eq!(fg.activity, "com.example.MainActivity");
+        assert_eq!(pid, Some(4242));
+
+        let (fg, pid) = parse_foreground_from_dumpsys_activity_top(
+            "  ACTIVITY com.example/com.example.Main 41d0 pid=(not running)",
+        )
+        .unwrap();
+        assert_eq!(fg.activity, "com.example.Main");
+        assert_eq!(pid, None);
+    }
+
+    const API19_ACTIVITIES: &str = r#"
+ACTIVITY MANAGER ACTIVITIES (dumpsys activity activities)
+  Stack #1:
+    Task id #7
+        Run #0: ActivityRecord{41d0f2a0 u0 com.example/.MainActivity t7}
+    mResumedActivity: ActivityRecord{41d0f2a0 u0 com.example/.MainActivity t7}
+        app=ProcessRecord{41c2a1b8 4242:com.example/u0a123}
+  mFocusedActivity: ActivityRecord{41d0f2a0 u0 com.example/.MainActivity t7}
+"#;
+
+    const API24_STACK_LIST: &str = r#"
+Stack id=1 bounds=[0,0][1080,1920] displayId=0 userId=0
+  taskId=12: com.example/.MainActivity bounds=[0,0][1080,1920] userId=0 visible=true topActivity=ComponentInfo{com.example/com.example.DetailActivity}
+Stack id=0 bounds=[0,0][1080,1920] displayId=0 userId=0
+  taskId=3: com.google.android.apps.nexuslauncher/.NexusLauncherActivity bounds=[0,0][1080,1920] userId=0 visible=false topActivity=ComponentInfo{com.google.android.apps.nexuslauncher/com.google.android.apps.nexuslauncher.NexusLauncherActivity}
+"#;
+
+    const API29_ACTIVITIES: &str = r#"
+Display #0 (activities from top to bottom):
+  Stack #42: type=standard mode=fullscreen
+    * TaskRecord{9a1 #42 A=com.example U=0 StackId=42 sz=1}
+      * Hist #0: ActivityRecord{d0c1 u0 com.example/.MainActivity t42}
+          app=ProcessRecord{9f3 4242:com.example/u0a123}
+    mResumedActivity: ActivityRecord{d0c1 u0 com.example/.MainActivity t42}
+ ResumedActivity: ActivityRecord{d0c1 u0 com.example/.MainActivity t42}
+"#;
+
+    const API34_ACTIVITIES: &str = r#"
+Display #0 (activities from top to bottom):
+  * Task{8c1 #42 type=standard A=10123:com.example U=0 visible=true mode=fullscreen sz=1}
+    mLastPausedActivity: ActivityRecord{77a u0 com.android.launcher3/.uioverrides.QuickstepLauncher t2}
+    topResumedActivity=ActivityRecord{3c1f u0 com.example/.ui.HomeActivity t42}
+    * Hist  #0: ActivityRecord{3c1f u0 com.example/.ui.HomeActivity t42}
+      app=ProcessRecord{5e2 4242:com.example/u0a123}
+  Resumed activities in task display areas (from top to bottom):
+    Resumed: ActivityRecord{3c1f u0 com.example/.ui.HomeActivity t42}
+"#;
+
+    /// A ROM that prints no resumed activity, only stale ones.
+    const OEM_ACTIVITIES: &str = r#"
+Display #0 (activities from top to bottom):
+    mLastResumedActivity: ActivityRecord{12 u0 com.android.settings/.Settings t9}
+    mLastPausedActivity: ActivityRecord{13 u0 com.android.settings/.Settings t9}
+"#;
+
+    const OEM_WINDOWS: &str = r#"
+WINDOW MANAGER WINDOWS (dumpsys window windows)
+  mCurrentFocus=Window{8f2c u0 com.example/com.example.MainActivity}
+  mFocusedApp=ActivityRecord{3c1f u0 com.example/.MainActivity t42}
+"#;
+
+    #[test]
+    fn picks_sources_by_api_level() {
+        use ForegroundSource::*;
+        assert_eq!(
+            sources_for_api(Some(19)),
+            &[ActivityActivities, WindowWindows, ActivityTop]
+        );
+        assert_eq!(sources_for_api(Some(24))[1], AmStackList);
+        assert_eq!(sources_for_api(Some(30))[1], AmStackList);
+        assert!(!sources_for_api(Some(34)).contains(&AmStackList));
+        assert!(!sources_for_api(None).contains(&AmStackList));
+    }
+
+    #[test]
+    fn parses_fixtures_for_each_api_level() {
+        let fg = ForegroundSource::ActivityActivities
+            .parse(API19_ACTIVITIES)
+            .unwrap();
+        assert_eq!(fg.component.activity, "com.example.MainActivity");
+        assert_eq!(fg.pid, Some(4242));
+
+        let fg = ForegroundSource::AmStackList
+            .parse(API24_STACK_LIST)
+            .unwrap();
+        assert_eq!(fg.component.package, "com.example");
+        assert_eq!(fg.component.activity, "com.example.DetailActivity");
+
+        let fg = ForegroundSource::ActivityActivities
+            .parse(API29_ACTIVITIES)
+            .unwrap();
+        assert_eq!(fg.component.activity, "com.example.MainActivity");
+
+        let fg = ForegroundSource::ActivityActivities
+            .parse(API34_ACTIVITIES)
+            .unwrap();
+        assert_eq!(fg.component.activity, "com.example.ui.HomeActivity");
+        assert_eq!(fg.pid, Some(4242));
+        assert_eq!(fg.process.as_deref(), Some("com.example"));
+    }
+
+    #[test]
+    fn falls_through_when_an_oem_rom_drops_the_resumed_activity() {
+        assert_eq!(
+            ForegroundSource::ActivityActivities.parse(OEM_ACTIVITIES),
+            None
+        );
+        let fg = ForegroundSource::WindowWindows.parse(OEM_WINDOWS).unwrap();
+        assert_eq!(fg.component.activity, "com.example.MainActivity");
+        assert!(parse_visible_from_am_stack_list(
+            "  taskId=3: com.android.launcher3/.Launcher bounds=[0,0][1080,1920] userId=0 visible=false"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn lists_every_app_in_split_screen() {
+        let output = r#"
+Display #0 (activities from top to bottom):
+  * Task{8c1 #42 type=standard A=10123:com.example U=0 visible=true mode=multi-window sz=1}
+    topResumedActivity=ActivityRecord{3c1f u0 com.example/.MainActivity t42}
+      app=ProcessRecord{5e2 4242:com.example/u0a123}
+  * Task{9d2 #43 type=standard A=10088:org.videolan.vlc U=0 visible=true mode=multi-window sz=1}
+      app=ProcessRecord{6f1 5150:org.videolan.vlc/u0a88}
+  Resumed activities in task display areas (from top to bottom):
+    Resumed: ActivityRecord{3c1f u0 com.example/.MainActivity t42}
+    Resumed: ActivityRecord{4d20 u0 org.videolan.vlc/.gui.MainActivity t43}
+"#;
+        let apps = ForegroundSource::ActivityActivities.parse_all(output);
+        let packages: Vec<&str> = apps.iter().map(|m| m.component.package.as_str()).collect();
+        assert_eq!(packages, ["com.example", "org.videolan.vlc"]);
+        assert_eq!(apps[0].pid, Some(4242));
+
+        let stacks = "  taskId=42: com.example/.MainActivity bounds=[0,0][1080,960] userId=0 visible=true\n  taskId=43: org.videolan.vlc/.gui.MainActivity bounds=[0,960][1080,1920] userId=0 visible=true\n";
+        assert_eq!(ForegroundSource::AmStackList.parse_all(stacks).len(), 2);
+    }
+
+    #[test]
+    fn prefers_by_package_or_process_substring() {
+        let app = |package: &str, process: &str| ForegroundApp {
+            package: package.to_string(),
+            activity: None,
+            process: process.to_string(),
+            pid: None,
+            user: None,
+        };
+        let apps = [
+            app("com.example", "com.example"),
+            app("org.videolan.vlc", "org.videolan.vlc:remote"),
+        ];
+        assert_eq!(preferred(&apps, "VLC")[0].package, "org.videolan.vlc");
+        assert_eq!(preferred(&apps, ":remote").len(), 1);
+        assert_eq!(preferred(&apps, "com.").len(), 1);
+        assert!(preferred(&apps, "chrome").is_empty());
+    }
 }
@@ -0,0 +1,54 @@
+/// Gadget library name inside the app's `code_cache`.
+pub const GADGET_LIB: &str = "libfrida-gadget.so";
+/// Gadget reads its config from the library path with `.so` replaced by `.config.so`.
+pub const GADGET_CONFIG: &str = "libfrida-gadget.config.so";
+/// Listening port on the device, away from frida-server's default 27042.
+pub const GADGET_PORT: u16 = 27052;
+/// Process name Gadget reports; spawning it resumes the waiting app.
+pub const GADGET_SPAWN_TARGET: &str = "re.frida.Gadget";
+
+/// Gadget config: listen on the device's loopback (reached through `adb forward`) and
+/// block the app until a client attaches.
+pub fn gadget_config(port: u16) -> String {
+    serde_json::json!({
+        "interaction": {
+            "type": "listen",
+            "address": "127.0.0.1",
+            "port": port,
+            "on_load": "wait"
+        }
+    })
+    .to_string()
+}
+
+/// Launcher component from `cmd package resolve-activity --brief <package>` output.
+pub fn parse_resolved_activity(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.contains('/') && !line.contains(' '))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_listens_and_waits() {
+        let config: serde_json::Value = serde_json::from_str(&gadget_config(27052)).unwrap();
+        assert_eq!(config["interaction"]["type"], "listen");
+        assert_eq!(config["interaction"]["port"], 27052);
+        assert_eq!(config["interaction"]["on_load"], "wait");
+    }
+
+    #[test]
+    fn parses_launcher_component() {
+        let output = "priority=0 preferredOrder=0 match=0x108000 specificIndex=-1 isDefault=true\ncom.example.app/.MainActivity\n";
+        assert_eq!(
+            parse_resolved_activity(output).as_deref(),
+            Some("com.example.app/.MainActivity")
+        );
+        assert_eq!(parse_resolved_activity("No activity found\n"), None);
+    }
+}
@@ -0,0 +1,563 @@
+use crate::core::error::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    Activity,
+    Service,
+    Receiver,
+    Provider,
+}
+
+impl ComponentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentKind::Activity => "activity",
+            ComponentKind::Service => "service",
+            ComponentKind::Receiver => "receiver",
+            ComponentKind::Provider => "provider",
+        }
+    }
+
+    fn from_tag(tag: &[u8]) -> Option<Self> {
+        match tag {
+            b"activity" | b"activity-alias" => Some(ComponentKind::Activity),
+            b"service" => Some(ComponentKind::Service),
+            b"receiver" => Some(ComponentKind::Receiver),
+            b"provider" => Some(ComponentKind::Provider),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntentFilter {
+    pub actions: Vec<String>,
+    pub categories: Vec<String>,
+    pub schemes: Vec<String>,
+    /// `host` or `host:port`.
+    pub hosts: Vec<String>,
+    /// Literal paths as-is, prefixes as `/prefix*`, suffixes as `*suffix` and patterns in
+    /// Android's own glob syntax.
+    pub paths: Vec<String>,
+    /// `android:autoVerify`, i.e. a verified App Link.
+    pub auto_verify: bool,
+}
+
+impl IntentFilter {
+    /// Opens URIs from other apps or the browser: a `VIEW` filter with a scheme.
+    pub fn is_deep_link(&self) -> bool {
+        !self.schemes.is_empty()
+            && self
+                .actions
+                .iter()
+                .any(|a| a == "android.intent.action.VIEW")
+    }
+
+    /// Every `scheme://host/path` combination the filter matches (Android merges all
+    /// `<data>` elements of one filter).
+    pub fn uri_patterns(&self) -> Vec<String> {
+        let mut patterns = Vec::new();
+        for scheme in &self.schemes {
+            if self.hosts.is_empty() {
+                patterns.push(format!("{}:", scheme));
+                continue;
+            }
+            for host in &self.hosts {
+                if self.paths.is_empty() {
+                    patterns.push(format!("{}://{}", scheme, host));
+                }
+                for path in &self.paths {
+                    patterns.push(format!("{}://{}{}", scheme, host, path));
+                }
+            }
+        }
+        patterns
+    }
+}
+
+/// An activity, service, receiver or provider declared in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub kind: ComponentKind,
+    /// Fully qualified class name.
+    pub name: String,
+    /// `android:exported`, or whether it has intent filters when the attribute is
+    /// missing (the default before Android 12).
+    pub exported: bool,
+    pub filters: Vec<IntentFilter>,
+}
+
+impl Component {
+    /// `package/.Class` (or `package/full.Class` outside the package), as taken by `am -n`.
+    pub fn component_name(&self, package: &str) -> String {
+        match self.name.strip_prefix(package) {
+            Some(rest) if rest.starts_with('.') => format!("{}/{}", package, rest),
+            _ => format!("{}/{}", package, self.name),
+        }
+    }
+
+    /// An `am` command line that launches this component (providers are not launched).
+    pub fn am_command(&self, package: &str) -> Option<String> {
+        let component = self.component_name(package);
+        let command = match self.kind {
+            ComponentKind::Activity => format!("am start -n {}", component),
+            ComponentKind::Service => format!("am startservice -n {}", component),
+            ComponentKind::Receiver => match self.filters.iter().flat_map(|f| &f.actions).next() {
+                Some(action) => format!("am broadcast -n {} -a {}", component, action),
+                None => format!("am broadcast -n {}", component),
+            },
+            ComponentKind::Provider => return None,
+        };
+        Some(command)
+    }
+
+    pub fn is_launcher(&self) -> bool {
+        self.kind == ComponentKind::Activity
+            && self.filters.iter().any(|f| {
+                f.actions.iter().any(|a| a == "android.intent.action.MAIN")
+                    && f.categories
+                        .iter()
+                        .any(|c| c == "android.intent.category.LAUNCHER")
+            })
+    }
+}
+
+/// The parts of a decoded `AndroidManifest.xml` (as written by jadx or apktool) that
+/// matter for picking hook targets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub package: String,
+    /// Custom `Application` subclass, the earliest app code to run.
+    pub application: Option<String>,
+    pub components: Vec<Component>,
+}
+
+impl Manifest {
+    pub fn launcher_activity(&self) -> Option<&Component> {
+        self.components.iter().find(|c| c.is_launcher())
+    }
+
+    pub fn exported(&self) -> impl Iterator<Item = &Component> {
+        self.components.iter().filter(|c| c.exported)
+    }
+}
+
+pub fn parse_manifest(xml: &str) -> Result<Manifest> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut manifest = Manifest::default();
+    let mut component: Option<(Component, Option<bool>)> = None;
+    let mut filter: Option<IntentFilter> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| anyhow::anyhow!("Failed to parse AndroidManifest.xml: {}", e))?;
+        let (start, empty) = match &event {
+            Event::Start(e) => (Some(e), false),
+            Event::Empty(e) => (Some(e), true),
+            _ => (None, false),
+        };
+
+        if let Some(e) = start {
+            let tag = e.name();
+            match tag.as_ref() {
+                b"manifest" => manifest.package = attr(e, "package").unwrap_or_default(),
+                b"application" => {
+                    manifest.application =
+                        attr(e, "android:name").map(|name| qualify(&manifest.package, &name))
+                }
+                b"intent-filter" if component.is_some() => {
+                    filter = Some(IntentFilter {
+                        auto_verify: attr(e, "android:autoVerify").is_some_and(|v| v == "true"),
+                        ..Default::default()
+                    })
+                }
+                b"action" => {
+                    if let (Some(filter), Some(name)) = (filter.as_mut(), attr(e, "android:name")) {
+                        filter.actions.push(name);
+                    }
+                }
+                b"category" => {
+                    if let (Some(filter), Some(name)) = (filter.as_mut(), attr(e, "android:name")) {
+                        filter.categories.push(name);
+                    }
+                }
+                b"data" => {
+                    if let Some(filter) = filter.as_mut() {
+                        add_manifest_data(filter, e);
+                    }
+                }
+                other => {
+                    if let Some(kind) = ComponentKind::from_tag(other) {
+                        let name = attr(e, "android:name")
+                            .or_else(|| attr(e, "android:targetActivity"))
+                            .unwrap_or_default();
+                        component = Some((
+                            Component {
+                                kind,
+                                name: qualify(&manifest.package, &name),
+                                exported: false,
+                                filters: Vec::new(),
+                            },
+                            attr(e, "android:exported").map(|v| v == "true"),
+                        ));
+                    }
+                }
+            }
+            if !empty {
+                continue;
+            }
+        }
+
+        let end = match &event {
+            Event::End(e) => Some(e.name().as_ref().to_vec()),
+            Event::Empty(e) => Some(e.name().as_ref().to_vec()),
+            Event::Eof => break,
+            _ => None,
+        };
+        let Some(end) = end else {
+            continue;
+        };
+        if end == b"intent-filter" {
+            if let (Some((component, _)), Some(filter)) = (component.as_mut(), filter.take()) {
+                component.filters.push(filter);
+            }
+        } else if ComponentKind::from_tag(&end).is_some() {
+            if let Some((mut component, exported)) = component.take() {
+                component.exported = exported.unwrap_or(!component.filters.is_empty());
+                manifest.components.push(component);
+            }
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Components with intent filters from the resolver tables of `dumpsys package <package>`.
+/// Each filter is listed once per action there; duplicates are folded by filter id.
+/// `exported` cannot be told from dumpsys and is reported as `true`.
+pub fn parse_dumpsys_components(dumpsys: &str, package: &str) -> Vec<Component> {
+    let mut components: Vec<Component> = Vec::new();
+    let mut seen_filters: Vec<String> = Vec::new();
+    let mut kind: Option<ComponentKind> = None;
+    // Index into `components` and into its `filters` of the filter being read.
+    let mut current: Option<(usize, usize)> = None;
+
+    for line in dumpsys.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') {
+            kind = match trimmed {
+                "Activity Resolver Table:" => Some(ComponentKind::Activity),
+                "Service Resolver Table:" => Some(ComponentKind::Service),
+                "Receiver Resolver Table:" => Some(ComponentKind::Receiver),
+                "Provider Resolver Table:" => Some(ComponentKind::Provider),
+                _ => None,
+            };
+            current = None;
+            continue;
+        }
+        let Some(kind) = kind else {
+            continue;
+        };
+
+        let words: Vec<&str> = trimmed.split_whitespace().collect();
+        if let [_, component, "filter", filter_id, ..] = words.as_slice() {
+            current = None;
+            let Some((owner, class)) = component.split_once('/') else {
+                continue;
+            };
+            if owner != package || seen_filters.iter().any(|f| f == filter_id) {
+                continue;
+            }
+            seen_filters.push(filter_id.to_string());
+            let name = qualify(package, class);
+            let index = match components
+                .iter()
+                .position(|c| c.kind == kind && c.name == name)
+            {
+                Some(index) => index,
+                None => {
+                    components.push(Component {
+                        kind,
+                        name,
+                        exported: true,
+                        filters: Vec::new(),
+                    });
+                    components.len() - 1
+                }
+            };
+            components[index].filters.push(IntentFilter::default());
+            current = Some((index, components[index].filters.len() - 1));
+            continue;
+        }
+
+        let Some((index, filter)) = current else {
+            continue;
+        };
+        let filter = &mut components[index].filters[filter];
+        if let Some(value) = quoted_value(trimmed, "Action:") {
+            filter.actions.push(value);
+        } else if let Some(value) = quoted_value(trimmed, "Category:") {
+            filter.categories.push(value);
+        } else if let Some(value) = quoted_value(trimmed, "Scheme:") {
+            push_unique(&mut filter.schemes, value);
+        } else if let Some(host) = quoted_value(trimmed, "Authority:") {
+            let port = trimmed.rsplit(':').next().map(str::trim).unwrap_or("-1");
+            let host = match port.parse::<i32>() {
+                Ok(port) if port >= 0 => format!("{}:{}", host, port),
+                _ => host,
+            };
+            push_unique(&mut filter.hosts, host);
+        } else if let Some(matcher) = quoted_value(trimmed, "Path:") {
+            if let Some(path) = dumpsys_path(&matcher) {
+                push_unique(&mut filter.paths, path);
+            }
+        } else if trimmed == "AutoVerify=true" {
+            filter.auto_verify = true;
+        }
+    }
+
+    components
+}
+
+/// `PatternMatcher{PREFIX: /item}` -> `/item*`.
+fn dumpsys_path(matcher: &str) -> Option<String> {
+    let inner = matcher.strip_prefix("PatternMatcher{")?.strip_suffix('}')?;
+    let (kind, path) = inner.split_once(": ")?;
+    Some(match kind {
+        "PREFIX" => format!("{}*", path),
+        "SUFFIX" => format!("*{}", path),
+        _ => path.to_string(),
+    })
+}
+
+fn add_manifest_data(filter: &mut IntentFilter, e: &BytesStart) {
+    if let Some(scheme) = attr(e, "android:scheme") {
+        push_unique(&mut filter.schemes, scheme);
+    }
+    if let Some(host) = attr(e, "android:host") {
+        let host = match attr(e, "android:port") {
+            Some(port) => format!("{}:{}", host, port),
+            None => host,
+        };
+        push_unique(&mut filter.hosts, host);
+    }
+    let path = attr(e, "android:path")
+        .or_else(|| attr(e, "android:pathPrefix").map(|p| format!("{}*", p)))
+        .or_else(|| attr(e, "android:pathSuffix").map(|p| format!("*{}", p)))
+        .or_else(|| attr(e, "android:pathPattern"))
+        .or_else(|| attr(e, "android:pathAdvancedPattern"));
+    if let Some(path) = path {
+        push_unique(&mut filter.paths, path);
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// `value` from a `<label> "value"` dumpsys line.
+fn quoted_value(line: &str, label: &str) -> Option<String> {
+    let rest = line.strip_prefix(label)?.trim();
+    let rest = rest.strip_prefix('"')?;
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+fn attr(e: &BytesStart, name: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.to_string())
+}
+
+/// Expand `.Main` / `Main` to `<package>.Main`.
+fn qualify(package: &str, name: &str) -> String {
+    if name.starts_with('.') {
+        format!("{}{}", package, name)
+    } else if !name.contains('.') && !package.is_empty() {
+        format!("{}.{}", package, name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example.app">
+    <application android:name=".App" android:label="@string/app_name">
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN"/>
+                <category android:name="android.intent.category.LAUNCHER"/>
+            </intent-filter>
+        </activity>
+        <activity android:name="com.example.app.ui.Settings"/>
+        <activity android:name=".LinkActivity" android:exported="true">
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW"/>
+                <category android:name="android.intent.category.DEFAULT"/>
+                <category android:name="android.intent.category.BROWSABLE"/>
+                <data android:scheme="https"/>
+                <data android:scheme="http"/>
+                <data android:host="example.com"/>
+                <data android:pathPrefix="/item/"/>
+            </intent-filter>
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW"/>
+                <data android:scheme="exampleapp" android:host="open" android:port="8080"/>
+            </intent-filter>
+        </activity>
+        <service android:name="SyncService">
+            <intent-filter>
+                <action android:name="com.example.SYNC"/>
+            </intent-filter>
+        </service>
+        <receiver android:name=".BootReceiver" android:exported="false">
+            <intent-filter>
+                <action android:name="android.intent.action.BOOT_COMPLETED"/>
+            </intent-filter>
+        </receiver>
+        <provider android:name="androidx.startup.InitializationProvider" android:exported="false"/>
+    </application>
+</manifest>"#;
+
+    const DUMPSYS: &str = "\
+Activity Resolver Table:
+  Non-Data Actions:
+      android.intent.action.MAIN:
+        5c1a2b3 com.example.app/.MainActivity filter 8d9e0f1
+          Action: \"android.intent.action.MAIN\"
+          Category: \"android.intent.category.LAUNCHER\"
+      android.intent.action.VIEW:
+        5c1a2b3 com.example.app/.MainActivity filter 8d9e0f1
+          Action: \"android.intent.action.MAIN\"
+          Category: \"android.intent.category.LAUNCHER\"
+        1111111 com.other/.Share filter 2222222
+          Action: \"android.intent.action.VIEW\"
+
+Receiver Resolver Table:
+  Non-Data Actions:
+      android.intent.action.BOOT_COMPLETED:
+        3a4b5c6 com.example.app/com.example.app.BootReceiver filter 7d8e9f0
+          Action: \"android.intent.action.BOOT_COMPLETED\"
+          mPriority=0, mOrder=0, mHasStaticPartialTypes=false, mHasDynamicPartialTypes=false
+
+Service Resolver Table:
+  Non-Data Actions:
+      com.example.SYNC:
+        aaaaaaa com.example.app/.SyncService filter bbbbbbb
+          Action: \"com.example.SYNC\"
+
+Packages:
+  Package [com.example.app] (4c1d2e3):
+    userId=10123
+";
+
+    #[test]
+    fn reads_components_from_dumpsys() {
+        let components = parse_dumpsys_components(DUMPSYS, "com.example.app");
+        let rows: Vec<String> = components
+            .iter()
+            .filter_map(|c| c.am_command("com.example.app"))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "am start -n com.example.app/.MainActivity",
+                "am broadcast -n com.example.app/.BootReceiver -a android.intent.action.BOOT_COMPLETED",
+                "am startservice -n com.example.app/.SyncService",
+            ]
+        );
+        assert!(components[0].is_launcher());
+        assert_eq!(components[0].filters.len(), 1);
+    }
+
+    #[test]
+    fn parses_application_components_and_launcher() {
+        let manifest = parse_manifest(MANIFEST).unwrap();
+        assert_eq!(manifest.package, "com.example.app");
+        assert_eq!(manifest.application.as_deref(), Some("com.example.app.App"));
+        assert_eq!(manifest.components.len(), 6);
+        assert_eq!(
+            manifest.launcher_activity().map(|c| c.name.as_str()),
+            Some("com.example.app.MainActivity")
+        );
+
+        let exported: Vec<(&str, &str)> = manifest
+            .exported()
+            .map(|c| (c.kind.as_str(), c.name.as_str()))
+            .collect();
+        assert_eq!(
+            exported,
+            vec![
+                ("activity", "com.example.app.MainActivity"),
+                ("activity", "com.example.app.LinkActivity"),
+                ("service", "com.example.app.SyncService"),
+            ]
+        );
+        assert_eq!(
+            manifest.components[5].name,
+            "androidx.startup.InitializationProvider"
+        );
+    }
+
+    #[test]
+    fn collects_deep_link_patterns_from_manifest() {
+        let manifest = parse_manifest(MANIFEST).unwrap();
+        let link = &manifest.components[2];
+        assert!(link.filters.iter().all(|f| f.is_deep_link()));
+        assert!(link.filters[0].auto_verify);
+        assert_eq!(
+            link.filters[0].uri_patterns(),
+            vec!["https://example.com/item/*", "http://example.com/item/*"]
+        );
+        assert_eq!(
+            link.filters[1].uri_patterns(),
+            vec!["exampleapp://open:8080"]
+        );
+        assert!(!manifest.components[0].filters[0].is_deep_link());
+    }
+
+    #[test]
+    fn reads_data_from_dumpsys_filters() {
+        let dumpsys = "\
+Activity Resolver Table:
+  Schemes:
+      https:
+        1a2b3c4 com.example.app/.LinkActivity filter 5d6e7f8
+          Action: \"android.intent.action.VIEW\"
+          Category: \"android.intent.category.BROWSABLE\"
+          Scheme: \"https\"
+          Authority: \"example.com\": -1
+          Path: \"PatternMatcher{PREFIX: /item/}\"
+          AutoVerify=true
+      exampleapp:
+        1a2b3c4 com.example.app/.LinkActivity filter 9999999
+          Action: \"android.intent.action.VIEW\"
+          Scheme: \"exampleapp\"
+          Authority: \"open\": 8080
+";
+        let components = parse_dumpsys_components(dumpsys, "com.example.app");
+        assert_eq!(components.len(), 1);
+        let patterns: Vec<String> = components[0]
+            .filters
+            .iter()
+            .flat_map(|f| f.uri_patterns())
+            .collect();
+        assert_eq!(
+            patterns,
+            vec!["https://example.com/item/*", "exampleapp://open:8080"]
+        );
+        assert!(components[0].filters[0].auto_verify);
+    }
+}
@@ -1,4 +1,13 @@
 pub mod adb;
+pub mod artifacts;
+pub mod device_info;
+pub mod elf;
 pub mod foreground;
+pub mod gadget;
+pub mod manifest;
+pub mod network;
+pub mod sdk;
+pub mod shell;
 
-pub use adb::{AdbClient, Device};
+pub use adb::{AdbClient, Device, Forward, Transport};
+pub use device_info::DeviceInfo;
@@ -0,0 +1,176 @@
+/// Default port for `adb tcpip`.
+pub const DEFAULT_TCPIP_PORT: u16 = 5555;
+
+/// Pick the device's Wi-Fi IPv4 address from `ip -f inet addr` output, preferring
+/// `wlan*` interfaces and ignoring loopback.
+pub fn parse_wifi_ipv4(ip_addr_output: &str) -> Option<String> {
+    let mut interface = String::new();
+    let mut fallback = None;
+
+    for line in ip_addr_output.lines() {
+        let trimmed = line.trim_start();
+        if !line.starts_with(char::is_whitespace) {
+            // "30: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> ..."
+            interface = line
+                .split(':')
+                .nth(1)
+                .map(|name| name.trim().to_string())
+                .unwrap_or_default();
+            continue;
+        }
+
+        let Some(rest) = trimmed.strip_prefix("inet ") else {
+            continue;
+        };
+        let Some(addr) = rest.split_whitespace().next() else {
+            continue;
+        };
+        let ip = addr.split('/').next().unwrap_or(addr);
+        if ip.starts_with("127.") {
+            continue;
+        }
+
+        if interface.starts_with("wlan") {
+            return Some(ip.to_string());
+        }
+        fallback.get_or_insert_with(|| ip.to_string());
+    }
+
+    fallback
+}
+
+/// Whether `adb connect` output reports success (adb exits 0 even when it fails).
+pub fn connect_succeeded(output: &str) -> bool {
+    let output = output.to_ascii_lowercase();
+    (output.contains("connected to") || output.contains("already connected"))
+        && !output.contains("failed")
+        && !output.contains("cannot")
+}
+
+/// `host:port` for a user-supplied address, adding adb's default port to a bare host.
+pub fn endpoint_with_port(address: &str) -> String {
+    let address = address.trim();
+    if is_network_serial(address) {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_TCPIP_PORT)
+    }
+}
+
+/// Serials of network-attached devices look like `host:port`.
+pub fn is_network_serial(serial: &str) -> bool {
+    serial
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+}
+
+/// Service types advertised by Android 11+ wireless debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdnsServiceKind {
+    /// `_adb-tls-connect._tcp`: an already-paired device accepting connections.
+    Connect,
+    /// `_adb-tls-pairing._tcp`: shown while the "Pair device with pairing code" dialog is open.
+    Pairing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdnsService {
+    pub name: String,
+    pub kind: MdnsServiceKind,
+    pub endpoint: String,
+}
+
+/// Parse `adb mdns services` output, keeping wireless-debugging services only.
+pub fn parse_mdns_services(output: &str) -> Vec<MdnsService> {
+    let mut services = Vec::new();
+
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+
+        let kind = if parts[1].starts_with("_adb-tls-connect._tcp") {
+            MdnsServiceKind::Connect
+        } else if parts[1].starts_with("_adb-tls-pairing._tcp") {
+            MdnsServiceKind::Pairing
+        } else {
+            continue;
+        };
+        if !is_network_serial(parts[2]) {
+            continue;
+        }
+
+        let service = MdnsService {
+            name: parts[0].to_string(),
+            kind,
+            endpoint: parts[2].to_string(),
+        };
+        if !services.contains(&service) {
+            services.push(service);
+        }
+    }
+
+    services
+}
+
+/// Whether `adb pair` output reports success.
+pub fn pair_succeeded(output: &str) -> bool {
+    output.contains("Successfully paired")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_wlan_address() {
+        let output = "\
+1: lo: <LOOPBACK,UP,LOWER_UP> mtu 65536 qdisc noqueue state UNKNOWN group default qlen 1000
+    inet 127.0.0.1/8 scope host lo
+       valid_lft forever preferred_lft forever
+12: rmnet_data0: <UP,LOWER_UP> mtu 1500
+    inet 10.23.4.5/30 scope global rmnet_data0
+30: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 qdisc mq state UP group default qlen 3000
+    inet 192.168.1.42/24 brd 192.168.1.255 scope global wlan0
+";
+        assert_eq!(parse_wifi_ipv4(output).as_deref(), Some("192.168.1.42"));
+        assert_eq!(
+            parse_wifi_ipv4("1: lo: <LOOPBACK>\n    inet 127.0.0.1/8 scope host lo\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn recognizes_connect_results_and_serials() {
+        assert!(connect_succeeded("connected to 192.168.1.42:5555"));
+        assert!(connect_succeeded("already connected to 192.168.1.42:5555"));
+        assert!(!connect_succeeded(
+            "failed to connect to '192.168.1.42:5555': Connection refused"
+        ));
+
+        assert!(is_network_serial("192.168.1.42:5555"));
+        assert!(!is_network_serial("emulator-5554"));
+        assert!(!is_network_serial("R58M12ABCDE"));
+
+        assert_eq!(endpoint_with_port("192.168.1.42"), "192.168.1.42:5555");
+        assert_eq!(endpoint_with_port(" 192.168.1.42:37199"), "192.168.1.42:37199");
+    }
+
+    #[test]
+    fn parses_wireless_debugging_services() {
+        let output = "\
+List of discovered mdns services
+adb-R58M12ABCDE-vWgJpq\t_adb-tls-connect._tcp.\t192.168.1.42:37199
+adb-R58M12ABCDE-vWgJpq\t_adb-tls-pairing._tcp.\t192.168.1.42:41821
+adb-R58M12ABCDE-vWgJpq\t_adb-tls-connect._tcp.\t192.168.1.42:37199
+other\t_adb._tcp.\t192.168.1.7:5555
+";
+        let services = parse_mdns_services(output);
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].kind, MdnsServiceKind::Connect);
+        assert_eq!(services[0].endpoint, "192.168.1.42:37199");
+        assert_eq!(services[1].kind, MdnsServiceKind::Pairing);
+        assert_eq!(services[1].name, "adb-R58M12ABCDE-vWgJpq");
+    }
+}
@@ -0,0 +1,188 @@
+use crate::config::GlobalConfigManager;
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{ensure_dir_exists, extract_zip, make_executable, HttpClient, ProcessExecutor};
+use colored::Colorize;
+use directories::BaseDirs;
+use std::path::{Path, PathBuf};
+
+const PLATFORM_TOOLS_BASE_URL: &str = "https://dl.google.com/android/repository";
+
+/// The configured adb if it runs, otherwise one found in an Android SDK. Never prompts;
+/// `frida-mgr doctor` offers to save a fallback to the global config.
+pub fn resolve_adb_path(configured: &str) -> String {
+    if ProcessExecutor::check_command_exists(configured) {
+        return configured.to_string();
+    }
+    match discover_adb() {
+        Some(found) => found.to_string_lossy().to_string(),
+        None => configured.to_string(),
+    }
+}
+
+/// First existing adb under `ANDROID_HOME`/`ANDROID_SDK_ROOT`, a default SDK location, or
+/// the platform-tools installed by `frida-mgr adb install`.
+pub fn discover_adb() -> Option<PathBuf> {
+    let home = BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let mut candidates = adb_candidates(
+        std::env::consts::OS,
+        |key| std::env::var(key).ok(),
+        home.as_deref(),
+    );
+    if let Ok(global_mgr) = GlobalConfigManager::new() {
+        candidates.push(cached_adb_path(&global_mgr.get_cache_dir()));
+    }
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// adb inside the platform-tools tree that `install_platform_tools` unpacks into `cache_dir`.
+pub fn cached_adb_path(cache_dir: &Path) -> PathBuf {
+    let binary = if cfg!(windows) { "adb.exe" } else { "adb" };
+    cache_dir.join("platform-tools").join(binary)
+}
+
+/// Download Google's platform-tools for the host OS and unpack it into `cache_dir`,
+/// replacing any previous copy. Returns the path of the unpacked adb.
+pub async fn install_platform_tools(http: &HttpClient, cache_dir: &Path) -> Result<PathBuf> {
+    let url = platform_tools_url(std::env::consts::OS).ok_or_else(|| {
+        FridaMgrError::Adb(format!(
+            "Android platform-tools are not published for {}",
+            std::env::consts::OS
+        ))
+    })?;
+
+    ensure_dir_exists(cache_dir).await?;
+    let archive = cache_dir.join("platform-tools.zip");
+    println!("{} Downloading {}...", "↓".blue().bold(), url.cyan());
+    http.download_file(&url, &archive).await?;
+
+    let tools_dir = cache_dir.join("platform-tools");
+    if tools_dir.exists() {
+        tokio::fs::remove_dir_all(&tools_dir).await?;
+    }
+    println!("{} Extracting...", "⚙".blue().bold());
+    extract_zip(&archive, cache_dir).await?;
+    tokio::fs::remove_file(&archive).await?;
+
+    let adb = cached_adb_path(cache_dir);
+    if !adb.is_file() {
+        return Err(FridaMgrError::Adb(format!(
+            "platform-tools archive did not contain {}",
+            adb.display()
+        )));
+    }
+    make_executable(&adb).await?;
+    Ok(adb)
+}
+
+/// Official "latest" platform-tools archive for a `std::env::consts::OS` value.
+fn platform_tools_url(os: &str) -> Option<String> {
+    let platform = match os {
+        "linux" => "linux",
+        "macos" => "darwin",
+        "windows" => "windows",
+        _ => return None,
+    };
+    Some(format!(
+        "{}/platform-tools-latest-{}.zip",
+        PLATFORM_TOOLS_BASE_URL, platform
+    ))
+}
+
+/// Where adb usually lives on `os`, in lookup order.
+fn adb_candidates(
+    os: &str,
+    env: impl Fn(&str) -> Option<String>,
+    home: Option<&Path>,
+) -> Vec<PathBuf> {
+    let binary = if os == "windows" { "adb.exe" } else { "adb" };
+    let mut sdk_roots: Vec<PathBuf> = ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+        .into_iter()
+        .filter_map(|key| env(key).filter(|v| !v.is_empty()))
+        .map(PathBuf::from)
+        .collect();
+
+    match os {
+        "macos" => {
+            if let Some(home) = home {
+                sdk_roots.push(home.join("Library/Android/sdk"));
+            }
+        }
+        "windows" => {
+            if let Some(local) = env("LOCALAPPDATA") {
+                sdk_roots.push(PathBuf::from(local).join("Android").join("Sdk"));
+            }
+        }
+        _ => {
+            if let Some(home) = home {
+                sdk_roots.push(home.join("Android/Sdk"));
+            }
+            sdk_roots.push(PathBuf::from("/opt/android-sdk"));
+            sdk_roots.push(PathBuf::from("/usr/lib/android-sdk"));
+        }
+    }
+
+    let mut candidates: Vec<PathBuf> = sdk_roots
+        .into_iter()
+        .map(|root| root.join("platform-tools").join(binary))
+        .collect();
+    if os == "macos" {
+        // Homebrew's android-platform-tools cask, for shells that miss its bin dir.
+        candidates.push(PathBuf::from("/opt/homebrew/bin/adb"));
+        candidates.push(PathBuf::from("/usr/local/bin/adb"));
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sdk_env_vars_come_first() {
+        let env = |key: &str| match key {
+            "ANDROID_HOME" => Some("/sdk".to_string()),
+            "ANDROID_SDK_ROOT" => Some(String::new()),
+            _ => None,
+        };
+        let candidates = adb_candidates("linux", env, Some(Path::new("/home/u")));
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/sdk/platform-tools/adb"),
+                PathBuf::from("/home/u/Android/Sdk/platform-tools/adb"),
+                PathBuf::from("/opt/android-sdk/platform-tools/adb"),
+                PathBuf::from("/usr/lib/android-sdk/platform-tools/adb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn platform_defaults_differ() {
+        let mac = adb_candidates("macos", |_| None, Some(Path::new("/Users/u")));
+        assert_eq!(
+            mac[0],
+            PathBuf::from("/Users/u/Library/Android/sdk/platform-tools/adb")
+        );
+        assert!(mac.contains(&PathBuf::from("/opt/homebrew/bin/adb")));
+
+        let windows = adb_candidates(
+            "windows",
+            |key| (key == "LOCALAPPDATA").then(|| "C:/Users/u/AppData/Local".to_string()),
+            None,
+        );
+        assert_eq!(windows.len(), 1);
+        assert!(windows[0].ends_with("Sdk/platform-tools/adb.exe"));
+    }
+
+    #[test]
+    fn platform_tools_url_per_host() {
+        assert_eq!(
+            platform_tools_url("macos").as_deref(),
+            Some("https://dl.google.com/android/repository/platform-tools-latest-darwin.zip")
+        );
+        assert!(platform_tools_url("linux")
+            .unwrap()
+            .ends_with("platform-tools-latest-linux.zip"));
+        assert_eq!(platform_tools_url("freebsd"), None);
+    }
+}
@@ -0,0 +1,59 @@
+//! Command lines for `adb shell`, which joins its arguments with spaces and hands the
+//! result to the device's `sh`, so every path or name has to be quoted for that shell.
+
+pub use crate::core::shell::{command, quote};
+
+/// Run `script` through the configured root command (`su`, `sudo`, `laotie`, ...). A plain
+/// command is used as a prefix and must accept `-c <script>`; a template such as
+/// `su 0 {cmd}` gets `sh -c <script>` in place of `{cmd}`, for su builds without `-c`.
+pub fn as_root(root_command: &str, script: &str) -> String {
+    if root_command.contains(ROOT_COMMAND_PLACEHOLDER) {
+        root_command.replace(
+            ROOT_COMMAND_PLACEHOLDER,
+            &format!("sh -c {}", quote(script)),
+        )
+    } else {
+        format!("{} -c {}", root_command, quote(script))
+    }
+}
+
+/// Where `as_root` puts the script in a `root_command` template.
+pub const ROOT_COMMAND_PLACEHOLDER: &str = "{cmd}";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_commands_and_root_wrappers() {
+        assert_eq!(
+            command(["rm", "-f", "/data/local/tmp/a b", "x.log"]),
+            "rm -f '/data/local/tmp/a b' x.log"
+        );
+        assert_eq!(
+            as_root("su", &command(["killall", "frida-server"])),
+            "su -c 'killall frida-server'"
+        );
+        assert_eq!(
+            as_root("su 0 {cmd}", "killall frida-server; echo done"),
+            "su 0 sh -c 'killall frida-server; echo done'"
+        );
+        assert_eq!(
+            as_root("su 0", &command(["rm", "-f", "/tmp/it's"])),
+            "su 0 -c 'rm -f '\\''/tmp/it'\\''\\'\\'''\\''s'\\'''"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn nested_quoting_survives_two_shells() {
+        let path = "/tmp/a b/it's $x";
+        let script = format!("printf %s {}", quote(path));
+        let outer = format!("sh -c {}", quote(&script));
+        let output = std::process::Command::new("sh")
+            .args(["-c", &outer])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), path);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::core::error::{FridaMgrError, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest at the root of every bundle archive.
+pub const BUNDLE_MANIFEST: &str = "bundle.toml";
+pub const BUNDLE_FORMAT: u32 = 1;
+/// Archive prefix for files restored relative to the project root.
+pub const PROJECT_PREFIX: &str = "project";
+/// Archive prefix for cached frida-server binaries (`servers/<version>/<arch>/<file>`).
+pub const SERVERS_PREFIX: &str = "servers";
+/// Pinned Python packages of the exported venv, inside the project prefix.
+pub const LOCKFILE: &str = "requirements.lock";
+
+/// Directories never copied into a bundle from script/agent trees.
+const SKIPPED_DIRS: &[&str] = &["node_modules", ".git", ".venv", "__pycache__"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub created_at: String,
+    pub project: String,
+    pub frida_version: String,
+    pub python_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frida_tools_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objection_version: Option<String>,
+    /// Project-relative paths stored under `project/`.
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub servers: Vec<BundledServer>,
+}
+
+/// A cached frida-server binary carried by the bundle.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BundledServer {
+    pub version: String,
+    /// Cache key under `servers/<version>/` (bare arch for Android, `os-arch` otherwise).
+    pub arch: String,
+    pub file: String,
+    pub sha256: String,
+}
+
+impl BundledServer {
+    pub fn archive_path(&self) -> String {
+        format!(
+            "{}/{}/{}/{}",
+            SERVERS_PREFIX, self.version, self.arch, self.file
+        )
+    }
+
+    /// Where the binary sits under a `servers/` cache root.
+    pub fn cache_path(&self, servers_dir: &Path) -> PathBuf {
+        servers_dir
+            .join(&self.version)
+            .join(&self.arch)
+            .join(&self.file)
+    }
+}
+
+/// One archive member: either a file on disk or generated content.
+pub enum BundleEntry {
+    File {
+        archive_path: String,
+        source: PathBuf,
+    },
+    Data {
+        archive_path: String,
+        data: Vec<u8>,
+    },
+}
+
+/// Write `entries` into a gzip-compressed tar archive at `output`.
+pub async fn write_archive(output: &Path, entries: Vec<BundleEntry>) -> Result<()> {
+    let output = output.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::create(&output)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        builder.follow_symlinks(true);
+
+        for entry in entries {
+            match entry {
+                BundleEntry::File {
+                    archive_path,
+                    source,
+                } => builder.append_path_with_name(&source, &archive_path)?,
+                BundleEntry::Data { archive_path, data } => {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_size(data.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+                    header.set_cksum();
+                    builder.append_data(&mut header, &archive_path, data.as_slice())?;
+                }
+            }
+        }
+
+        builder.into_inner()?.finish()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| FridaMgrError::CommandFailed(format!("Bundle writer panicked: {}", e)))?
+}
+
+/// Unpack a bundle: `project/` members into `project_dest` and `servers/` members into
+/// `servers_dest`. Only regular files at paths declared in the leading manifest are
+/// extracted, so a tampered archive cannot write elsewhere or plant links. Returns the
+/// manifest.
+pub async fn unpack_archive(
+    archive: &Path,
+    project_dest: &Path,
+    servers_dest: &Path,
+) -> Result<BundleManifest> {
+    let archive = archive.to_path_buf();
+    let project_dest = project_dest.to_path_buf();
+    let servers_dest = servers_dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || -> Result<BundleManifest> {
+        let not_a_bundle = || {
+            FridaMgrError::Config(format!(
+                "{} is not a frida-mgr bundle (missing {})",
+                archive.display(),
+                BUNDLE_MANIFEST
+            ))
+        };
+
+        let file = std::fs::File::open(&archive)?;
+        let mut reader = tar::Archive::new(GzDecoder::new(file));
+        let mut entries = reader.entries()?;
+
+        let mut first = entries.next().ok_or_else(not_a_bundle)??;
+        if first.path()?.to_str() != Some(BUNDLE_MANIFEST) {
+            return Err(not_a_bundle());
+        }
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut first, &mut content)?;
+        let manifest: BundleManifest = toml::from_str(&content)?;
+        if manifest.format > BUNDLE_FORMAT {
+            return Err(FridaMgrError::Config(format!(
+                "Bundle format {} is newer than this frida-mgr supports ({}); upgrade frida-mgr",
+                manifest.format, BUNDLE_FORMAT
+            )));
+        }
+
+        for entry in entries {
+            let mut entry = entry?;
+            if entry.header().entry_type() != tar::EntryType::Regular {
+                continue;
+            }
+            let name = entry.path()?.to_string_lossy().to_string();
+
+            let dest = if let Some(relative) = name
+                .strip_prefix(PROJECT_PREFIX)
+                .and_then(|r| r.strip_prefix('/'))
+            {
+                if !manifest.files.iter().any(|f| f == relative) {
+                    continue;
+                }
+                match project_relative(&project_dest, Path::new(relative)) {
+                    Some(safe) => project_dest.join(safe),
+                    None => continue,
+                }
+            } else {
+                let Some(server) = manifest.servers.iter().find(|s| s.archive_path() == name)
+                else {
+                    continue;
+                };
+                let relative = format!("{}/{}/{}", server.version, server.arch, server.file);
+                match project_relative(&servers_dest, Path::new(&relative)) {
+                    Some(safe) => servers_dest.join(safe),
+                    None => continue,
+                }
+            };
+
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            entry.unpack(&dest)?;
+        }
+
+        Ok(manifest)
+    })
+    .await
+    .map_err(|e| FridaMgrError::CommandFailed(format!("Bundle reader panicked: {}", e)))?
+}
+
+/// `path` relative to `base` with `.`/`..` resolved lexically, or `None` when it
+/// escapes `base` (such files cannot be restored into another project).
+pub fn project_relative(base: &Path, path: &Path) -> Option<String> {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    let relative = normalized.strip_prefix(base).ok()?;
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Every regular file under `dir`, skipping dependency and VCS directories.
+pub fn walk_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                let skipped = entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+                if !skipped {
+                    pending.push(path);
+                }
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_relative_paths_stay_inside_project() {
+        let base = Path::new("/work/app");
+        assert_eq!(
+            project_relative(base, Path::new("scripts/./unpin.js")).as_deref(),
+            Some("scripts/unpin.js")
+        );
+        assert_eq!(
+            project_relative(base, Path::new("/work/app/agent/dist/agent.js")).as_deref(),
+            Some("agent/dist/agent.js")
+        );
+        assert_eq!(project_relative(base, Path::new("../shared/x.js")), None);
+        assert_eq!(project_relative(base, Path::new(".")), None);
+    }
+
+    #[tokio::test]
+    async fn unpack_skips_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("bundle.tar.gz");
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT,
+            created_at: String::new(),
+            project: "t".to_string(),
+            frida_version: "16.5.2".to_string(),
+            python_version: "3.11".to_string(),
+            frida_tools_version: None,
+            objection_version: None,
+            files: vec!["a.js".to_string(), "link.js".to_string()],
+            servers: Vec::new(),
+        };
+
+        let file = std::fs::File::create(&archive).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for (name, data) in [
+            (BUNDLE_MANIFEST, toml::to_string(&manifest).unwrap()),
+            ("project/a.js", "send(1);".to_string()),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, data.as_bytes())
+                .unwrap();
+        }
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder
+            .append_link(&mut header, "project/link.js", "/etc/passwd")
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let project = dir.path().join("project");
+        unpack_archive(&archive, &project, &dir.path().join("servers"))
+            .await
+            .unwrap();
+        assert!(project.join("a.js").is_file());
+        assert!(std::fs::symlink_metadata(project.join("link.js")).is_err());
+    }
+
+    #[test]
+    fn walk_skips_dependency_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("node_modules/x")).unwrap();
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("node_modules/x/a.js"), b"").unwrap();
+        std::fs::write(dir.path().join("sub/b.js"), b"").unwrap();
+        std::fs::write(dir.path().join("c.js"), b"").unwrap();
+
+        let files = walk_files(dir.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("c.js"), dir.path().join("sub/b.js")]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::android::sdk::{cached_adb_path, install_platform_tools};
+use crate::config::GlobalConfigManager;
+use crate::core::error::Result;
+use crate::core::{HttpClient, ProcessExecutor};
+use colored::Colorize;
+
+/// Install Google's platform-tools into the global cache and point `android.adb_path` at it.
+pub async fn install(force: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let mut global_config = global_mgr.ensure_initialized().await?;
+    let cache_dir = global_mgr.get_cache_dir();
+
+    let cached = cached_adb_path(&cache_dir);
+    let adb = if cached.is_file() && !force {
+        println!(
+            "{} platform-tools already installed at {} (use --force to update)",
+            "ℹ".blue().bold(),
+            cached.display().to_string().yellow()
+        );
+        cached
+    } else {
+        let http = HttpClient::from_network_config(&global_config.network)?;
+        install_platform_tools(&http, &cache_dir).await?
+    };
+
+    let adb_path = adb.to_string_lossy().to_string();
+    if let Ok(version) = ProcessExecutor::execute_with_output(&adb_path, &["--version"]).await {
+        if let Some(first_line) = version.lines().next() {
+            println!("{} {}", "✓".green().bold(), first_line.trim().yellow());
+        }
+    }
+
+    if global_config.android.adb_path != adb_path {
+        global_config.android.adb_path = adb_path.clone();
+        global_mgr.save(&global_config).await?;
+        println!(
+            "{} Set android.adb_path → {} in {}",
+            "✓".green().bold(),
+            adb_path.cyan(),
+            global_mgr.config_path().display().to_string().yellow()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::android::AdbClient;
+use crate::cli::ExitStatus;
+use crate::config::ProjectConfigManager;
+use crate::core::ensure_dir_exists;
+use crate::core::error::Result;
+use crate::python::VenvExecutor;
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Project directory holding pulled APKs, laid out as `apks/<package>/base.apk`.
+pub const PULLED_APK_DIR: &str = "apks";
+
+pub async fn execute(args: Vec<String>, discover: bool) -> Result<ExitStatus> {
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::discover_project_dir(&current_dir, discover);
+
+    let args = resolve_apk_args(&args, &current_dir, &project_dir.join(PULLED_APK_DIR));
+
+    let executor = VenvExecutor::new(project_dir).with_work_dir(current_dir);
+    let exit_code = executor.run_interactive("frida-apk", &args).await?;
+
+    Ok(ExitStatus(exit_code))
+}
+
+/// Pull `package`'s base and split APKs into `<project>/apks/<package>/`, replacing what
+/// was pulled before, and return that directory.
+pub async fn pull_apks(
+    adb: &AdbClient,
+    device_id: &str,
+    package: &str,
+    project_dir: &Path,
+) -> Result<PathBuf> {
+    let remote_paths = adb.package_apk_paths(device_id, package).await?;
+    let dest = project_dir.join(PULLED_APK_DIR).join(package);
+    if dest.exists() {
+        tokio::fs::remove_dir_all(&dest).await?;
+    }
+    ensure_dir_exists(&dest).await?;
+
+    println!(
+        "{} Pulling {} APK(s) of {}...",
+        "↓".blue().bold(),
+        remote_paths.len(),
+        package.cyan()
+    );
+    for remote in &remote_paths {
+        let name = remote.rsplit('/').next().unwrap_or(remote);
+        adb.pull_file(device_id, remote, &dest.join(name)).await?;
+    }
+
+    Ok(dest)
+}
+
+/// Pulled APKs of one package, `base.apk` first.
+pub fn pulled_apks(dir: &Path) -> Vec<PathBuf> {
+    let mut apks: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "apk"))
+        .collect();
+    apks.sort_by_key(|path| (!path.ends_with("base.apk"), path.clone()));
+    apks
+}
+
+/// Rewrite positional arguments that name a pulled package (`com.example.app`) or a path
+/// inside the pulled-APK directory to the real file; anything else passes through untouched.
+fn resolve_apk_args(args: &[String], cwd: &Path, apk_dir: &Path) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut takes_value = false;
+
+    for arg in args {
+        if takes_value || arg.starts_with('-') {
+            takes_value = matches!(arg.as_str(), "-o" | "--output");
+            out.push(arg.clone());
+            continue;
+        }
+
+        let resolved = if cwd.join(arg).exists() {
+            None
+        } else if apk_dir.join(arg).join("base.apk").is_file() {
+            Some(apk_dir.join(arg).join("base.apk"))
+        } else if apk_dir.join(arg).is_file() {
+            Some(apk_dir.join(arg))
+        } else {
+            None
+        };
+
+        out.push(match resolved {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => arg.clone(),
+        });
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_pulled_packages_and_keeps_other_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let apk_dir = dir.path().join(PULLED_APK_DIR);
+        let pkg_dir = apk_dir.join("com.example.app");
+        std::fs::create_dir_all(&pkg_dir).unwrap();
+        std::fs::write(pkg_dir.join("base.apk"), b"").unwrap();
+        std::fs::write(pkg_dir.join("split_config.arm64_v8a.apk"), b"").unwrap();
+
+        let args: Vec<String> = [
+            "-o",
+            "com.example.app",
+            "com.example.app",
+            "com.example.app/split_config.arm64_v8a.apk",
+            "missing.apk",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let resolved = resolve_apk_args(&args, dir.path(), &apk_dir);
+        assert_eq!(resolved[0], "-o");
+        assert_eq!(resolved[1], "com.example.app");
+        assert_eq!(
+            resolved[2],
+            pkg_dir.join("base.apk").to_string_lossy().to_string()
+        );
+        assert_eq!(
+            resolved[3],
+            pkg_dir
+                .join("split_config.arm64_v8a.apk")
+                .to_string_lossy()
+                .to_string()
+        );
+        assert_eq!(resolved[4], "missing.apk");
+    }
+}
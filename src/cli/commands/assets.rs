@@ -0,0 +1,69 @@
+use crate::config::{GlobalConfigManager, VersionMapping};
+use crate::core::error::Result;
+use crate::core::{format_size, HttpClient};
+use crate::frida::{fetch_release_assets, ServerDownloader};
+use colored::Colorize;
+
+pub async fn execute(version: String) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+    let resolved_version = version_map.resolve_alias(&version);
+
+    let http = HttpClient::from_network_config(&global_config.network)?
+        .with_response_cache(global_mgr.get_http_cache_dir());
+    let assets = fetch_release_assets(&http, &resolved_version).await?;
+
+    if assets.is_empty() {
+        println!(
+            "{} Frida {} has no release assets",
+            "⚠".yellow().bold(),
+            resolved_version.cyan()
+        );
+        return Ok(());
+    }
+
+    let downloader = ServerDownloader::new(global_mgr.get_cache_dir());
+    let assets_dir = global_mgr.get_assets_cache_dir().join(&resolved_version);
+    let name_width = assets.iter().map(|a| a.name.len()).max().unwrap_or(0);
+
+    println!(
+        "{} {} ({} assets):",
+        "Frida".bold(),
+        resolved_version.cyan().bold(),
+        assets.len()
+    );
+    println!();
+
+    let mut cached_count = 0;
+    for asset in &assets {
+        let cached = downloader.is_asset_cached(&resolved_version, &asset.name)
+            || assets_dir.join(&asset.name).exists();
+        let marker = if cached {
+            cached_count += 1;
+            "●".green()
+        } else {
+            "○".dimmed()
+        };
+        let mut line = format!(
+            "  {} {:<width$}  {:>9}",
+            marker,
+            asset.name,
+            format_size(asset.size),
+            width = name_width
+        );
+        if cached {
+            line.push_str(&format!("  {}", "(cached)".green()));
+        }
+        println!("{}", line);
+    }
+
+    println!();
+    println!(
+        "{} cached locally; use {} to fetch any of them",
+        cached_count.to_string().cyan(),
+        "frida-mgr download <asset> --version <version>".cyan()
+    );
+
+    Ok(())
+}
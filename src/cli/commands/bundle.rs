@@ -0,0 +1,385 @@
+use crate::agent::AgentProject;
+use crate::bundle::{
+    project_relative, unpack_archive, walk_files, write_archive, BundleEntry, BundleManifest,
+    BundledServer, BUNDLE_FORMAT, BUNDLE_MANIFEST, LOCKFILE, PROJECT_PREFIX,
+};
+use crate::cli::commands::script::resolve_existing_script_path;
+use crate::config::{
+    register_project, AndroidServerSource, ArchType, GlobalConfigManager, ProjectConfig,
+    ProjectConfigManager,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{
+    compute_sha256, ensure_dir_exists, format_size, make_executable, notify, resolve_path,
+};
+use crate::frida::server::ServerCache;
+use crate::python::UvManager;
+use chrono::Utc;
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Project files collected for the `project/` half of a bundle.
+struct ProjectFiles<'a> {
+    project_dir: &'a Path,
+    files: Vec<String>,
+    entries: Vec<BundleEntry>,
+}
+
+impl<'a> ProjectFiles<'a> {
+    fn new(project_dir: &'a Path) -> Self {
+        Self {
+            project_dir,
+            files: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a file that lives inside the project; returns false when it cannot be bundled.
+    fn add(&mut self, path: &Path) -> bool {
+        let Some(relative) = project_relative(self.project_dir, path) else {
+            return false;
+        };
+        if !self.files.contains(&relative) {
+            self.entries.push(BundleEntry::File {
+                archive_path: format!("{}/{}", PROJECT_PREFIX, relative),
+                source: path.to_path_buf(),
+            });
+            self.files.push(relative);
+        }
+        true
+    }
+
+    fn add_data(&mut self, relative: &str, data: Vec<u8>) {
+        self.entries.push(BundleEntry::Data {
+            archive_path: format!("{}/{}", PROJECT_PREFIX, relative),
+            data,
+        });
+        self.files.push(relative.to_string());
+    }
+}
+
+pub async fn export(output: Option<String>) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .ok_or(FridaMgrError::NotInitialized)?;
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    let config = project_mgr.load().await?;
+    let global_mgr = GlobalConfigManager::new()?;
+
+    println!(
+        "{} Bundling {} (Frida {})...",
+        "⚙".blue().bold(),
+        config.project.name.cyan(),
+        config.frida.version.cyan()
+    );
+
+    let mut project = ProjectFiles::new(&project_dir);
+    project.add(project_mgr.config_path());
+    collect_project_files(&mut project, &config)?;
+
+    let uv_mgr = UvManager::new(project_dir.clone());
+    let (tools_version, objection_version) = if uv_mgr.venv_exists() {
+        let lock = uv_mgr.freeze().await?;
+        project.add_data(LOCKFILE, lock.into_bytes());
+        (
+            uv_mgr.get_installed_version("frida-tools").await?,
+            uv_mgr.get_installed_version("objection").await?,
+        )
+    } else {
+        eprintln!(
+            "{} No .venv found; the bundle will not include a {} (run {} first)",
+            "⚠".yellow().bold(),
+            LOCKFILE.yellow(),
+            "frida-mgr sync".cyan()
+        );
+        (None, None)
+    };
+
+    let (servers, server_entries) = collect_servers(&global_mgr, &config).await?;
+    if servers.is_empty() && config.android.server.source != AndroidServerSource::Local {
+        eprintln!(
+            "{} No cached frida-server {} for arch {}; run {} before exporting",
+            "⚠".yellow().bold(),
+            config.frida.version.yellow(),
+            config.android.arch.to_str().yellow(),
+            "frida-mgr install".cyan()
+        );
+    }
+
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        created_at: Utc::now().to_rfc3339(),
+        project: config.project.name.clone(),
+        frida_version: config.frida.version.clone(),
+        python_version: config.python.version.clone(),
+        frida_tools_version: tools_version,
+        objection_version,
+        files: project.files.clone(),
+        servers,
+    };
+
+    let mut entries = vec![BundleEntry::Data {
+        archive_path: BUNDLE_MANIFEST.to_string(),
+        data: toml::to_string_pretty(&manifest)?.into_bytes(),
+    }];
+    entries.extend(project.entries);
+    entries.extend(server_entries);
+
+    let output = match output {
+        Some(path) => resolve_path(&current_dir, &path),
+        None => current_dir.join(default_bundle_name(&config)),
+    };
+    write_archive(&output, entries).await?;
+
+    for file in &manifest.files {
+        println!("  + {}", file.dimmed());
+    }
+    for server in &manifest.servers {
+        println!(
+            "  + frida-server {} ({})",
+            server.version.dimmed(),
+            server.arch.dimmed()
+        );
+    }
+    let size = tokio::fs::metadata(&output).await?.len();
+    println!(
+        "{} Wrote {} ({})",
+        "✓".green().bold(),
+        output.display().to_string().yellow(),
+        format_size(size)
+    );
+
+    Ok(())
+}
+
+/// Restore a bundle into `dir` (default: current directory): servers go into the global
+/// cache after hash verification, project files are written out, and the venv is rebuilt
+/// from the bundled lockfile.
+pub async fn import(archive: String, dir: Option<String>, force: bool) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    let archive = resolve_path(&current_dir, &archive);
+    if !archive.is_file() {
+        return Err(FridaMgrError::FileNotFound(archive.display().to_string()));
+    }
+    let project_dir = match dir {
+        Some(dir) => resolve_path(&current_dir, &dir),
+        None => current_dir,
+    };
+    ensure_dir_exists(&project_dir).await?;
+
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    if project_mgr.config_path().exists() && !force {
+        return Err(FridaMgrError::Config(format!(
+            "{} already exists (use --force to overwrite it with the bundle)",
+            project_mgr.config_path().display()
+        )));
+    }
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let cache_dir = global_mgr.get_cache_dir();
+    let servers_dir = cache_dir.join("servers");
+
+    println!(
+        "{} Importing {}...",
+        "⚙".blue().bold(),
+        archive.display().to_string().yellow()
+    );
+    // Servers land in a staging directory and only reach the shared cache once every one
+    // matches its manifest digest.
+    ensure_dir_exists(&cache_dir).await?;
+    let staging = tempfile::tempdir_in(&cache_dir)?;
+    let manifest = unpack_archive(&archive, &project_dir, staging.path()).await?;
+
+    for server in &manifest.servers {
+        let staged = server.cache_path(staging.path());
+        if !staged.is_file() {
+            return Err(FridaMgrError::FileNotFound(format!(
+                "{} (listed in the bundle manifest but missing from the archive)",
+                server.archive_path()
+            )));
+        }
+        let actual = compute_sha256(&staged).await?;
+        if actual != server.sha256 {
+            return Err(FridaMgrError::ChecksumMismatch(format!(
+                "{}: expected {}, got {}",
+                server.archive_path(),
+                server.sha256,
+                actual
+            )));
+        }
+    }
+
+    let cache = ServerCache::new(&cache_dir);
+    for server in &manifest.servers {
+        let path = server.cache_path(&servers_dir);
+        ensure_dir_exists(path.parent().unwrap()).await?;
+        tokio::fs::rename(server.cache_path(staging.path()), &path).await?;
+        make_executable(&path).await?;
+        cache.touch(&server.version, &server.arch).await?;
+        println!(
+            "  {} frida-server {} ({}) verified and cached",
+            "✓".green(),
+            server.version.cyan(),
+            server.arch.cyan()
+        );
+    }
+
+    for file in &manifest.files {
+        println!("  + {}", file.dimmed());
+    }
+
+    let config = project_mgr.load().await?;
+    let lockfile = project_dir.join(LOCKFILE);
+    if lockfile.is_file() {
+        let uv_mgr = UvManager::new(project_dir.clone());
+        uv_mgr.ensure_venv(&config.python.version, false).await?;
+        uv_mgr.install_requirements(&lockfile).await?;
+    } else {
+        eprintln!(
+            "{} Bundle has no {}; run {} to build the venv",
+            "⚠".yellow().bold(),
+            LOCKFILE.yellow(),
+            "frida-mgr sync".cyan()
+        );
+    }
+
+    register_project(&global_mgr.get_project_registry_path(), &project_dir).await;
+    notify(
+        global_config.notifications,
+        "Bundle imported",
+        &format!("{} (Frida {})", manifest.project, manifest.frida_version),
+    )
+    .await;
+
+    println!(
+        "{} Imported {} (Frida {}) into {}",
+        "✓".green().bold(),
+        manifest.project.cyan(),
+        manifest.frida_version.cyan(),
+        project_dir.display().to_string().yellow()
+    );
+    Ok(())
+}
+
+/// The built agent, script search directories, files named by app profiles and a
+/// project-local frida-server.
+fn collect_project_files(project: &mut ProjectFiles, config: &ProjectConfig) -> Result<()> {
+    let project_dir = project.project_dir.to_path_buf();
+
+    let agent = AgentProject::from_agent_config(project_dir.clone(), &config.agent);
+    if agent.out_path.is_file() {
+        project.add(&agent.out_path);
+    } else if agent.entry_path.is_file() {
+        eprintln!(
+            "{} Agent is not built; run {} to include it",
+            "⚠".yellow().bold(),
+            "frida-mgr agent build".cyan()
+        );
+    }
+
+    for search_path in &config.scripts.paths {
+        let dir = resolve_path(&project_dir, search_path);
+        for file in walk_files(&dir)? {
+            if !project.add(&file) {
+                eprintln!(
+                    "{} Skipping {} (outside the project)",
+                    "⚠".yellow().bold(),
+                    dir.display().to_string().yellow()
+                );
+                break;
+            }
+        }
+    }
+
+    for registered in config.scripts.registry.values() {
+        let path = resolve_path(&project_dir, registered);
+        if path.is_file() {
+            project.add(&path);
+        }
+    }
+
+    for profile in config.apps.values() {
+        for script in &profile.scripts {
+            let resolved = PathBuf::from(resolve_existing_script_path(
+                &project_dir,
+                &project_dir,
+                Some(&config.scripts),
+                script,
+            ));
+            if resolved.is_file() {
+                project.add(&resolved);
+            }
+        }
+    }
+
+    if config.android.server.source == AndroidServerSource::Local {
+        if let Some(local) = &config.android.server.local {
+            for local_path in local.all_paths() {
+                let path = resolve_path(&project_dir, local_path);
+                if path.is_file() && !project.add(&path) {
+                    eprintln!(
+                        "{} Local frida-server {} is outside the project and was not bundled",
+                        "⚠".yellow().bold(),
+                        path.display().to_string().yellow()
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Cached frida-server binaries for the project's version and configured arch
+/// (every cached arch when `android.arch = "auto"`).
+async fn collect_servers(
+    global_mgr: &GlobalConfigManager,
+    config: &ProjectConfig,
+) -> Result<(Vec<BundledServer>, Vec<BundleEntry>)> {
+    let cache = ServerCache::new(&global_mgr.get_cache_dir());
+    let mut servers = Vec::new();
+    let mut entries = Vec::new();
+
+    for cached in cache.entries().await? {
+        let arch_matches =
+            config.android.arch == ArchType::Auto || cached.arch == config.android.arch.to_str();
+        if cached.version != config.frida.version || !arch_matches {
+            continue;
+        }
+
+        for path in walk_files(&cached.path)? {
+            let Some(file) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let server = BundledServer {
+                version: cached.version.clone(),
+                arch: cached.arch.clone(),
+                file,
+                sha256: compute_sha256(&path).await?,
+            };
+            entries.push(BundleEntry::File {
+                archive_path: server.archive_path(),
+                source: path,
+            });
+            servers.push(server);
+        }
+    }
+
+    Ok((servers, entries))
+}
+
+fn default_bundle_name(config: &ProjectConfig) -> String {
+    let name: String = config
+        .project
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!(
+        "{}-frida-{}.bundle.tar.gz",
+        name.trim_matches('-'),
+        config.frida.version
+    )
+}
@@ -0,0 +1,293 @@
+use crate::cli::commands::devices::prompt;
+use crate::config::{GlobalConfigManager, ProjectConfigManager, ProjectRegistry};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::format_size;
+use crate::frida::server::cache::{
+    cached_servers, select_lru_evictions, select_prunable, server_cache_dirs, CachedServer,
+    ServerCache, BYTES_PER_GB,
+};
+use chrono::Utc;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+/// The label of a cache root: empty for upstream `servers/`, `owner/repo` for a fork.
+fn origin(cache_dir: &Path, servers_dir: &Path) -> String {
+    servers_dir
+        .strip_prefix(cache_dir.join("forks"))
+        .map(|repo| repo.display().to_string())
+        .unwrap_or_default()
+}
+
+/// Every cached build with the label of its cache root.
+async fn cached_builds(cache_dir: &Path) -> Result<Vec<(String, CachedServer)>> {
+    Ok(cached_servers(cache_dir)
+        .await?
+        .into_iter()
+        .map(|(servers_dir, entry)| (origin(cache_dir, &servers_dir), entry))
+        .collect())
+}
+
+fn label(origin: &str, version: &str) -> String {
+    if origin.is_empty() {
+        version.to_string()
+    } else {
+        format!("{} ({})", version, origin)
+    }
+}
+
+/// Versions `prune` never deletes: those of registered projects and of the current one.
+async fn protected_versions(global_mgr: &GlobalConfigManager) -> Result<HashSet<String>> {
+    let registry =
+        ProjectRegistry::load_or_default(&global_mgr.get_project_registry_path()).await?;
+    let mut protected = registry.referenced_frida_versions().await;
+    if let Ok(project_mgr) = ProjectConfigManager::from_current_dir() {
+        if project_mgr.exists() {
+            protected.insert(project_mgr.load().await?.frida.version);
+        }
+    }
+    Ok(protected)
+}
+
+/// Disk usage of the frida-server cache per version, against `cache.max_size_gb`.
+pub async fn size() -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let cache_dir = global_mgr.get_cache_dir();
+    let builds = cached_builds(&cache_dir).await?;
+
+    let mut versions: BTreeMap<String, (u64, Vec<&str>)> = BTreeMap::new();
+    for (origin, entry) in &builds {
+        let usage = versions.entry(label(origin, &entry.version)).or_default();
+        usage.0 += entry.size_bytes;
+        usage.1.push(&entry.arch);
+    }
+    let total: u64 = builds.iter().map(|(_, entry)| entry.size_bytes).sum();
+
+    println!(
+        "{} frida-server cache: {} in {} build(s) under {}",
+        "ℹ".blue().bold(),
+        format_size(total).yellow(),
+        builds.len(),
+        cache_dir.display().to_string().cyan()
+    );
+    for (version, (size, arches)) in &versions {
+        println!(
+            "  {} {} ({})",
+            version.cyan(),
+            format_size(*size),
+            arches.join(", ").yellow()
+        );
+    }
+
+    let cache = &global_config.cache;
+    if cache.max_size_gb == 0 {
+        println!("  Limit: none (cache.max_size_gb = 0)");
+    } else {
+        println!(
+            "  Limit: {} GB (cache.max_size_gb), auto_clean {}",
+            cache.max_size_gb,
+            if cache.auto_clean { "on" } else { "off" }
+        );
+    }
+    Ok(())
+}
+
+/// Every cached frida-server build with its size and when it was last used.
+pub async fn list() -> Result<()> {
+    let cache_dir = GlobalConfigManager::new()?.get_cache_dir();
+    let builds = cached_builds(&cache_dir).await?;
+    if builds.is_empty() {
+        println!("{}", "No cached frida-server builds found".yellow());
+        return Ok(());
+    }
+
+    println!("{}", "Cached frida-server builds:".bold());
+    println!();
+    let now = Utc::now().timestamp();
+    for (origin, entry) in &builds {
+        println!(
+            "  {} {} {:>10}  {}",
+            format!("{:<24}", label(origin, &entry.version)).cyan(),
+            format!("{:<14}", entry.arch).yellow(),
+            format_size(entry.size_bytes),
+            format_last_used(now - entry.last_access).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Delete cached builds outside the `keep` most recently used versions and/or unused for
+/// `older_than`; with neither, evict least recently used builds until the cache
+/// fits `cache.max_size_gb`. Versions of registered projects and the current one are kept.
+pub async fn prune(keep: Option<usize>, older_than: Option<String>, dry_run: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let cache_dir = global_mgr.get_cache_dir();
+
+    // Without criteria, prune down to the size limit like `auto_clean` does after downloads.
+    let max_bytes = global_config.cache.max_size_gb * BYTES_PER_GB;
+    let by_size = keep.is_none() && older_than.is_none();
+    if by_size && max_bytes == 0 {
+        println!(
+            "{} No size limit configured (cache.max_size_gb = 0); pass --keep or --older-than",
+            "ℹ".blue().bold()
+        );
+        return Ok(());
+    }
+    let cutoff = match older_than.as_deref() {
+        Some(age) => Some(Utc::now().timestamp() - parse_age(age)?),
+        None => None,
+    };
+    let protected = protected_versions(&global_mgr).await?;
+
+    let mut selected: Vec<(PathBuf, CachedServer)> = Vec::new();
+    if by_size {
+        // Upstream and fork builds share the size limit.
+        let builds = cached_servers(&cache_dir).await?;
+        let entries: Vec<CachedServer> = builds.iter().map(|(_, entry)| entry.clone()).collect();
+        for idx in select_lru_evictions(&entries, max_bytes, &protected) {
+            selected.push(builds[idx].clone());
+        }
+    } else {
+        for servers_dir in server_cache_dirs(&cache_dir).await? {
+            let entries = ServerCache::at(servers_dir.clone()).entries().await?;
+            for idx in select_prunable(&entries, keep, cutoff, &protected) {
+                selected.push((servers_dir.clone(), entries[idx].clone()));
+            }
+        }
+    }
+
+    for (servers_dir, entry) in &selected {
+        println!(
+            "  {} {} ({}, {})",
+            if dry_run { "→".blue() } else { "✗".red() },
+            label(&origin(&cache_dir, servers_dir), &entry.version).cyan(),
+            entry.arch.yellow(),
+            format_size(entry.size_bytes)
+        );
+        if !dry_run {
+            ServerCache::at(servers_dir.clone()).remove(entry).await?;
+        }
+    }
+    let removed = selected.len();
+    let reclaimed: u64 = selected.iter().map(|(_, entry)| entry.size_bytes).sum();
+
+    if removed == 0 && by_size {
+        println!(
+            "{} Cache is within {} GB",
+            "✓".green().bold(),
+            global_config.cache.max_size_gb
+        );
+    } else if removed == 0 {
+        println!("{} Nothing to prune", "✓".green().bold());
+    } else if dry_run {
+        println!(
+            "{} {} build(s) ({}) would be removed (run without --dry-run to delete)",
+            "ℹ".blue().bold(),
+            removed,
+            format_size(reclaimed)
+        );
+    } else {
+        println!(
+            "{} Removed {} build(s), reclaimed {}",
+            "✓".green().bold(),
+            removed,
+            format_size(reclaimed).yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Delete every cached frida-server build, upstream and forks, once confirmed (or with
+/// `yes`).
+pub async fn clear(yes: bool) -> Result<()> {
+    let cache_dir = GlobalConfigManager::new()?.get_cache_dir();
+    let builds = cached_builds(&cache_dir).await?;
+    if builds.is_empty() {
+        println!(
+            "{} The frida-server cache is already empty",
+            "ℹ".blue().bold()
+        );
+        return Ok(());
+    }
+    let size = format_size(builds.iter().map(|(_, entry)| entry.size_bytes).sum());
+
+    if !yes {
+        if !io::stdin().is_terminal() {
+            return Err(FridaMgrError::CommandFailed(
+                "Refusing to delete without confirmation; pass --yes".to_string(),
+            ));
+        }
+        let answer = prompt(&format!(
+            "Delete {} cached frida-server build(s) ({})? [y/N]: ",
+            builds.len(),
+            size
+        ))?;
+        if !matches!(answer.as_str(), "y" | "Y" | "yes") {
+            println!("{} Nothing deleted", "ℹ".blue().bold());
+            return Ok(());
+        }
+    }
+
+    for root in [cache_dir.join("servers"), cache_dir.join("forks")] {
+        if !root.exists() {
+            continue;
+        }
+        tokio::fs::remove_dir_all(&root).await?;
+    }
+    println!(
+        "{} Removed {} build(s), reclaimed {}",
+        "✓".green().bold(),
+        builds.len(),
+        size.yellow()
+    );
+    Ok(())
+}
+
+/// Seconds in an age such as `90d`, `12w` or `48h`; a bare number counts days.
+fn parse_age(age: &str) -> Result<i64> {
+    let age = age.trim();
+    let (number, unit) = match age.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => age.split_at(idx),
+        None => (age, "d"),
+    };
+    let seconds = match unit {
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => 0,
+    };
+    match number.parse::<i64>() {
+        Ok(n) if seconds > 0 => Ok(n * seconds),
+        _ => Err(FridaMgrError::Config(format!(
+            "Invalid age '{}'; expected a number followed by h, d or w (e.g. 90d)",
+            age
+        ))),
+    }
+}
+
+fn format_last_used(elapsed: i64) -> String {
+    match elapsed / 86_400 {
+        days if days <= 0 => "used today".to_string(),
+        1 => "used 1 day ago".to_string(),
+        days => format!("used {} days ago", days),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age("90d").unwrap(), 90 * 86_400);
+        assert_eq!(parse_age("2w").unwrap(), 14 * 86_400);
+        assert_eq!(parse_age("48h").unwrap(), 48 * 3600);
+        assert_eq!(parse_age("30").unwrap(), 30 * 86_400);
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3m").is_err());
+        assert!(parse_age("").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::Result;
+use crate::core::ProcessExecutor;
+use std::env;
+
+/// Print the directories worth caching between CI runs, one `name=path` per line
+/// (appendable to `$GITHUB_OUTPUT`), or as a JSON object with `--json`.
+pub async fn cache_paths(json: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+
+    let mut paths: Vec<(&str, String)> = vec![
+        (
+            "global_cache",
+            global_mgr.get_cache_dir().to_string_lossy().to_string(),
+        ),
+        (
+            "version_map",
+            global_mgr
+                .get_version_map_path()
+                .to_string_lossy()
+                .to_string(),
+        ),
+    ];
+
+    // Ask uv itself so UV_CACHE_DIR and platform defaults are honoured.
+    let uv_cache = if ProcessExecutor::check_command_exists("uv") {
+        ProcessExecutor::execute_with_output("uv", &["cache", "dir"])
+            .await
+            .ok()
+    } else {
+        None
+    };
+    paths.push((
+        "uv_cache",
+        uv_cache.unwrap_or_else(|| global_config.uv.cache_dir.clone()),
+    ));
+
+    let current_dir = env::current_dir()?;
+    if let Some(project_dir) = ProjectConfigManager::find_project_root(&current_dir) {
+        paths.push((
+            "venv",
+            project_dir.join(".venv").to_string_lossy().to_string(),
+        ));
+    }
+
+    if json {
+        let map: serde_json::Map<String, serde_json::Value> = paths
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), serde_json::Value::String(v)))
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&map).map_err(|e| anyhow::anyhow!(e))?
+        );
+    } else {
+        for (name, path) in paths {
+            println!("{}={}", name, path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::agent::codegen::{render_hooks, write_hooks, HookTarget};
+use crate::agent::AgentProject;
+use crate::config::ProjectConfigManager;
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::env;
+
+pub async fn execute(
+    java: Vec<String>,
+    native: Vec<String>,
+    name: Option<String>,
+    force: bool,
+) -> Result<()> {
+    let mut targets = Vec::with_capacity(java.len() + native.len());
+    for spec in &java {
+        targets.push(HookTarget::parse_java(spec)?);
+    }
+    for spec in &native {
+        targets.push(HookTarget::parse_native(spec)?);
+    }
+    let Some(first) = targets.first() else {
+        return Err(FridaMgrError::Config(
+            "Nothing to generate; pass --java <Class!methods> and/or --native <module!symbols>"
+                .to_string(),
+        ));
+    };
+    let stem = name.unwrap_or_else(|| first.file_stem());
+
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .ok_or(FridaMgrError::NotInitialized)?;
+    let config = ProjectConfigManager::new(&project_dir).load().await?;
+    let agent = AgentProject::from_agent_config(project_dir, &config.agent);
+
+    let path = write_hooks(&agent, &stem, render_hooks(&targets), force).await?;
+
+    println!(
+        "{} Generated {} hook stub(s) in {}",
+        "✓".green().bold(),
+        targets.len().to_string().cyan(),
+        path.display().to_string().yellow()
+    );
+    if agent.entry_path.is_file() {
+        println!(
+            "  Imported from {}; run {} to rebuild",
+            agent.entry_path.display().to_string().yellow(),
+            "frida-mgr agent build".cyan()
+        );
+    } else {
+        println!(
+            "  No agent entry yet; run {} and import {} from it",
+            "frida-mgr agent init".cyan(),
+            format!("./hooks/{}", stem).cyan()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use crate::android::network::endpoint_with_port;
+use crate::android::AdbClient;
+use crate::cli::commands::devices::prompt;
+use crate::config::{DeviceAliases, GlobalConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::io::{self, IsTerminal};
+
+/// Connect adb to a wireless device at `address` (`host[:port]`, port 5555 by default),
+/// pairing first when `pair` names the pairing endpoint from the device's "Pair device
+/// with pairing code" dialog. The device must then be listed and authorized.
+pub async fn connect(
+    address: String,
+    pair: Option<String>,
+    code: Option<String>,
+    alias: Option<String>,
+    remember: bool,
+) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let mut global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let endpoint = endpoint_with_port(&address);
+
+    if let Some(pair) = pair {
+        // The pairing dialog shows its own port on the same host.
+        let pair_endpoint = match pair.parse::<u16>() {
+            Ok(port) => format!("{}:{}", host_of(&endpoint), port),
+            Err(_) => pair,
+        };
+        let code = match code {
+            Some(code) => code,
+            None if io::stdin().is_terminal() => prompt("Pairing code shown on the device: ")?,
+            None => {
+                return Err(FridaMgrError::Config(
+                    "--pair needs the pairing code; pass it with --code".to_string(),
+                ))
+            }
+        };
+        adb.pair(&pair_endpoint, &code).await?;
+        println!(
+            "{} Paired with {}",
+            "✓".green().bold(),
+            pair_endpoint.cyan()
+        );
+    }
+
+    println!("{} Connecting to {}...", "⚙".blue().bold(), endpoint.cyan());
+    adb.connect(&endpoint).await?;
+
+    let device = adb
+        .list_devices()
+        .await?
+        .into_iter()
+        .find(|d| d.id == endpoint)
+        .ok_or_else(|| {
+            FridaMgrError::Adb(format!(
+                "adb reported a connection, but {} is not in `adb devices`",
+                endpoint
+            ))
+        })?;
+    match device.state.as_str() {
+        "device" => {}
+        "unauthorized" => {
+            return Err(FridaMgrError::Adb(format!(
+                "{} is unauthorized; accept the USB debugging prompt on the device and retry",
+                endpoint
+            )))
+        }
+        state => {
+            return Err(FridaMgrError::Adb(format!(
+                "{} is connected but {}",
+                endpoint, state
+            )))
+        }
+    }
+    println!(
+        "{} Connected to {} ({})",
+        "✓".green().bold(),
+        endpoint.cyan(),
+        device.model.yellow()
+    );
+
+    if remember && !global_config.android.wireless_devices.contains(&endpoint) {
+        global_config
+            .android
+            .wireless_devices
+            .push(endpoint.clone());
+        global_mgr.save(&global_config).await?;
+        println!(
+            "  Remembered {}; commands reconnect it when no device is attached",
+            endpoint.yellow()
+        );
+    }
+
+    if let Some(alias) = alias {
+        let aliases_path = global_mgr.get_device_aliases_path();
+        let mut aliases = DeviceAliases::load_or_default(&aliases_path).await?;
+        if aliases.set(&alias, &endpoint) {
+            aliases.save(&aliases_path).await?;
+        }
+        println!(
+            "  Saved alias {} → {}; use {} with any device command",
+            alias.cyan(),
+            endpoint.yellow(),
+            format!("-d {}", alias).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Disconnect adb from `address` (device aliases work too), or from every network device.
+/// With `forget` the device is also dropped from the remembered wireless devices.
+pub async fn disconnect(address: Option<String>, forget: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let mut global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+
+    let endpoint = match address {
+        Some(address) => {
+            let aliases =
+                DeviceAliases::load_or_default(&global_mgr.get_device_aliases_path()).await?;
+            Some(endpoint_with_port(aliases.resolve(&address)))
+        }
+        None => None,
+    };
+
+    adb.disconnect(endpoint.as_deref()).await?;
+    println!(
+        "{} Disconnected {}",
+        "✓".green().bold(),
+        endpoint.as_deref().unwrap_or("all network devices").cyan()
+    );
+
+    if forget {
+        let known = &mut global_config.android.wireless_devices;
+        let before = known.len();
+        match endpoint.as_deref() {
+            Some(endpoint) => known.retain(|e| e != endpoint),
+            None => known.clear(),
+        }
+        let forgotten = before - known.len();
+        if forgotten > 0 {
+            global_mgr.save(&global_config).await?;
+            println!("  Forgot {} remembered device(s)", forgotten);
+        }
+    }
+
+    Ok(())
+}
+
+fn host_of(endpoint: &str) -> &str {
+    endpoint
+        .rsplit_once(':')
+        .map(|(host, _)| host)
+        .unwrap_or(endpoint)
+}
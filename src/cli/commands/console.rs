@@ -0,0 +1,251 @@
+use crate::cli::commands::foreground::{ensure_no_forbidden_args, resolve_foreground_context};
+use crate::config::{AgentBuildTool, GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::Result;
+use crate::core::{interrupt, notify};
+use crate::python::VenvExecutor;
+use crate::{agent, agent::AgentProject};
+use colored::Colorize;
+use std::env;
+use tokio::io::AsyncReadExt;
+use tokio::process::Child;
+
+const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
+    "-U",
+    "--usb",
+    "-D",
+    "--device",
+    "-H",
+    "--host",
+    "-n",
+    "--attach-name",
+    "-N",
+    "--attach-identifier",
+    "-p",
+    "--attach-pid",
+    "-f",
+    "--spawn",
+    "-F",
+    "--attach-frontmost",
+    "-l",
+    "--load",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConsoleKey {
+    Reload,
+    Respawn,
+    Quit,
+}
+
+fn parse_key(byte: u8) -> Option<ConsoleKey> {
+    match byte.to_ascii_lowercase() {
+        b'r' => Some(ConsoleKey::Reload),
+        b's' => Some(ConsoleKey::Respawn),
+        b'q' | 0x04 => Some(ConsoleKey::Quit),
+        _ => None,
+    }
+}
+
+/// How frida should reach the app on the next (re)start.
+enum Target {
+    Pid(u32),
+    Name(String),
+    Identifier(String),
+    Spawn(String),
+}
+
+impl Target {
+    fn args(&self) -> [String; 2] {
+        match self {
+            Target::Pid(pid) => ["-p".to_string(), pid.to_string()],
+            Target::Name(name) => ["-n".to_string(), name.clone()],
+            Target::Identifier(id) => ["-N".to_string(), id.clone()],
+            Target::Spawn(id) => ["-f".to_string(), id.clone()],
+        }
+    }
+}
+
+/// Puts the controlling terminal into single-keypress mode and restores it on drop.
+/// Output processing is left alone so frida's output still renders normally.
+struct KeypressMode {
+    #[cfg(unix)]
+    saved: Option<String>,
+}
+
+impl KeypressMode {
+    #[cfg(unix)]
+    fn enable() -> Self {
+        use std::process::{Command, Stdio};
+        let saved = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+        if saved.is_some() {
+            let _ = Command::new("stty")
+                .args(["-icanon", "-echo", "min", "1"])
+                .stdin(Stdio::inherit())
+                .status();
+        }
+        Self { saved }
+    }
+
+    #[cfg(not(unix))]
+    fn enable() -> Self {
+        Self {}
+    }
+}
+
+impl Drop for KeypressMode {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(saved) = self.saved.take() {
+            let _ = std::process::Command::new("stty")
+                .arg(saved)
+                .stdin(std::process::Stdio::inherit())
+                .status();
+        }
+    }
+}
+
+pub async fn execute(
+    device_id: Option<String>,
+    agent_dir: Option<String>,
+    agent_tool: Option<AgentBuildTool>,
+    args: Vec<String>,
+) -> Result<()> {
+    ensure_no_forbidden_args(
+        &args,
+        FORBIDDEN_FRIDA_ARGS,
+        "frida-mgr console selects the device, target and agent automatically",
+    )?;
+
+    let notifications = GlobalConfigManager::new()?.load().await?.notifications;
+    let foreground = resolve_foreground_context(device_id.as_deref(), None, None).await?;
+    foreground.print_summary();
+
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .unwrap_or_else(|| current_dir.clone());
+
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    let mut config = project_mgr.load().await?;
+    if let Some(dir) = agent_dir {
+        config.agent.dir = dir;
+    }
+    if let Some(tool) = agent_tool {
+        config.agent.tool = tool;
+    }
+    let agent_project = AgentProject::from_agent_config(project_dir.clone(), &config.agent);
+    let bundle = agent::build_agent(&agent_project).await?;
+
+    let executor = VenvExecutor::new(project_dir);
+    let device = foreground.device.id.clone();
+    let mut target = match foreground.pid {
+        Some(pid) => Target::Pid(pid),
+        None => Target::Name(foreground.process.clone()),
+    };
+
+    println!(
+        "{} Console: press {} to rebuild + reload the agent, {} to respawn the app, {} to quit",
+        "ℹ".blue().bold(),
+        "r".cyan().bold(),
+        "s".cyan().bold(),
+        "q".cyan().bold()
+    );
+
+    let start = |target: &Target| -> Result<Child> {
+        let mut frida_args = vec!["-D".to_string(), device.clone()];
+        frida_args.extend(target.args());
+        frida_args.push("-l".to_string());
+        frida_args.push(bundle.to_string_lossy().to_string());
+        frida_args.extend(args.iter().cloned());
+        executor.spawn_detached_stdin("frida", &frida_args)
+    };
+
+    let _keypress = KeypressMode::enable();
+    let mut stdin = tokio::io::stdin();
+    let mut child = Some(start(&target)?);
+    let mut buf = [0u8; 1];
+
+    loop {
+        let exited = async {
+            match child.as_mut() {
+                Some(c) => c.wait().await.ok(),
+                None => std::future::pending().await,
+            }
+        };
+
+        let key = tokio::select! {
+            read = stdin.read(&mut buf) => match read {
+                Ok(0) | Err(_) => ConsoleKey::Quit,
+                Ok(_) => match parse_key(buf[0]) {
+                    Some(key) => key,
+                    None => continue,
+                },
+            },
+            status = exited => {
+                child = None;
+                let code = status.and_then(|s| s.code()).unwrap_or(1);
+                println!(
+                    "{} frida exited ({}); press {} to re-attach, {} to respawn, {} to quit",
+                    "⚠".yellow().bold(),
+                    code,
+                    "r".cyan(),
+                    "s".cyan(),
+                    "q".cyan()
+                );
+                continue;
+            }
+            _ = interrupt::ctrl_c() => ConsoleKey::Quit,
+        };
+
+        if let Some(mut running) = child.take() {
+            let _ = running.kill().await;
+        }
+
+        match key {
+            ConsoleKey::Quit => break,
+            ConsoleKey::Reload => {
+                println!("{} Rebuilding agent...", "⚙".blue().bold());
+                if let Err(e) = agent::build_agent(&agent_project).await {
+                    eprintln!("{} {}", "✗".red().bold(), e);
+                    notify(notifications, "Agent rebuild failed", &e.to_string()).await;
+                }
+                // A respawned app has a new pid; re-attach by identifier from then on.
+                if let Target::Spawn(id) = &target {
+                    target = Target::Identifier(id.clone());
+                }
+            }
+            ConsoleKey::Respawn => {
+                println!(
+                    "{} Respawning {}...",
+                    "→".blue().bold(),
+                    foreground.package.cyan()
+                );
+                target = Target::Spawn(foreground.package.clone());
+            }
+        }
+
+        child = Some(start(&target)?);
+    }
+
+    println!("{} Console closed", "✓".green().bold());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_console_keys() {
+        assert_eq!(parse_key(b'r'), Some(ConsoleKey::Reload));
+        assert_eq!(parse_key(b'S'), Some(ConsoleKey::Respawn));
+        assert_eq!(parse_key(b'q'), Some(ConsoleKey::Quit));
+        assert_eq!(parse_key(0x04), Some(ConsoleKey::Quit));
+        assert_eq!(parse_key(b'x'), None);
+    }
+}
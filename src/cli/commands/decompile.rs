@@ -0,0 +1,137 @@
+use crate::android::manifest::{parse_manifest, Manifest};
+use crate::android::AdbClient;
+use crate::cli::commands::apk::{pull_apks, pulled_apks, PULLED_APK_DIR};
+use crate::cli::commands::device::resolve_device;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{interrupt, ProcessExecutor};
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Project directory jadx output goes to, laid out as `decompiled/<package>/`.
+pub const DECOMPILED_DIR: &str = "decompiled";
+
+/// Pull `package` (unless already pulled), decompile it with jadx into
+/// `decompiled/<package>/` and print where to start hooking.
+pub async fn execute(
+    package: String,
+    device_id: Option<String>,
+    refresh: bool,
+    jadx_args: Vec<String>,
+) -> Result<()> {
+    if !ProcessExecutor::check_command_exists("jadx") {
+        return Err(FridaMgrError::CommandFailed(
+            "jadx is not installed or not in PATH (https://github.com/skylot/jadx/releases); \
+             run 'frida-mgr doctor' to check"
+                .to_string(),
+        ));
+    }
+
+    let current_dir = env::current_dir()?;
+    let project_dir =
+        ProjectConfigManager::find_project_root(&current_dir).unwrap_or(current_dir.clone());
+
+    let apk_dir = project_dir.join(PULLED_APK_DIR).join(&package);
+    if refresh || !apk_dir.join("base.apk").is_file() {
+        let global_config = GlobalConfigManager::new()?.load().await?;
+        let adb = AdbClient::new(Some(global_config.android.adb_path));
+        let device = resolve_device(&adb, device_id.as_deref()).await?;
+        pull_apks(&adb, &device.id, &package, &project_dir).await?;
+    } else {
+        println!(
+            "{} Using pulled APKs in {} (--refresh to pull again)",
+            "ℹ".blue().bold(),
+            apk_dir.display().to_string().yellow()
+        );
+    }
+
+    let out_dir = project_dir.join(DECOMPILED_DIR).join(&package);
+    if out_dir.exists() {
+        tokio::fs::remove_dir_all(&out_dir).await?;
+    }
+    run_jadx(&pulled_apks(&apk_dir), &out_dir, &jadx_args).await?;
+
+    let manifest_path = out_dir.join("resources").join("AndroidManifest.xml");
+    match tokio::fs::read_to_string(&manifest_path).await {
+        Ok(xml) => print_entry_points(&parse_manifest(&xml)?),
+        Err(_) => eprintln!(
+            "{} jadx wrote no {}; entry points unavailable",
+            "⚠".yellow().bold(),
+            "resources/AndroidManifest.xml".yellow()
+        ),
+    }
+
+    println!();
+    println!(
+        "{} Sources in {}",
+        "✓".green().bold(),
+        out_dir.join("sources").display().to_string().cyan()
+    );
+    Ok(())
+}
+
+async fn run_jadx(apks: &[PathBuf], out_dir: &Path, extra_args: &[String]) -> Result<()> {
+    println!(
+        "{} Decompiling {} APK(s) with jadx...",
+        "⚙".blue().bold(),
+        apks.len()
+    );
+
+    let mut child = Command::new("jadx")
+        .arg("-d")
+        .arg(out_dir)
+        .args(extra_args)
+        .args(apks)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run jadx: {}", e)))?;
+    let _guard = child.id().map(interrupt::kill_on_interrupt);
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| FridaMgrError::CommandFailed(format!("Failed to run jadx: {}", e)))?;
+
+    // jadx exits non-zero whenever some method failed to decompile, which is routine for
+    // obfuscated apps; only a run that produced nothing is an error.
+    if !status.success() {
+        if out_dir.join("sources").is_dir() {
+            eprintln!(
+                "{} jadx finished with errors; some classes may be incomplete",
+                "⚠".yellow().bold()
+            );
+        } else {
+            return Err(FridaMgrError::CommandFailed(format!(
+                "jadx failed with exit code {:?}",
+                status.code()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn print_entry_points(manifest: &Manifest) {
+    println!();
+    println!("{}", "Entry points:".bold());
+    if let Some(application) = &manifest.application {
+        println!("  Application: {}", application.cyan());
+    }
+    if let Some(launcher) = manifest.launcher_activity() {
+        println!("  Launcher activity: {}", launcher.name.cyan());
+    }
+    let exported: Vec<_> = manifest.exported().filter(|c| !c.is_launcher()).collect();
+    if !exported.is_empty() {
+        println!("  Exported components:");
+        for component in exported {
+            println!(
+                "    {} {}",
+                format!("{:<8}", component.kind.as_str()).yellow(),
+                component.name
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::cli::commands::foreground::resolve_foreground_context;
+use crate::config::ProjectConfigManager;
+use crate::core::error::{FridaMgrError, Result};
+use crate::frida::protections::{ProtectionFindings, PROTECTIONS_SCRIPT};
+use crate::python::VenvExecutor;
+use colored::Colorize;
+use std::env;
+use std::io::Write;
+
+pub async fn execute(device_id: Option<String>, json: bool) -> Result<()> {
+    let foreground = resolve_foreground_context(device_id.as_deref(), None, None).await?;
+    if !json {
+        foreground.print_summary();
+        println!(
+            "{} Scanning {} for protections (read-only)...",
+            "⚙".blue().bold(),
+            foreground.package.cyan()
+        );
+    }
+
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .unwrap_or_else(|| current_dir.clone());
+
+    // A private, uniquely named file that lives (and is deleted) with this run, so
+    // concurrent runs and other users cannot swap the injected script.
+    let mut script = tempfile::Builder::new()
+        .prefix("frida-mgr-detect-protections-")
+        .suffix(".js")
+        .tempfile()?;
+    script.write_all(PROTECTIONS_SCRIPT.as_bytes())?;
+    script.flush()?;
+
+    let mut args = vec!["-D".to_string(), foreground.device.id.clone()];
+    match foreground.pid {
+        Some(pid) => args.extend(["-p".to_string(), pid.to_string()]),
+        None => args.extend(["-n".to_string(), foreground.process.clone()]),
+    }
+    args.extend([
+        "-q".to_string(),
+        "-l".to_string(),
+        script.path().to_string_lossy().to_string(),
+    ]);
+
+    let output = VenvExecutor::new(project_dir)
+        .with_work_dir(current_dir)
+        .run_captured("frida", &args)
+        .await?;
+
+    let findings = ProtectionFindings::from_output(&output.stdout).ok_or_else(|| {
+        FridaMgrError::CommandFailed(format!(
+            "frida did not report findings (exit code {}): {}",
+            output.exit_code,
+            output.stderr.trim()
+        ))
+    })?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&findings).map_err(|e| anyhow::anyhow!(e))?
+        );
+        return Ok(());
+    }
+
+    print_findings(&findings);
+    Ok(())
+}
+
+fn print_findings(findings: &ProtectionFindings) {
+    println!();
+    if findings.is_empty() {
+        println!("{} No known protections detected", "✓".green().bold());
+        return;
+    }
+
+    let sections = [
+        ("Root detection", &findings.root_detection),
+        ("SSL pinning", &findings.ssl_pinning),
+        ("Packers", &findings.packers),
+        ("Frida detection", &findings.frida_detection),
+    ];
+    for (title, items) in sections {
+        if items.is_empty() {
+            continue;
+        }
+        println!("{}", format!("{}:", title).bold());
+        for item in items {
+            println!("  {} {}", "●".yellow(), item);
+        }
+    }
+
+    println!();
+    println!("{}", "Suggested next steps:".bold());
+    for suggestion in &findings.suggestions {
+        println!("  → {}", suggestion);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::android::{AdbClient, Device};
+use crate::cli::commands::devices::prompt;
+use crate::cli::commands::remove;
+use crate::config::{DeviceAliases, GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::format_size;
+use colored::Colorize;
+use std::env;
+use std::io::{self, IsTerminal};
+
+async fn project_manager() -> Result<ProjectConfigManager> {
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .ok_or(FridaMgrError::NotInitialized)?;
+    Ok(ProjectConfigManager::new(&project_dir))
+}
+
+/// The device the project enclosing the working directory pinned with `frida-mgr device
+/// use`, if any. A frida.toml that cannot be read is an error rather than "no pin", so a
+/// command never quietly falls back to another device.
+pub async fn pinned_device() -> Result<Option<String>> {
+    let current_dir = env::current_dir()?;
+    let Some(project_dir) = ProjectConfigManager::find_project_root(&current_dir) else {
+        return Ok(None);
+    };
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    let config = project_mgr.load().await.map_err(|e| {
+        FridaMgrError::Config(format!(
+            "Could not read the pinned device from {}: {}",
+            project_mgr.config_path().display(),
+            e
+        ))
+    })?;
+    Ok(config.android.device)
+}
+
+/// The device `device` names (a serial or alias), else the device the enclosing project
+/// pinned, else the first connected device, reconnecting remembered wireless devices when
+/// none is.
+pub async fn resolve_device(adb: &AdbClient, device: Option<&str>) -> Result<Device> {
+    let pinned = match device {
+        Some(_) => None,
+        None => pinned_device().await?,
+    };
+    let global_mgr = GlobalConfigManager::new()?;
+
+    let Some(name) = device.or(pinned.as_deref()) else {
+        return match adb.get_device(None).await {
+            Err(FridaMgrError::NoDevice) => {
+                let endpoints = global_mgr.load().await?.android.wireless_devices;
+                if adb.reconnect_wireless_devices(&endpoints).await {
+                    adb.get_device(None).await
+                } else {
+                    Err(FridaMgrError::NoDevice)
+                }
+            }
+            result => result,
+        };
+    };
+
+    // A broken aliases file should not stop commands given a plain serial.
+    let serial = match DeviceAliases::load_or_default(&global_mgr.get_device_aliases_path()).await {
+        Ok(aliases) => aliases.resolve(name).to_string(),
+        Err(e) => {
+            eprintln!(
+                "{} Could not read device aliases ({}); treating '{}' as a serial",
+                "⚠".yellow().bold(),
+                e,
+                name
+            );
+            name.to_string()
+        }
+    };
+    adb.get_device(Some(&serial))
+        .await
+        .map_err(|e| match (e, &pinned) {
+            (FridaMgrError::DeviceNotFound(_), Some(pinned)) => FridaMgrError::Adb(format!(
+                "Pinned device {} (frida.toml android.device) is not connected; \
+                 pass --device or run `frida-mgr device clear`",
+                pinned
+            )),
+            (FridaMgrError::DeviceNotFound(_), None) => {
+                FridaMgrError::DeviceNotFound(name.to_string())
+            }
+            (e, _) => e,
+        })
+}
+
+/// Pin `device` (a serial or alias) as the project's default device in frida.toml.
+pub async fn pin(device: String) -> Result<()> {
+    let project_mgr = project_manager().await?;
+    let mut config = project_mgr.load().await?;
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let aliases = DeviceAliases::load_or_default(&global_mgr.get_device_aliases_path()).await?;
+    let serial = aliases.resolve(&device);
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let connected = adb
+        .list_devices()
+        .await
+        .map(|devices| devices.iter().any(|d| d.id == serial))
+        .unwrap_or(false);
+
+    config.android.device = Some(device.clone());
+    project_mgr.save(&config).await?;
+
+    println!(
+        "{} Pinned {} as the default device of {}",
+        "✓".green().bold(),
+        device.cyan(),
+        config.project.name.cyan()
+    );
+    if serial != device {
+        println!("  Alias for {}", serial.yellow());
+    }
+    if !connected {
+        println!(
+            "{} {} is not connected right now",
+            "⚠".yellow().bold(),
+            serial.yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Print the project's pinned device and whether it is connected.
+pub async fn show() -> Result<()> {
+    project_manager().await?;
+    let Some(device) = pinned_device().await? else {
+        println!(
+            "{} No device pinned; commands use the first connected device",
+            "ℹ".blue().bold()
+        );
+        println!("{} Pin one with: frida-mgr device use <id>", "→".blue());
+        return Ok(());
+    };
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let aliases = DeviceAliases::load_or_default(&global_mgr.get_device_aliases_path()).await?;
+    let serial = aliases.resolve(&device);
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let state = adb
+        .list_devices()
+        .await?
+        .into_iter()
+        .find(|d| d.id == serial)
+        .map(|d| d.state);
+
+    println!("{} Pinned device: {}", "ℹ".blue().bold(), device.cyan());
+    if serial != device {
+        println!("  Alias for {}", serial.yellow());
+    }
+    match state.as_deref() {
+        Some("device") => println!("  {}", "connected".green()),
+        Some(state) => println!("  {}", state.yellow()),
+        None => println!("  {}", "not connected".red()),
+    }
+    Ok(())
+}
+
+/// Remove the project's pinned device.
+pub async fn clear() -> Result<()> {
+    let project_mgr = project_manager().await?;
+    let mut config = project_mgr.load().await?;
+    let Some(device) = config.android.device.take() else {
+        println!("{} No device pinned", "ℹ".blue().bold());
+        return Ok(());
+    };
+
+    project_mgr.save(&config).await?;
+    println!(
+        "{} Unpinned {}; commands use the first connected device again",
+        "✓".green().bold(),
+        device.cyan()
+    );
+    Ok(())
+}
+
+/// List the frida-server binaries, logs and agent bundles left in the device's push
+/// directories with their sizes, and delete them once confirmed (or with `yes`).
+pub async fn cleanup(device_id: Option<String>, yes: bool) -> Result<()> {
+    let artifacts = remove::scan(device_id).await?;
+    let device_id = artifacts.device_id.clone();
+    if artifacts.found.is_empty() {
+        println!(
+            "{} No frida artifacts found on {}",
+            "ℹ".blue().bold(),
+            device_id.cyan()
+        );
+        return Ok(());
+    }
+
+    let count = artifacts.found.len();
+    let size = format_size(artifacts.total_size());
+    println!(
+        "{} Frida artifacts on {}:",
+        "ℹ".blue().bold(),
+        device_id.cyan()
+    );
+    artifacts.print("•".blue());
+    println!("  Total: {} file(s), {}", count, size.yellow());
+
+    if !yes {
+        if !io::stdin().is_terminal() {
+            return Err(FridaMgrError::CommandFailed(
+                "Refusing to delete without confirmation; pass --yes".to_string(),
+            ));
+        }
+        let answer = prompt(&format!("Delete {} file(s)? [y/N]: ", count))?;
+        if !matches!(answer.as_str(), "y" | "Y" | "yes") {
+            println!("{} Nothing deleted", "ℹ".blue().bold());
+            return Ok(());
+        }
+    }
+
+    artifacts.delete().await?;
+    println!(
+        "{} Removed {} file(s) ({}) from {}",
+        "✓".green().bold(),
+        count,
+        size,
+        device_id.cyan()
+    );
+    Ok(())
+}
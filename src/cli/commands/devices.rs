@@ -1,16 +1,85 @@
-use crate::android::AdbClient;
-use crate::config::{resolve_android_server_target, GlobalConfigManager};
+use crate::android::network::{MdnsService, MdnsServiceKind};
+use crate::android::{AdbClient, Device, DeviceInfo, Transport};
+use crate::cli::events::device_changes;
+use crate::cli::OutputFormat;
+use crate::config::{
+    resolve_android_server_target, ArchType, GlobalConfigManager, ProjectConfig,
+    ProjectConfigManager,
+};
 use crate::core::error::Result;
+use crate::core::interrupt;
 use colored::Colorize;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::Duration;
 
-pub async fn execute() -> Result<()> {
+/// How often `--watch` re-lists devices.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// `--state` / `--transport` restrictions on which devices are listed.
+#[derive(Debug, Default)]
+pub struct DeviceFilter {
+    pub state: Option<String>,
+    pub transport: Option<Transport>,
+}
+
+impl DeviceFilter {
+    fn is_active(&self) -> bool {
+        self.state.is_some() || self.transport.is_some()
+    }
+
+    fn matches(&self, device: &Device) -> bool {
+        self.state.as_ref().is_none_or(|s| *s == device.state)
+            && self.transport.is_none_or(|t| t == device.transport)
+    }
+
+    fn apply(&self, devices: Vec<Device>) -> Vec<Device> {
+        devices.into_iter().filter(|d| self.matches(d)).collect()
+    }
+}
+
+pub async fn execute(
+    discover: bool,
+    watch: bool,
+    output: OutputFormat,
+    long: bool,
+    filter: DeviceFilter,
+) -> Result<()> {
     let global_config = GlobalConfigManager::new()?.load().await?;
     let adb = AdbClient::new(Some(global_config.android.adb_path));
 
-    let devices = adb.list_devices().await?;
+    if watch {
+        return watch_devices(&adb, output, &filter).await;
+    }
+
+    if discover {
+        discover_devices(&adb).await?;
+        println!();
+    }
+
+    // Compatibility is judged against the project's frida version and arch, if any.
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    let project = if project_mgr.exists() {
+        Some(project_mgr.load().await?)
+    } else {
+        None
+    };
+    let devices = if long {
+        let root_command = project
+            .as_ref()
+            .map(|c| c.android.root_command.clone())
+            .unwrap_or_else(|| ProjectConfig::default().android.root_command);
+        adb.list_devices_detailed(&root_command).await?
+    } else {
+        adb.list_devices().await?
+    };
+    let devices = filter.apply(devices);
 
     if devices.is_empty() {
-        println!("{}", "No devices connected".yellow());
+        if filter.is_active() {
+            println!("{}", "No connected devices match the filter".yellow());
+        } else {
+            println!("{}", "No devices connected".yellow());
+        }
         return Ok(());
     }
 
@@ -20,6 +89,17 @@ pub async fn execute() -> Result<()> {
     let target = resolve_android_server_target(&global_config.android.default_push_path, None)?;
 
     for device in &devices {
+        if device.state != "device" {
+            println!(
+                "  {} {} ({}) [{}]",
+                "○".red(),
+                device.id.cyan(),
+                device.model.yellow(),
+                device.state.yellow()
+            );
+            continue;
+        }
+
         let arch_result = adb.get_arch(&device.id).await;
         let arch_str = arch_result
             .map(|a| a.to_str().to_string())
@@ -41,7 +121,237 @@ pub async fn execute() -> Result<()> {
             device.model.yellow(),
             arch_str.blue()
         );
+        if long {
+            print_details(
+                device,
+                project.as_ref().map(|c| c.frida.version.as_str()),
+                project
+                    .as_ref()
+                    .map_or(&ArchType::Auto, |c| &c.android.arch),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_details(device: &Device, frida_version: Option<&str>, arch: &ArchType) {
+    println!("      Transport: {}", device.transport.as_str().yellow());
+    match &device.info {
+        Some(info) => print_device_info(info, frida_version, arch, "      "),
+        None => println!("      Details:   {}", "unavailable".red()),
+    }
+}
+
+/// Print `info` as aligned `Label: value` lines, then what may keep `frida_version` from
+/// working on the device. Shared with `status`.
+pub fn print_device_info(
+    info: &DeviceInfo,
+    frida_version: Option<&str>,
+    arch: &ArchType,
+    indent: &str,
+) {
+    let unknown = || "unknown".to_string();
+    let abis = if info.abis.is_empty() {
+        unknown()
+    } else {
+        info.abis.join(", ")
+    };
+    let root = match &info.root {
+        Some(method) => method.green(),
+        None => "not rooted".red(),
+    };
+    let selinux = match info.selinux.as_deref() {
+        Some("Enforcing") => "Enforcing".yellow(),
+        Some(mode) => mode.green(),
+        None => "unknown".normal(),
+    };
+    println!(
+        "{}Product:   {} {}",
+        indent,
+        info.manufacturer.clone().unwrap_or_else(unknown).yellow(),
+        info.model.clone().unwrap_or_else(unknown).yellow()
+    );
+    println!("{}Version:   {}", indent, info.version_label().yellow());
+    println!("{}ABIs:      {}", indent, abis.blue());
+    println!("{}Root:      {}", indent, root);
+    println!("{}SELinux:   {}", indent, selinux);
+
+    let issues = info.compatibility_issues(frida_version, arch);
+    match (frida_version, issues.is_empty()) {
+        (Some(version), true) => println!(
+            "{}{} Compatible with frida {}",
+            indent,
+            "✓".green().bold(),
+            version
+        ),
+        (None, true) => {}
+        (_, false) => {
+            for issue in issues {
+                println!("{}{} {}", indent, "⚠".yellow().bold(), issue);
+            }
+        }
+    }
+}
+
+/// Report the devices present now, then every connect, disconnect and state change until
+/// Ctrl-C.
+async fn watch_devices(adb: &AdbClient, output: OutputFormat, filter: &DeviceFilter) -> Result<()> {
+    adb.check_installed()?;
+    if output == OutputFormat::Text {
+        println!(
+            "{} Watching for device changes; press Ctrl-C to stop",
+            "ℹ".blue().bold()
+        );
+    }
+
+    let mut known = Vec::new();
+    loop {
+        // A restarting adb daemon briefly fails to list; keep the last known state.
+        if let Ok(devices) = adb.list_devices().await {
+            let devices = filter.apply(devices);
+            for event in device_changes(&known, &devices) {
+                event.report(output);
+            }
+            known = devices;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(WATCH_INTERVAL) => {}
+            _ = interrupt::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// List wireless-debugging devices advertised over mDNS and offer to pair/connect one.
+async fn discover_devices(adb: &AdbClient) -> Result<()> {
+    println!(
+        "{} Browsing for wireless debugging devices...",
+        "ℹ".blue().bold()
+    );
+    let services = adb.mdns_services().await?;
+
+    if services.is_empty() {
+        println!(
+            "{}",
+            "No wireless debugging devices found (enable Developer options → Wireless debugging on the same network)"
+                .yellow()
+        );
+        return Ok(());
+    }
+
+    for (index, service) in services.iter().enumerate() {
+        let kind = match service.kind {
+            MdnsServiceKind::Connect => "connect".green(),
+            MdnsServiceKind::Pairing => "pairing".yellow(),
+        };
+        println!(
+            "  [{}] {} {} ({})",
+            index + 1,
+            service.endpoint.cyan(),
+            kind,
+            service.name.dimmed()
+        );
+    }
+
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let answer = prompt(&format!(
+        "Pair/connect which device? [1-{}, Enter to skip]: ",
+        services.len()
+    ))?;
+    let Some(service) = answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| services.get(i))
+    else {
+        return Ok(());
+    };
+
+    let connect = match service.kind {
+        MdnsServiceKind::Connect => Some(service.clone()),
+        MdnsServiceKind::Pairing => pair(adb, service).await?,
+    };
+
+    if let Some(connect) = connect {
+        adb.connect(&connect.endpoint).await?;
+        println!(
+            "{} Connected to {}",
+            "✓".green().bold(),
+            connect.endpoint.cyan()
+        );
     }
 
     Ok(())
 }
+
+/// Pair with `service` and return the matching connect service, if one is advertised.
+async fn pair(adb: &AdbClient, service: &MdnsService) -> Result<Option<MdnsService>> {
+    let code = prompt("Pairing code shown on the device: ")?;
+    adb.pair(&service.endpoint, &code).await?;
+    println!(
+        "{} Paired with {}",
+        "✓".green().bold(),
+        service.endpoint.cyan()
+    );
+
+    // The connect service may only be advertised after pairing, so browse again.
+    let services = adb.mdns_services().await?;
+    let host = service.endpoint.rsplit_once(':').map(|(host, _)| host);
+    let connect = services.iter().find(|s| {
+        s.kind == MdnsServiceKind::Connect
+            && (s.name == service.name || s.endpoint.rsplit_once(':').map(|(h, _)| h) == host)
+    });
+    if connect.is_none() {
+        println!(
+            "  No connect endpoint advertised yet; rerun {} once the device shows up",
+            "frida-mgr devices --discover".cyan()
+        );
+    }
+
+    Ok(connect.cloned())
+}
+
+pub fn prompt(message: &str) -> Result<String> {
+    print!("{}", message);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_state_and_transport() {
+        let usb = Device {
+            id: "R58M123ABC".to_string(),
+            state: "device".to_string(),
+            ..Device::default()
+        };
+        let tcp = Device {
+            id: "192.168.1.5:5555".to_string(),
+            state: "unauthorized".to_string(),
+            transport: Transport::Tcp,
+            ..Device::default()
+        };
+
+        let filter = DeviceFilter {
+            state: Some("device".to_string()),
+            transport: None,
+        };
+        assert!(filter.matches(&usb) && !filter.matches(&tcp));
+
+        let filter = DeviceFilter {
+            state: None,
+            transport: Some(Transport::Tcp),
+        };
+        assert!(!filter.matches(&usb) && filter.matches(&tcp));
+        assert!(DeviceFilter::default().matches(&tcp));
+    }
+}
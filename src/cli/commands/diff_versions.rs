@@ -0,0 +1,171 @@
+use crate::config::{GlobalConfigManager, VersionMapping};
+use crate::core::error::Result;
+use crate::core::HttpClient;
+use crate::frida::server::ServerCache;
+use crate::frida::{fetch_release_notes, releases_between};
+use chrono::NaiveDate;
+use colored::Colorize;
+
+/// Lines of each release body shown with `--changelog`.
+const CHANGELOG_LINES: usize = 12;
+
+pub async fn execute(from: String, to: String, changelog: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+    let from = version_map.resolve_alias(&from);
+    let to = version_map.resolve_alias(&to);
+
+    let from_info = version_map.mappings.get(&from);
+    let to_info = version_map.mappings.get(&to);
+
+    let entries = ServerCache::new(&global_mgr.get_cache_dir())
+        .entries()
+        .await?;
+    let cached = |version: &str| {
+        let arches: Vec<&str> = entries
+            .iter()
+            .filter(|e| e.version == version)
+            .map(|e| e.arch.as_str())
+            .collect();
+        if arches.is_empty() {
+            "none".to_string()
+        } else {
+            arches.join(", ")
+        }
+    };
+
+    println!(
+        "{} {} → {}",
+        "Frida".bold(),
+        from.cyan().bold(),
+        to.cyan().bold()
+    );
+    println!();
+
+    let missing = || "(not in version map)".to_string();
+    let rows = [
+        (
+            "released",
+            from_info.map_or_else(missing, |i| i.released.clone()),
+            to_info.map_or_else(missing, |i| i.released.clone()),
+        ),
+        (
+            "frida-tools",
+            from_info.map_or_else(missing, |i| i.tools.clone()),
+            to_info.map_or_else(missing, |i| i.tools.clone()),
+        ),
+        (
+            "objection",
+            from_info.map_or_else(missing, |i| objection_label(i.objection.as_deref())),
+            to_info.map_or_else(missing, |i| objection_label(i.objection.as_deref())),
+        ),
+        ("cached servers", cached(&from), cached(&to)),
+    ];
+
+    for (label, old, new) in &rows {
+        if old == new {
+            println!("  {:<15} {}", label, old.dimmed());
+        } else {
+            println!("  {:<15} {} → {}", label, old, new.yellow());
+        }
+    }
+
+    if let (Some(a), Some(b)) = (from_info, to_info) {
+        if let Some(days) = days_between(&a.released, &b.released) {
+            println!("  {:<15} {} days", "apart", days.abs());
+        }
+    }
+
+    println!();
+    for note in upgrade_notes(&from, &to) {
+        println!("{} {}", "⚠".yellow().bold(), note);
+    }
+    if from_info.is_none() || to_info.is_none() {
+        println!(
+            "{} Run {} to refresh the version map",
+            "ℹ".blue().bold(),
+            "frida-mgr sync --update-map".cyan()
+        );
+    }
+
+    if changelog {
+        let http = HttpClient::from_network_config(&global_config.network)?
+            .with_response_cache(global_mgr.get_http_cache_dir());
+        let releases = fetch_release_notes(&http).await?;
+        let between = releases_between(&releases, &from, &to);
+
+        println!();
+        if between.is_empty() {
+            println!(
+                "{} No GitHub releases found between {} and {}",
+                "ℹ".blue().bold(),
+                from,
+                to
+            );
+        }
+        for release in between {
+            let date = release
+                .published_at
+                .as_deref()
+                .and_then(|d| d.get(..10))
+                .unwrap_or("");
+            println!("{} {}", release.tag_name.cyan().bold(), date.dimmed());
+            let body = release.body.as_deref().unwrap_or("").trim();
+            if body.is_empty() {
+                println!("  {}", "(no release notes)".dimmed());
+            }
+            for line in body.lines().take(CHANGELOG_LINES) {
+                println!("  {}", line.trim_end());
+            }
+            if body.lines().count() > CHANGELOG_LINES {
+                println!("  {}", "...".dimmed());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn objection_label(version: Option<&str>) -> String {
+    version.unwrap_or("unpinned").to_string()
+}
+
+fn days_between(from: &str, to: &str) -> Option<i64> {
+    let from = NaiveDate::parse_from_str(from, "%Y-%m-%d").ok()?;
+    let to = NaiveDate::parse_from_str(to, "%Y-%m-%d").ok()?;
+    Some((to - from).num_days())
+}
+
+/// Warnings about the kind of jump between two versions.
+fn upgrade_notes(from: &str, to: &str) -> Vec<String> {
+    let (Ok(a), Ok(b)) = (semver::Version::parse(from), semver::Version::parse(to)) else {
+        return Vec::new();
+    };
+
+    let mut notes = Vec::new();
+    if a.major != b.major {
+        notes.push(format!(
+            "Major version change ({} → {}): script APIs and the server protocol may be incompatible; rebuild agents and re-push frida-server",
+            a.major, b.major
+        ));
+    }
+    if b < a {
+        notes.push("This is a downgrade".to_string());
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_major_bumps_and_downgrades() {
+        assert!(upgrade_notes("16.5.2", "16.6.6").is_empty());
+        assert_eq!(upgrade_notes("16.6.6", "17.0.0").len(), 1);
+        assert_eq!(upgrade_notes("17.0.0", "16.6.6").len(), 2);
+        assert!(upgrade_notes("latest", "16.6.6").is_empty());
+        assert_eq!(days_between("2024-09-01", "2024-12-10"), Some(100));
+    }
+}
@@ -1,11 +1,14 @@
 use crate::android::AdbClient;
-use crate::config::GlobalConfigManager;
+use crate::cli::commands::devices::prompt;
+use crate::cli::commands::local::is_local_project;
+use crate::config::{GlobalConfigManager, ProjectConfig, ProjectConfigManager};
 use crate::core::{error::Result, ProcessExecutor};
 use crate::python::UvManager;
 use colored::Colorize;
 use std::env;
+use std::io::{self, IsTerminal};
 
-pub async fn execute() -> Result<()> {
+pub async fn execute(fix: bool) -> Result<()> {
     println!("{}", "Running environment checks...".bold());
     println!();
 
@@ -48,36 +51,65 @@ pub async fn execute() -> Result<()> {
         println!("{}", "○ Not found (agent build disabled)".yellow());
     }
 
+    // Check jadx (optional, for decompile)
+    print!("Checking jadx... ");
+    if ProcessExecutor::check_command_exists("jadx") {
+        let version = ProcessExecutor::execute_with_output("jadx", &["--version"]).await;
+        match version {
+            Ok(v) => println!("{} ({})", "✓".green(), v.trim().yellow()),
+            Err(_) => println!("{}", "✓".green()),
+        }
+    } else {
+        println!("{}", "○ Not found (decompile disabled)".yellow());
+        println!("  Install from: https://github.com/skylot/jadx/releases");
+    }
+
+    // A local-platform project never talks to a device, so only the Python side matters.
+    let current_dir = env::current_dir()?;
+    let project_dir =
+        ProjectConfigManager::find_project_root(&current_dir).unwrap_or(current_dir.clone());
+    let local = is_local_project(&project_dir).await.unwrap_or(false);
+
     // Check ADB
     print!("Checking adb... ");
     let global_config = GlobalConfigManager::new()?.load().await?;
-    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let mut adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
 
     match adb.check_installed() {
+        _ if local => println!("{}", "○ Skipped (project.platform = \"local\")".dimmed()),
         Ok(_) => {
-            let version = ProcessExecutor::execute_with_output(
-                &global_config.android.adb_path,
-                &["--version"],
-            )
-            .await;
+            let version =
+                ProcessExecutor::execute_with_output(adb.adb_path(), &["--version"]).await;
             match version {
                 Ok(v) => {
                     let first_line = v.lines().next().unwrap_or(&v);
-                    println!("{} ({})", "✓".green(), first_line.trim().yellow())
+                    println!("{} ({})", "✓".green(), first_line.trim().yellow());
+                    if adb.adb_path() != global_config.android.adb_path {
+                        println!("  Using {}", adb.adb_path().cyan());
+                        offer_to_save_adb_path(adb.adb_path()).await?;
+                    }
                 }
                 Err(_) => println!("{}", "✓".green()),
             }
         }
+        Err(_) if fix => {
+            println!("{}", "✗ Not found, installing platform-tools".yellow());
+            super::adb::install(false).await?;
+            let global_config = GlobalConfigManager::new()?.load().await?;
+            adb = AdbClient::new(Some(global_config.android.adb_path));
+        }
         Err(_) => {
             println!("{}", "✗ Not found".red());
-            println!("  Install Android SDK Platform Tools");
+            println!(
+                "  Install Android SDK Platform Tools, or run {}",
+                "frida-mgr doctor --fix".cyan()
+            );
             all_ok = false;
         }
     }
 
     // Check for project
     print!("Checking project... ");
-    let current_dir = env::current_dir()?;
     let uv_mgr = UvManager::new(current_dir);
 
     if uv_mgr.venv_exists() {
@@ -100,20 +132,54 @@ pub async fn execute() -> Result<()> {
         println!("  Run {} to initialize", "frida-mgr init".cyan());
     }
 
+    // Root is exercised with the project's root command (default `su`).
+    let root_command = match ProjectConfigManager::new(&project_dir).load().await {
+        Ok(config) => config.android.root_command,
+        Err(_) => ProjectConfig::default().android.root_command,
+    };
+
     // Check devices
     print!("Checking devices... ");
-    match adb.list_devices().await {
-        Ok(devices) => {
+    let devices = if local {
+        None
+    } else {
+        Some(adb.list_devices().await)
+    };
+    match devices {
+        None => println!("{}", "○ Skipped (frida's local device is used)".dimmed()),
+        Some(Ok(devices)) => {
             if devices.is_empty() {
                 println!("{}", "○ No devices connected".yellow());
             } else {
                 println!("{} {} device(s) connected", "✓".green(), devices.len());
                 for device in &devices {
                     println!("  - {} ({})", device.id.cyan(), device.model.yellow());
+                    if device.state != "device" {
+                        continue;
+                    }
+                    match adb.selinux_mode(&device.id).await {
+                        Ok(mode) if mode == "Enforcing" => println!(
+                            "    SELinux: {} (set android.selinux = \"permissive\" if frida-server dies on start)",
+                            mode.yellow()
+                        ),
+                        Ok(mode) => println!("    SELinux: {}", mode.green()),
+                        Err(_) => println!("    SELinux: {}", "unknown".dimmed()),
+                    }
+                    match adb.root_method(&device.id, &root_command).await {
+                        Ok(Some(method)) => println!("    Root: {}", method.green()),
+                        Ok(None) => {
+                            println!(
+                                "    Root: {}",
+                                format!("✗ '{}' did not get a root shell", root_command).red()
+                            );
+                            all_ok = false;
+                        }
+                        Err(_) => println!("    Root: {}", "unknown".dimmed()),
+                    }
                 }
             }
         }
-        Err(_) => {
+        Some(Err(_)) => {
             println!("{}", "✗ Failed to check".red());
             all_ok = false;
         }
@@ -133,3 +199,25 @@ pub async fn execute() -> Result<()> {
 
     Ok(())
 }
+
+/// adb was found in an Android SDK rather than at `android.adb_path`; offer to save it so
+/// later runs use it directly.
+async fn offer_to_save_adb_path(found: &str) -> Result<()> {
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+    let answer = prompt("  Save it as android.adb_path in the global config? [Y/n]: ")?;
+    if !matches!(answer.as_str(), "" | "y" | "Y" | "yes") {
+        return Ok(());
+    }
+    let global_mgr = GlobalConfigManager::new()?;
+    let mut config = global_mgr.load().await?;
+    config.android.adb_path = found.to_string();
+    global_mgr.save(&config).await?;
+    println!(
+        "  {} Saved to {}",
+        "✓".green(),
+        global_mgr.config_path().display()
+    );
+    Ok(())
+}
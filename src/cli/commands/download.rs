@@ -0,0 +1,99 @@
+use crate::config::{GlobalConfigManager, ProjectConfigManager, VersionMapping};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::mirror::HOST_HEALTH_FILE;
+use crate::core::{compute_sha256, ensure_dir_exists, AssetHosts, HttpClient};
+use crate::frida::{fetch_release_assets, find_asset};
+use colored::Colorize;
+
+pub async fn execute(asset: String, version: Option<String>, force: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+
+    let version = match version {
+        Some(v) => v,
+        None => {
+            let project_mgr = ProjectConfigManager::from_current_dir()?;
+            if !project_mgr.exists() {
+                return Err(FridaMgrError::Config(
+                    "No frida.toml found; pass --version to pick a Frida release".to_string(),
+                ));
+            }
+            project_mgr.load().await?.frida.version
+        }
+    };
+    let resolved_version = version_map.resolve_alias(&version);
+
+    let http = HttpClient::from_network_config(&global_config.network)?
+        .with_response_cache(global_mgr.get_http_cache_dir());
+    let assets = fetch_release_assets(&http, &resolved_version).await?;
+    let asset = find_asset(&assets, &asset)?;
+    let expected_sha256 = asset.sha256();
+
+    let dest_dir = global_mgr.get_assets_cache_dir().join(&resolved_version);
+    let dest = dest_dir.join(&asset.name);
+
+    if dest.exists() && !force {
+        let intact = match &expected_sha256 {
+            Some(expected) => compute_sha256(&dest).await? == *expected,
+            None => true,
+        };
+        if intact {
+            println!(
+                "{} {} already cached",
+                "✓".green().bold(),
+                asset.name.cyan()
+            );
+            println!("  {}", dest.display());
+            return Ok(());
+        }
+        println!(
+            "{} Cached {} failed checksum verification; downloading again",
+            "⚠".yellow().bold(),
+            asset.name.yellow()
+        );
+    }
+
+    println!(
+        "{} Downloading {} (Frida {})...",
+        "↓".blue().bold(),
+        asset.name.cyan(),
+        resolved_version.cyan()
+    );
+    ensure_dir_exists(&dest_dir).await?;
+
+    let hosts = AssetHosts::new(
+        &global_config.network.fallback_hosts,
+        global_mgr.get_cache_dir().join(HOST_HEALTH_FILE),
+    );
+    hosts
+        .download(&http, &asset.host_path(&resolved_version), &dest)
+        .await?;
+
+    let actual = compute_sha256(&dest).await?;
+    match &expected_sha256 {
+        Some(expected) if *expected != actual => {
+            tokio::fs::remove_file(&dest).await?;
+            return Err(FridaMgrError::ChecksumMismatch(format!(
+                "{} (expected sha256 {}, got {})",
+                asset.name, expected, actual
+            )));
+        }
+        Some(_) => println!("{} Checksum verified", "✓".green().bold()),
+        None => println!(
+            "{} No published checksum for {}; sha256 {}",
+            "ℹ".blue().bold(),
+            asset.name,
+            actual.dimmed()
+        ),
+    }
+
+    println!(
+        "{} {} downloaded and cached",
+        "✓".green().bold(),
+        asset.name.cyan()
+    );
+    println!("  {}", dest.display());
+
+    Ok(())
+}
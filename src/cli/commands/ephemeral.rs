@@ -0,0 +1,185 @@
+use crate::android::{shell, AdbClient};
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::foreground::ensure_no_forbidden_args;
+use crate::cli::commands::frida::run_frida;
+use crate::cli::commands::script::{project_scripts, resolve_existing_script_path};
+use crate::cli::commands::spawn::{spawn_pause_flag, FORBIDDEN_FRIDA_ARGS};
+use crate::cli::ExitStatus;
+use crate::config::{
+    resolve_android_server_target, AndroidServerTarget, GlobalConfigManager, ProjectConfigManager,
+    ServerLogConfig,
+};
+use crate::core::error::Result;
+use crate::core::interrupt;
+use crate::frida::{ServerDistribution, ServerDownloader};
+use colored::Colorize;
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::hash::{BuildHasher, Hasher};
+
+/// Random ports are drawn from `[EPHEMERAL_PORT_BASE, EPHEMERAL_PORT_BASE + EPHEMERAL_PORT_SPAN)`.
+const EPHEMERAL_PORT_BASE: u16 = 20000;
+const EPHEMERAL_PORT_SPAN: u16 = 40000;
+
+/// Spawn `package` against a throwaway frida-server: pushed under a random name, started
+/// on a random port, and removed together with its log and forward when frida exits.
+pub async fn spawn(
+    device_id: Option<String>,
+    package: String,
+    scripts: Vec<String>,
+    args: Vec<String>,
+) -> Result<ExitStatus> {
+    ensure_no_forbidden_args(
+        &args,
+        FORBIDDEN_FRIDA_ARGS,
+        "frida-mgr ephemeral spawn selects the device and target automatically",
+    )?;
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .unwrap_or_else(|| current_dir.clone());
+    let config = ProjectConfigManager::new(&project_dir).load().await?;
+    let root_command = config.android.root_command.clone();
+
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+    let arch = adb.get_arch(&device.id).await?;
+    let binary =
+        ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+            .with_distribution(ServerDistribution::from_config(&config.android.server))
+            .download(&config.frida.version, &arch)
+            .await?;
+
+    let default_target =
+        resolve_android_server_target(&global_config.android.default_push_path, None)?;
+    let seed = random_seed();
+    let target = AndroidServerTarget {
+        remote_path: default_target.remote_path.clone(),
+        process_name: ephemeral_name(seed),
+    }
+    .in_dir(default_target.dir());
+    let (remote_path, name) = (target.remote_path, target.process_name);
+    let port = ephemeral_port(seed);
+
+    println!(
+        "{} Ephemeral frida-server {} on port {}",
+        "ℹ".blue().bold(),
+        remote_path.cyan(),
+        port.to_string().yellow()
+    );
+
+    // If frida-mgr itself is interrupted before the session starts, still scrub the device.
+    let root_command = adb.root_command_for(&device.id, &root_command).await;
+    let _cleanup = interrupt::run_on_interrupt(
+        adb.adb_path(),
+        &[
+            "-s".to_string(),
+            device.id.clone(),
+            "shell".to_string(),
+            shell::as_root(
+                &root_command,
+                &format!(
+                    "{}; {}",
+                    shell::command(["killall", &name]),
+                    shell::command(["rm", "-f", &remote_path, &format!("{}.log", remote_path)])
+                ),
+            ),
+        ],
+    );
+
+    let session = async {
+        adb.push_file(&device.id, &binary, &remote_path).await?;
+        adb.make_executable(&device.id, &remote_path).await?;
+        // Always `<binary>.log`, which cleanup removes with the binary.
+        adb.start_server(
+            &device.id,
+            &remote_path,
+            &name,
+            port,
+            &root_command,
+            config.android.selinux,
+            &ServerLogConfig::default(),
+        )
+        .await?;
+        adb.forward(&device.id, port).await?;
+
+        let mut frida_args = vec![
+            "-H".to_string(),
+            format!("127.0.0.1:{}", port),
+            "-f".to_string(),
+            package.clone(),
+        ];
+        if !args.iter().any(|a| a == "--pause" || a == "--no-pause") {
+            if let Some(flag) = spawn_pause_flag(Some(&config.frida.version), false) {
+                frida_args.push(flag.to_string());
+            }
+        }
+        let project_scripts = project_scripts(&project_dir).await;
+        for script in &scripts {
+            frida_args.push("-l".to_string());
+            frida_args.push(resolve_existing_script_path(
+                &current_dir,
+                &project_dir,
+                project_scripts.as_ref(),
+                script,
+            ));
+        }
+        frida_args.extend(args.iter().cloned());
+
+        run_frida(&project_dir, &frida_args, Some(&device.id), false).await
+    };
+    let exit_code = session.await;
+
+    println!(
+        "{} Removing ephemeral frida-server from {}...",
+        "⚙".blue().bold(),
+        device.id.cyan()
+    );
+    let _ = adb.remove_forward(&device.id, port).await;
+    let stopped = adb.kill_server(&device.id, &name, &root_command).await;
+    let removed = adb
+        .remove_remote_files(
+            &device.id,
+            &[remote_path.clone(), format!("{}.log", remote_path)],
+            &root_command,
+        )
+        .await;
+
+    let exit_code = exit_code?;
+    stopped?;
+    removed?;
+    println!(
+        "{} Device cleaned up (binary, log and port forward removed)",
+        "✓".green().bold()
+    );
+    Ok(ExitStatus(exit_code))
+}
+
+fn random_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Eight hex characters; nothing on the device names it as frida.
+fn ephemeral_name(seed: u64) -> String {
+    format!("{:08x}", seed as u32)
+}
+
+fn ephemeral_port(seed: u64) -> u16 {
+    EPHEMERAL_PORT_BASE + ((seed >> 32) % EPHEMERAL_PORT_SPAN as u64) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_ports_come_from_the_seed() {
+        assert_eq!(ephemeral_name(0x1234_5678_9abc_def0), "9abcdef0");
+        assert_eq!(ephemeral_name(0xff), "000000ff");
+        assert_eq!(ephemeral_port(0), EPHEMERAL_PORT_BASE);
+        assert!(ephemeral_port(u64::MAX) < EPHEMERAL_PORT_BASE + EPHEMERAL_PORT_SPAN);
+    }
+}
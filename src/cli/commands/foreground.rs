@@ -1,7 +1,16 @@
+use crate::android::elf::{arch_compatibility, ArchCompatibility};
+use crate::android::foreground::{preferred, ForegroundApp};
 use crate::android::{AdbClient, Device};
-use crate::config::GlobalConfigManager;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::devices::prompt;
+use crate::config::{
+    android_server_candidates, resolve_android_server_target, ArchType, GlobalConfigManager,
+    ProjectConfigManager, ServerLogConfig,
+};
 use crate::core::error::{FridaMgrError, Result};
+use crate::frida::{ServerDistribution, ServerDownloader};
 use colored::Colorize;
+use std::io::{self, IsTerminal};
 
 pub struct ForegroundContext {
     pub device: Device,
@@ -9,6 +18,11 @@ pub struct ForegroundContext {
     pub process: String,
     pub pid: Option<u32>,
     pub activity: Option<String>,
+    /// Android user (work profile, ...) the app runs in, when known.
+    pub user: Option<u32>,
+    pub device_arch: Option<ArchType>,
+    /// Arch the app process runs as, when it could be determined.
+    pub app_arch: Option<ArchType>,
 }
 
 impl ForegroundContext {
@@ -25,14 +39,93 @@ impl ForegroundContext {
         if let Some(activity) = self.activity.as_deref() {
             println!("  Activity: {}", activity.cyan());
         }
+        if let Some(user) = self.user.filter(|user| *user != 0) {
+            println!("  User: {}", user.to_string().yellow());
+        }
+        if let (Some(app_arch), Some(device_arch)) = (self.server32_arch(), &self.device_arch) {
+            println!(
+                "{} {} runs as a 32-bit {} process on this {} device; a {} frida-server may fail to attach",
+                "⚠".yellow().bold(),
+                self.package.cyan(),
+                app_arch.to_str().yellow(),
+                device_arch.to_str(),
+                device_arch.to_str()
+            );
+            println!(
+                "  Pass {} (top/spawn) to start a matching 32-bit frida-server alongside",
+                "--server32".cyan()
+            );
+        }
+    }
+
+    /// The 32-bit arch to run a companion frida-server for, when the app is a 32-bit
+    /// process on a 64-bit device.
+    pub fn server32_arch(&self) -> Option<&ArchType> {
+        match (&self.app_arch, &self.device_arch) {
+            (Some(app), Some(device))
+                if arch_compatibility(app, device) == ArchCompatibility::Compat32 =>
+            {
+                Some(app)
+            }
+            _ => None,
+        }
     }
 }
 
-pub async fn resolve_foreground_context(device_id: Option<&str>) -> Result<ForegroundContext> {
+/// The foreground app on the device, limited to Android user `user` (a work profile, ...)
+/// when given; see `choose_foreground_app` for split-screen.
+pub async fn resolve_foreground_context(
+    device_id: Option<&str>,
+    prefer: Option<&str>,
+    user: Option<u32>,
+) -> Result<ForegroundContext> {
     let global_config = GlobalConfigManager::new()?.load().await?;
     let adb = AdbClient::new(Some(global_config.android.adb_path));
-    let device = adb.get_device(device_id).await?;
-    let foreground = adb.get_foreground_app(&device.id).await?;
+    let device = resolve_device(&adb, device_id).await?;
+    let mut apps = adb.get_foreground_apps(&device.id).await?;
+    if let Some(user) = user {
+        let in_user = in_user(&apps, user);
+        if in_user.is_empty() {
+            return Err(FridaMgrError::CommandFailed(format!(
+                "No foreground app runs in user {} (in the foreground: {})",
+                user,
+                package_list(apps.iter())
+            )));
+        }
+        apps = in_user;
+    }
+    let mut foreground = choose_foreground_app(apps, prefer)?;
+    // Sources that do not name the user leave a pid that may belong to another profile.
+    if let (None, Some(user)) = (foreground.user, user) {
+        if !adb
+            .list_packages(&device.id, Some(user))
+            .await?
+            .contains(&foreground.package)
+        {
+            return Err(FridaMgrError::CommandFailed(format!(
+                "{} is not installed for user {}",
+                foreground.package, user
+            )));
+        }
+        if let Some((pid, process)) = adb
+            .user_process(&device.id, &foreground.package, user)
+            .await?
+        {
+            foreground.pid = Some(pid);
+            foreground.process = process;
+        }
+        foreground.user = Some(user);
+    }
+    // ABI detection only feeds a warning; never fail target resolution over it.
+    let device_arch = adb.get_arch(&device.id).await.ok();
+    let app_arch = match &device_arch {
+        Some(arch) => adb
+            .app_arch(&device.id, &foreground.package, foreground.pid, arch)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
 
     Ok(ForegroundContext {
         device,
@@ -40,9 +133,176 @@ pub async fn resolve_foreground_context(device_id: Option<&str>) -> Result<Foreg
         process: foreground.process,
         pid: foreground.pid,
         activity: foreground.activity,
+        user: foreground.user,
+        device_arch,
+        app_arch,
     })
 }
 
+/// Pick the target when several apps are resumed at once (split-screen, freeform): the one
+/// whose package or process contains `prefer`, otherwise the user's choice, or without a
+/// terminal the focused app. `apps` lists the focused app first.
+pub fn choose_foreground_app(
+    apps: Vec<ForegroundApp>,
+    prefer: Option<&str>,
+) -> Result<ForegroundApp> {
+    let candidates: Vec<&ForegroundApp> = match prefer {
+        Some(prefer) => {
+            let matching = preferred(&apps, prefer);
+            if matching.is_empty() {
+                return Err(FridaMgrError::CommandFailed(format!(
+                    "No foreground app matches --prefer '{}' (in the foreground: {})",
+                    prefer,
+                    package_list(apps.iter())
+                )));
+            }
+            matching
+        }
+        None => apps.iter().collect(),
+    };
+    if candidates.len() == 1 {
+        return Ok(candidates[0].clone());
+    }
+
+    if !io::stdin().is_terminal() {
+        eprintln!(
+            "{} {} apps are in the foreground ({}); using the focused one, {}. Pass --prefer <package> to pick another",
+            "⚠".yellow().bold(),
+            candidates.len(),
+            package_list(candidates.iter().copied()),
+            candidates[0].package.cyan()
+        );
+        return Ok(candidates[0].clone());
+    }
+
+    println!(
+        "{} {} apps are in the foreground:",
+        "ℹ".blue().bold(),
+        candidates.len()
+    );
+    for (index, app) in candidates.iter().enumerate() {
+        println!(
+            "{:>4}  {} {}",
+            (index + 1).to_string().yellow(),
+            app.package,
+            if index == 0 {
+                "(focused)".dimmed()
+            } else {
+                "".normal()
+            }
+        );
+    }
+    let answer = prompt(&format!(
+        "{} Target app (number, default 1): ",
+        "?".blue().bold()
+    ))?;
+    if answer.is_empty() {
+        return Ok(candidates[0].clone());
+    }
+    answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| candidates.get(index))
+        .map(|app| (*app).clone())
+        .ok_or_else(|| FridaMgrError::CommandFailed(format!("'{}' is not in the list", answer)))
+}
+
+/// The apps that run in `user`, keeping those whose user is unknown.
+pub fn in_user(apps: &[ForegroundApp], user: u32) -> Vec<ForegroundApp> {
+    apps.iter()
+        .filter(|app| !matches!(app.user, Some(other) if other != user))
+        .cloned()
+        .collect()
+}
+
+fn package_list<'a>(apps: impl Iterator<Item = &'a ForegroundApp>) -> String {
+    apps.map(|app| match app.user {
+        Some(user) if user != 0 => format!("{} (user {})", app.package, user),
+        _ => app.package.clone(),
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// frida's `--aux` option making `-f` spawn the app in Android user `user`; frida takes a
+/// uid and derives the user from it (`uid / 100000`).
+pub fn spawn_user_args(user: Option<u32>) -> Vec<String> {
+    match user {
+        Some(user) if user != 0 => vec![format!("--aux=uid=(int){}", user * 100_000)],
+        _ => Vec::new(),
+    }
+}
+
+/// frida's device selection for the foreground app: `-D <serial>`, or `-H` to a companion
+/// 32-bit frida-server when `server32` is set and the app needs one.
+pub async fn device_args(foreground: &ForegroundContext, server32: bool) -> Result<Vec<String>> {
+    if server32 {
+        match foreground.server32_arch() {
+            Some(arch) => {
+                let endpoint = start_server32(&foreground.device.id, arch).await?;
+                return Ok(vec!["-H".to_string(), endpoint]);
+            }
+            None => println!(
+                "{} {} is not a 32-bit process on a 64-bit device; ignoring --server32",
+                "ℹ".blue().bold(),
+                foreground.package.cyan()
+            ),
+        }
+    }
+    Ok(vec!["-D".to_string(), foreground.device.id.clone()])
+}
+
+/// Push and start a 32-bit frida-server next to the main one (`<name>32` on port + 1) and
+/// forward it to localhost. Returns the endpoint to pass to frida's `-H`.
+pub async fn start_server32(device_id: &str, arch: &ArchType) -> Result<String> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let config = ProjectConfigManager::from_current_dir()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
+
+    let binary =
+        ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+            .with_distribution(ServerDistribution::from_config(&config.android.server))
+            .download(&config.frida.version, arch)
+            .await?;
+
+    let target = resolve_android_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+    )?;
+    let target = adb
+        .locate_server(
+            device_id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+    let remote_path = format!("{}32", target.remote_path);
+    let process_name = format!("{}32", target.process_name);
+    let port = config.android.server_port + 1;
+
+    adb.push_file(device_id, &binary, &remote_path).await?;
+    adb.make_executable(device_id, &remote_path).await?;
+    adb.start_server(
+        device_id,
+        &remote_path,
+        &process_name,
+        port,
+        &config.android.root_command,
+        config.android.selinux,
+        // A custom log path belongs to the main server; this one logs next to its binary.
+        &ServerLogConfig {
+            path: None,
+            ..config.android.log.clone()
+        },
+    )
+    .await?;
+    adb.forward(device_id, port).await?;
+
+    Ok(format!("127.0.0.1:{}", port))
+}
+
 pub fn ensure_no_forbidden_args(
     raw_args: &[String],
     forbidden_args: &[&str],
@@ -68,6 +328,13 @@ pub fn ensure_no_forbidden_args(
 mod tests {
     use super::*;
 
+    #[test]
+    fn spawns_in_other_users_through_the_uid_option() {
+        assert!(spawn_user_args(None).is_empty());
+        assert!(spawn_user_args(Some(0)).is_empty());
+        assert_eq!(spawn_user_args(Some(10)), ["--aux=uid=(int)1000000"]);
+    }
+
     #[test]
     fn allows_non_forbidden_args() {
         let args = vec![
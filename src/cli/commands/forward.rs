@@ -0,0 +1,83 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::Result;
+use colored::Colorize;
+
+/// `port`, else the project's `android.server_port`.
+async fn server_port(port: Option<u16>) -> Result<u16> {
+    match port {
+        Some(port) => Ok(port),
+        None => Ok(ProjectConfigManager::from_current_dir()?
+            .load()
+            .await?
+            .android
+            .server_port),
+    }
+}
+
+async fn adb_client() -> Result<AdbClient> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    Ok(AdbClient::new(Some(global_config.android.adb_path)))
+}
+
+/// Forward local TCP `port` (default: `android.server_port`) to the same port on the device.
+pub async fn setup(device_id: Option<String>, port: Option<u16>) -> Result<()> {
+    let port = server_port(port).await?;
+    let adb = adb_client().await?;
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    adb.forward_persistent(&device.id, port).await?;
+    println!(
+        "{} Forwarded tcp:{} to {}",
+        "✓".green().bold(),
+        port,
+        device.id.cyan()
+    );
+    println!("  Connect with {}", format!("-H 127.0.0.1:{}", port).cyan());
+    Ok(())
+}
+
+/// Remove the forward of `port` (default: `android.server_port`) from the device.
+pub async fn remove(device_id: Option<String>, port: Option<u16>) -> Result<()> {
+    let port = server_port(port).await?;
+    let adb = adb_client().await?;
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    adb.remove_forward(&device.id, port).await?;
+    println!(
+        "{} Removed the forward of tcp:{} from {}",
+        "✓".green().bold(),
+        port,
+        device.id.cyan()
+    );
+    Ok(())
+}
+
+/// Print every adb forward, marking the ones for the project's server port.
+pub async fn list() -> Result<()> {
+    let adb = adb_client().await?;
+    let forwards = adb.list_forwards().await?;
+    if forwards.is_empty() {
+        println!("{} No adb forwards set up", "ℹ".blue().bold());
+        return Ok(());
+    }
+
+    let server_port = server_port(None).await.ok();
+    println!("{}", "adb forwards:".bold());
+    for forward in forwards {
+        let frida = server_port.is_some_and(|port| forward.is_tcp_port(port));
+        println!(
+            "  {} {} → {}{}",
+            forward.device_id.cyan(),
+            forward.local.yellow(),
+            forward.remote,
+            if frida {
+                " (frida-server)".green().to_string()
+            } else {
+                String::new()
+            }
+        );
+    }
+    Ok(())
+}
@@ -1,12 +1,70 @@
+use crate::agent::sourcemap::AgentSourceMaps;
+use crate::cli::ExitStatus;
+use crate::config::ProjectConfigManager;
 use crate::core::error::Result;
+use crate::python::executor::run_log_path;
 use crate::python::VenvExecutor;
 use std::env;
+use std::path::Path;
 
-pub async fn execute(args: Vec<String>) -> Result<()> {
+pub async fn execute(args: Vec<String>, log_output: bool, discover: bool) -> Result<ExitStatus> {
     let current_dir = env::current_dir()?;
-    let executor = VenvExecutor::new(current_dir);
+    let project_dir = ProjectConfigManager::discover_project_dir(&current_dir, discover);
+    let exit_code = run_frida(&project_dir, &args, None, log_output).await?;
 
-    let exit_code = executor.run_interactive("frida", &args).await?;
+    Ok(ExitStatus(exit_code))
+}
+
+/// Run frida from the project venv, teeing output to `logs/` when `--log-output` is given
+/// or `frida.log_output` is set in frida.toml. frida itself runs from the invocation directory.
+/// `device` is the serial frida-mgr resolved, exported to frida's environment.
+pub async fn run_frida(
+    venv_dir: &Path,
+    args: &[String],
+    device: Option<&str>,
+    log_output: bool,
+) -> Result<i32> {
+    run_frida_with_source_maps(venv_dir, args, device, log_output, None).await
+}
+
+/// `run_frida`, rewriting positions in the agent bundle that frida prints (stack traces)
+/// to the original sources when `source_maps` is given.
+pub async fn run_frida_with_source_maps(
+    venv_dir: &Path,
+    args: &[String],
+    device: Option<&str>,
+    log_output: bool,
+    source_maps: Option<&AgentSourceMaps>,
+) -> Result<i32> {
+    let project_dir = ProjectConfigManager::find_project_root(venv_dir);
+    let log_output = log_output
+        || match project_dir.as_deref() {
+            Some(dir) => ProjectConfigManager::new(dir)
+                .load()
+                .await
+                .map(|config| config.frida.log_output)
+                .unwrap_or(false),
+            None => false,
+        };
 
-    std::process::exit(exit_code);
+    let executor = VenvExecutor::new(venv_dir.to_path_buf())
+        .with_work_dir(env::current_dir()?)
+        .with_device(device);
+    let log_path =
+        log_output.then(|| run_log_path(project_dir.as_deref().unwrap_or(venv_dir), "frida"));
+    match (source_maps, log_path) {
+        (Some(maps), log_path) => {
+            executor
+                .run_interactive_translated("frida", args, log_path.as_deref(), &|line| {
+                    maps.translate_line(line)
+                })
+                .await
+        }
+        (None, Some(log_path)) => {
+            executor
+                .run_interactive_logged("frida", args, &log_path)
+                .await
+        }
+        (None, None) => executor.run_interactive("frida", args).await,
+    }
 }
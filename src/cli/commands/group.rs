@@ -0,0 +1,100 @@
+use crate::config::GlobalConfigManager;
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+/// Run `run` for each member of the device group `group` from the global config's
+/// `[devices.groups]`, in order. A failing device does not stop the others; the failures
+/// are reported as they happen and named again in one error at the end.
+pub async fn for_each_member<F, Fut>(group: &str, run: F) -> Result<()>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let members = group_members(&global_config.devices.groups, group)?;
+
+    let mut failures = Vec::new();
+    for (index, member) in members.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!(
+            "{} {} ({}/{} in {})",
+            "→".blue(),
+            member.cyan().bold(),
+            index + 1,
+            members.len(),
+            group.cyan()
+        );
+        if let Err(e) = run(member.clone()).await {
+            println!("{} {}", "✗".red().bold(), e);
+            failures.push(member.as_str());
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(FridaMgrError::CommandFailed(format!(
+        "{} of {} device(s) in group '{}' failed: {}",
+        failures.len(),
+        members.len(),
+        group,
+        failures.join(", ")
+    )))
+}
+
+/// The device serials or aliases of `name`.
+fn group_members(groups: &BTreeMap<String, Vec<String>>, name: &str) -> Result<Vec<String>> {
+    let Some(members) = groups.get(name) else {
+        let known = if groups.is_empty() {
+            "none defined".to_string()
+        } else {
+            groups.keys().cloned().collect::<Vec<_>>().join(", ")
+        };
+        return Err(FridaMgrError::Config(format!(
+            "Unknown device group '{}' (known: {}); define it under [devices.groups] in the global config",
+            name, known
+        )));
+    };
+    if members.is_empty() {
+        return Err(FridaMgrError::Config(format!(
+            "Device group '{}' has no members",
+            name
+        )));
+    }
+
+    let mut unique = Vec::with_capacity(members.len());
+    for member in members {
+        if !unique.contains(member) {
+            unique.push(member.clone());
+        }
+    }
+    Ok(unique)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_group_members() {
+        let groups = BTreeMap::from([
+            (
+                "lab".to_string(),
+                vec![
+                    "SERIAL1".to_string(),
+                    "pixel".to_string(),
+                    "SERIAL1".to_string(),
+                ],
+            ),
+            ("empty".to_string(), Vec::new()),
+        ]);
+        assert_eq!(group_members(&groups, "lab").unwrap(), ["SERIAL1", "pixel"]);
+        assert!(group_members(&groups, "empty").is_err());
+        let err = group_members(&groups, "farm").unwrap_err().to_string();
+        assert!(err.contains("known: empty, lab"), "{}", err);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::agent::AgentProject;
+use crate::android::AdbClient;
+use crate::config::{
+    resolve_android_server_target, AndroidServerSource, ArchType, DeviceAliases,
+    GlobalConfigManager, ProjectConfigManager, VersionMapping,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::resolve_path;
+use crate::frida::server::ServerCache;
+use crate::frida::ServerDistribution;
+use crate::python::UvManager;
+use colored::{ColoredString, Colorize};
+
+/// One-screen summary of the project in the current directory.
+pub async fn execute() -> Result<()> {
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    if !project_mgr.exists() {
+        return Err(FridaMgrError::NotInitialized);
+    }
+    let config = project_mgr.load().await?;
+    let project_dir = project_mgr
+        .config_path()
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+    let resolved_frida = version_map.resolve_alias(&config.frida.version);
+
+    println!(
+        "{} {}",
+        config.project.name.cyan().bold(),
+        project_dir.display().to_string().dimmed()
+    );
+    if !config.project.description.is_empty() {
+        println!("  {}", config.project.description);
+    }
+
+    println!();
+    println!("{}", "Versions (configured → installed):".bold());
+    let uv_mgr = UvManager::new(project_dir.clone());
+    let venv = uv_mgr.venv_exists();
+    let frida_configured = if config.frida.version == resolved_frida {
+        resolved_frida.clone()
+    } else {
+        format!("{} ({})", config.frida.version, resolved_frida)
+    };
+    let tools_configured = match &config.frida.tools_version {
+        Some(v) => v.clone(),
+        None => match version_map.get_tools_version(&resolved_frida) {
+            Some(v) => format!("{} (version map)", v),
+            None => "auto".to_string(),
+        },
+    };
+    let objection_configured = match &config.objection.version {
+        Some(v) => v.clone(),
+        None => match version_map.get_objection_version(&resolved_frida) {
+            Some(v) => format!("{} (version map)", v),
+            None => "auto".to_string(),
+        },
+    };
+    let rows = [
+        ("frida", frida_configured, Some(resolved_frida.as_str())),
+        (
+            "frida-tools",
+            tools_configured,
+            config.frida.tools_version.as_deref(),
+        ),
+        (
+            "objection",
+            objection_configured,
+            config.objection.version.as_deref(),
+        ),
+    ];
+    for (package, configured, expected) in rows {
+        let installed = if venv {
+            uv_mgr.get_installed_version(package).await.ok().flatten()
+        } else {
+            None
+        };
+        println!(
+            "  {:<12} {} → {}",
+            package,
+            configured.cyan(),
+            installed_label(expected, installed.as_deref())
+        );
+    }
+
+    println!();
+    println!("{}", "Python:".bold());
+    println!("  Version: {}", config.python.version.yellow());
+    println!(
+        "  Venv: {} ({})",
+        uv_mgr.get_venv_path().display(),
+        if venv {
+            "present".green()
+        } else {
+            "missing, run frida-mgr sync".red()
+        }
+    );
+    if !config.python.packages.is_empty() {
+        println!("  Extra packages: {}", config.python.packages.join(", "));
+    }
+
+    println!();
+    println!("{}", "Agent:".bold());
+    let agent = AgentProject::from_config(project_dir.clone(), &config);
+    println!(
+        "  {} ({}), entry {}",
+        config.agent.dir.yellow(),
+        config.agent.tool.as_str(),
+        config.agent.entry
+    );
+    println!(
+        "  Output: {} ({})",
+        config.agent.out,
+        if agent.out_path.exists() {
+            "built".green()
+        } else if agent.entry_path.exists() {
+            "not built".yellow()
+        } else {
+            "no agent".dimmed()
+        }
+    );
+    if !config.scripts.registry.is_empty() {
+        println!(
+            "  Scripts: {}",
+            config
+                .scripts
+                .registry
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    println!();
+    println!("{}", "Device defaults:".bold());
+    let target = resolve_android_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+    )?;
+    println!("  Arch: {}", config.android.arch.to_str().yellow());
+    println!(
+        "  Server: {} on port {} (root: {})",
+        target.remote_path.cyan(),
+        config.android.server_port,
+        config.android.root_command
+    );
+    let profiles: Vec<String> = config
+        .apps
+        .iter()
+        .map(|(name, app)| match &app.device {
+            Some(device) => format!("{} → {} @ {}", name, app.package, device),
+            None => format!("{} → {}", name, app.package),
+        })
+        .collect();
+    if !profiles.is_empty() {
+        println!("  App profiles: {}", profiles.join("; "));
+    }
+    let aliases = DeviceAliases::load_or_default(&global_mgr.get_device_aliases_path()).await?;
+    if !aliases.aliases.is_empty() {
+        println!(
+            "  Device aliases: {}",
+            aliases
+                .aliases
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    match adb.list_devices().await {
+        Ok(devices) if !devices.is_empty() => {
+            for device in devices {
+                println!("  {} {} ({})", "●".green(), device.id.cyan(), device.model);
+            }
+        }
+        Ok(_) => println!("  {} No devices connected", "○".dimmed()),
+        Err(_) => println!("  {} adb unavailable", "○".dimmed()),
+    }
+
+    println!();
+    println!("{}", "frida-server:".bold());
+    match config.android.server.source {
+        AndroidServerSource::Local => {
+            let mut entries = Vec::new();
+            if let Some(local) = &config.android.server.local {
+                if !local.path.is_empty() {
+                    entries.push(("default", local.path.as_str()));
+                }
+                entries.extend(local.paths.iter().map(|(a, p)| (a.as_str(), p.as_str())));
+            }
+            for (arch, path) in entries {
+                let present = resolve_path(&project_dir, path).exists();
+                println!(
+                    "  {} {} → {}",
+                    if present { "●".green() } else { "○".red() },
+                    arch,
+                    path
+                );
+            }
+        }
+        AndroidServerSource::Download | AndroidServerSource::Custom => {
+            let distribution = ServerDistribution::from_config(&config.android.server);
+            let cached: Vec<String> =
+                ServerCache::at(distribution.servers_dir(&global_mgr.get_cache_dir()))
+                    .entries()
+                    .await?
+                    .into_iter()
+                    .filter(|e| e.version == resolved_frida)
+                    .map(|e| e.arch)
+                    .collect();
+            for arch in &ArchType::ANDROID {
+                if cached.iter().any(|c| c == arch.to_str()) {
+                    println!("  {} {} (cached)", "●".green(), arch.to_str());
+                } else {
+                    println!("  {} {}", "○".dimmed(), arch.to_str());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn installed_label(expected: Option<&str>, installed: Option<&str>) -> ColoredString {
+    match (expected, installed) {
+        (_, None) => "not installed".red(),
+        (Some(expected), Some(installed)) if expected != installed => {
+            format!("{} (out of sync)", installed).yellow()
+        }
+        (_, Some(installed)) => installed.green(),
+    }
+}
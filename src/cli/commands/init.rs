@@ -1,15 +1,17 @@
 use crate::config::{
-    AndroidServerSource, GlobalConfigManager, LocalServerConfig, ProjectConfig,
-    ProjectConfigManager, VersionMapping, VersionOverrides,
+    register_project, AndroidServerSource, GlobalConfigManager, LocalServerConfig, ProjectConfig,
+    ProjectConfigManager, ProjectPlatform, VersionMapping, VersionOverrides,
 };
 use crate::core::error::Result;
-use crate::core::resolve_path;
-use crate::frida::ServerDownloader;
-use crate::python::{PypiClient, UvManager};
+use crate::core::{resolve_path, HttpClient};
+use crate::frida::server::cache::auto_clean;
+use crate::frida::{ServerDistribution, ServerDownloader, ServerPlatform};
+use crate::python::{ExistingPythonEnv, PypiClient, UvManager};
 use chrono::{NaiveDate, TimeZone, Utc};
 use colored::Colorize;
 use std::env;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     frida_version: Option<String>,
     python_version: Option<String>,
@@ -19,6 +21,8 @@ pub async fn execute(
     local_server_path: Option<String>,
     frida_tools: Option<String>,
     objection: Option<String>,
+    all_arches: bool,
+    local: bool,
 ) -> Result<()> {
     let global_mgr = GlobalConfigManager::new()?;
     let global_config = global_mgr.ensure_initialized().await?;
@@ -53,8 +57,12 @@ pub async fn execute(
             format!("global defaults ({})", global_mgr.config_path().display()),
         ),
     };
-    let python_ver =
-        python_version.unwrap_or_else(|| global_config.defaults.python_version.clone());
+    // Adopt an existing .venv / pyproject.toml instead of creating a parallel environment.
+    let existing_env = ExistingPythonEnv::detect(&current_dir).await?;
+    let python_ver = existing_env.choose_python(
+        python_version.as_deref(),
+        &global_config.defaults.python_version,
+    )?;
 
     // Resolve frida version alias
     let resolved_frida = version_map.resolve_alias(&frida_ver);
@@ -149,6 +157,29 @@ pub async fn execute(
         project_name.cyan()
     );
     println!("  Python version: {}", python_ver.yellow());
+    if let Some(found) = existing_env.venv_python.as_deref() {
+        println!(
+            "  {} Reusing existing {} (Python {})",
+            "ℹ".blue().bold(),
+            ".venv".yellow(),
+            found.yellow()
+        );
+    }
+    if existing_env.has_pyproject {
+        match existing_env.requires_python.as_deref() {
+            Some(spec) => println!(
+                "  {} Found {} (requires-python {}); frida packages go into its .venv",
+                "ℹ".blue().bold(),
+                "pyproject.toml".yellow(),
+                spec.yellow()
+            ),
+            None => println!(
+                "  {} Found {}; frida packages go into its .venv",
+                "ℹ".blue().bold(),
+                "pyproject.toml".yellow()
+            ),
+        }
+    }
     println!(
         "  Frida version: {} (from {})",
         resolved_frida.yellow(),
@@ -198,7 +229,10 @@ pub async fn execute(
     // installable version after the Frida release date; otherwise fall back to unpinned.
     if objection.is_none() && objection_allow_fallback {
         if let Some(v) = objection_version_to_install.as_deref() {
-            let pypi = PypiClient::new();
+            let pypi = PypiClient::with_http(
+                HttpClient::from_network_config(&global_config.network)?
+                    .with_response_cache(global_mgr.get_http_cache_dir()),
+            );
 
             let mut needs_alternative = false;
             let mut reason: Option<String> = None;
@@ -280,12 +314,18 @@ pub async fn execute(
     config.frida.tools_version = frida_tools.clone();
     config.objection.version = objection.clone();
     config.android.server.source = server_source_config;
+    if local {
+        config.project.platform = ProjectPlatform::Local;
+    }
 
     if config.android.server.source == AndroidServerSource::Local {
         let path = local_server_path
             .clone()
             .expect("clap enforces --local-server-path when --server-source=local");
-        config.android.server.local = Some(LocalServerConfig { path });
+        config.android.server.local = Some(LocalServerConfig {
+            path,
+            paths: Default::default(),
+        });
     }
 
     if let Some(arch_str) = arch {
@@ -309,6 +349,7 @@ pub async fn execute(
     // Save config
     project_mgr.create(config.clone()).await?;
     println!("{} Created {}", "✓".green().bold(), "frida.toml".yellow());
+    register_project(&global_mgr.get_project_registry_path(), &current_dir).await;
 
     // Create Python virtual environment
     let uv_mgr = UvManager::new(current_dir.clone());
@@ -406,14 +447,27 @@ pub async fn execute(
     }
 
     // Download frida-server (only when using download source)
-    if config.android.server.source == AndroidServerSource::Download {
-        let cache_dir = GlobalConfigManager::new()?.get_cache_dir();
-        let downloader = ServerDownloader::new(cache_dir);
-
-        // Download for specified arch or default to arm64
-        let download_arch = &config.android.arch;
-        downloader.download(&resolved_frida, download_arch).await?;
-    } else {
+    if config.downloads_android_server() {
+        let downloader =
+            ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+                .with_notifications(global_config.notifications)
+                .with_distribution(ServerDistribution::from_config(&config.android.server));
+
+        if all_arches {
+            downloader
+                .download_all(
+                    &resolved_frida,
+                    ServerPlatform::Android,
+                    &ServerPlatform::Android.published_arches(),
+                )
+                .await?;
+        } else {
+            // Download for specified arch or default to arm64
+            let download_arch = &config.android.arch;
+            downloader.download(&resolved_frida, download_arch).await?;
+        }
+        auto_clean(&global_mgr, &global_config.cache).await?;
+    } else if config.android.server.source == AndroidServerSource::Local {
         let local_path = config
             .android
             .server
@@ -434,9 +488,17 @@ pub async fn execute(
     println!("{} Project initialized successfully!", "✓".green().bold());
     println!();
     println!("Next steps:");
-    println!("  1. Connect your Android device");
-    println!("  2. Run: {} to push frida-server", "frida-mgr push".cyan());
-    println!("  3. Start hacking with Frida!");
+    if local {
+        println!(
+            "  1. Run: {} to attach to a process on this machine",
+            "frida-mgr top".cyan()
+        );
+        println!("  2. Start hacking with Frida!");
+    } else {
+        println!("  1. Connect your Android device");
+        println!("  2. Run: {} to push frida-server", "frida-mgr push".cyan());
+        println!("  3. Start hacking with Frida!");
+    }
 
     Ok(())
 }
@@ -1,18 +1,25 @@
 use crate::config::{
-    AndroidServerSource, GlobalConfigManager, ProjectConfigManager, VersionMapping,
+    register_project, ArchType, GlobalConfigManager, ProjectConfigManager, VersionMapping,
 };
 use crate::core::error::Result;
-use crate::frida::ServerDownloader;
+use crate::frida::server::cache::auto_clean;
+use crate::frida::{ServerDistribution, ServerDownloader, ServerPlatform};
 use crate::python::UvManager;
 use colored::Colorize;
 use std::env;
 
-pub async fn execute(version: String) -> Result<()> {
+pub async fn execute(
+    version: String,
+    platform: Option<ServerPlatform>,
+    arches: Vec<ArchType>,
+    all_arches: bool,
+) -> Result<()> {
     let current_dir = env::current_dir()?;
     let project_mgr = ProjectConfigManager::from_current_dir()?;
     let config = project_mgr.load().await?;
 
     let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
     let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
     let resolved_version = version_map.resolve_alias(&version);
 
@@ -64,15 +71,39 @@ pub async fn execute(version: String) -> Result<()> {
         (None, None) => println!("  Objection version: {} (let uv resolve)", "auto".yellow()),
     }
 
-    // Download frida-server if needed
-    if config.android.server.source == AndroidServerSource::Download {
-        let global_config = GlobalConfigManager::new()?;
-        let cache_dir = global_config.get_cache_dir();
-        let downloader = ServerDownloader::new(cache_dir);
+    // Download frida-server if needed; an explicit --platform always downloads
+    let platform_requested = platform.is_some();
+    let platform = platform.unwrap_or_default();
+    let needs_download = platform_requested || config.downloads_android_server();
+    if needs_download {
+        let mut arches = if all_arches {
+            platform.published_arches()
+        } else {
+            arches
+        };
+        if arches.is_empty() {
+            arches.push(match platform {
+                ServerPlatform::Android => config.android.arch.clone(),
+                _ => ArchType::from_abi(std::env::consts::ARCH),
+            });
+        }
+        let downloader =
+            ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+                .with_notifications(global_config.notifications)
+                .with_distribution(ServerDistribution::from_config(&config.android.server));
 
-        downloader
-            .download(&resolved_version, &config.android.arch)
-            .await?;
+        match arches.as_slice() {
+            [arch] => {
+                downloader
+                    .download_for(&resolved_version, platform, arch)
+                    .await?;
+            }
+            _ => {
+                downloader
+                    .download_all(&resolved_version, platform, &arches)
+                    .await?;
+            }
+        }
     }
 
     // Update Python packages
@@ -120,6 +151,14 @@ pub async fn execute(version: String) -> Result<()> {
 
     // Update config
     project_mgr.update_frida_version(&resolved_version).await?;
+    if let Some(project_dir) = project_mgr.config_path().parent() {
+        register_project(&global_mgr.get_project_registry_path(), project_dir).await;
+    }
+
+    // Evict only after frida.toml points at the new version so it stays protected.
+    if needs_download {
+        auto_clean(&global_mgr, &global_config.cache).await?;
+    }
 
     println!();
     println!(
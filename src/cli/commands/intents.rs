@@ -0,0 +1,80 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::targets::package_components;
+use crate::config::GlobalConfigManager;
+use crate::core::error::Result;
+use colored::Colorize;
+
+/// List the deep-link and app-link URI patterns `package` handles, or with `launch` fire a
+/// VIEW intent for that URI at the package on the device.
+pub async fn execute(
+    package: String,
+    device_id: Option<String>,
+    launch: Option<String>,
+) -> Result<()> {
+    if let Some(uri) = launch {
+        let global_config = GlobalConfigManager::new()?.load().await?;
+        let adb = AdbClient::new(Some(global_config.android.adb_path));
+        let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+        println!(
+            "{} Opening {} in {}...",
+            "⚙".blue().bold(),
+            uri.cyan(),
+            package.cyan()
+        );
+        adb.open_uri(&device.id, &package, &uri).await?;
+        println!("{} Intent delivered", "✓".green().bold());
+        return Ok(());
+    }
+
+    let (components, source) = package_components(&package, device_id.as_deref()).await?;
+    let links: Vec<_> = components
+        .iter()
+        .filter(|c| c.filters.iter().any(|f| f.is_deep_link()))
+        .collect();
+    if links.is_empty() {
+        println!(
+            "{} No deep links found in {}",
+            "ℹ".blue().bold(),
+            source.yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Deep links of {} (from {})",
+        "ℹ".blue().bold(),
+        package.cyan(),
+        source.yellow()
+    );
+    for component in links {
+        println!();
+        println!(
+            "{}{}",
+            component.component_name(&package).cyan(),
+            if component.exported {
+                String::new()
+            } else {
+                " (not exported)".red().to_string()
+            }
+        );
+        for filter in component.filters.iter().filter(|f| f.is_deep_link()) {
+            for pattern in filter.uri_patterns() {
+                if filter.auto_verify {
+                    println!("  {} {}", pattern, "(app link)".green());
+                } else {
+                    println!("  {}", pattern);
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} Open one with: frida-mgr intents {} --launch <uri>",
+        "→".blue(),
+        package
+    );
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::cli::commands::ps::{frida_ps_json, PsEntry};
+use crate::config::{ProjectConfigManager, ProjectPlatform};
+use crate::core::error::{FridaMgrError, Result};
+use crate::python::VenvExecutor;
+use colored::Colorize;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+/// Whether the project in `project_dir` instruments host processes
+/// (`project.platform = "local"`). Directories without frida.toml are Android projects.
+pub async fn is_local_project(project_dir: &Path) -> Result<bool> {
+    let project_mgr = ProjectConfigManager::new(project_dir);
+    if !project_mgr.exists() {
+        return Ok(false);
+    }
+    Ok(project_mgr.load().await?.project.platform == ProjectPlatform::Local)
+}
+
+/// Fail if any of the given device-only options was passed in a local project.
+pub fn reject_device_options(command: &str, options: &[(&str, bool)]) -> Result<()> {
+    let used: Vec<&str> = options
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect();
+    if used.is_empty() {
+        return Ok(());
+    }
+    Err(FridaMgrError::Config(format!(
+        "{} cannot be used with `frida-mgr {}` when project.platform = \"local\"",
+        used.join(", "),
+        command
+    )))
+}
+
+/// Ask which host process to attach to, or with `applications` which installed
+/// application to spawn, from `frida-ps` run against frida's local device.
+pub async fn pick_target(executor: &VenvExecutor, applications: bool) -> Result<PsEntry> {
+    let mut entries = if applications {
+        frida_ps_json(executor, None, &["-a", "-i"]).await?
+    } else {
+        frida_ps_json(executor, None, &[]).await?
+    };
+    entries.retain(|e| !applications || e.identifier.is_some());
+    entries.sort_by_key(|e| e.name.to_lowercase());
+    if entries.is_empty() {
+        return Err(FridaMgrError::CommandFailed(if applications {
+            "frida reports no spawnable applications on this host; run `frida-mgr frida -f <program>` instead".to_string()
+        } else {
+            "frida-ps listed no processes on this host".to_string()
+        }));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let detail = match (&entry.identifier, entry.pid) {
+            (Some(identifier), _) => identifier.clone(),
+            (None, Some(pid)) => pid.to_string(),
+            (None, None) => String::new(),
+        };
+        println!(
+            "{:>4}  {} {}",
+            (index + 1).to_string().yellow(),
+            entry.name,
+            detail.dimmed()
+        );
+    }
+
+    print!(
+        "{} {} (number or name): ",
+        "?".blue().bold(),
+        if applications {
+            "Application to spawn"
+        } else {
+            "Process to attach to"
+        }
+    );
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+
+    select_entry(&entries, answer.trim())
+        .cloned()
+        .ok_or_else(|| {
+            FridaMgrError::CommandFailed(format!(
+                "'{}' does not match exactly one entry",
+                answer.trim()
+            ))
+        })
+}
+
+/// The entry `answer` names: its 1-based number, its exact name or identifier, or the only
+/// one whose name contains it (ignoring case).
+fn select_entry<'a>(entries: &'a [PsEntry], answer: &str) -> Option<&'a PsEntry> {
+    if answer.is_empty() {
+        return None;
+    }
+    if let Ok(number) = answer.parse::<usize>() {
+        return number.checked_sub(1).and_then(|index| entries.get(index));
+    }
+    if let Some(exact) = entries
+        .iter()
+        .find(|e| e.name == answer || e.identifier.as_deref() == Some(answer))
+    {
+        return Some(exact);
+    }
+    let needle = answer.to_lowercase();
+    let mut matches = entries
+        .iter()
+        .filter(|e| e.name.to_lowercase().contains(&needle));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => Some(only),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: u32, name: &str) -> PsEntry {
+        PsEntry {
+            pid: Some(pid),
+            name: name.to_string(),
+            identifier: None,
+        }
+    }
+
+    #[test]
+    fn selects_by_number_name_or_unique_substring() {
+        let entries = [
+            entry(10, "Safari"),
+            entry(20, "SafariBookmarksSyncAgent"),
+            entry(30, "Slack"),
+        ];
+        assert_eq!(select_entry(&entries, "3").unwrap().pid, Some(30));
+        assert!(select_entry(&entries, "0").is_none());
+        assert!(select_entry(&entries, "4").is_none());
+        assert_eq!(select_entry(&entries, "Safari").unwrap().pid, Some(10));
+        assert_eq!(select_entry(&entries, "slack").unwrap().pid, Some(30));
+        assert!(select_entry(&entries, "saf").is_none());
+        assert!(select_entry(&entries, "").is_none());
+    }
+
+    #[test]
+    fn rejects_device_options_in_local_projects() {
+        assert!(reject_device_options("top", &[("--device", false)]).is_ok());
+        let err = reject_device_options("top", &[("--device", true), ("--follow", true)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--device, --follow"), "{}", err);
+    }
+}
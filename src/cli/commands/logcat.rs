@@ -0,0 +1,121 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::foreground::resolve_foreground_context;
+use crate::config::GlobalConfigManager;
+use crate::core::error::{FridaMgrError, Result};
+use colored::{ColoredString, Colorize};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+/// Stream logcat for `package` (default: the foreground app) filtered to its PID, coloured
+/// by priority, and with `output` also written uncoloured to that file.
+pub async fn execute(
+    package: Option<String>,
+    device_id: Option<String>,
+    output: Option<String>,
+) -> Result<()> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+
+    let (device_id, package, pid) = match package {
+        Some(package) => {
+            let device = resolve_device(&adb, device_id.as_deref()).await?;
+            let pid = adb.process_pid(&device.id, &package).await?;
+            (device.id, package, pid)
+        }
+        None => {
+            let foreground = resolve_foreground_context(device_id.as_deref(), None, None).await?;
+            let pid = match foreground.pid {
+                Some(pid) => Some(pid),
+                None => {
+                    adb.process_pid(&foreground.device.id, &foreground.process)
+                        .await?
+                }
+            };
+            (foreground.device.id, foreground.package, pid)
+        }
+    };
+    let pid = pid.ok_or_else(|| {
+        FridaMgrError::CommandFailed(format!(
+            "{} is not running on {}; launch it first or use `frida-mgr spawn`",
+            package, device_id
+        ))
+    })?;
+
+    let mut file = match &output {
+        Some(path) => Some(LineWriter::new(File::create(path)?)),
+        None => None,
+    };
+    eprintln!(
+        "{} Logcat for {} (PID {}) on {}; press Ctrl-C to stop",
+        "ℹ".blue().bold(),
+        package.cyan(),
+        pid.to_string().yellow(),
+        device_id.cyan()
+    );
+    if let Some(path) = &output {
+        eprintln!("  Saving to {}", path.cyan());
+    }
+
+    let mut write_error = None;
+    adb.logcat(&device_id, pid, &mut |line| {
+        if let Some(file) = file.as_mut() {
+            if write_error.is_none() {
+                write_error = writeln!(file, "{}", line).err();
+            }
+        }
+        println!("{}", colorize(line));
+    })
+    .await?;
+
+    match write_error {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+/// The priority letter of a `logcat -v threadtime` line
+/// (`date time pid tid priority tag: message`).
+fn priority(line: &str) -> Option<char> {
+    let token = line.split_whitespace().nth(4)?;
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c @ ('V' | 'D' | 'I' | 'W' | 'E' | 'F' | 'A')), None) => Some(c),
+        _ => None,
+    }
+}
+
+fn colorize(line: &str) -> ColoredString {
+    match priority(line) {
+        Some('V') => line.dimmed(),
+        Some('D') => line.blue(),
+        Some('I') => line.green(),
+        Some('W') => line.yellow(),
+        Some('E') => line.red(),
+        Some('F' | 'A') => line.red().bold(),
+        _ => line.normal(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_threadtime_priority() {
+        assert_eq!(
+            priority("10-16 12:00:01.123  4321  4350 E AndroidRuntime: FATAL EXCEPTION: main"),
+            Some('E')
+        );
+        assert_eq!(
+            priority("10-16 12:00:01.200  4321  4321 I chatty  : uid=10123 expire 3 lines"),
+            Some('I')
+        );
+        assert_eq!(priority("--------- beginning of crash"), None);
+        assert_eq!(
+            priority("10-16 12:00:01.123  4321  4350 Ex tag: message"),
+            None
+        );
+        assert_eq!(priority(""), None);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, GlobalConfigManager,
+    ProjectConfigManager, SERVER_LOG_DISABLED,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::interrupt;
+use colored::Colorize;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How often `--follow` checks the log for new output.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Print the tail of the frida-server log `start` writes on the device, keep printing new
+/// output with `follow`, or empty it with `clear`.
+pub async fn execute(
+    device_id: Option<String>,
+    lines: usize,
+    follow: bool,
+    clear: bool,
+) -> Result<()> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    let config = ProjectConfigManager::from_current_dir()?.load().await?;
+    let target = resolve_device_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+        &device.id,
+    )
+    .await?;
+    let target = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+    let log_path = config.android.log.path_for(&target.remote_path);
+    if log_path == SERVER_LOG_DISABLED {
+        return Err(FridaMgrError::Config(
+            "frida-server output is not logged (android.log.enabled = false)".to_string(),
+        ));
+    }
+
+    if clear {
+        adb.truncate_server_log(&device.id, &log_path, &config.android.root_command)
+            .await?;
+        println!("{} Cleared {}", "✓".green().bold(), log_path.cyan());
+        return Ok(());
+    }
+
+    match adb.tail_server_log(&device.id, &log_path, lines).await? {
+        Some(output) => print!("{}", output),
+        None if !follow => {
+            println!(
+                "{} No log at {} yet; it is written once {} starts the server",
+                "ℹ".blue().bold(),
+                log_path.yellow(),
+                "frida-mgr start".cyan()
+            );
+            return Ok(());
+        }
+        None => {}
+    }
+    if !follow {
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} Following {}; press Ctrl-C to stop",
+        "ℹ".blue().bold(),
+        log_path.cyan()
+    );
+    let mut offset = adb.server_log_size(&device.id, &log_path).await?;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(FOLLOW_INTERVAL) => {}
+            _ = interrupt::ctrl_c() => return Ok(()),
+        }
+
+        let size = adb.server_log_size(&device.id, &log_path).await?;
+        if size < offset {
+            // Rotated or cleared by a (re)start; read the new log from the top.
+            eprintln!(
+                "{} Log was truncated; following from the start",
+                "ℹ".blue().bold()
+            );
+            offset = 0;
+        }
+        if size > offset {
+            let output = adb
+                .read_server_log(&device.id, &log_path, offset, size - offset)
+                .await?;
+            print!("{}", output);
+            io::stdout().flush()?;
+            offset = size;
+        }
+    }
+}
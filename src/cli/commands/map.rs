@@ -0,0 +1,64 @@
+use crate::cli::ExitStatus;
+use crate::config::{GlobalConfigManager, VersionMapping};
+use crate::core::error::Result;
+use crate::core::HttpClient;
+use colored::Colorize;
+
+pub async fn verify(prune: bool) -> Result<ExitStatus> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let map_path = global_mgr.get_version_map_path();
+    let mut version_map = VersionMapping::load_or_init(&map_path).await?;
+
+    println!(
+        "{} Checking {} mappings against PyPI...",
+        "⚙".blue().bold(),
+        version_map.mappings.len()
+    );
+    let http = HttpClient::from_network_config(&global_config.network)?
+        .with_response_cache(global_mgr.get_http_cache_dir());
+    let problems = version_map.verify_against_pypi(&http).await?;
+
+    if problems.is_empty() {
+        println!(
+            "{} All {} mappings resolve to installable releases",
+            "✓".green().bold(),
+            version_map.mappings.len()
+        );
+        return Ok(ExitStatus::SUCCESS);
+    }
+
+    for problem in &problems {
+        println!(
+            "  {} {} → {}",
+            "✗".red().bold(),
+            problem.frida.cyan(),
+            problem.issue
+        );
+    }
+
+    let mut broken: Vec<String> = problems.iter().map(|p| p.frida.clone()).collect();
+    broken.dedup();
+    println!();
+
+    if !prune {
+        println!(
+            "{} {} broken mapping(s); run {} to remove them, or {} to rebuild the map",
+            "⚠".yellow().bold(),
+            broken.len(),
+            "frida-mgr map verify --prune".cyan(),
+            "frida-mgr sync --update-map".cyan()
+        );
+        return Ok(ExitStatus(1));
+    }
+
+    version_map.remove_versions(&broken);
+    version_map.save(&map_path).await?;
+    println!(
+        "{} Removed {} broken mapping(s) from {}",
+        "✓".green().bold(),
+        broken.len(),
+        map_path.display()
+    );
+    Ok(ExitStatus::SUCCESS)
+}
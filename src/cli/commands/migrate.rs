@@ -0,0 +1,330 @@
+use crate::android::elf::read_elf_arch;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{decompress_xz, resolve_path, ProcessExecutor};
+use crate::frida::server::cache::ServerCache;
+use crate::python::UvManager;
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// frida, frida-tools and objection versions found in an existing setup.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DetectedVersions {
+    frida: Option<String>,
+    tools: Option<String>,
+    objection: Option<String>,
+}
+
+impl DetectedVersions {
+    fn set(&mut self, package: &str, version: &str) {
+        let slot = match normalize_package(package).as_str() {
+            "frida" => &mut self.frida,
+            "frida-tools" => &mut self.tools,
+            "objection" => &mut self.objection,
+            _ => return,
+        };
+        *slot = Some(version.to_string());
+    }
+}
+
+/// Import an existing frida setup (requirements.txt, a project .venv or a global pip
+/// install) into a new frida.toml, and pull local frida-server binaries into the cache.
+pub async fn execute(
+    requirements: Option<String>,
+    servers: Vec<String>,
+    python: Option<String>,
+    recreate_venv: bool,
+) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    if ProjectConfigManager::new(&current_dir).exists() {
+        println!("{} Project already initialized", "ℹ".yellow().bold());
+        return Ok(());
+    }
+
+    let (detected, source) = detect_versions(&current_dir, requirements.as_deref()).await?;
+    let Some(frida) = detected.frida.clone() else {
+        return Err(FridaMgrError::Config(
+            "No frida installation found; pass --requirements <file> with a frida pin, or use 'frida-mgr init'"
+                .to_string(),
+        ));
+    };
+
+    println!(
+        "{} Found existing setup in {}",
+        "✓".green().bold(),
+        source.yellow()
+    );
+    for (package, version) in [
+        ("frida", detected.frida.as_deref()),
+        ("frida-tools", detected.tools.as_deref()),
+        ("objection", detected.objection.as_deref()),
+    ] {
+        println!(
+            "  {:<12} {}",
+            package,
+            version.map(|v| v.cyan()).unwrap_or_else(|| "—".dimmed())
+        );
+    }
+
+    let global_mgr = GlobalConfigManager::new()?;
+    global_mgr.ensure_initialized().await?;
+    let mut server_paths: Vec<PathBuf> = servers
+        .iter()
+        .map(|s| resolve_path(&current_dir, s))
+        .collect();
+    if server_paths.is_empty() {
+        server_paths = find_local_servers(&current_dir).await?;
+    }
+    import_servers(&global_mgr.get_cache_dir(), &server_paths, &frida).await?;
+
+    if recreate_venv {
+        let venv = UvManager::new(current_dir.clone()).get_venv_path();
+        if venv.exists() {
+            println!(
+                "{} Removing existing {} so it is rebuilt",
+                "⚙".blue().bold(),
+                ".venv".yellow()
+            );
+            tokio::fs::remove_dir_all(&venv).await?;
+        }
+    }
+
+    println!();
+    super::init::execute(
+        Some(frida),
+        python,
+        None,
+        None,
+        crate::cli::InitServerSource::Download,
+        None,
+        detected.tools,
+        detected.objection,
+        false,
+        false,
+    )
+    .await
+}
+
+/// Versions from, in order of preference: a requirements file, the project's `.venv`,
+/// then a globally pip-installed frida.
+async fn detect_versions(
+    dir: &Path,
+    requirements: Option<&str>,
+) -> Result<(DetectedVersions, String)> {
+    let requirements_path = match requirements {
+        Some(path) => {
+            let path = resolve_path(dir, path);
+            if !path.is_file() {
+                return Err(FridaMgrError::FileNotFound(path.display().to_string()));
+            }
+            Some(path)
+        }
+        None => Some(dir.join("requirements.txt")).filter(|p| p.is_file()),
+    };
+    if let Some(path) = requirements_path {
+        let content = tokio::fs::read_to_string(&path).await?;
+        let detected = parse_requirements(&content);
+        if detected.frida.is_some() {
+            return Ok((detected, path.display().to_string()));
+        }
+    }
+
+    let uv_mgr = UvManager::new(dir.to_path_buf());
+    if uv_mgr.venv_exists() {
+        let mut detected = DetectedVersions::default();
+        for package in ["frida", "frida-tools", "objection"] {
+            if let Some(version) = uv_mgr.get_installed_version(package).await.ok().flatten() {
+                detected.set(package, &version);
+            }
+        }
+        if detected.frida.is_some() {
+            return Ok((detected, ".venv".to_string()));
+        }
+    }
+
+    for python in ["python3", "python"] {
+        if !ProcessExecutor::check_command_exists(python) {
+            continue;
+        }
+        // pip exits non-zero when any package is missing but still reports the others.
+        let Ok(output) = ProcessExecutor::execute(
+            python,
+            &["-m", "pip", "show", "frida", "frida-tools", "objection"],
+            None,
+        )
+        .await
+        else {
+            continue;
+        };
+        let detected = parse_pip_show(&String::from_utf8_lossy(&output.stdout));
+        if detected.frida.is_some() {
+            return Ok((detected, format!("global pip ({})", python)));
+        }
+    }
+
+    Ok((DetectedVersions::default(), String::new()))
+}
+
+/// `name==version` pins for the frida packages; ranges and unpinned entries are ignored.
+fn parse_requirements(content: &str) -> DetectedVersions {
+    let mut detected = DetectedVersions::default();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let line = line.split(';').next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+        let Some((name, version)) = line.split_once("==") else {
+            continue;
+        };
+        let name = name.split('[').next().unwrap_or(name).trim();
+        let version = version.trim_start_matches('=').trim();
+        if !version.is_empty() {
+            detected.set(name, version);
+        }
+    }
+    detected
+}
+
+/// `Name:`/`Version:` pairs from `pip show` output.
+fn parse_pip_show(output: &str) -> DetectedVersions {
+    let mut detected = DetectedVersions::default();
+    let mut name: Option<&str> = None;
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim());
+        } else if let Some(value) = line.strip_prefix("Version:") {
+            if let Some(package) = name.take() {
+                detected.set(package, value.trim());
+            }
+        }
+    }
+    detected
+}
+
+fn normalize_package(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+/// `frida-server*` files in the project directory, e.g. `frida-server-16.5.9-android-arm64`.
+async fn find_local_servers(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let is_server = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|n| n.starts_with("frida-server"));
+        if is_server && entry.file_type().await?.is_file() {
+            found.push(entry.path());
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+async fn import_servers(cache_dir: &Path, paths: &[PathBuf], frida: &str) -> Result<()> {
+    let cache = ServerCache::new(cache_dir);
+    for path in paths {
+        if !path.is_file() {
+            return Err(FridaMgrError::FileNotFound(path.display().to_string()));
+        }
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        let mut binary = path.clone();
+        let unpacked = cache_dir.join(format!("{}.migrate", name));
+        if name.ends_with(".xz") {
+            decompress_xz(path, &unpacked).await?;
+            binary = unpacked.clone();
+        }
+
+        let arch = read_elf_arch(&binary).await;
+        let version = server_version_from_name(name).unwrap_or_else(|| frida.to_string());
+        let imported = match arch {
+            Ok(Some(arch)) => cache
+                .import(&version, arch.to_str(), &binary)
+                .await
+                .map(|_| Some(arch)),
+            other => other,
+        };
+        if binary == unpacked {
+            tokio::fs::remove_file(&unpacked).await?;
+        }
+
+        let Some(arch) = imported? else {
+            println!(
+                "{} Skipping {}: not an Android frida-server binary",
+                "⚠".yellow().bold(),
+                name.yellow()
+            );
+            continue;
+        };
+        println!(
+            "{} Imported {} into the cache as frida-server {} ({})",
+            "✓".green().bold(),
+            name.yellow(),
+            version.cyan(),
+            arch.to_str()
+        );
+        if version != frida {
+            println!(
+                "  {} Its version differs from frida {}; the project will download a matching server",
+                "⚠".yellow().bold(),
+                frida.cyan()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Version embedded in a release asset name such as `frida-server-16.5.9-android-arm64.xz`.
+fn server_version_from_name(name: &str) -> Option<String> {
+    let rest = name.strip_prefix("frida-server-")?;
+    let version = rest.split('-').next()?;
+    semver::Version::parse(version)
+        .ok()
+        .map(|_| version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requirements_pins_are_detected() {
+        let detected = parse_requirements(
+            "# tooling\nFrida==16.5.9\nfrida_tools==13.6.0 ; python_version >= '3.8'\nobjection>=1.11\nrequests\n-r base.txt\n",
+        );
+        assert_eq!(
+            detected,
+            DetectedVersions {
+                frida: Some("16.5.9".to_string()),
+                tools: Some("13.6.0".to_string()),
+                objection: None,
+            }
+        );
+    }
+
+    #[test]
+    fn pip_show_blocks_are_parsed() {
+        let output = "Name: frida\nVersion: 16.5.9\nSummary: Dynamic instrumentation toolkit\n---\nName: frida-tools\nVersion: 13.6.0\n";
+        let detected = parse_pip_show(output);
+        assert_eq!(detected.frida.as_deref(), Some("16.5.9"));
+        assert_eq!(detected.tools.as_deref(), Some("13.6.0"));
+        assert_eq!(detected.objection, None);
+    }
+
+    #[test]
+    fn server_version_comes_from_asset_name() {
+        assert_eq!(
+            server_version_from_name("frida-server-16.5.9-android-arm64.xz").as_deref(),
+            Some("16.5.9")
+        );
+        assert_eq!(server_version_from_name("frida-server"), None);
+        assert_eq!(server_version_from_name("frida-server-arm64"), None);
+    }
+}
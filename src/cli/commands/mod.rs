@@ -1,16 +1,48 @@
+pub mod adb;
 pub mod agent;
+pub mod apk;
+pub mod assets;
+pub mod bundle;
+pub mod cache;
+pub mod ci;
+pub mod codegen;
+pub mod connect;
+pub mod console;
+pub mod decompile;
+pub mod detect_protections;
+pub mod device;
 pub mod devices;
+pub mod diff_versions;
 pub mod doctor;
+pub mod download;
+pub mod ephemeral;
 pub mod foreground;
+pub mod forward;
 pub mod frida;
+pub mod group;
+pub mod info;
 pub mod init;
 pub mod install;
+pub mod intents;
 pub mod list;
+pub mod local;
+pub mod logcat;
+pub mod logs;
+pub mod map;
+pub mod migrate;
 pub mod objection;
 pub mod objection_fg;
 pub mod pip;
+pub mod projects;
+pub mod provision;
+pub mod ps;
 pub mod push;
+pub mod remove;
+pub mod restart;
 pub mod run;
+pub mod run_app;
+pub mod run_as;
+pub mod run_script;
 pub mod script;
 pub mod shell;
 pub mod spawn;
@@ -18,5 +50,9 @@ pub mod start;
 pub mod status;
 pub mod stop;
 pub mod sync;
+pub mod targets;
+pub mod tcpip;
 pub mod top;
+pub mod uninstall;
 pub mod uv;
+pub mod watchdog;
@@ -1,12 +1,15 @@
+use crate::cli::ExitStatus;
+use crate::config::ProjectConfigManager;
 use crate::core::error::Result;
 use crate::python::VenvExecutor;
 use std::env;
 
-pub async fn execute(args: Vec<String>) -> Result<()> {
+pub async fn execute(args: Vec<String>, discover: bool) -> Result<ExitStatus> {
     let current_dir = env::current_dir()?;
-    let executor = VenvExecutor::new(current_dir);
+    let project_dir = ProjectConfigManager::discover_project_dir(&current_dir, discover);
+    let executor = VenvExecutor::new(project_dir).with_work_dir(current_dir);
 
     let exit_code = executor.run_interactive("objection", &args).await?;
 
-    std::process::exit(exit_code);
+    Ok(ExitStatus(exit_code))
 }
@@ -1,4 +1,6 @@
 use crate::cli::commands::foreground::{ensure_no_forbidden_args, resolve_foreground_context};
+use crate::cli::commands::push::ensure_server_running;
+use crate::cli::ExitStatus;
 use crate::core::error::Result;
 use crate::python::VenvExecutor;
 use std::env;
@@ -85,7 +87,12 @@ async fn detect_objection_cli_info(executor: &VenvExecutor) -> Option<ObjectionC
     Some(parse_objection_cli_info(&help))
 }
 
-pub async fn execute(device_id: Option<String>, args: Vec<String>) -> Result<()> {
+pub async fn execute(
+    device_id: Option<String>,
+    ensure_server: bool,
+    user: Option<u32>,
+    args: Vec<String>,
+) -> Result<ExitStatus> {
     ensure_no_forbidden_args(
         &args,
         FORBIDDEN_OBJECTION_ARGS,
@@ -93,10 +100,10 @@ pub async fn execute(device_id: Option<String>, args: Vec<String>) -> Result<()>
     )?;
 
     let current_dir = env::current_dir()?;
-    let executor = VenvExecutor::new(current_dir);
-
-    let foreground = resolve_foreground_context(device_id.as_deref()).await?;
+    let foreground = resolve_foreground_context(device_id.as_deref(), None, user).await?;
     foreground.print_summary();
+    ensure_server_running(&foreground.device.id, ensure_server, false).await?;
+    let executor = VenvExecutor::new(current_dir).with_device(Some(&foreground.device.id));
 
     let cli_info = detect_objection_cli_info(&executor).await;
     let Some(cli_info) = cli_info else {
@@ -125,7 +132,11 @@ pub async fn execute(device_id: Option<String>, args: Vec<String>) -> Result<()>
     }
 
     objection_args.push(target_flag.to_string());
-    objection_args.push(foreground.package);
+    // The package name would match the app in every profile; objection takes a pid too.
+    match (foreground.user, foreground.pid) {
+        (Some(user), Some(pid)) if user != 0 => objection_args.push(pid.to_string()),
+        _ => objection_args.push(foreground.package),
+    }
 
     if args.is_empty() {
         objection_args.push(cli_info.default_subcommand.unwrap_or("explore").to_string());
@@ -136,7 +147,7 @@ pub async fn execute(device_id: Option<String>, args: Vec<String>) -> Result<()>
     let exit_code = executor
         .run_interactive("objection", &objection_args)
         .await?;
-    std::process::exit(exit_code);
+    Ok(ExitStatus(exit_code))
 }
 
 #[cfg(test)]
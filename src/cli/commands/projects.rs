@@ -0,0 +1,159 @@
+use crate::config::{GlobalConfigManager, ProjectConfigManager, ProjectRegistry};
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+
+struct ProjectEntry {
+    dir: PathBuf,
+    name: Option<String>,
+    frida_version: Option<String>,
+}
+
+impl ProjectEntry {
+    fn dir_name(&self) -> String {
+        self.dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.name.as_deref() == Some(query) || self.dir_name() == query
+    }
+}
+
+async fn load_entries(registry: &ProjectRegistry) -> Vec<ProjectEntry> {
+    let mut entries = Vec::new();
+    for dir in registry.project_dirs() {
+        let config = ProjectConfigManager::new(&dir).load().await.ok();
+        entries.push(ProjectEntry {
+            name: config.as_ref().map(|c| c.project.name.clone()),
+            frida_version: config.map(|c| c.frida.version),
+            dir,
+        });
+    }
+    entries
+}
+
+async fn load_registry(global_mgr: &GlobalConfigManager) -> Result<ProjectRegistry> {
+    ProjectRegistry::load_or_default(&global_mgr.get_project_registry_path()).await
+}
+
+pub async fn list() -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let registry = load_registry(&global_mgr).await?;
+    let entries = load_entries(&registry).await;
+
+    if entries.is_empty() {
+        println!("{}", "No projects registered yet".yellow());
+        println!(
+            "Projects are recorded by {} / {} / {}",
+            "frida-mgr init".cyan(),
+            "install".cyan(),
+            "sync".cyan()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Known frida-mgr projects:".bold());
+    println!();
+
+    let mut stale = 0;
+    for entry in &entries {
+        match (&entry.name, &entry.frida_version) {
+            (Some(name), Some(version)) => println!(
+                "  {} {} (Frida {})\n    {}",
+                "●".green(),
+                name.cyan(),
+                version.yellow(),
+                entry.dir.display()
+            ),
+            _ => {
+                stale += 1;
+                println!(
+                    "  {} {} {}\n    {}",
+                    "○".red(),
+                    entry.dir_name().dimmed(),
+                    "(missing frida.toml)".red(),
+                    entry.dir.display()
+                );
+            }
+        }
+    }
+
+    if stale > 0 {
+        println!();
+        println!(
+            "{} {} stale entr{}; run {} to remove them",
+            "⚠".yellow().bold(),
+            stale,
+            if stale == 1 { "y" } else { "ies" },
+            "frida-mgr projects prune".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Print the directory of a registered project, for use as `cd "$(frida-mgr projects open <name>)"`.
+pub async fn open(name: String) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let registry = load_registry(&global_mgr).await?;
+    let entries = load_entries(&registry).await;
+
+    let matches: Vec<&ProjectEntry> = entries.iter().filter(|e| e.matches(&name)).collect();
+    let entry = match matches.as_slice() {
+        [entry] => *entry,
+        [] => {
+            return Err(FridaMgrError::Config(format!(
+                "No registered project named '{}'. Run 'frida-mgr projects list' to see them.",
+                name
+            )))
+        }
+        many => {
+            return Err(FridaMgrError::Config(format!(
+                "'{}' matches {} projects: {}",
+                name,
+                many.len(),
+                many.iter()
+                    .map(|e| e.dir.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )))
+        }
+    };
+
+    if !entry.dir.is_dir() {
+        return Err(FridaMgrError::FileNotFound(format!(
+            "{} (run 'frida-mgr projects prune')",
+            entry.dir.display()
+        )));
+    }
+
+    // Plain path on stdout so shells can capture it.
+    println!("{}", entry.dir.display());
+    Ok(())
+}
+
+pub async fn prune() -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let registry_path = global_mgr.get_project_registry_path();
+    let mut registry = ProjectRegistry::load_or_default(&registry_path).await?;
+
+    let removed = registry.prune_missing();
+    if removed.is_empty() {
+        println!("{} No stale projects found", "✓".green().bold());
+        return Ok(());
+    }
+
+    registry.save(&registry_path).await?;
+    for path in &removed {
+        println!("  - {}", path.dimmed());
+    }
+    println!(
+        "{} Removed {} stale project(s)",
+        "✓".green().bold(),
+        removed.len().to_string().cyan()
+    );
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::push::{push_server, resolve_server_arch};
+use crate::config::{
+    android_server_candidates, resolve_android_server_target, AndroidServerSource, ArchType,
+    GlobalConfig, GlobalConfigManager, ProjectConfig, ProjectConfigManager,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::frida::{ServerDistribution, ServerDownloader};
+use colored::Colorize;
+use std::path::Path;
+use std::time::Duration;
+
+/// How often and how long to wait for frida-server to open its port after it started.
+const LISTEN_ATTEMPTS: u32 = 10;
+const LISTEN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Device-side changes made so far, undone in reverse when a later step fails.
+struct Rollback<'a> {
+    adb: &'a AdbClient,
+    device_id: &'a str,
+    root_command: &'a str,
+    port: u16,
+    /// Files that did not exist before provisioning (the pushed binary and its log).
+    files: Vec<String>,
+    started: Option<String>,
+    forwarded: bool,
+}
+
+impl Rollback<'_> {
+    async fn run(&self) {
+        eprintln!(
+            "{} Provisioning failed; rolling back {}",
+            "⚠".yellow().bold(),
+            self.device_id.yellow()
+        );
+        if self.forwarded
+            && self
+                .adb
+                .remove_forward(self.device_id, self.port)
+                .await
+                .is_ok()
+        {
+            eprintln!("  Removed the forward of port {}", self.port);
+        }
+        if let Some(server_name) = &self.started {
+            if self
+                .adb
+                .kill_server(self.device_id, server_name, self.root_command)
+                .await
+                .is_ok()
+            {
+                eprintln!("  Stopped {}", server_name);
+            }
+        }
+        if !self.files.is_empty()
+            && self
+                .adb
+                .remove_remote_files(self.device_id, &self.files, self.root_command)
+                .await
+                .is_ok()
+        {
+            eprintln!("  Removed {}", self.files.join(", "));
+        }
+    }
+}
+
+/// Bring a device from nothing to a listening frida-server in one go: detect the
+/// architecture, make sure the matching server is cached, push it, start it, check the
+/// port is open and forward it when asked to (or `android.auto_forward` is set). Device-side
+/// steps are rolled back on failure.
+pub async fn execute(device_id: Option<String>, forward: bool) -> Result<()> {
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    let config = project_mgr.load().await?;
+    let forward = forward || config.android.auto_forward;
+    let project_dir = project_mgr
+        .config_path()
+        .parent()
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
+    let total = total_steps(forward);
+
+    step(1, total, "Detecting device and architecture");
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+    println!(
+        "{} Target device: {} ({})",
+        "ℹ".blue().bold(),
+        device.id.cyan(),
+        device.model.yellow()
+    );
+    let arch = resolve_server_arch(&adb, &device.id, &config, None).await?;
+
+    step(2, total, "Making sure the frida-server build is available");
+    match config.android.server.source {
+        AndroidServerSource::Download | AndroidServerSource::Custom => {
+            ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+                .with_notifications(global_config.notifications)
+                .with_distribution(ServerDistribution::from_config(&config.android.server))
+                .download(&config.frida.version, &arch)
+                .await?;
+        }
+        AndroidServerSource::Local => println!(
+            "{} Using the local build from android.server.local",
+            "ℹ".blue().bold()
+        ),
+    }
+
+    // Note what is already on the device so rollback only removes what this run added.
+    let default_target = resolve_android_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+    )?;
+    let existing = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(default_target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+    let existing = adb
+        .file_exists(&device.id, &existing.remote_path)
+        .await
+        .then_some(existing.remote_path);
+
+    let mut rollback = Rollback {
+        adb: &adb,
+        device_id: &device.id,
+        root_command: &config.android.root_command,
+        port: config.android.server_port,
+        files: Vec::new(),
+        started: None,
+        forwarded: false,
+    };
+    let result = provision_device(
+        &mut rollback,
+        &config,
+        &project_dir,
+        &global_config,
+        &arch,
+        existing.as_deref(),
+        forward,
+    )
+    .await;
+    let server_name = match result {
+        Ok(server_name) => server_name,
+        Err(e) => {
+            rollback.run().await;
+            return Err(e);
+        }
+    };
+
+    println!();
+    println!(
+        "{} {} is provisioned: {} is listening on port {}",
+        "✓".green().bold(),
+        device.id.cyan(),
+        server_name.cyan(),
+        config.android.server_port.to_string().cyan()
+    );
+    if forward {
+        println!(
+            "  Forwarded to this machine; connect with {}",
+            format!("-H 127.0.0.1:{}", config.android.server_port).cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Steps 3 onwards, recording each device-side change in `rollback`. Returns the name of
+/// the running server process.
+async fn provision_device(
+    rollback: &mut Rollback<'_>,
+    config: &ProjectConfig,
+    project_dir: &Path,
+    global_config: &GlobalConfig,
+    arch: &ArchType,
+    existing: Option<&str>,
+    forward: bool,
+) -> Result<String> {
+    let adb = rollback.adb;
+    let device_id = rollback.device_id;
+    let port = config.android.server_port;
+    let total = total_steps(forward);
+
+    step(3, total, "Pushing frida-server and making it executable");
+    let target = push_server(adb, device_id, config, project_dir, global_config, arch).await?;
+    if existing != Some(target.remote_path.as_str()) {
+        rollback.files.push(target.remote_path.clone());
+        rollback
+            .files
+            .extend(config.android.log.file_for(&target.remote_path));
+    }
+
+    step(4, total, "Starting frida-server");
+    rollback.started = Some(target.process_name.clone());
+    adb.start_server(
+        device_id,
+        &target.remote_path,
+        &target.process_name,
+        port,
+        &config.android.root_command,
+        config.android.selinux,
+        &config.android.log,
+    )
+    .await?;
+
+    step(
+        5,
+        total,
+        &format!("Checking that port {} is listening", port),
+    );
+    let mut listening = false;
+    for _ in 0..LISTEN_ATTEMPTS {
+        if adb.check_port_listening(device_id, port).await? {
+            listening = true;
+            break;
+        }
+        tokio::time::sleep(LISTEN_INTERVAL).await;
+    }
+    if !listening {
+        return Err(FridaMgrError::Adb(format!(
+            "{} is running, but nothing is listening on port {}",
+            target.process_name, port
+        )));
+    }
+
+    if forward {
+        step(
+            6,
+            total,
+            &format!("Forwarding tcp:{} to this machine", port),
+        );
+        adb.forward_persistent(device_id, port).await?;
+        rollback.forwarded = true;
+    }
+
+    Ok(target.process_name)
+}
+
+fn total_steps(forward: bool) -> usize {
+    if forward {
+        6
+    } else {
+        5
+    }
+}
+
+fn step(index: usize, total: usize, what: &str) {
+    println!();
+    println!(
+        "{} {} {}",
+        "⚙".blue().bold(),
+        format!("[{}/{}]", index, total).dimmed(),
+        what
+    );
+}
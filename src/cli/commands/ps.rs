@@ -0,0 +1,237 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::ExitStatus;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use crate::python::VenvExecutor;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+
+/// One row of `frida-ps -j` (`identifier` only with `-a`/`-i`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct PsEntry {
+    #[serde(default)]
+    pub pid: Option<u32>,
+    pub name: String,
+    #[serde(default)]
+    pub identifier: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct AppProcess {
+    pid: u32,
+    /// Process name; `:remote`-style suffixes mark an app's secondary processes.
+    name: String,
+}
+
+/// Running processes of one installed package.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct RunningApp {
+    identifier: String,
+    /// Label from the launcher, or the identifier when none is known.
+    name: String,
+    processes: Vec<AppProcess>,
+}
+
+/// `frida-ps` itself, or with `--apps` a per-package view of running apps with their
+/// labels and secondary processes grouped together.
+pub async fn execute(
+    device_id: Option<String>,
+    apps: bool,
+    json: bool,
+    args: Vec<String>,
+    discover: bool,
+) -> Result<ExitStatus> {
+    if !apps {
+        return super::run::execute(device_id, "frida-ps".to_string(), args, discover).await;
+    }
+
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::discover_project_dir(&current_dir, discover);
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+    let packages: HashSet<String> = adb.list_packages(&device.id, None).await?.into_iter().collect();
+
+    let executor = VenvExecutor::new(project_dir).with_work_dir(current_dir);
+    let processes = frida_ps_json(&executor, Some(&device.id), &[]).await?;
+    // Labels are a nicety; older frida-ps without -i/--json support just leaves them out.
+    let labels: HashMap<String, String> = frida_ps_json(&executor, Some(&device.id), &["-a", "-i"])
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| entry.identifier.map(|id| (id, entry.name)))
+        .collect();
+
+    let running = group_running_apps(&processes, &packages, &labels);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&running).map_err(|e| anyhow::anyhow!(e))?
+        );
+        return Ok(ExitStatus::SUCCESS);
+    }
+
+    if running.is_empty() {
+        println!("{} No running apps on {}", "○".dimmed(), device.id.cyan());
+        return Ok(ExitStatus::SUCCESS);
+    }
+
+    let name_width = running
+        .iter()
+        .map(|a| a.name.chars().count())
+        .max()
+        .unwrap_or(4);
+    println!(
+        "{:>7}  {:<name_width$}  {}",
+        "PID".bold(),
+        "Name".bold(),
+        "Identifier".bold()
+    );
+    for app in &running {
+        let main = &app.processes[0];
+        println!(
+            "{:>7}  {:<name_width$}  {}",
+            main.pid,
+            app.name,
+            app.identifier.cyan()
+        );
+        for process in &app.processes[1..] {
+            println!(
+                "{:>7}  {:<name_width$}  {}",
+                process.pid.to_string().dimmed(),
+                "",
+                process.name.dimmed()
+            );
+        }
+    }
+
+    Ok(ExitStatus::SUCCESS)
+}
+
+/// `frida-ps -j` on `device_id`, or on frida's local device when `None`.
+pub async fn frida_ps_json(
+    executor: &VenvExecutor,
+    device_id: Option<&str>,
+    extra: &[&str],
+) -> Result<Vec<PsEntry>> {
+    let mut args = match device_id {
+        Some(id) => vec!["-D".to_string(), id.to_string()],
+        None => Vec::new(),
+    };
+    args.push("-j".to_string());
+    args.extend(extra.iter().map(|a| a.to_string()));
+    let output = executor.run_captured("frida-ps", &args).await?;
+    if output.exit_code != 0 {
+        return Err(FridaMgrError::CommandFailed(format!(
+            "frida-ps failed: {}",
+            output.stderr.trim()
+        )));
+    }
+    serde_json::from_str(&output.stdout).map_err(|e| anyhow::anyhow!(e).into())
+}
+
+/// Processes whose name is an installed package, or `<package>:<suffix>`, grouped per
+/// package with the main process first. Apps are ordered by label.
+fn group_running_apps(
+    processes: &[PsEntry],
+    packages: &HashSet<String>,
+    labels: &HashMap<String, String>,
+) -> Vec<RunningApp> {
+    let mut grouped: BTreeMap<&str, Vec<AppProcess>> = BTreeMap::new();
+    for entry in processes {
+        let Some(pid) = entry.pid.filter(|&pid| pid != 0) else {
+            continue;
+        };
+        let package = entry.name.split(':').next().unwrap_or(&entry.name);
+        if let Some(package) = packages.get(package) {
+            grouped.entry(package).or_default().push(AppProcess {
+                pid,
+                name: entry.name.clone(),
+            });
+        }
+    }
+
+    let mut running: Vec<RunningApp> = grouped
+        .into_iter()
+        .map(|(identifier, mut processes)| {
+            processes.sort_by_key(|p| (p.name != identifier, p.pid));
+            RunningApp {
+                identifier: identifier.to_string(),
+                name: labels
+                    .get(identifier)
+                    .cloned()
+                    .unwrap_or_else(|| identifier.to_string()),
+                processes,
+            }
+        })
+        .collect();
+    running.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.identifier.cmp(&b.identifier))
+    });
+    running
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: u32, name: &str) -> PsEntry {
+        PsEntry {
+            pid: Some(pid),
+            name: name.to_string(),
+            identifier: None,
+        }
+    }
+
+    #[test]
+    fn groups_secondary_processes_under_their_package() {
+        let processes = vec![
+            entry(900, "com.example.app:push"),
+            entry(812, "com.example.app"),
+            entry(1, "init"),
+            entry(2000, "com.android.chrome"),
+            entry(2001, "com.android.chrome:sandboxed_process0"),
+        ];
+        let packages: HashSet<String> = ["com.example.app", "com.android.chrome"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let labels: HashMap<String, String> =
+            [("com.android.chrome".to_string(), "Chrome".to_string())].into();
+
+        let running = group_running_apps(&processes, &packages, &labels);
+        assert_eq!(running.len(), 2);
+        assert_eq!(running[0].name, "Chrome");
+        assert_eq!(running[0].processes.len(), 2);
+        assert_eq!(running[1].name, "com.example.app");
+        assert_eq!(
+            running[1].processes,
+            vec![
+                AppProcess {
+                    pid: 812,
+                    name: "com.example.app".to_string()
+                },
+                AppProcess {
+                    pid: 900,
+                    name: "com.example.app:push".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_frida_ps_json() {
+        let entries: Vec<PsEntry> = serde_json::from_str(
+            r#"[{"pid": 2000, "name": "Chrome", "identifier": "com.android.chrome"}, {"pid": 0, "name": "Maps", "identifier": "com.google.android.apps.maps"}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries[0].identifier.as_deref(), Some("com.android.chrome"));
+        assert_eq!(entries[1].pid, Some(0));
+    }
+}
@@ -1,25 +1,40 @@
+use crate::android::elf::{arch_compatibility, read_elf_arch, ArchCompatibility};
 use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::run_as;
 use crate::config::{
-    resolve_android_server_target, AndroidServerSource, GlobalConfigManager, ProjectConfigManager,
+    android_server_candidates, random_server_name, resolve_android_server_target,
+    resolve_device_server_target, AndroidServerSource, AndroidServerTarget, ArchType, GlobalConfig,
+    GlobalConfigManager, ProjectConfig, ProjectConfigManager, ServerNames,
 };
-use crate::core::error::Result;
+use crate::core::error::{FridaMgrError, Result};
 use crate::core::resolve_path;
-use crate::frida::ServerDownloader;
+use crate::frida::{ServerDistribution, ServerDownloader};
 use colored::Colorize;
+use std::path::Path;
 
-pub async fn execute(device_id: Option<String>, auto_start: bool) -> Result<()> {
+/// `arch` overrides both `android.arch` and detection, e.g. to push the x86_64 server to
+/// an emulator that translates ARM; `random_name` turns on `android.randomize_server_name`.
+pub async fn execute(
+    device_id: Option<String>,
+    auto_start: bool,
+    arch: Option<ArchType>,
+    random_name: bool,
+) -> Result<()> {
     let project_mgr = ProjectConfigManager::from_current_dir()?;
-    let config = project_mgr.load().await?;
+    let mut config = project_mgr.load().await?;
+    config.android.randomize_server_name |= random_name;
     let project_dir = project_mgr
         .config_path()
         .parent()
         .unwrap_or(std::path::Path::new("."));
 
     let global_config = GlobalConfigManager::new()?.load().await?;
-    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
 
     // Get device
-    let device = adb.get_device(device_id.as_deref()).await?;
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
     println!(
         "{} Target device: {} ({})",
         "ℹ".blue().bold(),
@@ -27,9 +42,252 @@ pub async fn execute(device_id: Option<String>, auto_start: bool) -> Result<()>
         device.model.yellow()
     );
 
+    let arch = resolve_server_arch(&adb, &device.id, &config, arch).await?;
+    let target = push_server(
+        &adb,
+        &device.id,
+        &config,
+        project_dir,
+        &global_config,
+        &arch,
+    )
+    .await?;
+    let remote_path = target.remote_path;
+    let server_name = target.process_name;
+
+    // Start if requested or configured
+    let should_start = auto_start || config.android.auto_start;
+
+    if let Some(package) = config.android.run_as() {
+        run_as::install(&adb, &device.id, package, &remote_path, &server_name).await?;
+        if should_start {
+            run_as::start(
+                &adb,
+                &device.id,
+                package,
+                &server_name,
+                config.android.server_port,
+            )
+            .await?;
+        } else {
+            println!("  Run {} to start the server", "frida-mgr start".cyan());
+        }
+        return Ok(());
+    }
+
+    if should_start {
+        adb.start_server(
+            &device.id,
+            &remote_path,
+            &server_name,
+            config.android.server_port,
+            &config.android.root_command,
+            config.android.selinux,
+            &config.android.log,
+        )
+        .await?;
+
+        println!();
+        println!(
+            "{} {} is running on port {}",
+            "✓".green().bold(),
+            server_name.cyan(),
+            config.android.server_port.to_string().cyan()
+        );
+    } else {
+        println!();
+        println!(
+            "{} {} pushed to device",
+            "✓".green().bold(),
+            server_name.cyan()
+        );
+        println!("  Run {} to start the server", "frida-mgr start".cyan());
+    }
+
+    Ok(())
+}
+
+/// Before attaching to `device_id`: warn when the frida-server on the device reports a
+/// different version than `frida.version` (or with `auto_push` replace it), and when the
+/// server is not running and `android.auto_start` or `android.stop_on_exit` is set (or
+/// `ensure` was passed), push the cached binary and start it, so a cold device needs no
+/// separate `push`/`start`. Otherwise a server that is not running only gets a warning
+/// naming the ways to start it.
+pub async fn ensure_server_running(device_id: &str, ensure: bool, auto_push: bool) -> Result<()> {
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    if !project_mgr.exists() {
+        return if ensure || auto_push {
+            Err(FridaMgrError::NotInitialized)
+        } else {
+            Ok(())
+        };
+    }
+    let config = project_mgr.load().await?;
+    let project_dir = project_mgr
+        .config_path()
+        .parent()
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
+    let target = resolve_device_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+        device_id,
+    )
+    .await?;
+    let installed = adb
+        .locate_server(
+            device_id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+
+    let outdated = match adb
+        .server_version(device_id, &installed.remote_path)
+        .await
+        .ok()
+        .flatten()
+    {
+        Some(version) if version != config.frida.version => Some(version),
+        _ => None,
+    };
+    match &outdated {
+        Some(version) if auto_push => println!(
+            "{} {} on {} is {}, but the project uses {}; replacing it",
+            "ℹ".blue().bold(),
+            installed.process_name.cyan(),
+            device_id.cyan(),
+            version.yellow(),
+            config.frida.version.cyan()
+        ),
+        Some(version) => eprintln!(
+            "{} {} on {} is {}, but the project uses {}; run {} or pass {}",
+            "⚠".yellow().bold(),
+            installed.process_name.cyan(),
+            device_id.cyan(),
+            version.yellow(),
+            config.frida.version.cyan(),
+            "frida-mgr push --start".cyan(),
+            "--auto-push".cyan()
+        ),
+        None => {}
+    }
+
+    if outdated.is_none() || !auto_push {
+        let running = adb
+            .check_server_running(device_id, &installed.process_name)
+            .await;
+        if running.as_ref().is_ok_and(|running| *running) {
+            return Ok(());
+        }
+        if !ensure && !config.android.auto_start && !config.android.stop_on_exit {
+            // The server may run outside frida-mgr (another name, a Magisk module, a
+            // forwarded host), so only hint at the ways to start it. Without ps there is
+            // no telling at all.
+            if running.is_ok() {
+                eprintln!(
+                    "{} {} is not running on {}; if frida-server is not started some other way, run {}, pass {}, or set {} in frida.toml",
+                    "⚠".yellow().bold(),
+                    installed.process_name.cyan(),
+                    device_id.cyan(),
+                    "frida-mgr start".cyan(),
+                    "--ensure-server".cyan(),
+                    "android.auto_start = true".cyan()
+                );
+            }
+            return Ok(());
+        }
+        println!(
+            "{} {} is not running on {}; pushing and starting it",
+            "ℹ".blue().bold(),
+            installed.process_name.cyan(),
+            device_id.cyan()
+        );
+    }
+
+    let arch = resolve_server_arch(&adb, device_id, &config, None).await?;
+    let target = push_server(
+        &adb,
+        device_id,
+        &config,
+        &project_dir,
+        &global_config,
+        &arch,
+    )
+    .await?;
+    start_pushed_server(&adb, device_id, &config, &target).await
+}
+
+/// Start the server `push_server` just put at `target`: through `root_command`, or in
+/// `run-as` mode moved into the app's data dir and started as the app.
+pub async fn start_pushed_server(
+    adb: &AdbClient,
+    device_id: &str,
+    config: &ProjectConfig,
+    target: &AndroidServerTarget,
+) -> Result<()> {
+    if let Some(package) = config.android.run_as() {
+        run_as::install(
+            adb,
+            device_id,
+            package,
+            &target.remote_path,
+            &target.process_name,
+        )
+        .await?;
+        return run_as::start(
+            adb,
+            device_id,
+            package,
+            &target.process_name,
+            config.android.server_port,
+        )
+        .await;
+    }
+
+    adb.start_server(
+        device_id,
+        &target.remote_path,
+        &target.process_name,
+        config.android.server_port,
+        &config.android.root_command,
+        config.android.selinux,
+        &config.android.log,
+    )
+    .await?;
+    println!(
+        "{} {} is running on port {}",
+        "✓".green().bold(),
+        target.process_name.cyan(),
+        config.android.server_port.to_string().cyan()
+    );
+    Ok(())
+}
+
+/// The architecture of the server to push: `arch` when given, else `android.arch`, else
+/// what the device reports.
+pub async fn resolve_server_arch(
+    adb: &AdbClient,
+    device_id: &str,
+    config: &ProjectConfig,
+    arch: Option<ArchType>,
+) -> Result<ArchType> {
     // Detect architecture if auto
-    let target_arch = if config.android.arch == crate::config::ArchType::Auto {
-        let detected = adb.get_arch(&device.id).await?;
+    let target_arch = if let Some(arch) = arch {
+        let detected = adb.get_arch(device_id).await?;
+        println!(
+            "{} Using requested architecture {} (device reports {})",
+            "ℹ".blue().bold(),
+            arch.to_str().yellow(),
+            detected.to_str().yellow()
+        );
+        warn_arch_override(&arch, &detected);
+        arch
+    } else if config.android.arch == crate::config::ArchType::Auto {
+        let detected = adb.get_arch(device_id).await?;
         println!(
             "{} Detected architecture: {}",
             "ℹ".blue().bold(),
@@ -39,22 +297,36 @@ pub async fn execute(device_id: Option<String>, auto_start: bool) -> Result<()>
     } else {
         config.android.arch.clone()
     };
+    Ok(target_arch)
+}
 
+/// Copy the project's frida-server for `target_arch` to the first usable push location and
+/// make it executable. Returns where it went.
+pub async fn push_server(
+    adb: &AdbClient,
+    device_id: &str,
+    config: &ProjectConfig,
+    project_dir: &Path,
+    global_config: &GlobalConfig,
+    target_arch: &ArchType,
+) -> Result<AndroidServerTarget> {
     let server_path = match config.android.server.source {
-        AndroidServerSource::Download => {
+        AndroidServerSource::Download | AndroidServerSource::Custom => {
             // Get frida-server from cache
             let cache_dir = GlobalConfigManager::new()?.get_cache_dir();
-            let downloader = ServerDownloader::new(cache_dir);
+            let downloader = ServerDownloader::new(cache_dir)
+                .with_distribution(ServerDistribution::from_config(&config.android.server));
 
             downloader
-                .get_cached(&config.frida.version, &target_arch)
+                .get_cached(&config.frida.version, target_arch)
                 .await
                 .ok_or_else(|| {
                     crate::core::error::FridaMgrError::FileNotFound(format!(
-                        "frida-server {} for {}. Run 'frida-mgr install {}' first.",
+                        "frida-server {} for {}. Run 'frida-mgr install {} --arch {}' first.",
                         config.frida.version,
                         target_arch.to_str(),
-                        config.frida.version
+                        config.frida.version,
+                        target_arch.to_str()
                     ))
                 })?
         }
@@ -65,60 +337,314 @@ pub async fn execute(device_id: Option<String>, auto_start: bool) -> Result<()>
                 .local
                 .as_ref()
                 .expect("config validation enforces local config when source=local");
-            let resolved = resolve_path(project_dir, &local_cfg.path);
+            let local_path = local_cfg.path_for(target_arch).ok_or_else(|| {
+                crate::core::error::FridaMgrError::Config(format!(
+                    "No local frida-server configured for {} (add it to android.server.local.paths)",
+                    target_arch.to_str()
+                ))
+            })?;
+            let resolved = resolve_path(project_dir, local_path);
             if !resolved.is_file() {
                 return Err(crate::core::error::FridaMgrError::FileNotFound(format!(
                     "Local frida-server not found or not a file: {}",
                     resolved.display()
                 )));
             }
+            check_local_server_arch(&resolved, target_arch).await?;
             resolved
         }
     };
 
+    let random_name = config
+        .android
+        .randomize_server_name
+        .then(random_server_name);
     let target = resolve_android_server_target(
         &global_config.android.default_push_path,
-        config.android.server_name.as_deref(),
+        random_name
+            .as_deref()
+            .or(config.android.server_name.as_deref()),
     )?;
-    let remote_path = target.remote_path;
-    let server_name = target.process_name;
+    let candidates = android_server_candidates(target, &global_config.android.push_path_fallbacks);
+    let target = choose_push_target(adb, device_id, candidates).await?;
 
     // Push to device
-    adb.push_file(&device.id, &server_path, &remote_path)
+    adb.push_file(device_id, &server_path, &target.remote_path)
         .await?;
 
     // Make executable
-    adb.make_executable(&device.id, &remote_path).await?;
+    adb.make_executable(device_id, &target.remote_path).await?;
 
-    // Start if requested or configured
-    let should_start = auto_start || config.android.auto_start;
+    record_server_name(
+        adb,
+        device_id,
+        config,
+        &target,
+        random_name.as_deref(),
+        &GlobalConfigManager::new()?.get_server_names_path(),
+    )
+    .await?;
+    if random_name.is_some() {
+        println!(
+            "{} Pushed as {} (random name)",
+            "ℹ".blue().bold(),
+            target.process_name.cyan()
+        );
+    }
 
-    if should_start {
-        adb.start_server(
-            &device.id,
-            &remote_path,
-            &server_name,
-            config.android.server_port,
-            &config.android.root_command,
-        )
-        .await?;
+    Ok(target)
+}
 
-        println!();
-        println!(
-            "{} {} is running on port {}",
-            "✓".green().bold(),
-            server_name.cyan(),
-            config.android.server_port.to_string().cyan()
+/// Remember in `path` the randomized name `target` was pushed under (or that it has none),
+/// after stopping and removing the server a previous randomized push left on the device.
+async fn record_server_name(
+    adb: &AdbClient,
+    device_id: &str,
+    config: &ProjectConfig,
+    target: &AndroidServerTarget,
+    random_name: Option<&str>,
+    path: &Path,
+) -> Result<()> {
+    let mut names = ServerNames::load_or_default(path).await?;
+    if let Some(previous) = names
+        .get(device_id)
+        .filter(|previous| Some(*previous) != random_name)
+    {
+        let previous = AndroidServerTarget {
+            remote_path: format!("{}/{}", target.dir().trim_end_matches('/'), previous),
+            process_name: previous.to_string(),
+        };
+        let root_command = &config.android.root_command;
+        if adb
+            .check_server_running(device_id, &previous.process_name)
+            .await
+            .unwrap_or(false)
+        {
+            let _ = adb
+                .kill_server(device_id, &previous.process_name, root_command)
+                .await;
+        }
+        let mut files = vec![previous.remote_path.clone()];
+        files.extend(config.android.log.file_for(&previous.remote_path));
+        if adb
+            .remove_remote_files(device_id, &files, root_command)
+            .await
+            .is_ok()
+        {
+            println!(
+                "{} Removed {} from the previous randomized push",
+                "ℹ".blue().bold(),
+                previous.remote_path.yellow()
+            );
+        }
+    }
+    if names.set(device_id, random_name) {
+        names.save(path).await?;
+    }
+    Ok(())
+}
+
+/// Pushing a server the device cannot run natively is allowed when asked for explicitly,
+/// but say what to expect.
+fn warn_arch_override(requested: &ArchType, device_arch: &ArchType) {
+    match arch_compatibility(requested, device_arch) {
+        ArchCompatibility::Match => {}
+        ArchCompatibility::Compat32 => eprintln!(
+            "{} A {} server on a {} device cannot instrument 64-bit processes",
+            "⚠".yellow().bold(),
+            requested.to_str().yellow(),
+            device_arch.to_str().yellow()
+        ),
+        ArchCompatibility::Mismatch => eprintln!(
+            "{} A {} server does not match the {} device; it only runs if the device translates {} binaries",
+            "⚠".yellow().bold(),
+            requested.to_str().yellow(),
+            device_arch.to_str().yellow(),
+            requested.to_str()
+        ),
+    }
+}
+
+/// Refuse a local frida-server built for another architecture instead of letting it fail
+/// mysteriously at start time.
+async fn check_local_server_arch(path: &Path, device_arch: &ArchType) -> Result<()> {
+    let Some(binary_arch) = read_elf_arch(path).await? else {
+        eprintln!(
+            "{} Could not read an Android ELF header from {}; skipping arch check",
+            "⚠".yellow().bold(),
+            path.display().to_string().yellow()
         );
-    } else {
-        println!();
-        println!(
-            "{} {} pushed to device",
-            "✓".green().bold(),
-            server_name.cyan()
+        return Ok(());
+    };
+
+    match arch_compatibility(&binary_arch, device_arch) {
+        ArchCompatibility::Match => Ok(()),
+        ArchCompatibility::Compat32 => {
+            eprintln!(
+                "{} {} is a {} build on a {} device; it cannot instrument 64-bit processes",
+                "⚠".yellow().bold(),
+                path.display().to_string().yellow(),
+                binary_arch.to_str().yellow(),
+                device_arch.to_str().yellow()
+            );
+            Ok(())
+        }
+        ArchCompatibility::Mismatch => Err(FridaMgrError::Config(format!(
+            "Local frida-server {} is built for {}, but the device is {} (set android.server.local.paths.{})",
+            path.display(),
+            binary_arch.to_str(),
+            device_arch.to_str(),
+            device_arch.to_str()
+        ))),
+    }
+}
+
+/// First candidate whose directory accepts an executable file on this device.
+async fn choose_push_target(
+    adb: &AdbClient,
+    device_id: &str,
+    candidates: Vec<AndroidServerTarget>,
+) -> Result<AndroidServerTarget> {
+    for (index, candidate) in candidates.iter().enumerate() {
+        if adb.probe_exec_dir(device_id, candidate.dir()).await? {
+            if index > 0 {
+                println!(
+                    "{} Using fallback location {}",
+                    "ℹ".blue().bold(),
+                    candidate.remote_path.yellow()
+                );
+            }
+            return Ok(candidate.clone());
+        }
+        eprintln!(
+            "{} {} is not writable or does not allow exec on this device",
+            "⚠".yellow().bold(),
+            candidate.dir().yellow()
         );
-        println!("  Run {} to start the server", "frida-mgr start".cyan());
     }
 
-    Ok(())
+    Err(FridaMgrError::Adb(
+        "No writable, executable location to push frida-server to; add directories to \
+         android.push_path_fallbacks in the global config"
+            .to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AndroidServerMode;
+    use crate::core::ScriptedRunner;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn previous_random_push_is_removed_without_touching_dev_null() {
+        let steps = [
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nroot 1 0 0 0 0 0 S init\n",
+            ),
+            (
+                "-s emulator-5554 shell su -c id",
+                0,
+                "uid=0(root) gid=0(root)",
+            ),
+            (
+                "-s emulator-5554 shell su -c 'rm -f /data/local/tmp/0badc0de'",
+                0,
+                "",
+            ),
+        ];
+        let adb = AdbClient::new(None);
+        let runner = Arc::new(steps.iter().fold(
+            ScriptedRunner::new(),
+            |runner, (command, code, stdout)| {
+                runner.expect(&format!("{} {}", adb.adb_path(), command), *code, stdout)
+            },
+        ));
+        let adb = adb.with_runner(runner.clone());
+
+        let dir = tempfile::tempdir().unwrap();
+        let names_path = dir.path().join("server-names.toml");
+        let mut names = ServerNames::default();
+        names.set("emulator-5554", Some("0badc0de"));
+        names.save(&names_path).await.unwrap();
+
+        let mut config = ProjectConfig::default();
+        config.android.log.enabled = false;
+        let target = AndroidServerTarget {
+            remote_path: "/data/local/tmp/1a2b3c4d".to_string(),
+            process_name: "1a2b3c4d".to_string(),
+        };
+
+        record_server_name(
+            &adb,
+            "emulator-5554",
+            &config,
+            &target,
+            Some("1a2b3c4d"),
+            &names_path,
+        )
+        .await
+        .unwrap();
+        assert!(runner.remaining().is_empty());
+        let names = ServerNames::load_or_default(&names_path).await.unwrap();
+        assert_eq!(names.get("emulator-5554"), Some("1a2b3c4d"));
+    }
+
+    #[tokio::test]
+    async fn run_as_mode_starts_the_server_as_the_app() {
+        let steps = [
+            ("-s emulator-5554 shell run-as com.example.app id", 0, ""),
+            (
+                "-s emulator-5554 shell cat /data/local/tmp/frida-server | run-as com.example.app sh -c 'mkdir -p code_cache && cat > code_cache/frida-server && chmod 700 code_cache/frida-server'; r=$?; rm -f /data/local/tmp/frida-server; exit $r",
+                0,
+                "",
+            ),
+            (
+                "-s emulator-5554 shell run-as com.example.app killall frida-server",
+                1,
+                "",
+            ),
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nroot 1 0 0 0 0 0 S init\n",
+            ),
+            (
+                "-s emulator-5554 shell run-as com.example.app sh -c 'nohup code_cache/frida-server -l 127.0.0.1:27042 > code_cache/frida-server.log 2>&1 &'",
+                0,
+                "",
+            ),
+            (
+                "-s emulator-5554 shell ps -A",
+                0,
+                "USER PID PPID VSZ RSS WCHAN ADDR S NAME\nu0_a123 4242 1 0 0 0 0 S code_cache/frida-server\n",
+            ),
+            ("-s emulator-5554 forward tcp:27042 tcp:27042", 0, ""),
+        ];
+        let adb = AdbClient::new(None);
+        let runner = Arc::new(steps.iter().fold(
+            ScriptedRunner::new(),
+            |runner, (command, code, stdout)| {
+                runner.expect(&format!("{} {}", adb.adb_path(), command), *code, stdout)
+            },
+        ));
+        let adb = adb.with_runner(runner.clone());
+
+        let mut config = ProjectConfig::default();
+        config.android.mode = AndroidServerMode::RunAs;
+        config.android.run_as_package = Some("com.example.app".to_string());
+        config.android.server_port = 27042;
+        let target = AndroidServerTarget {
+            remote_path: "/data/local/tmp/frida-server".to_string(),
+            process_name: "frida-server".to_string(),
+        };
+
+        start_pushed_server(&adb, "emulator-5554", &config, &target)
+            .await
+            .unwrap();
+        assert!(runner.remaining().is_empty());
+    }
 }
@@ -0,0 +1,174 @@
+use crate::android::artifacts::{frida_artifacts, Artifact, ArtifactKind};
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::config::{
+    android_server_candidates, resolve_android_server_target, GlobalConfigManager, ProjectConfig,
+    ProjectConfigManager, ServerNames,
+};
+use crate::core::error::Result;
+use crate::core::format_size;
+use colored::{ColoredString, Colorize};
+use std::path::PathBuf;
+
+/// Frida artifacts `scan` found on a device, with what deleting them needs.
+pub struct DeviceArtifacts {
+    adb: AdbClient,
+    pub device_id: String,
+    root_command: String,
+    names_path: PathBuf,
+    names: ServerNames,
+    /// On-device path of each artifact, in push directory order.
+    pub found: Vec<(String, Artifact)>,
+}
+
+impl DeviceArtifacts {
+    pub fn total_size(&self) -> u64 {
+        self.found.iter().map(|(_, artifact)| artifact.size).sum()
+    }
+
+    /// Print one line per artifact: `marker path (kind, size)`.
+    pub fn print(&self, marker: ColoredString) {
+        for (path, artifact) in &self.found {
+            println!(
+                "  {} {} ({}, {})",
+                marker,
+                path.cyan(),
+                artifact.kind.label().yellow(),
+                format_size(artifact.size)
+            );
+        }
+    }
+
+    /// Stop the servers among the artifacts, delete every artifact and forget the device's
+    /// randomized server name.
+    pub async fn delete(mut self) -> Result<()> {
+        for (_, artifact) in &self.found {
+            if artifact.kind == ArtifactKind::Server
+                && self
+                    .adb
+                    .check_server_running(&self.device_id, &artifact.name)
+                    .await
+                    .unwrap_or(false)
+            {
+                self.adb
+                    .kill_server(&self.device_id, &artifact.name, &self.root_command)
+                    .await?;
+            }
+        }
+
+        let paths: Vec<String> = self.found.iter().map(|(path, _)| path.clone()).collect();
+        if !paths.is_empty() {
+            self.adb
+                .remove_remote_files(&self.device_id, &paths, &self.root_command)
+                .await?;
+        }
+
+        if self.names.set(&self.device_id, None) {
+            self.names.save(&self.names_path).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Find pushed frida-server binaries, their logs and deployed agent bundles in the push
+/// directories on the device (the default push path and `push_path_fallbacks`). Servers
+/// are also recognised under the project's `server_name` and the device's randomized name.
+pub async fn scan(device_id: Option<String>) -> Result<DeviceArtifacts> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    let config = if project_mgr.exists() {
+        project_mgr.load().await?
+    } else {
+        ProjectConfig::default()
+    };
+    let root_command = config.android.root_command.clone();
+
+    let default_target =
+        resolve_android_server_target(&global_config.android.default_push_path, None)?;
+    let mut server_names = vec![default_target.process_name.clone()];
+    if let Some(name) = config.android.server_name.as_deref() {
+        server_names.push(name.to_string());
+    }
+    // A randomized push only shows up in ps under its own name.
+    let names_path = global_mgr.get_server_names_path();
+    let names = ServerNames::load_or_default(&names_path).await?;
+    if let Some(name) = names.get(&device.id) {
+        server_names.push(name.to_string());
+    }
+    let mut dirs: Vec<String> = Vec::new();
+    for candidate in
+        android_server_candidates(default_target, &global_config.android.push_path_fallbacks)
+    {
+        let dir = candidate.dir().to_string();
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    let mut found = Vec::new();
+    for dir in &dirs {
+        let files = adb
+            .list_marked_files(&device.id, dir, &root_command)
+            .await?;
+        found.extend(
+            frida_artifacts(&files, &server_names)
+                .into_iter()
+                .map(|a| (format!("{}/{}", dir.trim_end_matches('/'), a.name), a)),
+        );
+    }
+
+    Ok(DeviceArtifacts {
+        adb,
+        device_id: device.id,
+        root_command,
+        names_path,
+        names,
+        found,
+    })
+}
+
+/// Delete pushed frida-server binaries, their logs and deployed agent bundles from the
+/// push directories on the device (the default push path and `push_path_fallbacks`).
+pub async fn execute(device_id: Option<String>, dry_run: bool) -> Result<()> {
+    let artifacts = scan(device_id).await?;
+    let device_id = artifacts.device_id.clone();
+    let removed = artifacts.found.len();
+    let size = format_size(artifacts.total_size());
+
+    if dry_run {
+        artifacts.print("→".blue());
+    } else {
+        artifacts.print("✗".red());
+        artifacts.delete().await?;
+    }
+
+    if removed == 0 {
+        println!(
+            "{} No frida artifacts found on {}",
+            "ℹ".blue().bold(),
+            device_id.cyan()
+        );
+    } else if dry_run {
+        println!(
+            "{} {} artifact(s) ({}) would be removed from {} (run without --dry-run to delete)",
+            "ℹ".blue().bold(),
+            removed,
+            size,
+            device_id.cyan()
+        );
+    } else {
+        println!(
+            "{} Removed {} artifact(s) ({}) from {}",
+            "✓".green().bold(),
+            removed,
+            size,
+            device_id.cyan()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::android::{shell, AdbClient};
+use crate::cli::commands::device::resolve_device;
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, GlobalConfigManager,
+    ProjectConfigManager,
+};
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::time::Duration;
+
+/// How long the old server gets to exit before restarting gives up.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Stop frida-server, wait until the old process is really gone, then start it again
+/// through the usual start verification and report the new PID.
+pub async fn execute(device_id: Option<String>) -> Result<()> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    let config = ProjectConfigManager::from_current_dir()?.load().await?;
+    let target = resolve_device_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+        &device.id,
+    )
+    .await?;
+    let target = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+    let root_command = &config.android.root_command;
+
+    let old_pid = adb.server_pid(&device.id, &target.process_name).await?;
+    if old_pid.is_some() {
+        adb.kill_server(&device.id, &target.process_name, root_command)
+            .await?;
+        wait_for_exit(&adb, &device.id, &target.process_name, root_command).await?;
+    } else {
+        println!(
+            "{} {} was not running",
+            "ℹ".blue().bold(),
+            target.process_name.cyan()
+        );
+    }
+
+    adb.start_server(
+        &device.id,
+        &target.remote_path,
+        &target.process_name,
+        config.android.server_port,
+        root_command,
+        config.android.selinux,
+        &config.android.log,
+    )
+    .await?;
+    if config.android.auto_forward {
+        adb.forward_persistent(&device.id, config.android.server_port)
+            .await?;
+    }
+
+    let new_pid = adb.server_pid(&device.id, &target.process_name).await?;
+    let pid = |pid: Option<u32>| pid.map_or("-".to_string(), |pid| pid.to_string());
+    println!(
+        "{} {} restarted on {} (port: {}, pid: {} → {})",
+        "✓".green().bold(),
+        target.process_name.cyan(),
+        device.id.cyan(),
+        config.android.server_port.to_string().yellow(),
+        pid(old_pid),
+        pid(new_pid).green()
+    );
+    Ok(())
+}
+
+/// Poll until `process_name` no longer shows up in `ps`; a server still shutting down
+/// would otherwise hold the port the new one binds.
+async fn wait_for_exit(
+    adb: &AdbClient,
+    device_id: &str,
+    process_name: &str,
+    root_command: &str,
+) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + EXIT_TIMEOUT;
+    while adb.check_server_running(device_id, process_name).await? {
+        if tokio::time::Instant::now() >= deadline {
+            return Err(FridaMgrError::Adb(format!(
+                "{} did not exit within {}s; kill it with `adb shell \"{}\"` and retry",
+                process_name,
+                EXIT_TIMEOUT.as_secs(),
+                shell::as_root(
+                    &adb.root_command_for(device_id, root_command).await,
+                    &shell::command(["killall", "-9", process_name])
+                )
+            )));
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+    }
+    Ok(())
+}
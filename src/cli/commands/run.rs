@@ -1,12 +1,37 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::local;
+use crate::cli::ExitStatus;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
 use crate::core::error::Result;
 use crate::python::VenvExecutor;
 use std::env;
 
-pub async fn execute(command: String, args: Vec<String>) -> Result<()> {
+pub async fn execute(
+    device_id: Option<String>,
+    command: String,
+    args: Vec<String>,
+    discover: bool,
+) -> Result<ExitStatus> {
     let current_dir = env::current_dir()?;
-    let executor = VenvExecutor::new(current_dir);
+    let project_dir = ProjectConfigManager::discover_project_dir(&current_dir, discover);
+
+    if device_id.is_some() && local::is_local_project(&project_dir).await? {
+        local::reject_device_options(&command, &[("--device", true)])?;
+    }
+    let serial = match device_id.as_deref() {
+        Some(name) => {
+            let global_config = GlobalConfigManager::new()?.load().await?;
+            let adb = AdbClient::new(Some(global_config.android.adb_path));
+            Some(resolve_device(&adb, Some(name)).await?.id)
+        }
+        None => None,
+    };
+    let executor = VenvExecutor::new(project_dir)
+        .with_work_dir(current_dir)
+        .with_device(serial.as_deref());
 
     let exit_code = executor.run_interactive(&command, &args).await?;
 
-    std::process::exit(exit_code);
+    Ok(ExitStatus(exit_code))
 }
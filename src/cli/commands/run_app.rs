@@ -0,0 +1,120 @@
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::frida::run_frida;
+use crate::cli::commands::script::resolve_existing_script_path;
+use crate::cli::commands::spawn::spawn_pause_flag;
+use crate::cli::ExitStatus;
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, GlobalConfigManager,
+    ProjectConfigManager,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::{agent, agent::AgentProject};
+use colored::Colorize;
+use std::env;
+
+pub async fn execute(name: String, device_id: Option<String>) -> Result<ExitStatus> {
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .ok_or(FridaMgrError::NotInitialized)?;
+    let config = ProjectConfigManager::new(&project_dir).load().await?;
+
+    let profile = config.apps.get(&name).ok_or_else(|| {
+        let known: Vec<&str> = config.apps.keys().map(String::as_str).collect();
+        FridaMgrError::Config(if known.is_empty() {
+            format!(
+                "No app profile '{}'; add an [apps.{}] table to frida.toml",
+                name, name
+            )
+        } else {
+            format!(
+                "No app profile '{}' (available: {})",
+                name,
+                known.join(", ")
+            )
+        })
+    })?;
+
+    println!(
+        "{} App profile {}: {} ({})",
+        "ℹ".blue().bold(),
+        name.cyan(),
+        profile.package.cyan(),
+        if profile.spawn { "spawn" } else { "attach" }.yellow()
+    );
+
+    // Make sure frida-server is up before handing over to frida.
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+    let device = resolve_device(&adb, device_id.as_deref().or(profile.device.as_deref())).await?;
+    let target = resolve_device_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+        &device.id,
+    )
+    .await?;
+    let target = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+    if adb
+        .check_server_running(&device.id, &target.process_name)
+        .await?
+    {
+        println!(
+            "{} {} already running on {}",
+            "✓".green().bold(),
+            target.process_name.cyan(),
+            device.id.cyan()
+        );
+    } else {
+        adb.start_server(
+            &device.id,
+            &target.remote_path,
+            &target.process_name,
+            config.android.server_port,
+            &config.android.root_command,
+            config.android.selinux,
+            &config.android.log,
+        )
+        .await?;
+    }
+
+    let mut frida_args = vec!["-D".to_string(), device.id.clone()];
+    frida_args.push(if profile.spawn { "-f" } else { "-N" }.to_string());
+    frida_args.push(profile.package.clone());
+    if profile.spawn {
+        let pause = !config.frida.spawn_no_pause;
+        if let Some(flag) = spawn_pause_flag(Some(&config.frida.version), pause) {
+            frida_args.push(flag.to_string());
+        }
+    }
+
+    let agent_project = AgentProject::from_agent_config(project_dir.clone(), &config.agent);
+    let build_agent = profile
+        .agent
+        .unwrap_or_else(|| agent_project.entry_path.is_file());
+    if build_agent {
+        let out = agent::build_agent(&agent_project).await?;
+        frida_args.push("-l".to_string());
+        frida_args.push(out.to_string_lossy().to_string());
+    }
+
+    for script in &profile.scripts {
+        frida_args.push("-l".to_string());
+        frida_args.push(resolve_existing_script_path(
+            &project_dir,
+            &project_dir,
+            Some(&config.scripts),
+            script,
+        ));
+    }
+
+    frida_args.extend(profile.args.iter().cloned());
+
+    let exit_code = run_frida(&project_dir, &frida_args, Some(&device.id), false).await?;
+
+    Ok(ExitStatus(exit_code))
+}
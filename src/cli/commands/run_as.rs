@@ -0,0 +1,54 @@
+use crate::android::AdbClient;
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+
+/// Move the frida-server `push` staged at `staged` into `package`'s data dir
+/// (`android.mode = "run-as"`).
+pub async fn install(
+    adb: &AdbClient,
+    device_id: &str,
+    package: &str,
+    staged: &str,
+    server_name: &str,
+) -> Result<()> {
+    if !adb.is_debuggable(device_id, package).await? {
+        return Err(FridaMgrError::Adb(format!(
+            "{} is not debuggable; android.mode = \"run-as\" needs android:debuggable=\"true\" (try `frida-mgr apk` to patch it)",
+            package
+        )));
+    }
+
+    let path = adb
+        .install_run_as_server(device_id, package, staged, server_name)
+        .await?;
+    println!(
+        "{} Installed into {} ({})",
+        "✓".green().bold(),
+        package.cyan(),
+        path.yellow()
+    );
+    Ok(())
+}
+
+/// Start frida-server as `package` and forward its loopback port to this machine, the only
+/// way to reach it.
+pub async fn start(
+    adb: &AdbClient,
+    device_id: &str,
+    package: &str,
+    server_name: &str,
+    port: u16,
+) -> Result<()> {
+    adb.start_run_as_server(device_id, package, server_name, port)
+        .await?;
+    adb.forward_persistent(device_id, port).await?;
+    println!(
+        "{} {} started as {} on {}; connect with {}",
+        "✓".green().bold(),
+        server_name.cyan(),
+        package.cyan(),
+        device_id.cyan(),
+        format!("-H 127.0.0.1:{}", port).cyan()
+    );
+    Ok(())
+}
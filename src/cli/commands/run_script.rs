@@ -0,0 +1,175 @@
+use crate::agent::sourcemap::AgentSourceMaps;
+use crate::cli::commands::frida::run_frida_with_source_maps;
+use crate::cli::{ExitStatus, OutputFormat};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::interrupt;
+use crate::python::VenvExecutor;
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::process::Child;
+
+/// How often script mtimes are checked when frida cannot reload scripts itself.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Load standalone scripts into the foreground app; `top` without an agent build.
+pub async fn execute(
+    device_id: Option<String>,
+    scripts: Vec<String>,
+    watch: bool,
+    args: Vec<String>,
+    log_output: bool,
+) -> Result<ExitStatus> {
+    super::top::execute(
+        device_id,
+        None,
+        None,
+        scripts,
+        args,
+        log_output,
+        watch,
+        false,
+        false,
+        OutputFormat::Text,
+        false,
+        false,
+        false,
+        false,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Run frida with `args` and keep the loaded `scripts` live: frida's own auto-reload when
+/// the installed CLI has it, otherwise a restart of the whole session on every change.
+/// `source_maps` translate agent stack traces, except across such restarts.
+pub async fn run_watched(
+    project_dir: &Path,
+    args: &[String],
+    scripts: &[PathBuf],
+    device: Option<&str>,
+    log_output: bool,
+    source_maps: Option<&AgentSourceMaps>,
+) -> Result<i32> {
+    if args.iter().any(|arg| arg == "--no-auto-reload") {
+        return Err(FridaMgrError::Config(
+            "--watch reloads scripts on change; drop --no-auto-reload".to_string(),
+        ));
+    }
+    if scripts.is_empty() {
+        println!(
+            "{} No -l scripts to watch; running frida normally",
+            "⚠".yellow().bold()
+        );
+        return run_frida_with_source_maps(project_dir, args, device, log_output, source_maps)
+            .await;
+    }
+
+    let executor = VenvExecutor::new(project_dir.to_path_buf())
+        .with_work_dir(env::current_dir()?)
+        .with_device(device);
+    let help = executor
+        .run_captured("frida", &["--help".to_string()])
+        .await?;
+    if supports_auto_reload(&help.stdout) {
+        println!(
+            "{} Watching {} script(s); frida reloads them in place when they change",
+            "ℹ".blue().bold(),
+            scripts.len()
+        );
+        return run_frida_with_source_maps(project_dir, args, device, log_output, source_maps)
+            .await;
+    }
+
+    println!(
+        "{} This frida cannot reload scripts; restarting the session when they change",
+        "⚠".yellow().bold()
+    );
+    if log_output {
+        println!(
+            "{} --log-output is not applied while restarting on change",
+            "⚠".yellow().bold()
+        );
+    }
+    restart_on_change(&executor, args, scripts).await
+}
+
+async fn restart_on_change(
+    executor: &VenvExecutor,
+    args: &[String],
+    scripts: &[PathBuf],
+) -> Result<i32> {
+    let mut stamps = modified_times(scripts).await;
+    let mut child: Option<Child> = Some(executor.spawn_detached_stdin("frida", args)?);
+
+    loop {
+        let exited = async {
+            match child.as_mut() {
+                Some(c) => c.wait().await.ok(),
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            status = exited => {
+                child = None;
+                let code = status.and_then(|s| s.code()).unwrap_or(1);
+                println!(
+                    "{} frida exited ({}); waiting for a script change (Ctrl-C to quit)",
+                    "⚠".yellow().bold(),
+                    code
+                );
+            }
+            _ = tokio::time::sleep(POLL_INTERVAL) => {
+                let current = modified_times(scripts).await;
+                if current == stamps {
+                    continue;
+                }
+                stamps = current;
+                println!("{} Script changed, restarting frida...", "→".blue().bold());
+                if let Some(mut running) = child.take() {
+                    let _ = running.kill().await;
+                }
+                child = Some(executor.spawn_detached_stdin("frida", args)?);
+            }
+            _ = interrupt::ctrl_c() => {
+                if let Some(mut running) = child.take() {
+                    let _ = running.kill().await;
+                }
+                return Ok(0);
+            }
+        }
+    }
+}
+
+async fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    let mut times = Vec::with_capacity(paths.len());
+    for path in paths {
+        let modified = tokio::fs::metadata(path)
+            .await
+            .and_then(|meta| meta.modified())
+            .ok();
+        times.push(modified);
+    }
+    times
+}
+
+/// Whether `frida --help` lists the auto-reload switch (frida-tools watches `-l` files
+/// and reloads them by default when it does).
+fn supports_auto_reload(help: &str) -> bool {
+    help.contains("--no-auto-reload")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_auto_reload_support() {
+        let help = "  -l SCRIPT, --load SCRIPT\n  --auto-reload         Enable auto reload of provided scripts and c module (on by default)\n  --no-auto-reload      Disable auto reload\n";
+        assert!(supports_auto_reload(help));
+        assert!(!supports_auto_reload("  -l SCRIPT, --load SCRIPT\n"));
+    }
+}
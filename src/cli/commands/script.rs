@@ -1,17 +1,247 @@
-use crate::core::resolve_path;
-use std::path::Path;
+use crate::agent::AgentProject;
+use crate::bundle::project_relative;
+use crate::config::{ProjectConfigManager, ScriptsConfig};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{ensure_dir_exists, resolve_path};
+use colored::Colorize;
+use std::env;
+use std::path::{Path, PathBuf};
 
-pub fn resolve_existing_script_path(current_dir: &Path, project_dir: &Path, raw: &str) -> String {
-    let p1 = resolve_path(current_dir, raw);
-    if p1.exists() {
-        return p1.to_string_lossy().to_string();
+/// Extensions tried for extension-less script names (`-l unpin`).
+const SCRIPT_EXTENSIONS: &[&str] = &["js", "ts"];
+
+/// Resolve a `-l` argument: relative to the invocation directory, then the project root,
+/// then a `scripts.registry` name, then each `scripts.paths` entry, inferring `.js`/`.ts`
+/// for bare names. Unresolved arguments are passed through for frida to report.
+pub fn resolve_existing_script_path(
+    current_dir: &Path,
+    project_dir: &Path,
+    scripts: Option<&ScriptsConfig>,
+    raw: &str,
+) -> String {
+    for base in [current_dir, project_dir] {
+        if let Some(found) = find_script(base, raw) {
+            return found.to_string_lossy().to_string();
+        }
+    }
+
+    let Some(scripts) = scripts else {
+        return raw.to_string();
+    };
+
+    if let Some(registered) = scripts.registry.get(raw) {
+        let path = resolve_path(project_dir, registered);
+        if path.is_file() {
+            return path.to_string_lossy().to_string();
+        }
     }
 
-    let p2 = resolve_path(project_dir, raw);
-    if p2.exists() {
-        return p2.to_string_lossy().to_string();
+    for search_path in &scripts.paths {
+        if let Some(found) = find_script(&resolve_path(project_dir, search_path), raw) {
+            return found.to_string_lossy().to_string();
+        }
     }
 
     raw.to_string()
 }
 
+/// The `[scripts]` section of the project's frida.toml; `None` outside a project.
+pub async fn project_scripts(project_dir: &Path) -> Option<ScriptsConfig> {
+    let project_mgr = ProjectConfigManager::new(project_dir);
+    if !project_mgr.config_path().exists() {
+        return None;
+    }
+    project_mgr.load().await.ok().map(|config| config.scripts)
+}
+
+/// Scaffold a standalone hook script in the first `scripts.paths` directory and register
+/// it under `[scripts.registry]`, so `-l <name>` finds it without an agent build.
+pub async fn new_script(name: String, js: bool, force: bool) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with(['.', '-'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+    if !valid {
+        return Err(FridaMgrError::Config(format!(
+            "Invalid script name '{}' (use letters, digits, '_', '-' and '.')",
+            name
+        )));
+    }
+
+    let current_dir = env::current_dir()?;
+    let project_dir = ProjectConfigManager::find_project_root(&current_dir)
+        .ok_or(FridaMgrError::NotInitialized)?;
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    let mut config = project_mgr.load().await?;
+
+    if config.scripts.registry.contains_key(&name) && !force {
+        return Err(FridaMgrError::Config(format!(
+            "Script '{}' is already registered (use --force to overwrite it)",
+            name
+        )));
+    }
+
+    let dir = config
+        .scripts
+        .paths
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "scripts".to_string());
+    let ext = if js { "js" } else { "ts" };
+    let relative = format!("{}/{}.{}", dir.trim_end_matches('/'), name, ext);
+    let path = resolve_path(&project_dir, &relative);
+    if path.exists() && !force {
+        return Err(FridaMgrError::Config(format!(
+            "{} already exists (use --force to overwrite it)",
+            path.display()
+        )));
+    }
+
+    let agent = AgentProject::from_agent_config(project_dir.clone(), &config.agent);
+    let reference = gum_types_reference(&project_dir, &relative, &agent.agent_dir);
+    if let Some(parent) = path.parent() {
+        ensure_dir_exists(parent).await?;
+    }
+    tokio::fs::write(&path, template_script(&name, &reference, js)).await?;
+
+    config
+        .scripts
+        .registry
+        .insert(name.clone(), relative.clone());
+    project_mgr.save(&config).await?;
+
+    println!(
+        "{} Created {} and registered it as {}",
+        "✓".green().bold(),
+        relative.yellow(),
+        name.cyan()
+    );
+    println!(
+        "  Load it with {} (or spawn/run-app)",
+        format!("frida-mgr top -l {}", name).cyan()
+    );
+    Ok(())
+}
+
+/// `/// <reference ...>` line for frida-gum typings: a path to the agent's installed
+/// `@types/frida-gum` when there is one, otherwise the package name.
+fn gum_types_reference(project_dir: &Path, script_relative: &str, agent_dir: &Path) -> String {
+    let typings = agent_dir.join("node_modules/@types/frida-gum/index.d.ts");
+    let typings_relative = typings
+        .is_file()
+        .then(|| project_relative(project_dir, &typings))
+        .flatten();
+    let script_relative = project_relative(project_dir, Path::new(script_relative));
+
+    match (typings_relative, script_relative) {
+        (Some(typings), Some(script)) => {
+            let depth = script.matches('/').count();
+            format!(
+                "/// <reference path=\"{}{}\" />",
+                "../".repeat(depth),
+                typings
+            )
+        }
+        _ => "/// <reference types=\"frida-gum\" />".to_string(),
+    }
+}
+
+fn template_script(name: &str, reference: &str, js: bool) -> String {
+    let args = if js { "...args" } else { "...args: any[]" };
+    format!(
+        r#"{reference}
+// {name}: standalone hook script (frida-mgr top -l {name}).
+
+Java.perform(() => {{
+  // const Target = Java.use("com.example.Target");
+  // Target.method.implementation = function ({args}) {{
+  //   console.log("[{name}] method called", JSON.stringify(args));
+  //   return this.method(...args);
+  // }};
+  console.log("[{name}] loaded");
+}});
+"#
+    )
+}
+
+fn find_script(base: &Path, raw: &str) -> Option<PathBuf> {
+    let direct = resolve_path(base, raw);
+    if direct.is_file() {
+        return Some(direct);
+    }
+
+    if Path::new(raw).extension().is_some() {
+        return None;
+    }
+    SCRIPT_EXTENSIONS
+        .iter()
+        .map(|ext| resolve_path(base, &format!("{}.{}", raw, ext)))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typings_reference_is_relative_to_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let agent = dir.path().join("agent");
+        let typings = agent.join("node_modules/@types/frida-gum");
+        std::fs::create_dir_all(&typings).unwrap();
+
+        assert_eq!(
+            gum_types_reference(dir.path(), "scripts/unpin.ts", &agent),
+            "/// <reference types=\"frida-gum\" />"
+        );
+
+        std::fs::write(typings.join("index.d.ts"), b"").unwrap();
+        assert_eq!(
+            gum_types_reference(dir.path(), "scripts/unpin.ts", &agent),
+            "/// <reference path=\"../agent/node_modules/@types/frida-gum/index.d.ts\" />"
+        );
+    }
+
+    #[test]
+    fn resolves_names_from_search_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("engagement");
+        let shared = dir.path().join("shared-hooks");
+        std::fs::create_dir_all(project.join("scripts")).unwrap();
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(project.join("scripts").join("unpin.js"), b"").unwrap();
+        std::fs::write(shared.join("trace.ts"), b"").unwrap();
+        std::fs::write(project.join("hook.js"), b"").unwrap();
+
+        std::fs::write(project.join("one-off.ts"), b"").unwrap();
+
+        let scripts = ScriptsConfig {
+            paths: vec!["scripts".to_string(), "../shared-hooks".to_string()],
+            registry: [("quick".to_string(), "one-off.ts".to_string())].into(),
+        };
+        let paths = Some(&scripts);
+        let cwd = project.join("scripts");
+
+        assert_eq!(
+            resolve_existing_script_path(&cwd, &project, paths, "unpin"),
+            cwd.join("unpin.js").to_string_lossy()
+        );
+        assert_eq!(
+            resolve_existing_script_path(&cwd, &project, paths, "hook.js"),
+            project.join("hook.js").to_string_lossy()
+        );
+        assert_eq!(
+            resolve_existing_script_path(&cwd, &project, paths, "trace"),
+            project.join("../shared-hooks").join("trace.ts").to_string_lossy()
+        );
+        assert_eq!(
+            resolve_existing_script_path(&cwd, &project, paths, "quick"),
+            project.join("one-off.ts").to_string_lossy()
+        );
+        assert_eq!(
+            resolve_existing_script_path(&cwd, &project, paths, "missing"),
+            "missing"
+        );
+    }
+}
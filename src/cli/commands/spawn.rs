@@ -1,12 +1,24 @@
-use crate::cli::commands::foreground::{ensure_no_forbidden_args, resolve_foreground_context};
+use crate::agent::sourcemap::session_source_maps;
+use crate::android::gadget::{gadget_config, GADGET_PORT, GADGET_SPAWN_TARGET};
+use crate::android::AdbClient;
+use crate::cli::commands::foreground::{
+    device_args, ensure_no_forbidden_args, resolve_foreground_context, spawn_user_args,
+};
+use crate::cli::commands::frida::run_frida_with_source_maps;
+use crate::cli::commands::local;
+use crate::cli::commands::push::ensure_server_running;
 use crate::cli::commands::script::resolve_existing_script_path;
-use crate::config::{AgentBuildTool, ProjectConfigManager};
-use crate::core::error::Result;
-use crate::{agent, agent::AgentProject};
+use crate::cli::commands::watchdog::{run_monitored, SessionServer};
+use crate::cli::ExitStatus;
+use crate::config::{AgentBuildTool, GlobalConfigManager, ProjectConfigManager, ProjectPlatform};
+use crate::core::error::{FridaMgrError, Result};
+use crate::frida::ServerDownloader;
 use crate::python::VenvExecutor;
+use crate::{agent, agent::AgentProject};
+use colored::Colorize;
 use std::env;
 
-const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
+pub const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
     "-U",
     "--usb",
     "-D",
@@ -23,34 +35,119 @@ const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
     "--attach-frontmost",
 ];
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     device_id: Option<String>,
     agent_dir: Option<String>,
     agent_tool: Option<AgentBuildTool>,
     scripts: Vec<String>,
     args: Vec<String>,
-) -> Result<()> {
+    log_output: bool,
+    pause: Option<bool>,
+    run_as: bool,
+    server32: bool,
+    restart_server: bool,
+    ensure_server: bool,
+    auto_push: bool,
+    stop_on_exit: bool,
+    user: Option<u32>,
+) -> Result<ExitStatus> {
     ensure_no_forbidden_args(
         &args,
         FORBIDDEN_FRIDA_ARGS,
         "frida-mgr spawn selects the device and target automatically",
     )?;
 
-    let foreground = resolve_foreground_context(device_id.as_deref()).await?;
-    foreground.print_summary();
-
     let current_dir = env::current_dir()?;
     let project_dir =
         ProjectConfigManager::find_project_root(&current_dir).unwrap_or_else(|| current_dir.clone());
 
-    let mut frida_args = Vec::with_capacity(8 + scripts.len() * 2 + args.len());
-    frida_args.push("-D".to_string());
-    frida_args.push(foreground.device.id);
-    frida_args.push("-f".to_string());
-    frida_args.push(foreground.package);
+    let project_mgr = ProjectConfigManager::new(&project_dir);
+    let project_config = if project_mgr.config_path().exists() {
+        Some(project_mgr.load().await?)
+    } else {
+        None
+    };
 
+    let is_local = project_config
+        .as_ref()
+        .is_some_and(|c| c.project.platform == ProjectPlatform::Local);
+    let mut frida_args = Vec::with_capacity(9 + scripts.len() * 2 + args.len());
+    let serial = if is_local {
+        local::reject_device_options(
+            "spawn",
+            &[
+                ("--device", device_id.is_some()),
+                ("--run-as", run_as),
+                ("--server32", server32),
+                ("--restart-server", restart_server),
+                ("--ensure-server", ensure_server),
+                ("--auto-push", auto_push),
+                ("--stop-on-exit", stop_on_exit),
+                ("--user", user.is_some()),
+            ],
+        )?;
+        let executor = VenvExecutor::new(project_dir.clone()).with_work_dir(current_dir.clone());
+        let target = local::pick_target(&executor, true).await?;
+        frida_args.push("-f".to_string());
+        frida_args.push(target.identifier.unwrap_or(target.name));
+        None
+    } else {
+        let foreground = resolve_foreground_context(device_id.as_deref(), None, user).await?;
+        foreground.print_summary();
+        if let Some(user) = foreground.user.filter(|user| run_as && *user != 0) {
+            return Err(FridaMgrError::CommandFailed(format!(
+                "--run-as only reaches apps of the primary user; {} runs in user {}",
+                foreground.package, user
+            )));
+        }
+        if run_as {
+            let config = project_config
+                .as_ref()
+                .ok_or(FridaMgrError::NotInitialized)?;
+            let endpoint = launch_with_gadget(
+                &foreground.device.id,
+                &foreground.package,
+                &config.frida.version,
+            )
+            .await?;
+            frida_args.push("-H".to_string());
+            frida_args.push(endpoint);
+            frida_args.push("-f".to_string());
+            frida_args.push(GADGET_SPAWN_TARGET.to_string());
+        } else {
+            // A 32-bit companion server replaces the main one for this session.
+            if !(server32 && foreground.server32_arch().is_some()) {
+                ensure_server_running(
+                    &foreground.device.id,
+                    ensure_server || stop_on_exit,
+                    auto_push,
+                )
+                .await?;
+            }
+            frida_args.extend(device_args(&foreground, server32).await?);
+            frida_args.push("-f".to_string());
+            frida_args.push(foreground.package);
+            frida_args.extend(spawn_user_args(foreground.user));
+        }
+        Some(foreground.device.id)
+    };
+
+    let pause = pause.unwrap_or_else(|| {
+        project_config
+            .as_ref()
+            .map(|c| !c.frida.spawn_no_pause)
+            .unwrap_or(false)
+    });
+    let frida_version = project_config.as_ref().map(|c| c.frida.version.as_str());
+    if !args.iter().any(|a| a == "--pause" || a == "--no-pause") {
+        if let Some(flag) = spawn_pause_flag(frida_version, pause) {
+            frida_args.push(flag.to_string());
+        }
+    }
+
+    let mut source_maps = None;
     if let Some(dir) = agent_dir.as_deref() {
-        let project_mgr = ProjectConfigManager::new(&project_dir);
         let mut config = project_mgr.load().await?;
         config.agent.dir = dir.to_string();
         if let Some(tool) = agent_tool {
@@ -60,17 +157,106 @@ pub async fn execute(
         let out = agent::build_agent(&agent_project).await?;
         frida_args.push("-l".to_string());
         frida_args.push(out.to_string_lossy().to_string());
+        source_maps = session_source_maps(&agent_project).await;
     }
 
+    let project_scripts = project_config.map(|c| c.scripts);
     for script in scripts {
         frida_args.push("-l".to_string());
-        frida_args.push(resolve_existing_script_path(&current_dir, &project_dir, &script));
+        frida_args.push(resolve_existing_script_path(
+            &current_dir,
+            &project_dir,
+            project_scripts.as_ref(),
+            &script,
+        ));
     }
 
     frida_args.extend(args);
 
-    let executor = VenvExecutor::new(project_dir);
-    let exit_code = executor.run_interactive("frida", &frida_args).await?;
+    // Gadget (--run-as) and a --server32 companion are reached with -H and not watched.
+    let server = match serial.as_deref() {
+        Some(serial) if frida_args.first().is_some_and(|arg| arg == "-D") => {
+            SessionServer::running(serial, restart_server, stop_on_exit)
+                .await
+                .ok()
+                .flatten()
+        }
+        _ => None,
+    };
+    let exit_code = run_monitored(server, || {
+        run_frida_with_source_maps(
+            &project_dir,
+            &frida_args,
+            serial.as_deref(),
+            log_output,
+            source_maps.as_ref(),
+        )
+    })
+    .await?;
+
+    Ok(ExitStatus(exit_code))
+}
+
+/// Relaunch a debuggable app with frida-gadget injected through `run-as` (no root, no
+/// repack) and return the forwarded host endpoint Gadget listens on.
+async fn launch_with_gadget(device_id: &str, package: &str, frida_version: &str) -> Result<String> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path.clone()))
+        .with_push_attempts(global_config.network.max_retries);
 
-    std::process::exit(exit_code);
+    if !adb.is_debuggable(device_id, package).await? {
+        return Err(FridaMgrError::Adb(format!(
+            "{} is not debuggable; run-as injection needs android:debuggable=\"true\" (try `frida-mgr apk` to patch it)",
+            package
+        )));
+    }
+
+    let arch = adb.get_arch(device_id).await?;
+    let gadget =
+        ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+            .download_gadget(frida_version, &arch)
+            .await?;
+
+    let agent = adb
+        .install_run_as_gadget(device_id, package, &gadget, &gadget_config(GADGET_PORT))
+        .await?;
+    adb.forward(device_id, GADGET_PORT).await?;
+
+    println!(
+        "{} Launching {} with frida-gadget via run-as...",
+        "⚙".blue().bold(),
+        package.cyan()
+    );
+    adb.start_with_agent(device_id, package, &agent).await?;
+
+    Ok(format!("127.0.0.1:{}", GADGET_PORT))
+}
+
+/// frida flag that leaves a spawned app paused or resumed. Frida 15 made resuming the
+/// default (adding `--pause`) and dropped `--no-pause`; unknown versions are treated as current.
+pub fn spawn_pause_flag(frida_version: Option<&str>, pause: bool) -> Option<&'static str> {
+    let major = frida_version
+        .and_then(|v| v.trim().split('.').next())
+        .and_then(|m| m.parse::<u32>().ok());
+    match (major, pause) {
+        (Some(major), false) if major < 15 => Some("--no-pause"),
+        (Some(major), true) if major < 15 => None,
+        (_, true) => Some("--pause"),
+        (_, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_flag_follows_frida_version() {
+        assert_eq!(spawn_pause_flag(Some("14.2.18"), false), Some("--no-pause"));
+        assert_eq!(spawn_pause_flag(Some("14.2.18"), true), None);
+        assert_eq!(spawn_pause_flag(Some("16.6.6"), false), None);
+        assert_eq!(spawn_pause_flag(Some("16.6.6"), true), Some("--pause"));
+        assert_eq!(spawn_pause_flag(None, true), Some("--pause"));
+    }
 }
@@ -1,38 +1,179 @@
 use crate::android::AdbClient;
-use crate::config::{resolve_android_server_target, GlobalConfigManager, ProjectConfigManager};
-use crate::core::error::Result;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::run_as;
+use crate::cli::commands::watchdog::{self, WatchedServer};
+use crate::cli::{ExitStatus, OutputFormat};
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, GlobalConfigManager,
+    ProjectConfigManager,
+};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::interrupt;
 use colored::Colorize;
+use std::time::Duration;
 
-pub async fn execute(device_id: Option<String>) -> Result<()> {
+pub async fn execute(
+    device_id: Option<String>,
+    foreground: bool,
+    watch: bool,
+    output: OutputFormat,
+    stop_on_exit: bool,
+    command: Vec<String>,
+    discover: bool,
+) -> Result<ExitStatus> {
     let global_config = GlobalConfigManager::new()?.load().await?;
-    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let adb = AdbClient::new(Some(global_config.android.adb_path))
+        .with_status_on_stderr(output == OutputFormat::Jsonl);
 
-    let device = adb.get_device(device_id.as_deref()).await?;
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
 
     let config = ProjectConfigManager::from_current_dir()?.load().await?;
-    let target = resolve_android_server_target(
+    let target = resolve_device_server_target(
         &global_config.android.default_push_path,
         config.android.server_name.as_deref(),
-    )?;
+        &device.id,
+    )
+    .await?;
+    let target = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
     let remote_path = target.remote_path;
     let server_name = target.process_name;
 
+    if let Some(package) = config.android.run_as() {
+        if foreground || watch || stop_on_exit {
+            return Err(FridaMgrError::Config(
+                "--foreground, --watch and --stop-on-exit are not supported with android.mode = \"run-as\"".to_string(),
+            ));
+        }
+        run_as::start(
+            &adb,
+            &device.id,
+            package,
+            &server_name,
+            config.android.server_port,
+        )
+        .await?;
+        return Ok(ExitStatus::SUCCESS);
+    }
+
+    if foreground {
+        adb.run_server_foreground(
+            &device.id,
+            &remote_path,
+            &server_name,
+            config.android.server_port,
+            &config.android.root_command,
+        )
+        .await?;
+        return Ok(ExitStatus::SUCCESS);
+    }
+
     adb.start_server(
         &device.id,
         &remote_path,
         &server_name,
         config.android.server_port,
         &config.android.root_command,
+        config.android.selinux,
+        &config.android.log,
     )
     .await?;
 
-    println!(
+    let started = format!(
         "{} {} started on {} (port: {})",
         "✓".green().bold(),
         server_name.cyan(),
         device.id.cyan(),
         config.android.server_port.to_string().yellow()
     );
+    match output {
+        OutputFormat::Text => println!("{}", started),
+        OutputFormat::Jsonl => eprintln!("{}", started),
+    }
+
+    if config.android.auto_forward {
+        adb.forward_persistent(&device.id, config.android.server_port)
+            .await?;
+        let forwarded = format!(
+            "{} Forwarded to this machine; connect with {}",
+            "✓".green().bold(),
+            format!("-H 127.0.0.1:{}", config.android.server_port).cyan()
+        );
+        match output {
+            OutputFormat::Text => println!("{}", forwarded),
+            OutputFormat::Jsonl => eprintln!("{}", forwarded),
+        }
+    }
+
+    if watch {
+        let server = WatchedServer {
+            adb: &adb,
+            device_id: &device.id,
+            remote_path: &remote_path,
+            process_name: &server_name,
+            port: config.android.server_port,
+            root_command: &config.android.root_command,
+            run_as: config.android.run_as(),
+            selinux: config.android.selinux,
+            log: &config.android.log,
+            notifications: global_config.notifications,
+            output,
+        };
+        watchdog::supervise(
+            &server,
+            Duration::from_secs(watchdog::DEFAULT_INTERVAL_SECS),
+        )
+        .await?;
+    }
+
+    if stop_on_exit {
+        let status = match command.split_first() {
+            Some((program, args)) => {
+                super::run::execute(
+                    Some(device.id.clone()),
+                    program.clone(),
+                    args.to_vec(),
+                    discover,
+                )
+                .await
+            }
+            None => {
+                println!(
+                    "{} Press Ctrl-C to stop {} and clean up",
+                    "ℹ".blue().bold(),
+                    server_name.cyan()
+                );
+                let _ = interrupt::ctrl_c().await;
+                println!();
+                Ok(ExitStatus::SUCCESS)
+            }
+        };
+
+        // Clean up even when the wrapped command failed.
+        let stopped = adb
+            .kill_server(&device.id, &server_name, &config.android.root_command)
+            .await;
+        let log_removed = adb
+            .remove_server_log(
+                &device.id,
+                &config.android.log.path_for(&remote_path),
+                &config.android.root_command,
+            )
+            .await;
+        let status = status?;
+        stopped?;
+        log_removed?;
+        println!(
+            "{} {} stopped and its log removed",
+            "✓".green().bold(),
+            server_name.cyan()
+        );
+        return Ok(status);
+    }
 
-    Ok(())
+    Ok(ExitStatus::SUCCESS)
 }
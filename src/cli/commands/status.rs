@@ -1,14 +1,36 @@
 use crate::android::AdbClient;
-use crate::config::{resolve_android_server_target, GlobalConfigManager, ProjectConfigManager};
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::devices::print_device_info;
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, AndroidServerSource, ArchType,
+    GlobalConfigManager, ProjectConfig, ProjectConfigManager,
+};
 use crate::core::error::Result;
+use crate::frida::{ServerDistribution, ServerDownloader};
+use crate::python::VenvExecutor;
 use colored::Colorize;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// How long `frida-ps` gets to list processes through the forward.
+const FRIDA_PS_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long the raw handshake waits for the server to answer.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Whether a running frida-server actually answers on its port.
+enum Health {
+    Responding(&'static str),
+    NotResponding(String),
+}
 
 pub async fn execute(device_id: Option<String>) -> Result<()> {
     let config_result = ProjectConfigManager::from_current_dir()?.load().await;
     let global_config = GlobalConfigManager::new()?.load().await?;
     let adb = AdbClient::new(Some(global_config.android.adb_path));
 
-    let device = adb.get_device(device_id.as_deref()).await?;
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
 
     println!("{}", "Device Status:".bold());
     println!("  Device ID: {}", device.id.cyan());
@@ -18,29 +40,99 @@ pub async fn execute(device_id: Option<String>) -> Result<()> {
     // Get architecture
     let arch = adb.get_arch(&device.id).await?;
     println!("  Architecture: {}", arch.to_str().yellow());
+    let (root_command, frida_version, configured_arch) = match &config_result {
+        Ok(config) => (
+            config.android.root_command.clone(),
+            Some(config.frida.version.as_str()),
+            config.android.arch.clone(),
+        ),
+        Err(_) => (
+            ProjectConfig::default().android.root_command,
+            None,
+            ArchType::Auto,
+        ),
+    };
+    let info = adb.get_device_info(&device.id, &root_command).await?;
+    print_device_info(&info, frida_version, &configured_arch, "  ");
 
     // Check server status
     let server_name_override = config_result
         .as_ref()
         .ok()
         .and_then(|c| c.android.server_name.as_deref());
-    let target = resolve_android_server_target(
+    let target = resolve_device_server_target(
         &global_config.android.default_push_path,
         server_name_override,
-    )?;
+        &device.id,
+    )
+    .await?;
     let status = adb
         .get_server_status(&device.id, &target.process_name)
         .await?;
-    let status_colored = if status == "running" {
-        status.green()
-    } else {
-        status.red()
+    let health = match (&config_result, status.as_str()) {
+        (Ok(config), "running") => {
+            let project_dir = ProjectConfigManager::from_current_dir()?
+                .config_path()
+                .parent()
+                .unwrap_or(Path::new("."))
+                .to_path_buf();
+            Some(check_health(&adb, &device.id, config.android.server_port, &project_dir).await)
+        }
+        _ => None,
+    };
+    let status_colored = match (&health, status.as_str()) {
+        (Some(Health::NotResponding(_)), _) => "running but not responding".yellow(),
+        (_, "running") => status.green(),
+        _ => status.red(),
     };
     println!(
         "  Frida server ({}): {}",
         target.process_name.cyan(),
         status_colored
     );
+    match &health {
+        Some(Health::Responding(how)) => {
+            println!("    {}", format!("responds to {}", how).dimmed())
+        }
+        Some(Health::NotResponding(reason)) => {
+            println!("    {}", reason.yellow());
+            println!(
+                "    Try {} to restart it, or check its log on the device",
+                "frida-mgr start".cyan()
+            );
+        }
+        None => {}
+    }
+
+    // Compare the binary on the device with the project's Frida version
+    if let Ok(config) = &config_result {
+        let installed = adb
+            .locate_server(
+                &device.id,
+                android_server_candidates(
+                    target.clone(),
+                    &global_config.android.push_path_fallbacks,
+                ),
+            )
+            .await;
+        match adb
+            .server_version(&device.id, &installed.remote_path)
+            .await?
+        {
+            Some(version) if version == config.frida.version => {
+                println!("  Server version: {}", version.green())
+            }
+            Some(version) => {
+                println!(
+                    "  Server version: {} {}",
+                    version.red(),
+                    format!("(project uses {})", config.frida.version).yellow()
+                );
+                println!("    Run {} to replace it", "frida-mgr push --start".cyan());
+            }
+            None => println!("  Server version: {}", "not pushed".red()),
+        }
+    }
 
     // Show project info if available
     if let Ok(config) = config_result {
@@ -52,7 +144,87 @@ pub async fn execute(device_id: Option<String>) -> Result<()> {
             "  Server port: {}",
             config.android.server_port.to_string().yellow()
         );
+        match config.android.server.source {
+            AndroidServerSource::Local => println!("  Server source: {}", "local".yellow()),
+            AndroidServerSource::Download | AndroidServerSource::Custom => {
+                let distribution = ServerDistribution::from_config(&config.android.server);
+                println!("  Server source: {}", distribution.describe().yellow());
+                let cached = ServerDownloader::new(GlobalConfigManager::new()?.get_cache_dir())
+                    .with_distribution(distribution)
+                    .get_cached(&config.frida.version, &arch)
+                    .await;
+                match cached {
+                    Some(path) => println!("  Cached server: {}", path.display()),
+                    None => println!("  Cached server: {}", "not downloaded".red()),
+                }
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Talk to the server through a temporary forward from a free local port (leaving any
+/// forward of `port` itself alone): `frida-ps -H` from the venv when it is installed,
+/// otherwise a raw HTTP request, which frida-server answers on its listening port.
+async fn check_health(adb: &AdbClient, device_id: &str, port: u16, project_dir: &Path) -> Health {
+    let local = match free_local_port().await {
+        Ok(local) => local,
+        Err(e) => return Health::NotResponding(format!("no free local port to forward: {}", e)),
+    };
+    if let Err(e) = adb.forward_local(device_id, local, port).await {
+        return Health::NotResponding(e.to_string());
+    }
+
+    let executor = VenvExecutor::new(project_dir.to_path_buf());
+    let health = if executor.venv_exists() && executor.command_exists("frida-ps") {
+        frida_ps_health(&executor, local).await
+    } else {
+        handshake_health(local).await
+    };
+
+    let _ = adb.remove_forward(device_id, local).await;
+    health
+}
+
+async fn frida_ps_health(executor: &VenvExecutor, local: u16) -> Health {
+    let args = vec!["-H".to_string(), format!("127.0.0.1:{}", local)];
+    match tokio::time::timeout(FRIDA_PS_TIMEOUT, executor.run_captured("frida-ps", &args)).await {
+        Ok(Ok(output)) if output.exit_code == 0 => Health::Responding("frida-ps"),
+        Ok(Ok(output)) => Health::NotResponding(format!(
+            "frida-ps could not connect: {}",
+            output.stderr.trim().lines().last().unwrap_or("no output")
+        )),
+        Ok(Err(e)) => Health::NotResponding(e.to_string()),
+        Err(_) => Health::NotResponding(format!(
+            "frida-ps got no answer within {}s",
+            FRIDA_PS_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// adb accepts the local connection even when nothing listens on the device, then closes
+/// it; only a reply shows the server is there.
+async fn handshake_health(local: u16) -> Health {
+    let exchange = async {
+        let mut stream = TcpStream::connect(("127.0.0.1", local)).await?;
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut reply = [0u8; 64];
+        stream.read(&mut reply).await
+    };
+    match tokio::time::timeout(HANDSHAKE_TIMEOUT, exchange).await {
+        Ok(Ok(n)) if n > 0 => Health::Responding("a TCP handshake"),
+        Ok(Ok(_)) => Health::NotResponding("the connection was closed without a reply".to_string()),
+        Ok(Err(e)) => Health::NotResponding(format!("connecting failed: {}", e)),
+        Err(_) => {
+            Health::NotResponding(format!("no reply within {}s", HANDSHAKE_TIMEOUT.as_secs()))
+        }
+    }
+}
+
+async fn free_local_port() -> std::io::Result<u16> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    Ok(listener.local_addr()?.port())
+}
@@ -1,19 +1,35 @@
+use crate::agent::{
+    agent_dependencies, agent_package_manager, frida_pin_mismatch, install_agent_dependencies,
+    update_frida_pins, AgentProject,
+};
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::ExitStatus;
 use crate::config::{
-    AndroidServerSource, GlobalConfigManager, ProjectConfigManager, VersionMapping,
+    register_project, ArchType, GlobalConfigManager, ProjectConfig, ProjectConfigManager,
+    ProjectRegistry, VersionMapping,
 };
 use crate::core::error::{FridaMgrError, Result};
-use crate::frida::ServerDownloader;
+use crate::core::{dir_size, format_size, notify, HttpClient};
+use crate::frida::server::cache::{auto_clean, prune_unreferenced};
+use crate::frida::server::ServerCache;
+use crate::frida::{ServerDistribution, ServerDownloader};
 use crate::python::UvManager;
 use colored::Colorize;
+use std::collections::HashSet;
 use std::env;
+use std::path::{Path, PathBuf};
 
 pub async fn execute(
     update_map: bool,
     prerelease: bool,
     no_project: bool,
     recreate_venv: bool,
+    prune: bool,
+    agent_install: bool,
 ) -> Result<()> {
     let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
     let map_path = global_mgr.get_version_map_path();
 
     let version_map = if update_map {
@@ -21,7 +37,9 @@ pub async fn execute(
             "{} Refreshing version mapping from GitHub releases...",
             "⚙".blue().bold()
         );
-        let map = VersionMapping::build_from_github_releases(prerelease).await?;
+        let http = HttpClient::from_network_config(&global_config.network)?
+            .with_response_cache(global_mgr.get_http_cache_dir());
+        let map = VersionMapping::build_from_github_releases(&http, prerelease).await?;
         if map.mappings.is_empty() {
             return Err(FridaMgrError::Download(
                 "Version mapping sync produced 0 entries; refusing to overwrite mapping file"
@@ -35,6 +53,12 @@ pub async fn execute(
             map_path.display().to_string().yellow(),
             map.mappings.len().to_string().cyan()
         );
+        notify(
+            global_config.notifications,
+            "Version map synced",
+            &format!("{} Frida versions mapped", map.mappings.len()),
+        )
+        .await;
         map
     } else {
         VersionMapping::load_or_init(&map_path).await?
@@ -156,11 +180,32 @@ pub async fn execute(
         }
     }
 
-    if config.android.server.source == AndroidServerSource::Download {
-        let downloader = ServerDownloader::new(global_mgr.get_cache_dir());
-        downloader
-            .download(&resolved_frida, &config.android.arch)
-            .await?;
+    if config.downloads_android_server() {
+        let downloader =
+            ServerDownloader::with_network(global_mgr.get_cache_dir(), &global_config.network)?
+                .with_notifications(global_config.notifications)
+                .with_distribution(ServerDistribution::from_config(&config.android.server));
+        match server_arch(&config.android.arch, &global_config.android.adb_path).await {
+            Some(arch) => {
+                downloader.download(&resolved_frida, &arch).await?;
+            }
+            None => {
+                println!(
+                    "{} No device connected; android.arch = \"auto\" falls back to {}",
+                    "ℹ".blue().bold(),
+                    ArchType::Arm64.to_str().yellow()
+                );
+                downloader
+                    .download(&resolved_frida, &ArchType::Arm64)
+                    .await?;
+                print_cached_arches(
+                    &global_mgr,
+                    &ServerDistribution::from_config(&config.android.server),
+                    &resolved_frida,
+                )
+                .await?;
+            }
+        }
     }
 
     if config.frida.version != resolved_frida {
@@ -173,5 +218,425 @@ pub async fn execute(
         );
     }
 
+    if let Some(project_dir) = project_mgr.config_path().parent() {
+        sync_agent_dependencies(project_dir, &config, &resolved_frida, agent_install).await?;
+        register_project(&global_mgr.get_project_registry_path(), project_dir).await;
+    }
+    if config.downloads_android_server() {
+        auto_clean(&global_mgr, &global_config.cache).await?;
+    }
+    if prune {
+        prune_stale_artifacts(&global_mgr).await?;
+    }
+
+    Ok(())
+}
+
+/// Re-pin the agent's npm packages that follow Frida (@types/frida-gum, frida-compile) for
+/// `frida` and, with `install`, bring its node_modules up to date. Projects without an agent
+/// package.json are left alone.
+async fn sync_agent_dependencies(
+    project_dir: &Path,
+    config: &ProjectConfig,
+    frida: &str,
+    install: bool,
+) -> Result<()> {
+    let agent = AgentProject::from_config(project_dir.to_path_buf(), config);
+    if !agent.agent_dir.join("package.json").is_file() {
+        return Ok(());
+    }
+
+    for update in update_frida_pins(&agent, frida).await? {
+        println!(
+            "{} Pinned agent {} {} → {} for Frida {}",
+            "✓".green().bold(),
+            update.name,
+            update.from.dimmed(),
+            update.to.cyan(),
+            frida
+        );
+    }
+
+    if agent_dependencies(&agent)
+        .await?
+        .iter()
+        .all(|dep| dep.satisfied())
+    {
+        return Ok(());
+    }
+    if install {
+        return install_agent_dependencies(&agent).await;
+    }
+    println!(
+        "{} Agent dependencies are out of date; run {} in {} or sync with {}",
+        "ℹ".blue().bold(),
+        format!("{} install", agent_package_manager(&agent)).cyan(),
+        agent.agent_dir.display().to_string().yellow(),
+        "--agent-install".cyan()
+    );
+    Ok(())
+}
+
+/// Forget registered projects whose frida.toml is gone, then delete cached frida-server
+/// builds and release assets that no remaining project's configuration refers to.
+async fn prune_stale_artifacts(global_mgr: &GlobalConfigManager) -> Result<()> {
+    println!(
+        "{} Pruning artifacts no registered project uses...",
+        "⚙".blue().bold()
+    );
+
+    let registry_path = global_mgr.get_project_registry_path();
+    let mut registry = ProjectRegistry::load_or_default(&registry_path).await?;
+    let forgotten = registry.prune_missing();
+    if !forgotten.is_empty() {
+        registry.save(&registry_path).await?;
+    }
+    for path in &forgotten {
+        println!("  - project {} (no frida.toml)", path.dimmed());
+    }
+
+    let cache_dir = global_mgr.get_cache_dir();
+    let configs = registry.project_configs().await;
+    let referenced_servers: HashSet<(PathBuf, String)> = configs
+        .iter()
+        .map(|config| {
+            let distribution = ServerDistribution::from_config(&config.android.server);
+            (
+                distribution.servers_dir(&cache_dir),
+                config.frida.version.clone(),
+            )
+        })
+        .collect();
+    let referenced_versions: HashSet<&str> =
+        configs.iter().map(|c| c.frida.version.as_str()).collect();
+
+    let mut reclaimed = 0;
+    let mut removed = forgotten.len();
+    for server in prune_unreferenced(&cache_dir, &referenced_servers).await? {
+        let label = server.path.strip_prefix(&cache_dir).unwrap_or(&server.path);
+        println!(
+            "  - {} {}",
+            label.display(),
+            format_size(server.size_bytes).dimmed()
+        );
+        reclaimed += server.size_bytes;
+        removed += 1;
+    }
+
+    // Release assets (gadgets, wheels, devkits) are cached per Frida version.
+    let assets_dir = global_mgr.get_assets_cache_dir();
+    if assets_dir.exists() {
+        let mut versions = tokio::fs::read_dir(&assets_dir).await?;
+        while let Some(entry) = versions.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !entry.file_type().await?.is_dir() || referenced_versions.contains(name.as_str()) {
+                continue;
+            }
+            let size = dir_size(&entry.path()).await?;
+            tokio::fs::remove_dir_all(entry.path()).await?;
+            println!("  - assets/{} {}", name, format_size(size).dimmed());
+            reclaimed += size;
+            removed += 1;
+        }
+    }
+
+    if removed == 0 {
+        println!("{} Nothing to prune", "✓".green().bold());
+    } else {
+        println!(
+            "{} Pruned {} item(s), reclaimed {}",
+            "✓".green().bold(),
+            removed.to_string().cyan(),
+            format_size(reclaimed).yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Arch to cache frida-server for: the configured one, or the connected device's when
+/// `android.arch = "auto"`. `None` when it is auto and no device answers.
+async fn server_arch(configured: &ArchType, adb_path: &str) -> Option<ArchType> {
+    if *configured != ArchType::Auto {
+        return Some(configured.clone());
+    }
+
+    let adb = AdbClient::new(Some(adb_path.to_string()));
+    let device = resolve_device(&adb, None).await.ok()?;
+    let arch = adb.get_arch(&device.id).await.ok()?;
+    println!(
+        "{} Detected architecture {} on {} ({})",
+        "ℹ".blue().bold(),
+        arch.to_str().yellow(),
+        device.id.cyan(),
+        device.model
+    );
+    Some(arch)
+}
+
+async fn print_cached_arches(
+    global_mgr: &GlobalConfigManager,
+    distribution: &ServerDistribution,
+    version: &str,
+) -> Result<()> {
+    let cached: Vec<String> =
+        ServerCache::at(distribution.servers_dir(&global_mgr.get_cache_dir()))
+            .entries()
+            .await?
+            .into_iter()
+            .filter(|e| e.version == version)
+            .map(|e| e.arch)
+            .collect();
+    let (present, missing): (Vec<&ArchType>, Vec<&ArchType>) = ArchType::ANDROID
+        .iter()
+        .partition(|arch| cached.iter().any(|c| c == arch.to_str()));
+    let names = |arches: &[&ArchType]| {
+        arches
+            .iter()
+            .map(|a| a.to_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    println!(
+        "  Cached frida-server {}: {}",
+        version,
+        names(&present).green()
+    );
+    if !missing.is_empty() {
+        println!(
+            "  Missing: {} (connect the device and re-run sync, or {})",
+            names(&missing).yellow(),
+            format!("frida-mgr install {} --arch <arch>", version).cyan()
+        );
+    }
     Ok(())
 }
+
+/// One difference between frida.toml and what is actually installed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Drift {
+    subject: String,
+    expected: String,
+    actual: String,
+}
+
+impl Drift {
+    fn new(
+        subject: impl Into<String>,
+        expected: impl Into<String>,
+        actual: impl Into<String>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+}
+
+/// Compare frida.toml against the venv, the server cache and the agent's node_modules
+/// without changing anything; exits non-zero when something is out of sync.
+pub async fn check() -> Result<ExitStatus> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    let config = project_mgr.load().await?;
+    let project_dir = project_mgr
+        .config_path()
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or(env::current_dir()?);
+
+    let resolved_frida = version_map.resolve_alias(&config.frida.version);
+    let mut drifts = Vec::new();
+    let mut notes = Vec::new();
+
+    let uv_mgr = UvManager::new(project_dir.clone());
+    if !uv_mgr.venv_exists() {
+        drifts.push(Drift::new(".venv", "present", "missing"));
+    } else {
+        let installed = |package: &'static str| async {
+            uv_mgr.get_installed_version(package).await.ok().flatten()
+        };
+
+        let frida = installed("frida").await;
+        if let Some(drift) = version_drift("frida", Some(&resolved_frida), frida.as_deref()) {
+            drifts.push(drift);
+        }
+
+        let pins = [
+            (
+                "frida-tools",
+                config.frida.tools_version.clone(),
+                version_map.get_tools_version(&resolved_frida),
+            ),
+            (
+                "objection",
+                config.objection.version.clone(),
+                version_map.get_objection_version(&resolved_frida),
+            ),
+        ];
+        for (package, pinned, mapped) in pins {
+            let actual = installed(package).await;
+            if let Some(drift) = version_drift(package, pinned.as_deref(), actual.as_deref()) {
+                drifts.push(drift);
+            } else if pinned.is_none() {
+                // sync may legitimately fall back from a version-map suggestion.
+                if let Some(note) = version_drift(package, mapped.as_deref(), actual.as_deref()) {
+                    notes.push(note);
+                }
+            }
+        }
+
+        for requirement in &config.python.packages {
+            let (name, pinned) = split_requirement(requirement);
+            let actual = uv_mgr.get_installed_version(name).await.ok().flatten();
+            if let Some(drift) = version_drift(name, pinned, actual.as_deref()) {
+                drifts.push(drift);
+            }
+        }
+    }
+
+    if config.downloads_android_server() {
+        let downloader = ServerDownloader::new(global_mgr.get_cache_dir())
+            .with_distribution(ServerDistribution::from_config(&config.android.server));
+        let arch = server_arch(&config.android.arch, &global_config.android.adb_path)
+            .await
+            .unwrap_or(ArchType::Arm64);
+        if downloader
+            .get_cached(&resolved_frida, &arch)
+            .await
+            .is_none()
+        {
+            drifts.push(Drift::new(
+                format!("frida-server ({})", arch.to_str()),
+                format!("{} cached", resolved_frida),
+                "not cached",
+            ));
+        }
+    }
+
+    let agent = AgentProject::from_config(project_dir, &config);
+    for dep in agent_dependencies(&agent).await? {
+        if !dep.satisfied() {
+            drifts.push(Drift::new(
+                format!("agent {}", dep.name),
+                dep.spec.clone(),
+                dep.installed
+                    .clone()
+                    .unwrap_or_else(|| "not installed".to_string()),
+            ));
+        } else if let Some(installed) = dep.installed.as_deref() {
+            if let Some(expected) = frida_pin_mismatch(&dep.name, &resolved_frida, installed) {
+                drifts.push(Drift::new(
+                    format!("agent {}", dep.name),
+                    expected,
+                    installed,
+                ));
+            }
+        }
+    }
+
+    println!(
+        "{} Drift report for {} (Frida {})",
+        "ℹ".blue().bold(),
+        project_mgr.config_path().display().to_string().yellow(),
+        resolved_frida.cyan()
+    );
+    for note in &notes {
+        println!(
+            "  {} {}: version map suggests {}, installed {} (compatible fallback)",
+            "⚠".yellow().bold(),
+            note.subject,
+            note.expected,
+            note.actual
+        );
+    }
+
+    if drifts.is_empty() {
+        println!("{} Environment matches frida.toml", "✓".green().bold());
+        return Ok(ExitStatus::SUCCESS);
+    }
+
+    for drift in &drifts {
+        println!(
+            "  {} {}: expected {}, found {}",
+            "✗".red().bold(),
+            drift.subject,
+            drift.expected.green(),
+            drift.actual.red()
+        );
+    }
+    println!();
+    println!(
+        "{} {} item(s) out of sync; run {} to fix the environment",
+        "⚠".yellow().bold(),
+        drifts.len(),
+        "frida-mgr sync".cyan()
+    );
+    if drifts.iter().any(|d| d.subject.starts_with("agent ")) {
+        println!(
+            "  Agent dependencies: run {} in the agent directory",
+            "npm install".cyan()
+        );
+    }
+    Ok(ExitStatus(1))
+}
+
+/// `None` when `actual` satisfies `expected`; an unpinned (`None`) expectation only
+/// requires the package to be installed.
+fn version_drift(subject: &str, expected: Option<&str>, actual: Option<&str>) -> Option<Drift> {
+    match (expected, actual) {
+        (_, None) => Some(Drift::new(
+            subject,
+            expected.unwrap_or("installed"),
+            "not installed",
+        )),
+        (Some(expected), Some(actual)) if expected != actual => {
+            Some(Drift::new(subject, expected, actual))
+        }
+        _ => None,
+    }
+}
+
+/// Package name and `==` pin from a `python.packages` requirement such as `requests==2.32.3`.
+fn split_requirement(requirement: &str) -> (&str, Option<&str>) {
+    let requirement = requirement.split(';').next().unwrap_or(requirement).trim();
+    if let Some((name, version)) = requirement.split_once("==") {
+        return (name.trim(), Some(version.trim()));
+    }
+    let end = requirement
+        .find(|c: char| "<>!~=[ ".contains(c))
+        .unwrap_or(requirement.len());
+    (&requirement[..end], None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_version_drift() {
+        assert_eq!(version_drift("frida", Some("16.6.6"), Some("16.6.6")), None);
+        assert_eq!(version_drift("objection", None, Some("1.11.0")), None);
+        assert_eq!(
+            version_drift("frida", Some("16.6.6"), Some("16.5.2")),
+            Some(Drift::new("frida", "16.6.6", "16.5.2"))
+        );
+        assert_eq!(
+            version_drift("objection", None, None),
+            Some(Drift::new("objection", "installed", "not installed"))
+        );
+    }
+
+    #[test]
+    fn splits_requirements() {
+        assert_eq!(
+            split_requirement("requests==2.32.3"),
+            ("requests", Some("2.32.3"))
+        );
+        assert_eq!(split_requirement("rich>=13"), ("rich", None));
+        assert_eq!(split_requirement("lief"), ("lief", None));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::android::manifest::{
+    parse_dumpsys_components, parse_manifest, Component, ComponentKind,
+};
+use crate::android::AdbClient;
+use crate::cli::commands::decompile::DECOMPILED_DIR;
+use crate::cli::commands::device::resolve_device;
+use crate::config::{GlobalConfigManager, ProjectConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::env;
+
+/// List `package`'s activities, services and receivers with their intent filters and the
+/// `am` command that launches each. Reads the manifest `decompile` wrote when there is one
+/// (which knows `exported`), otherwise the resolver tables of `dumpsys package`.
+pub async fn execute(package: String, device_id: Option<String>, all: bool) -> Result<()> {
+    let (components, source) = package_components(&package, device_id.as_deref()).await?;
+    let components: Vec<&Component> = components
+        .iter()
+        .filter(|c| c.kind != ComponentKind::Provider && (all || c.exported))
+        .collect();
+    if components.is_empty() {
+        println!(
+            "{} No {}components with intent filters found in {}",
+            "ℹ".blue().bold(),
+            if all { "" } else { "exported " },
+            source.yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} component(s) of {} (from {})",
+        "ℹ".blue().bold(),
+        components.len(),
+        package.cyan(),
+        source.yellow()
+    );
+    for component in components {
+        println!();
+        println!(
+            "{} {}{}",
+            format!("{:<8}", component.kind.as_str()).yellow(),
+            component.component_name(&package).cyan(),
+            if component.is_launcher() {
+                " (launcher)".green().to_string()
+            } else if !component.exported {
+                " (not exported)".red().to_string()
+            } else {
+                String::new()
+            }
+        );
+        for filter in &component.filters {
+            let mut line = filter.actions.join(", ");
+            if !filter.categories.is_empty() {
+                line.push_str(&format!(" [{}]", filter.categories.join(", ")));
+            }
+            if !line.is_empty() {
+                println!("  {}", line);
+            }
+        }
+        if let Some(command) = component.am_command(&package) {
+            println!("  {} {}", "→".blue(), command);
+        }
+    }
+
+    Ok(())
+}
+
+/// Every component of `package` and where they were read from: the manifest `decompile`
+/// wrote when there is one, otherwise `dumpsys package` on the device.
+pub async fn package_components(
+    package: &str,
+    device_id: Option<&str>,
+) -> Result<(Vec<Component>, String)> {
+    let current_dir = env::current_dir()?;
+    let project_dir =
+        ProjectConfigManager::find_project_root(&current_dir).unwrap_or(current_dir.clone());
+    let manifest_path = project_dir
+        .join(DECOMPILED_DIR)
+        .join(package)
+        .join("resources")
+        .join("AndroidManifest.xml");
+
+    if manifest_path.is_file() {
+        let manifest = parse_manifest(&tokio::fs::read_to_string(&manifest_path).await?)?;
+        return Ok((manifest.components, manifest_path.display().to_string()));
+    }
+
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let device = resolve_device(&adb, device_id).await?;
+    let dumpsys = adb.dumpsys_package(&device.id, package).await?;
+    if !dumpsys.contains(&format!("Package [{}]", package)) {
+        return Err(FridaMgrError::Adb(format!(
+            "Package {} is not installed on {}",
+            package, device.id
+        )));
+    }
+    Ok((
+        parse_dumpsys_components(&dumpsys, package),
+        format!("dumpsys package on {}", device.id),
+    ))
+}
@@ -0,0 +1,98 @@
+use crate::android::network::DEFAULT_TCPIP_PORT;
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::config::{DeviceAliases, GlobalConfigManager};
+use crate::core::error::{FridaMgrError, Result};
+use colored::Colorize;
+use std::time::Duration;
+
+const CONNECT_ATTEMPTS: u32 = 5;
+
+pub async fn execute(
+    device_id: Option<String>,
+    port: Option<u16>,
+    alias: Option<String>,
+) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let global_config = global_mgr.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+    let port = port.unwrap_or(DEFAULT_TCPIP_PORT);
+
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    // Read the address while still on USB; adbd restarts in TCP mode below.
+    let ip = adb.wifi_ip(&device.id).await?.ok_or_else(|| {
+        FridaMgrError::Adb(format!(
+            "{} has no Wi-Fi IPv4 address; connect it to the same network first",
+            device.id
+        ))
+    })?;
+    let endpoint = format!("{}:{}", ip, port);
+
+    eprintln!(
+        "{} adb on {} will be reachable from anyone on this network and its traffic is unencrypted.",
+        "⚠".yellow().bold(),
+        endpoint.yellow()
+    );
+    eprintln!(
+        "  Only use this on trusted networks; run {} or reboot the device to turn it off.",
+        format!("adb -s {} usb", endpoint).cyan()
+    );
+
+    println!(
+        "{} Switching {} to TCP/IP mode on port {}...",
+        "⚙".blue().bold(),
+        device.id.cyan(),
+        port.to_string().cyan()
+    );
+    adb.tcpip(&device.id, port).await?;
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        match adb.connect(&endpoint).await {
+            Ok(()) => break,
+            Err(e) if attempt >= CONNECT_ATTEMPTS => return Err(e),
+            Err(_) => continue,
+        }
+    }
+    println!("{} Connected to {}", "✓".green().bold(), endpoint.cyan());
+
+    let alias = alias.unwrap_or_else(|| default_alias(&device.model, &ip));
+    let aliases_path = global_mgr.get_device_aliases_path();
+    let mut aliases = DeviceAliases::load_or_default(&aliases_path).await?;
+    if aliases.set(&alias, &endpoint) {
+        aliases.save(&aliases_path).await?;
+    }
+
+    println!(
+        "  Saved alias {} → {}; use {} with any device command",
+        alias.cyan(),
+        endpoint.yellow(),
+        format!("-d {}", alias).cyan()
+    );
+
+    Ok(())
+}
+
+/// `<model>-wifi`, falling back to the IP for devices that report no model.
+fn default_alias(model: &str, ip: &str) -> String {
+    let model: String = model
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let model = model.trim_matches('-');
+
+    if model.is_empty() || model == "unknown" {
+        ip.to_string()
+    } else {
+        format!("{}-wifi", model)
+    }
+}
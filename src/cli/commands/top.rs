@@ -1,10 +1,30 @@
-use crate::cli::commands::foreground::{ensure_no_forbidden_args, resolve_foreground_context};
-use crate::cli::commands::script::resolve_existing_script_path;
-use crate::config::{AgentBuildTool, ProjectConfigManager};
+use crate::agent::sourcemap::session_source_maps;
+use crate::android::foreground::preferred;
+use crate::android::AdbClient;
+use crate::cli::commands::device::resolve_device;
+use crate::cli::commands::foreground::{
+    device_args, ensure_no_forbidden_args, in_user, resolve_foreground_context,
+};
+use crate::cli::commands::frida::run_frida_with_source_maps;
+use crate::cli::commands::local;
+use crate::cli::commands::push::ensure_server_running;
+use crate::cli::commands::run_script::run_watched;
+use crate::cli::commands::script::{project_scripts, resolve_existing_script_path};
+use crate::cli::commands::watchdog::{run_monitored, SessionServer};
+use crate::cli::events::StateEvent;
+use crate::cli::{ExitStatus, OutputFormat};
+use crate::config::{AgentBuildTool, GlobalConfigManager, ProjectConfigManager};
 use crate::core::error::Result;
-use crate::{agent, agent::AgentProject};
+use crate::core::interrupt;
 use crate::python::VenvExecutor;
+use crate::{agent, agent::AgentProject};
+use colored::Colorize;
 use std::env;
+use std::time::Duration;
+use tokio::process::Child;
+
+/// How often `--follow` checks which app is in the foreground.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
 
 const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
     "-U",
@@ -23,37 +43,93 @@ const FORBIDDEN_FRIDA_ARGS: &[&str] = &[
     "--attach-frontmost",
 ];
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     device_id: Option<String>,
     agent_dir: Option<String>,
     agent_tool: Option<AgentBuildTool>,
     scripts: Vec<String>,
     args: Vec<String>,
-) -> Result<()> {
+    log_output: bool,
+    watch: bool,
+    server32: bool,
+    follow: bool,
+    output: OutputFormat,
+    restart_server: bool,
+    ensure_server: bool,
+    auto_push: bool,
+    stop_on_exit: bool,
+    prefer: Option<String>,
+    user: Option<u32>,
+) -> Result<ExitStatus> {
     ensure_no_forbidden_args(
         &args,
         FORBIDDEN_FRIDA_ARGS,
         "frida-mgr top selects the device and target automatically",
     )?;
 
-    let foreground = resolve_foreground_context(device_id.as_deref()).await?;
-    foreground.print_summary();
-
     let current_dir = env::current_dir()?;
     let project_dir =
         ProjectConfigManager::find_project_root(&current_dir).unwrap_or_else(|| current_dir.clone());
 
     let mut frida_args = Vec::with_capacity(8 + scripts.len() * 2 + args.len());
-    frida_args.push("-D".to_string());
-    frida_args.push(foreground.device.id);
-    if let Some(pid) = foreground.pid {
-        frida_args.push("-p".to_string());
-        frida_args.push(pid.to_string());
+    let serial = if local::is_local_project(&project_dir).await? {
+        local::reject_device_options(
+            "top",
+            &[
+                ("--device", device_id.is_some()),
+                ("--server32", server32),
+                ("--follow", follow),
+                ("--restart-server", restart_server),
+                ("--ensure-server", ensure_server),
+                ("--auto-push", auto_push),
+                ("--stop-on-exit", stop_on_exit),
+                ("--prefer", prefer.is_some()),
+                ("--user", user.is_some()),
+            ],
+        )?;
+        let executor = VenvExecutor::new(project_dir.clone()).with_work_dir(current_dir.clone());
+        let target = local::pick_target(&executor, false).await?;
+        if let Some(pid) = target.pid {
+            frida_args.push("-p".to_string());
+            frida_args.push(pid.to_string());
+        } else {
+            frida_args.push("-n".to_string());
+            frida_args.push(target.name);
+        }
+        None
+    } else if follow {
+        // The foreground is picked on every poll; a split-screen picker here would be moot.
+        let global_config = GlobalConfigManager::new()?.load().await?;
+        let adb = AdbClient::new(Some(global_config.android.adb_path));
+        let device = resolve_device(&adb, device_id.as_deref()).await?;
+        ensure_server_running(&device.id, ensure_server || stop_on_exit, auto_push).await?;
+        Some(device.id)
     } else {
-        frida_args.push("-n".to_string());
-        frida_args.push(foreground.process);
-    }
+        let foreground =
+            resolve_foreground_context(device_id.as_deref(), prefer.as_deref(), user).await?;
+        // A 32-bit companion server replaces the main one for this session.
+        if !(server32 && foreground.server32_arch().is_some()) {
+            ensure_server_running(
+                &foreground.device.id,
+                ensure_server || stop_on_exit,
+                auto_push,
+            )
+            .await?;
+        }
+        foreground.print_summary();
+        frida_args.extend(device_args(&foreground, server32).await?);
+        if let Some(pid) = foreground.pid {
+            frida_args.push("-p".to_string());
+            frida_args.push(pid.to_string());
+        } else {
+            frida_args.push("-n".to_string());
+            frida_args.push(foreground.process);
+        }
+        Some(foreground.device.id)
+    };
 
+    let mut source_maps = None;
     if let Some(dir) = agent_dir.as_deref() {
         let project_mgr = ProjectConfigManager::new(&project_dir);
         let mut config = project_mgr.load().await?;
@@ -65,17 +141,170 @@ pub async fn execute(
         let out = agent::build_agent(&agent_project).await?;
         frida_args.push("-l".to_string());
         frida_args.push(out.to_string_lossy().to_string());
+        source_maps = session_source_maps(&agent_project).await;
     }
 
+    let project_scripts = project_scripts(&project_dir).await;
+    let mut watched = Vec::with_capacity(scripts.len());
     for script in scripts {
+        let path = resolve_existing_script_path(
+            &current_dir,
+            &project_dir,
+            project_scripts.as_ref(),
+            &script,
+        );
+        watched.push(current_dir.join(&path));
         frida_args.push("-l".to_string());
-        frida_args.push(resolve_existing_script_path(&current_dir, &project_dir, &script));
+        frida_args.push(path);
     }
 
     frida_args.extend(args);
 
-    let executor = VenvExecutor::new(project_dir);
-    let exit_code = executor.run_interactive("frida", &frida_args).await?;
+    if let Some(serial) = serial.as_deref().filter(|_| follow) {
+        if log_output {
+            eprintln!(
+                "{} --log-output is not applied while following the foreground app",
+                "⚠".yellow().bold()
+            );
+        }
+        let executor = VenvExecutor::new(project_dir)
+            .with_work_dir(current_dir)
+            .with_device(Some(serial))
+            .with_detached_stdout_to_stderr(output == OutputFormat::Jsonl);
+        let result = follow_foreground(
+            &executor,
+            serial,
+            &frida_args,
+            prefer.as_deref(),
+            user,
+            output,
+        )
+        .await;
+        return match SessionServer::running(serial, false, stop_on_exit).await {
+            Ok(Some(server)) => server.finish(result).await,
+            _ => result,
+        };
+    }
+
+    // Only the main frida-server is watched; a --server32 companion is reached with -H.
+    let server = match serial.as_deref() {
+        Some(serial) if frida_args.first().is_some_and(|arg| arg == "-D") => {
+            SessionServer::running(serial, restart_server, stop_on_exit)
+                .await
+                .ok()
+                .flatten()
+        }
+        _ => None,
+    };
+    let exit_code = run_monitored(server, || async {
+        if watch {
+            run_watched(
+                &project_dir,
+                &frida_args,
+                &watched,
+                serial.as_deref(),
+                log_output,
+                source_maps.as_ref(),
+            )
+            .await
+        } else {
+            run_frida_with_source_maps(
+                &project_dir,
+                &frida_args,
+                serial.as_deref(),
+                log_output,
+                source_maps.as_ref(),
+            )
+            .await
+        }
+    })
+    .await?;
+
+    Ok(ExitStatus(exit_code))
+}
+
+/// Attach frida to whatever app is in the foreground, re-attaching each time it changes,
+/// until Ctrl-C. `load_args` are the frida arguments after the target selection. In
+/// split-screen the focused app is followed, or the one matching `prefer` while visible;
+/// with `user` only apps in that Android user are.
+async fn follow_foreground(
+    executor: &VenvExecutor,
+    serial: &str,
+    load_args: &[String],
+    prefer: Option<&str>,
+    user: Option<u32>,
+    output: OutputFormat,
+) -> Result<ExitStatus> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path));
+
+    let mut current: Option<(String, Option<u32>)> = None;
+    let mut child: Option<Child> = None;
+    loop {
+        if let Some(running) = child.as_mut() {
+            if let Ok(Some(status)) = running.try_wait() {
+                child = None;
+                eprintln!(
+                    "{} frida exited ({}); waiting for the foreground app to change",
+                    "⚠".yellow().bold(),
+                    status.code().unwrap_or(1)
+                );
+            }
+        }
+
+        // The foreground can be briefly unreadable (lock screen, app switching); retry.
+        let mut apps = adb
+            .get_foreground_apps(serial)
+            .await
+            .map(|apps| match user {
+                Some(user) => in_user(&apps, user),
+                None => apps,
+            })
+            .unwrap_or_default();
+        if !apps.is_empty() {
+            let index = prefer
+                .and_then(|prefer| preferred(&apps, prefer).first().copied())
+                .and_then(|app| apps.iter().position(|a| a == app))
+                .unwrap_or(0);
+            let app = apps.swap_remove(index);
+            let target = (app.package.clone(), app.pid);
+            if current.as_ref() != Some(&target) {
+                if let Some(mut running) = child.take() {
+                    let _ = running.kill().await;
+                }
+                StateEvent::ForegroundChanged {
+                    device: serial.to_string(),
+                    package: app.package,
+                    process: app.process.clone(),
+                    pid: app.pid,
+                }
+                .report(output);
+                let args = follow_args(serial, app.pid, &app.process, load_args);
+                child = Some(executor.spawn_detached_stdin("frida", &args)?);
+                current = Some(target);
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(FOLLOW_INTERVAL) => {}
+            _ = interrupt::ctrl_c() => {
+                if let Some(mut running) = child.take() {
+                    let _ = running.kill().await;
+                }
+                return Ok(ExitStatus::SUCCESS);
+            }
+        }
+    }
+}
 
-    std::process::exit(exit_code);
+fn follow_args(serial: &str, pid: Option<u32>, process: &str, load_args: &[String]) -> Vec<String> {
+    let target = match pid {
+        Some(pid) => ["-p".to_string(), pid.to_string()],
+        None => ["-n".to_string(), process.to_string()],
+    };
+    ["-D".to_string(), serial.to_string()]
+        .into_iter()
+        .chain(target)
+        .chain(load_args.iter().cloned())
+        .collect()
 }
@@ -0,0 +1,62 @@
+use crate::config::{GlobalConfigManager, ProjectConfigManager, VersionMapping};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::format_size;
+use crate::frida::server::cache::{server_cache_dirs, ServerCache};
+use colored::Colorize;
+
+/// Delete the cached frida-server builds of `version` (only the `arch` directory when
+/// given), upstream and from forks. The version the current project uses is kept unless
+/// `force` is set.
+pub async fn execute(version: String, arch: Option<String>, force: bool) -> Result<()> {
+    let global_mgr = GlobalConfigManager::new()?;
+    let version_map = VersionMapping::load_or_init(&global_mgr.get_version_map_path()).await?;
+    let version = version_map.resolve_alias(&version);
+
+    let project_mgr = ProjectConfigManager::from_current_dir()?;
+    if !force && project_mgr.exists() && project_mgr.load().await?.frida.version == version {
+        return Err(FridaMgrError::Config(format!(
+            "frida {} is the version {} uses; switch with `frida-mgr install <version>` first or pass --force",
+            version,
+            project_mgr.config_path().display()
+        )));
+    }
+
+    let cache_dir = global_mgr.get_cache_dir();
+    let mut removed = 0;
+    let mut reclaimed = 0;
+    for servers_dir in server_cache_dirs(&cache_dir).await? {
+        let cache = ServerCache::at(servers_dir);
+        for entry in cache.entries().await? {
+            if entry.version != version || arch.as_ref().is_some_and(|arch| *arch != entry.arch) {
+                continue;
+            }
+            cache.remove(&entry).await?;
+            println!(
+                "  {} {}",
+                "✗".red(),
+                entry.path.display().to_string().dimmed()
+            );
+            removed += 1;
+            reclaimed += entry.size_bytes;
+        }
+    }
+
+    let target = match &arch {
+        Some(arch) => format!("{} for {}", version, arch),
+        None => version.clone(),
+    };
+    if removed == 0 {
+        return Err(FridaMgrError::FileNotFound(format!(
+            "no cached frida-server {} (see `frida-mgr cache list`)",
+            target
+        )));
+    }
+    println!(
+        "{} Uninstalled frida-server {} ({} build(s), reclaimed {})",
+        "✓".green().bold(),
+        target.cyan(),
+        removed,
+        format_size(reclaimed).yellow()
+    );
+    Ok(())
+}
@@ -0,0 +1,580 @@
+use crate::android::{shell, AdbClient};
+use crate::cli::commands::device::resolve_device;
+use crate::cli::events::StateEvent;
+use crate::cli::OutputFormat;
+use crate::config::{
+    android_server_candidates, resolve_device_server_target, AndroidServerTarget,
+    GlobalConfigManager, ProjectConfig, ProjectConfigManager, SelinuxPolicy, ServerLogConfig,
+    ServerNames,
+};
+use crate::core::error::Result;
+use crate::core::interrupt::CleanupGuard;
+use crate::core::{interrupt, notify};
+use colored::Colorize;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Default seconds between liveness checks.
+pub const DEFAULT_INTERVAL_SECS: u64 = 5;
+const MAX_RESTART_DELAY_SECS: u64 = 60;
+/// How often a `top`/`spawn` session checks that its frida-server is still alive.
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Everything needed to check on and restart one frida-server.
+pub struct WatchedServer<'a> {
+    pub adb: &'a AdbClient,
+    pub device_id: &'a str,
+    pub remote_path: &'a str,
+    pub process_name: &'a str,
+    pub port: u16,
+    pub root_command: &'a str,
+    /// `android.mode = "run-as"`: the app the server runs as instead of root.
+    pub run_as: Option<&'a str>,
+    pub selinux: SelinuxPolicy,
+    pub log: &'a ServerLogConfig,
+    pub notifications: bool,
+    pub output: OutputFormat,
+}
+
+pub async fn execute(
+    device_id: Option<String>,
+    interval: Option<u64>,
+    output: OutputFormat,
+) -> Result<()> {
+    let global_config = GlobalConfigManager::new()?.load().await?;
+    let adb = AdbClient::new(Some(global_config.android.adb_path))
+        .with_status_on_stderr(output == OutputFormat::Jsonl);
+
+    let device = resolve_device(&adb, device_id.as_deref()).await?;
+
+    let config = ProjectConfigManager::from_current_dir()?.load().await?;
+    let target = resolve_device_server_target(
+        &global_config.android.default_push_path,
+        config.android.server_name.as_deref(),
+        &device.id,
+    )
+    .await?;
+    let target = adb
+        .locate_server(
+            &device.id,
+            android_server_candidates(target, &global_config.android.push_path_fallbacks),
+        )
+        .await;
+
+    let server = WatchedServer {
+        adb: &adb,
+        device_id: &device.id,
+        remote_path: &target.remote_path,
+        process_name: &target.process_name,
+        port: config.android.server_port,
+        root_command: &config.android.root_command,
+        run_as: config.android.run_as(),
+        selinux: config.android.selinux,
+        log: &config.android.log,
+        notifications: global_config.notifications,
+        output,
+    };
+
+    let running = adb
+        .check_server_running(&device.id, &target.process_name)
+        .await
+        .unwrap_or(false);
+    if !running {
+        server.start().await?;
+    }
+
+    supervise(
+        &server,
+        Duration::from_secs(interval.unwrap_or(DEFAULT_INTERVAL_SECS)),
+    )
+    .await
+}
+
+impl WatchedServer<'_> {
+    pub async fn start(&self) -> Result<()> {
+        if let Some(package) = self.run_as {
+            self.adb
+                .start_run_as_server(self.device_id, package, self.process_name, self.port)
+                .await?;
+            return self.adb.forward_persistent(self.device_id, self.port).await;
+        }
+        self.adb
+            .start_server(
+                self.device_id,
+                self.remote_path,
+                self.process_name,
+                self.port,
+                self.root_command,
+                self.selinux,
+                self.log,
+            )
+            .await
+    }
+
+    pub async fn is_running(&self) -> bool {
+        self.adb
+            .check_server_running(self.device_id, self.process_name)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Informational lines stay off stdout when it carries jsonl events.
+    fn info(&self, line: String) {
+        match self.output {
+            OutputFormat::Text => println!("{}", line),
+            OutputFormat::Jsonl => eprintln!("{}", line),
+        }
+    }
+
+    fn event(&self, event: fn(String, String) -> StateEvent) {
+        event(self.device_id.to_string(), self.process_name.to_string()).report(self.output);
+    }
+}
+
+/// Poll the server every `interval` and restart it (with exponential backoff) whenever it
+/// dies, until Ctrl-C.
+pub async fn supervise(server: &WatchedServer<'_>, interval: Duration) -> Result<()> {
+    server.info(format!(
+        "{} Watching {} on {} every {}s; press Ctrl-C to stop watching",
+        "ℹ".blue().bold(),
+        server.process_name.cyan(),
+        server.device_id.cyan(),
+        interval.as_secs()
+    ));
+
+    loop {
+        if !sleep_or_interrupt(server, interval).await {
+            break;
+        }
+        if server.is_running().await {
+            continue;
+        }
+
+        server.event(|device, process| StateEvent::ServerDied { device, process });
+        notify(
+            server.notifications,
+            "frida-server died",
+            &format!(
+                "{} on {}; restarting",
+                server.process_name, server.device_id
+            ),
+        )
+        .await;
+
+        let mut failures = 0;
+        loop {
+            match server.start().await {
+                Ok(()) => {
+                    server.event(|device, process| StateEvent::ServerRestarted { device, process });
+                    notify(
+                        server.notifications,
+                        "frida-server restarted",
+                        &format!("{} on {}", server.process_name, server.device_id),
+                    )
+                    .await;
+                    break;
+                }
+                Err(e) => {
+                    failures += 1;
+                    let delay = restart_delay(failures);
+                    StateEvent::ServerRestartFailed {
+                        device: server.device_id.to_string(),
+                        process: server.process_name.to_string(),
+                        error: e.to_string(),
+                        retry_in_secs: delay.as_secs(),
+                    }
+                    .report(server.output);
+                    if !sleep_or_interrupt(server, delay).await {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The project's frida-server as seen from an interactive frida session on `device_id`.
+pub struct SessionServer {
+    adb: AdbClient,
+    device_id: String,
+    target: AndroidServerTarget,
+    config: ProjectConfig,
+    restart: bool,
+    /// Set with `--stop-on-exit` / `android.stop_on_exit`: what `finish` tears down.
+    stop: Option<SessionStop>,
+}
+
+/// How to take a session's server off the device once the session ends.
+struct SessionStop {
+    /// Delete the binary and its log too; only for randomized names, which are re-pushed
+    /// on every start anyway.
+    remove: bool,
+    names_path: PathBuf,
+    /// Does the same if frida-mgr is interrupted outside the frida session.
+    _interrupt: CleanupGuard,
+}
+
+#[derive(Default)]
+struct Outage {
+    down: bool,
+    restarted: bool,
+}
+
+impl SessionServer {
+    /// The server to keep an eye on, or `None` when it is not running before the session
+    /// starts (frida then talks to something frida-mgr does not manage). With
+    /// `stop_on_exit` (or `android.stop_on_exit`) `finish` stops it after the session.
+    pub async fn running(
+        device_id: &str,
+        restart: bool,
+        stop_on_exit: bool,
+    ) -> Result<Option<Self>> {
+        let global_mgr = GlobalConfigManager::new()?;
+        let global_config = global_mgr.load().await?;
+        let adb = AdbClient::new(Some(global_config.android.adb_path.clone()));
+        let project_mgr = ProjectConfigManager::from_current_dir()?;
+        let config = if project_mgr.exists() {
+            project_mgr.load().await?
+        } else {
+            ProjectConfig::default()
+        };
+        let target = resolve_device_server_target(
+            &global_config.android.default_push_path,
+            config.android.server_name.as_deref(),
+            device_id,
+        )
+        .await?;
+        let target = adb
+            .locate_server(
+                device_id,
+                android_server_candidates(target, &global_config.android.push_path_fallbacks),
+            )
+            .await;
+
+        if !adb
+            .check_server_running(device_id, &target.process_name)
+            .await
+            .unwrap_or(false)
+        {
+            return Ok(None);
+        }
+
+        let stop = if stop_on_exit || config.android.stop_on_exit {
+            let names_path = global_mgr.get_server_names_path();
+            let remove = ServerNames::load_or_default(&names_path)
+                .await?
+                .get(device_id)
+                == Some(target.process_name.as_str());
+            let mut script = shell::command(["killall", &target.process_name]);
+            if remove {
+                script = format!(
+                    "{}; {}",
+                    script,
+                    shell::command(
+                        ["rm".to_string(), "-f".to_string()]
+                            .into_iter()
+                            .chain(session_files(&target, &config))
+                    )
+                );
+            }
+            let script = match config.android.run_as() {
+                Some(package) => shell::command(["run-as", package, "sh", "-c", &script]),
+                None => {
+                    let root_command = adb
+                        .root_command_for(device_id, &config.android.root_command)
+                        .await;
+                    shell::as_root(&root_command, &script)
+                }
+            };
+            let interrupt = interrupt::run_on_interrupt(
+                adb.adb_path(),
+                &[
+                    "-s".to_string(),
+                    device_id.to_string(),
+                    "shell".to_string(),
+                    script,
+                ],
+            );
+            Some(SessionStop {
+                remove,
+                names_path,
+                _interrupt: interrupt,
+            })
+        } else {
+            None
+        };
+
+        Ok(Some(Self {
+            adb,
+            device_id: device_id.to_string(),
+            target,
+            config,
+            restart,
+            stop,
+        }))
+    }
+
+    /// After the session: with stop-on-exit, stop the server (and delete a randomized one),
+    /// then hand back the session's `result`.
+    pub async fn finish<T>(self, result: Result<T>) -> Result<T> {
+        let Some(stop) = &self.stop else {
+            return result;
+        };
+        let name = &self.target.process_name;
+        let root_command = &self.config.android.root_command;
+        println!(
+            "{} Stopping {} on {} (stop on exit)...",
+            "⚙".blue().bold(),
+            name.cyan(),
+            self.device_id.cyan()
+        );
+        let run_as = self.config.android.run_as();
+        let stopped = match run_as {
+            Some(package) => {
+                self.adb
+                    .kill_run_as_server(&self.device_id, package, name)
+                    .await
+            }
+            None => {
+                self.adb
+                    .kill_server(&self.device_id, name, root_command)
+                    .await
+            }
+        };
+        let removed = if stop.remove {
+            let removed = match run_as {
+                Some(package) => {
+                    self.adb
+                        .remove_run_as_server(&self.device_id, package, name)
+                        .await
+                }
+                None => {
+                    let files = session_files(&self.target, &self.config);
+                    self.adb
+                        .remove_remote_files(&self.device_id, &files, root_command)
+                        .await
+                }
+            };
+            match removed {
+                Ok(()) => forget_server_name(&stop.names_path, &self.device_id).await,
+                Err(e) => Err(e),
+            }
+        } else {
+            Ok(())
+        };
+
+        let result = result?;
+        stopped?;
+        removed?;
+        if stop.remove {
+            println!(
+                "{} {} stopped and removed from the device",
+                "✓".green().bold(),
+                self.target.remote_path.cyan()
+            );
+        } else {
+            println!("{} {} stopped", "✓".green().bold(), name.cyan());
+        }
+        Ok(result)
+    }
+
+    fn watched(&self) -> WatchedServer<'_> {
+        WatchedServer {
+            adb: &self.adb,
+            device_id: &self.device_id,
+            remote_path: &self.target.remote_path,
+            process_name: &self.target.process_name,
+            port: self.config.android.server_port,
+            root_command: &self.config.android.root_command,
+            run_as: self.config.android.run_as(),
+            selinux: self.config.android.selinux,
+            log: &self.config.android.log,
+            notifications: false,
+            output: OutputFormat::Text,
+        }
+    }
+
+    /// Note a dead server (banner once per outage) and, with `restart`, bring it back.
+    async fn handle_outage(&self, outage: &mut Outage) {
+        let server = self.watched();
+        if !outage.down {
+            outage.down = true;
+            eprintln!();
+            eprintln!(
+                "{}",
+                format!(
+                    "✗ {} died on {}; this frida session has lost its server",
+                    server.process_name, server.device_id
+                )
+                .red()
+                .bold()
+            );
+            if self.restart {
+                eprintln!("  Restarting it; frida reattaches once the current session exits");
+            } else {
+                eprintln!(
+                    "  Exit frida and run {} (or pass {} to recover automatically)",
+                    "frida-mgr start".cyan(),
+                    "--restart-server".cyan()
+                );
+            }
+        }
+        if !self.restart {
+            return;
+        }
+        match server.start().await {
+            Ok(()) => {
+                outage.down = false;
+                outage.restarted = true;
+                server.event(|device, process| StateEvent::ServerRestarted { device, process });
+            }
+            Err(e) => eprintln!(
+                "{} Restart failed ({}); retrying in {}s",
+                "✗".red().bold(),
+                e,
+                SESSION_CHECK_INTERVAL.as_secs()
+            ),
+        }
+    }
+
+    /// Poll until the session future is dropped.
+    async fn monitor(&self, outage: &mut Outage) {
+        loop {
+            tokio::time::sleep(SESSION_CHECK_INTERVAL).await;
+            if outage.down && !self.restart {
+                continue;
+            }
+            if !self.watched().is_running().await {
+                self.handle_outage(outage).await;
+            }
+        }
+    }
+}
+
+/// Run a frida session (`session` starts one and returns its exit code) while checking on
+/// `server` in the background. When the server died and was restarted meanwhile, the
+/// session is started again to reattach. Ends with `SessionServer::finish`.
+pub async fn run_monitored<F, Fut>(server: Option<SessionServer>, mut session: F) -> Result<i32>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<i32>>,
+{
+    let Some(server) = server else {
+        return session().await;
+    };
+
+    let result = monitor_sessions(&server, session).await;
+    server.finish(result).await
+}
+
+async fn monitor_sessions<F, Fut>(server: &SessionServer, mut session: F) -> Result<i32>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<i32>>,
+{
+    loop {
+        let mut outage = Outage::default();
+        let exit_code = {
+            let run = session();
+            tokio::pin!(run);
+            tokio::select! {
+                exit_code = &mut run => exit_code?,
+                _ = server.monitor(&mut outage) => unreachable!("monitor polls until cancelled"),
+            }
+        };
+
+        // frida usually exits right as the server goes away, before the next poll.
+        if !outage.down && !outage.restarted && !server.watched().is_running().await {
+            server.handle_outage(&mut outage).await;
+        }
+        if !outage.restarted {
+            return Ok(exit_code);
+        }
+        println!(
+            "{} Reattaching to {}...",
+            "→".blue().bold(),
+            server.device_id.cyan()
+        );
+    }
+}
+
+/// A session server's binary and its log, when one is written. In `run-as` mode both sit
+/// in the app's `code_cache`, relative to the data dir `run-as` starts in.
+fn session_files(target: &AndroidServerTarget, config: &ProjectConfig) -> Vec<String> {
+    if config.android.run_as().is_some() {
+        let binary = format!("code_cache/{}", target.process_name);
+        let log = format!("{}.log", binary);
+        return vec![binary, log];
+    }
+    let mut files = vec![target.remote_path.clone()];
+    files.extend(config.android.log.file_for(&target.remote_path));
+    files
+}
+
+/// The randomized server on `device_id` is gone; later commands look for the configured
+/// name again.
+async fn forget_server_name(names_path: &std::path::Path, device_id: &str) -> Result<()> {
+    let mut names = ServerNames::load_or_default(names_path).await?;
+    if names.set(device_id, None) {
+        names.save(names_path).await?;
+    }
+    Ok(())
+}
+
+/// Wait for `duration`; false when Ctrl-C arrived first.
+async fn sleep_or_interrupt(server: &WatchedServer<'_>, duration: Duration) -> bool {
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => true,
+        _ = interrupt::ctrl_c() => {
+            server.info(format!("\n{} Stopped watching (frida-server left running)", "ℹ".blue().bold()));
+            false
+        }
+    }
+}
+
+/// Backoff after `failures` consecutive failed restarts: 2s, 4s, 8s, ... capped at a minute.
+fn restart_delay(failures: u32) -> Duration {
+    Duration::from_secs((1u64 << failures.min(6)).min(MAX_RESTART_DELAY_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_backs_off_and_caps() {
+        assert_eq!(restart_delay(1), Duration::from_secs(2));
+        assert_eq!(restart_delay(3), Duration::from_secs(8));
+        assert_eq!(restart_delay(6), Duration::from_secs(60));
+        assert_eq!(restart_delay(100), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn session_files_skip_a_disabled_log() {
+        let target = AndroidServerTarget {
+            remote_path: "/data/local/tmp/1a2b3c4d".to_string(),
+            process_name: "1a2b3c4d".to_string(),
+        };
+        let mut config = ProjectConfig::default();
+        assert_eq!(
+            session_files(&target, &config),
+            ["/data/local/tmp/1a2b3c4d", "/data/local/tmp/1a2b3c4d.log"]
+        );
+        config.android.log.enabled = false;
+        assert_eq!(
+            session_files(&target, &config),
+            ["/data/local/tmp/1a2b3c4d"]
+        );
+
+        // run-as keeps both in the app's code_cache.
+        config.android.mode = crate::config::AndroidServerMode::RunAs;
+        config.android.run_as_package = Some("com.example.app".to_string());
+        assert_eq!(
+            session_files(&target, &config),
+            ["code_cache/1a2b3c4d", "code_cache/1a2b3c4d.log"]
+        );
+    }
+}
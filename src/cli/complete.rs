@@ -0,0 +1,107 @@
+use crate::android::adb::parse_devices;
+use crate::android::sdk::resolve_adb_path;
+use crate::config::{DeviceAliases, GlobalConfig, GlobalConfigManager, VersionMapping};
+use clap_complete::engine::CompletionCandidate;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Connected device serials plus saved device aliases, for `-d/--device <TAB>`.
+pub fn devices(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(global_mgr) = GlobalConfigManager::new() else {
+        return Vec::new();
+    };
+    let adb_path = resolve_adb_path(
+        &read_toml::<GlobalConfig>(global_mgr.config_path())
+            .map(|config| config.android.adb_path)
+            .unwrap_or_else(|| "adb".to_string()),
+    );
+
+    let mut candidates = Vec::new();
+    if let Some(output) = Command::new(adb_path)
+        .args(["devices", "-l"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+    {
+        for device in parse_devices(&String::from_utf8_lossy(&output.stdout)) {
+            candidates.push(CompletionCandidate::new(device.id).help(Some(device.model.into())));
+        }
+    }
+
+    if let Some(aliases) = read_toml::<DeviceAliases>(&global_mgr.get_device_aliases_path()) {
+        for (alias, endpoint) in aliases.aliases {
+            candidates.push(CompletionCandidate::new(alias).help(Some(endpoint.into())));
+        }
+    }
+
+    filter_prefix(candidates, current)
+}
+
+/// Device groups from the global config, for `--group <TAB>`.
+pub fn device_groups(current: &OsStr) -> Vec<CompletionCandidate> {
+    let candidates = GlobalConfigManager::new()
+        .ok()
+        .and_then(|mgr| read_toml::<GlobalConfig>(mgr.config_path()))
+        .map(|config| {
+            config
+                .devices
+                .groups
+                .into_iter()
+                .map(|(name, members)| {
+                    CompletionCandidate::new(name).help(Some(members.join(", ").into()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    filter_prefix(candidates, current)
+}
+
+/// Versions and aliases from the local version map, for `install <TAB>` and friends.
+pub fn versions(current: &OsStr) -> Vec<CompletionCandidate> {
+    let map = GlobalConfigManager::new()
+        .ok()
+        .and_then(|mgr| read_toml::<VersionMapping>(&mgr.get_version_map_path()))
+        .unwrap_or_else(VersionMapping::builtin);
+
+    let mut aliases: Vec<(&String, &String)> = map.aliases.iter().collect();
+    aliases.sort();
+    let mut candidates: Vec<CompletionCandidate> = aliases
+        .into_iter()
+        .map(|(alias, target)| {
+            CompletionCandidate::new(alias).help(Some(format!("→ {}", target).into()))
+        })
+        .collect();
+    for version in map.list_versions() {
+        let help = map
+            .mappings
+            .get(&version)
+            .map(|info| format!("frida-tools {}, {}", info.tools, info.released));
+        candidates.push(CompletionCandidate::new(version).help(help.map(Into::into)));
+    }
+
+    filter_prefix(candidates, current)
+}
+
+/// Completion runs synchronously before the runtime does any work, so files are read
+/// with std rather than through the async config managers.
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+fn filter_prefix(
+    candidates: Vec<CompletionCandidate>,
+    current: &OsStr,
+) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    candidates
+        .into_iter()
+        .filter(|c| {
+            c.get_value()
+                .to_string_lossy()
+                .starts_with(current.as_ref())
+        })
+        .collect()
+}
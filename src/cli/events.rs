@@ -0,0 +1,220 @@
+use crate::android::Device;
+use crate::cli::OutputFormat;
+use chrono::{SecondsFormat, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A state change reported by `devices --watch`, `top --follow` and the watchdog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StateEvent {
+    /// A device appeared, or its adb state (e.g. `unauthorized` → `device`) changed.
+    DeviceConnected {
+        device: String,
+        model: String,
+        state: String,
+    },
+    DeviceDisconnected {
+        device: String,
+    },
+    ForegroundChanged {
+        device: String,
+        package: String,
+        process: String,
+        pid: Option<u32>,
+    },
+    ServerDied {
+        device: String,
+        process: String,
+    },
+    ServerRestarted {
+        device: String,
+        process: String,
+    },
+    ServerRestartFailed {
+        device: String,
+        process: String,
+        error: String,
+        retry_in_secs: u64,
+    },
+}
+
+#[derive(Serialize)]
+struct Stamped<'a> {
+    time: &'a str,
+    #[serde(flatten)]
+    event: &'a StateEvent,
+}
+
+impl StateEvent {
+    /// A status line for people (problems on stderr), or with `--output jsonl` one JSON
+    /// object per line on stdout.
+    pub fn report(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Text if self.is_problem() => eprintln!("{}", self.text()),
+            OutputFormat::Text => println!("{}", self.text()),
+            OutputFormat::Jsonl => {
+                let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+                println!("{}", self.json_line(&now));
+            }
+        }
+    }
+
+    fn is_problem(&self) -> bool {
+        matches!(
+            self,
+            StateEvent::ServerDied { .. } | StateEvent::ServerRestartFailed { .. }
+        )
+    }
+
+    fn json_line(&self, time: &str) -> String {
+        // Only strings and integers; serializing cannot fail.
+        serde_json::to_string(&Stamped { time, event: self }).unwrap_or_default()
+    }
+
+    fn text(&self) -> String {
+        match self {
+            StateEvent::DeviceConnected {
+                device,
+                model,
+                state,
+            } => {
+                let state = if state == "device" {
+                    String::new()
+                } else {
+                    format!(" [{}]", state.yellow())
+                };
+                format!(
+                    "{} {} ({}) connected{}",
+                    "●".green(),
+                    device.cyan(),
+                    model.yellow(),
+                    state
+                )
+            }
+            StateEvent::DeviceDisconnected { device } => {
+                format!("{} {} disconnected", "○".red(), device.cyan())
+            }
+            StateEvent::ForegroundChanged {
+                package,
+                process,
+                pid,
+                ..
+            } => {
+                let pid = pid.map(|p| format!(", PID {}", p)).unwrap_or_default();
+                format!(
+                    "{} Foreground: {} ({}{})",
+                    "→".blue().bold(),
+                    package.cyan(),
+                    process.yellow(),
+                    pid
+                )
+            }
+            StateEvent::ServerDied { device, process } => format!(
+                "{} {} is no longer running on {}; restarting...",
+                "⚠".yellow().bold(),
+                process.yellow(),
+                device.cyan()
+            ),
+            StateEvent::ServerRestarted { device, process } => format!(
+                "{} {} restarted on {}",
+                "✓".green().bold(),
+                process.cyan(),
+                device.cyan()
+            ),
+            StateEvent::ServerRestartFailed {
+                error,
+                retry_in_secs,
+                ..
+            } => format!(
+                "{} Restart failed ({}); retrying in {}s",
+                "✗".red().bold(),
+                error,
+                retry_in_secs
+            ),
+        }
+    }
+}
+
+/// Connect/disconnect events turning the `previous` device list into `current`.
+pub fn device_changes(previous: &[Device], current: &[Device]) -> Vec<StateEvent> {
+    let before: HashMap<&str, &str> = previous
+        .iter()
+        .map(|d| (d.id.as_str(), d.state.as_str()))
+        .collect();
+    let after: HashMap<&str, &Device> = current.iter().map(|d| (d.id.as_str(), d)).collect();
+
+    let mut events: Vec<StateEvent> = previous
+        .iter()
+        .filter(|d| !after.contains_key(d.id.as_str()))
+        .map(|d| StateEvent::DeviceDisconnected {
+            device: d.id.clone(),
+        })
+        .collect();
+    events.extend(
+        current
+            .iter()
+            .filter(|d| before.get(d.id.as_str()) != Some(&d.state.as_str()))
+            .map(|d| StateEvent::DeviceConnected {
+                device: d.id.clone(),
+                model: d.model.clone(),
+                state: d.state.clone(),
+            }),
+    );
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(id: &str, state: &str) -> Device {
+        Device {
+            id: id.to_string(),
+            model: "Pixel_7".to_string(),
+            state: state.to_string(),
+            ..Device::default()
+        }
+    }
+
+    #[test]
+    fn events_serialize_as_tagged_json_lines() {
+        let event = StateEvent::ForegroundChanged {
+            device: "emulator-5554".to_string(),
+            package: "com.example.app".to_string(),
+            process: "com.example.app".to_string(),
+            pid: Some(812),
+        };
+        assert_eq!(
+            event.json_line("2026-01-01T00:00:00.000Z"),
+            r#"{"time":"2026-01-01T00:00:00.000Z","event":"foreground_changed","device":"emulator-5554","package":"com.example.app","process":"com.example.app","pid":812}"#
+        );
+    }
+
+    #[test]
+    fn device_changes_report_arrivals_departures_and_state() {
+        let previous = vec![device("a", "device"), device("b", "unauthorized")];
+        let current = vec![device("b", "device"), device("c", "device")];
+        let events = device_changes(&previous, &current);
+        assert_eq!(
+            events,
+            vec![
+                StateEvent::DeviceDisconnected {
+                    device: "a".to_string()
+                },
+                StateEvent::DeviceConnected {
+                    device: "b".to_string(),
+                    model: "Pixel_7".to_string(),
+                    state: "device".to_string()
+                },
+                StateEvent::DeviceConnected {
+                    device: "c".to_string(),
+                    model: "Pixel_7".to_string(),
+                    state: "device".to_string()
+                },
+            ]
+        );
+        assert!(device_changes(&current, &current).is_empty());
+    }
+}
@@ -1,7 +1,13 @@
 pub mod commands;
+pub mod complete;
+pub mod events;
+pub mod progress;
 
-use crate::config::AgentBuildTool;
+use crate::android::Transport;
+use crate::config::{AgentBuildTool, ArchType};
+use crate::frida::ServerPlatform;
 use clap::{Parser, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
 
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum InitServerSource {
@@ -24,6 +30,71 @@ impl From<AgentTool> for AgentBuildTool {
     }
 }
 
+/// How watch/follow modes report state changes.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// One JSON event object per line on stdout
+    Jsonl,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum DeviceTransport {
+    Usb,
+    Tcp,
+    Emulator,
+}
+
+impl From<DeviceTransport> for Transport {
+    fn from(value: DeviceTransport) -> Self {
+        match value {
+            DeviceTransport::Usb => Transport::Usb,
+            DeviceTransport::Tcp => Transport::Tcp,
+            DeviceTransport::Emulator => Transport::Emulator,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum TargetPlatform {
+    Android,
+    Linux,
+    Macos,
+    Windows,
+}
+
+impl From<TargetPlatform> for ServerPlatform {
+    fn from(value: TargetPlatform) -> Self {
+        match value {
+            TargetPlatform::Android => ServerPlatform::Android,
+            TargetPlatform::Linux => ServerPlatform::Linux,
+            TargetPlatform::Macos => ServerPlatform::Macos,
+            TargetPlatform::Windows => ServerPlatform::Windows,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum TargetArch {
+    Arm,
+    Arm64,
+    X86,
+    #[value(name = "x86_64")]
+    X8664,
+}
+
+impl From<TargetArch> for ArchType {
+    fn from(value: TargetArch) -> Self {
+        match value {
+            TargetArch::Arm => ArchType::Arm,
+            TargetArch::Arm64 => ArchType::Arm64,
+            TargetArch::X86 => ArchType::X86,
+            TargetArch::X8664 => ArchType::X8664,
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(
     name = "frida-mgr",
@@ -32,6 +103,11 @@ impl From<AgentTool> for AgentBuildTool {
     long_about = None
 )]
 pub struct Cli {
+    /// Use the current directory as the project instead of searching parent directories
+    /// for frida.toml (applies to run, frida, ps, trace, objection, shell and apk)
+    #[arg(long, global = true)]
+    pub no_discover: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -65,12 +141,208 @@ pub enum AgentCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Show disk usage per version against cache.max_size_gb
+    Size,
+
+    /// List cached frida-server builds with their size and last use
+    List,
+
+    /// Delete cached builds (default: least recently used ones until the cache fits cache.max_size_gb)
+    Prune {
+        /// Keep the N most recently used versions
+        #[arg(long)]
+        keep: Option<usize>,
+
+        /// Only delete builds unused for this long (e.g. 90d, 12w, 48h)
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Show what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete every cached frida-server build
+    Clear {
+        /// Delete without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProjectsCommands {
+    /// List registered projects and flag stale paths
+    List,
+
+    /// Print the directory of a project (e.g. cd "$(frida-mgr projects open <name>)")
+    Open {
+        /// Project name (from frida.toml) or directory name
+        name: String,
+    },
+
+    /// Remove registered paths that no longer contain a frida.toml
+    Prune,
+}
+
+#[derive(Subcommand)]
+pub enum BundleCommands {
+    /// Pack frida.toml, cached servers, the built agent, scripts and a lockfile into one archive
+    Export {
+        /// Archive path (default: <project>-frida-<version>.bundle.tar.gz)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Restore a bundle: cache verified servers, write project files, rebuild the locked venv
+    Import {
+        /// Bundle archive created by `bundle export`
+        archive: String,
+
+        /// Project directory to restore into (default: current directory)
+        #[arg(long)]
+        dir: Option<String>,
+
+        /// Overwrite an existing frida.toml
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ScriptCommands {
+    /// Scaffold a standalone hook script and register it under [scripts.registry]
+    New {
+        /// Script name, used as the file name and the `-l` name
+        name: String,
+
+        /// Write plain JavaScript instead of TypeScript
+        #[arg(long)]
+        js: bool,
+
+        /// Overwrite an existing script or registry entry
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MapCommands {
+    /// Check that every pinned frida-tools/objection release exists on PyPI and accepts
+    /// its mapped Frida version
+    Verify {
+        /// Remove broken mappings from the version map
+        #[arg(long)]
+        prune: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CiCommands {
+    /// Print directories CI runners should cache (name=path lines, or JSON)
+    CachePaths {
+        /// Emit a JSON object instead of name=path lines
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AdbCommands {
+    /// Download Android platform-tools into the cache and use its adb
+    Install {
+        /// Re-download even if platform-tools are already cached
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DeviceCommands {
+    /// Pin a device (serial or alias) as this project's default
+    Use {
+        /// Device ID or alias
+        #[arg(add = ArgValueCompleter::new(complete::devices))]
+        device: String,
+    },
+
+    /// Show the pinned device and whether it is connected
+    Show,
+
+    /// Unpin the device; commands fall back to the first connected device
+    Clear,
+
+    /// List leftover frida-server binaries, logs and agents in the push directories with
+    /// their sizes, and delete them after confirmation
+    Cleanup {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Delete without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ForwardCommands {
+    /// Forward a local TCP port to the same port on the device
+    Setup {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Port to forward (default: android.server_port)
+        #[arg(short, long)]
+        port: Option<u16>,
+    },
+
+    /// Remove the forward of a port from the device
+    Remove {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Port whose forward to remove (default: android.server_port)
+        #[arg(short, long)]
+        port: Option<u16>,
+    },
+
+    /// List adb forwards on all devices
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum EphemeralCommands {
+    /// Spawn a package against a randomly named frida-server on a random port, then remove
+    /// the server, its log and the port forward when the session ends
+    Spawn {
+        /// Package to spawn
+        package: String,
+
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// JavaScript script to load (-l); can be repeated
+        #[arg(short = 'l', long = "load")]
+        scripts: Vec<String>,
+
+        /// Extra frida arguments (excluding device/target selection)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize a new Frida project
     Init {
         /// Frida version to install (default: latest)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::versions))]
         frida: Option<String>,
 
         /// Python version to use (default: 3.11)
@@ -100,12 +372,67 @@ pub enum Commands {
         /// objection version to install (default: mapped by frida version, or let uv resolve)
         #[arg(long)]
         objection: Option<String>,
+
+        /// Also download frida-server for every other Android architecture (e.g. for emulators)
+        #[arg(long)]
+        all_arches: bool,
+
+        /// Instrument processes on this machine instead of an Android device (project.platform = "local")
+        #[arg(long, conflicts_with_all = ["arch", "server_source", "all_arches"])]
+        local: bool,
+    },
+
+    /// Create frida.toml from an existing setup (requirements.txt, .venv or global pip)
+    Migrate {
+        /// requirements file with frida pins (default: ./requirements.txt when present)
+        #[arg(short, long)]
+        requirements: Option<String>,
+
+        /// frida-server binary to import into the cache (repeatable; default: ./frida-server*)
+        #[arg(long = "server")]
+        servers: Vec<String>,
+
+        /// Python version to use (default: the existing .venv's, or the global default)
+        #[arg(short, long)]
+        python: Option<String>,
+
+        /// Remove an existing .venv and rebuild it instead of reusing it
+        #[arg(long)]
+        recreate_venv: bool,
     },
 
     /// Install and switch to a specific Frida version
     Install {
         /// Frida version to install (e.g., 16.6.6, latest, stable)
+        #[arg(add = ArgValueCompleter::new(complete::versions))]
+        version: String,
+
+        /// Platform of the frida-server to download (default: android)
+        #[arg(long, value_enum)]
+        platform: Option<TargetPlatform>,
+
+        /// frida-server architecture; comma-separate several to download them concurrently (default: android.arch from frida.toml, or the host arch for desktop platforms)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        arch: Vec<TargetArch>,
+
+        /// Download frida-server for every architecture the platform is published for
+        #[arg(long, conflicts_with = "arch")]
+        all_arches: bool,
+    },
+
+    /// Remove a Frida version's cached frida-server builds
+    Uninstall {
+        /// Frida version to remove (e.g., 16.6.6)
+        #[arg(add = ArgValueCompleter::new(complete::versions))]
         version: String,
+
+        /// Only remove this architecture (cache directory name, e.g. arm64 or linux-x86_64)
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// Remove it even if the current project uses this version
+        #[arg(long)]
+        force: bool,
     },
 
     /// List available or installed Frida versions
@@ -115,46 +442,327 @@ pub enum Commands {
         installed: bool,
     },
 
+    /// Compare two Frida versions: mapped tools/objection, release dates, cached servers
+    #[command(name = "diff-versions")]
+    DiffVersions {
+        /// Current version (e.g., 16.5.2, stable)
+        #[arg(add = ArgValueCompleter::new(complete::versions))]
+        from: String,
+
+        /// Candidate version (e.g., 16.6.6, latest)
+        #[arg(add = ArgValueCompleter::new(complete::versions))]
+        to: String,
+
+        /// Also show the GitHub release notes in between
+        #[arg(long)]
+        changelog: bool,
+    },
+
+    /// List the release assets published for a Frida version
+    Assets {
+        /// Frida version (e.g., 16.6.6, latest, stable)
+        #[arg(add = ArgValueCompleter::new(complete::versions))]
+        version: String,
+    },
+
+    /// Download any release asset (devkits, bindings, ...) into the cache
+    Download {
+        /// Asset name, or a substring matching exactly one asset
+        asset: String,
+
+        /// Frida version (default: frida.version from frida.toml)
+        #[arg(long, add = ArgValueCompleter::new(complete::versions))]
+        version: Option<String>,
+
+        /// Re-download even if the asset is already cached
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Push frida-server to connected device
     Push {
         /// Device ID (default: first connected device)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
 
+        /// Run on every device of this group (global config [devices.groups])
+        #[arg(
+            short,
+            long,
+            conflicts_with = "device",
+            add = ArgValueCompleter::new(complete::device_groups)
+        )]
+        group: Option<String>,
+
         /// Automatically start the server after pushing
         #[arg(short, long)]
         start: bool,
+
+        /// Push the server for this architecture instead of android.arch or the detected one
+        #[arg(long, value_enum)]
+        arch: Option<TargetArch>,
+
+        /// Push under a fresh random file/process name (as android.randomize_server_name)
+        #[arg(long)]
+        random_name: bool,
+    },
+
+    /// Set up a device in one step: detect arch, fetch, push, start and verify frida-server
+    Provision {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Also forward the server port to this machine (adb forward)
+        #[arg(short, long)]
+        forward: bool,
+    },
+
+    /// Manage adb forwards of the frida-server port to this machine
+    Forward {
+        #[command(subcommand)]
+        command: ForwardCommands,
     },
 
     /// Start frida-server on device
     Start {
         /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Run on every device of this group (global config [devices.groups])
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["device", "foreground", "watchdog", "stop_on_exit"],
+            add = ArgValueCompleter::new(complete::device_groups)
+        )]
+        group: Option<String>,
+
+        /// Run attached to the terminal, streaming server output until Ctrl-C
+        #[arg(short, long, conflicts_with = "watchdog")]
+        foreground: bool,
+
+        /// Keep running and restart the server whenever it dies (see `watchdog`)
         #[arg(short, long)]
+        watchdog: bool,
+
+        /// How the watchdog reports server deaths and restarts
+        #[arg(long, value_enum, default_value_t, requires = "watchdog")]
+        output: OutputFormat,
+
+        /// Stay until Ctrl-C (or until COMMAND exits), then stop the server and delete its log
+        #[arg(long, conflicts_with_all = ["foreground", "watchdog"])]
+        stop_on_exit: bool,
+
+        /// Command to run (from the project venv) while the server is up; needs --stop-on-exit
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            value_name = "COMMAND",
+            requires = "stop_on_exit"
+        )]
+        command: Vec<String>,
+    },
+
+    /// Keep frida-server alive, restarting it with backoff whenever it dies
+    Watchdog {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
+
+        /// Seconds between liveness checks (default: 5)
+        #[arg(short, long)]
+        interval: Option<u64>,
+
+        /// Report server deaths and restarts as text or one JSON event per line
+        #[arg(long, value_enum, default_value_t)]
+        output: OutputFormat,
     },
 
     /// Stop frida-server on device
     Stop {
         /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Run on every device of this group (global config [devices.groups])
+        #[arg(
+            short,
+            long,
+            conflicts_with = "device",
+            add = ArgValueCompleter::new(complete::device_groups)
+        )]
+        group: Option<String>,
+    },
+
+    /// Restart frida-server: stop it, wait for it to exit, start and verify it again
+    Restart {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Run on every device of this group (global config [devices.groups])
+        #[arg(
+            short,
+            long,
+            conflicts_with = "device",
+            add = ArgValueCompleter::new(complete::device_groups)
+        )]
+        group: Option<String>,
+    },
+
+    /// Show the frida-server log written on the device
+    Logs {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Number of lines to show
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+
+        /// Keep printing new output until Ctrl-C
         #[arg(short, long)]
+        follow: bool,
+
+        /// Empty the log instead of printing it
+        #[arg(long, conflicts_with_all = ["follow", "lines"])]
+        clear: bool,
+    },
+
+    /// Stream logcat for the foreground (or given) app only, coloured by priority
+    Logcat {
+        /// Package name (default: the foreground app)
+        package: Option<String>,
+
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Also save the log to this file
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+
+    /// Remove pushed frida-server binaries, their logs and agent bundles from the device
+    Remove {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
+
+        /// List what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show device and server status
     Status {
         /// Device ID (default: first connected device)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
+
+        /// Run on every device of this group (global config [devices.groups])
+        #[arg(
+            short,
+            long,
+            conflicts_with = "device",
+            add = ArgValueCompleter::new(complete::device_groups)
+        )]
+        group: Option<String>,
     },
 
+    /// Summarize the current project: versions, venv, agent, device defaults, cached servers
+    Info,
+
     /// List connected Android devices
-    Devices,
+    Devices {
+        /// Also browse for wireless debugging devices (mDNS) and offer to pair/connect
+        #[arg(long, conflicts_with = "watch")]
+        discover: bool,
+
+        /// Keep running and report devices as they connect and disconnect
+        #[arg(short, long)]
+        watch: bool,
+
+        /// How --watch reports changes: text or one JSON event per line
+        #[arg(long, value_enum, default_value_t, requires = "watch")]
+        output: OutputFormat,
+
+        /// Also show transport, model, Android version, ABIs, root, SELinux and frida compatibility
+        #[arg(short, long, conflicts_with = "watch")]
+        long: bool,
+
+        /// Only devices in this adb state (device, unauthorized, offline, ...)
+        #[arg(long)]
+        state: Option<String>,
+
+        /// Only devices connected over this transport
+        #[arg(long, value_enum)]
+        transport: Option<DeviceTransport>,
+    },
+
+    /// Enable adb over Wi-Fi on a USB-connected device and connect to it
+    Tcpip {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// TCP port for adbd (default: 5555)
+        #[arg(short, long)]
+        port: Option<u16>,
+
+        /// Alias to save for the network endpoint (default: <model>-wifi)
+        #[arg(long)]
+        alias: Option<String>,
+    },
+
+    /// Connect adb to a wireless device, pairing it first if needed
+    Connect {
+        /// Device address as host[:port] (default port: 5555)
+        address: String,
+
+        /// Pair first using the pairing port (or host:port) shown on the device
+        #[arg(long, value_name = "PORT")]
+        pair: Option<String>,
+
+        /// Pairing code shown on the device (prompted for when omitted)
+        #[arg(long, requires = "pair")]
+        code: Option<String>,
+
+        /// Alias to save for the endpoint
+        #[arg(long)]
+        alias: Option<String>,
+
+        /// Don't remember the device for automatic reconnection
+        #[arg(long)]
+        no_remember: bool,
+    },
+
+    /// Disconnect adb from a wireless device (default: all network devices)
+    Disconnect {
+        /// Device address or alias
+        #[arg(add = ArgValueCompleter::new(complete::devices))]
+        address: Option<String>,
+
+        /// Also stop reconnecting to it automatically
+        #[arg(long)]
+        forget: bool,
+    },
 
     /// Check environment and dependencies
-    Doctor,
+    Doctor {
+        /// Install missing tools frida-mgr can provide itself (currently adb)
+        #[arg(long)]
+        fix: bool,
+    },
 
     /// Run a command in the virtual environment
     Run {
+        /// Device to export as ANDROID_SERIAL / FRIDA_MGR_DEVICE to the command
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
         /// Command to run
         command: String,
 
@@ -166,6 +774,10 @@ pub enum Commands {
     /// Run frida with the project's virtual environment (shortcut for 'run frida')
     #[command(name = "frida")]
     Frida {
+        /// Also write everything frida prints to a timestamped file under logs/
+        #[arg(long)]
+        log_output: bool,
+
         /// Arguments to pass to frida (e.g., -l script.js -U com.example.app)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -183,7 +795,7 @@ pub enum Commands {
     #[command(name = "top", visible_alias = "fg")]
     Top {
         /// Device ID (default: first connected device)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
 
         /// Build a project agent and load it (-l); pass a directory or omit value for default "agent"
@@ -198,16 +810,121 @@ pub enum Commands {
         #[arg(short = 'l', long = "load")]
         scripts: Vec<String>,
 
+        /// Also write everything frida prints to a timestamped file under logs/
+        #[arg(long)]
+        log_output: bool,
+
+        /// Reload the -l scripts whenever they change (restarts frida if it cannot reload)
+        #[arg(short, long)]
+        watch: bool,
+
+        /// For a 32-bit app on a 64-bit device, start a matching 32-bit frida-server and use it
+        #[arg(long)]
+        server32: bool,
+
+        /// Keep running and re-attach whenever a different app comes to the foreground
+        #[arg(long, conflicts_with_all = ["watch", "server32"])]
+        follow: bool,
+
+        /// How --follow reports foreground changes (jsonl moves frida's own output to stderr)
+        #[arg(
+            long,
+            value_enum,
+            default_value_t,
+            requires = "follow",
+            conflicts_with = "agent"
+        )]
+        output: OutputFormat,
+
+        /// If frida-server dies mid-session, restart it and reattach once frida exits
+        #[arg(long, conflicts_with = "follow")]
+        restart_server: bool,
+
+        /// Push and start frida-server first if it is not running (always on with android.auto_start)
+        #[arg(long)]
+        ensure_server: bool,
+
+        /// Replace frida-server first if it reports another version than frida.version
+        #[arg(long)]
+        auto_push: bool,
+
+        /// Start frida-server on demand and stop it when the session ends; a randomized one is also deleted (always on with android.stop_on_exit)
+        #[arg(long)]
+        stop_on_exit: bool,
+
+        /// In split-screen, target the foreground app whose package contains this text
+        #[arg(long, value_name = "PKG")]
+        prefer: Option<String>,
+
+        /// Android user (work profile, ...) the target app runs in, as listed by `pm list users`
+        #[arg(long, value_name = "ID")]
+        user: Option<u32>,
+
+        /// Extra frida arguments (excluding device/target selection)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Load standalone scripts into the foreground app (no agent build)
+    #[command(name = "run-script")]
+    RunScript {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Script to load (-l): a path, or a name from [scripts.registry]/scripts.paths; can be repeated
+        #[arg(short = 'l', long = "load", required = true)]
+        scripts: Vec<String>,
+
+        /// Reload the scripts whenever they change (restarts frida if it cannot reload)
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Also write everything frida prints to a timestamped file under logs/
+        #[arg(long)]
+        log_output: bool,
+
         /// Extra frida arguments (excluding device/target selection)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
 
+    /// Start frida against a named [apps.<name>] profile from frida.toml
+    #[command(name = "run-app")]
+    RunApp {
+        /// Profile name
+        name: String,
+
+        /// Device ID (default: profile device, or first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+    },
+
+    /// Attach to the foreground app with the built agent; `r` rebuilds and reloads it,
+    /// `s` respawns the app, `q` quits
+    Console {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Agent directory (default: from frida.toml agent.dir, or "agent")
+        #[arg(long, value_name = "DIR")]
+        agent: Option<String>,
+
+        /// Agent build tool override (default: from frida.toml agent.tool)
+        #[arg(long, value_enum)]
+        agent_tool: Option<AgentTool>,
+
+        /// Extra frida arguments (excluding device/target/script selection)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// Spawn the current foreground app and run frida
     #[command(name = "spawn", visible_alias = "sp")]
     Spawn {
         /// Device ID (default: first connected device)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
 
         /// Build a project agent and load it (-l); pass a directory or omit value for default "agent"
@@ -222,18 +939,84 @@ pub enum Commands {
         #[arg(short = 'l', long = "load")]
         scripts: Vec<String>,
 
+        /// Also write everything frida prints to a timestamped file under logs/
+        #[arg(long)]
+        log_output: bool,
+
+        /// Keep the app paused after spawning (default: frida.spawn_no_pause)
+        #[arg(long, overrides_with = "no_pause")]
+        pause: bool,
+
+        /// Resume the app right after spawning
+        #[arg(long, overrides_with = "pause")]
+        no_pause: bool,
+
+        /// Inject frida-gadget via run-as instead of using frida-server (debuggable apps, no root)
+        #[arg(long)]
+        run_as: bool,
+
+        /// For a 32-bit app on a 64-bit device, start a matching 32-bit frida-server and use it
+        #[arg(long, conflicts_with = "run_as")]
+        server32: bool,
+
+        /// If frida-server dies mid-session, restart it and spawn again once frida exits
+        #[arg(long, conflicts_with = "run_as")]
+        restart_server: bool,
+
+        /// Push and start frida-server first if it is not running (always on with android.auto_start)
+        #[arg(long, conflicts_with = "run_as")]
+        ensure_server: bool,
+
+        /// Replace frida-server first if it reports another version than frida.version
+        #[arg(long, conflicts_with = "run_as")]
+        auto_push: bool,
+
+        /// Start frida-server on demand and stop it when the session ends; a randomized one is also deleted (always on with android.stop_on_exit)
+        #[arg(long, conflicts_with = "run_as")]
+        stop_on_exit: bool,
+
+        /// Android user (work profile, ...) the target app runs in, as listed by `pm list users`
+        #[arg(long, value_name = "ID", conflicts_with = "run_as")]
+        user: Option<u32>,
+
         /// Extra frida arguments (excluding device/target selection)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
 
+    /// Run a frida session that leaves no frida-server artifacts on the device
+    Ephemeral {
+        #[command(subcommand)]
+        command: EphemeralCommands,
+    },
+
+    /// Scan the foreground app for root detection, SSL pinning, packers and anti-Frida checks
+    #[command(name = "detect-protections")]
+    DetectProtections {
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Run objection for the current foreground app (defaults to `explore`)
     #[command(name = "objection-fg", visible_alias = "og")]
     ObjectionFg {
         /// Device ID (default: first connected device)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
         device: Option<String>,
 
+        /// Push and start frida-server first if it is not running (always on with android.auto_start)
+        #[arg(long)]
+        ensure_server: bool,
+
+        /// Android user (work profile, ...) the target app runs in, as listed by `pm list users`
+        #[arg(long, value_name = "ID")]
+        user: Option<u32>,
+
         /// Objection arguments after the auto-injected target selector (e.g., `--name <package>`)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -242,6 +1025,18 @@ pub enum Commands {
     /// Run frida-ps with the project's virtual environment
     #[command(name = "ps")]
     Ps {
+        /// Device ID (required by --apps; otherwise only exported to frida-ps)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Group running apps per package with their labels and secondary (:remote) processes
+        #[arg(long)]
+        apps: bool,
+
+        /// Print the --apps view as JSON
+        #[arg(long, requires = "apps")]
+        json: bool,
+
         /// Arguments to pass to frida-ps
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -255,6 +1050,70 @@ pub enum Commands {
         args: Vec<String>,
     },
 
+    /// Run frida-apk with the project's virtual environment
+    ///
+    /// frida-apk patches an APK so it can be instrumented without root: it sets
+    /// android:debuggable and extractNativeLibs in the manifest. Common usage:
+    ///   frida-mgr apk app.apk                  make app.apk debuggable
+    ///   frida-mgr apk -o patched.apk app.apk   write the patched APK elsewhere
+    /// A pulled package name (e.g. com.example.app) resolves to apks/<package>/base.apk
+    /// in the project.
+    #[command(name = "apk", verbatim_doc_comment)]
+    Apk {
+        /// Arguments to pass to frida-apk
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Pull an app's APKs and decompile them with jadx into decompiled/<package>/,
+    /// then list its entry points (Application class, launcher and exported components)
+    Decompile {
+        /// Package name (e.g., com.example.app)
+        package: String,
+
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Pull the APKs again even if apks/<package>/ already has them
+        #[arg(long)]
+        refresh: bool,
+
+        /// Extra arguments to pass to jadx (after --)
+        #[arg(last = true)]
+        jadx_args: Vec<String>,
+    },
+
+    /// List an app's exported activities, services and receivers with their intent
+    /// filters and the am command that launches each (from decompiled/<package>/ if
+    /// present, otherwise dumpsys package)
+    Targets {
+        /// Package name (e.g., com.example.app)
+        package: String,
+
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Include components that are not exported (decompiled manifest only)
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// List the deep-link URIs a package handles, or open one on the device
+    Intents {
+        /// Package name (e.g., com.example.app)
+        package: String,
+
+        /// Device ID (default: first connected device)
+        #[arg(short, long, add = ArgValueCompleter::new(complete::devices))]
+        device: Option<String>,
+
+        /// Fire a VIEW intent for this URI at the package instead of listing
+        #[arg(long, value_name = "URI")]
+        launch: Option<String>,
+    },
+
     /// Enter the virtual environment shell
     Shell,
 
@@ -291,6 +1150,18 @@ pub enum Commands {
         /// Recreate the virtual environment (required when python.version changes)
         #[arg(long)]
         recreate_venv: bool,
+
+        /// Only report drift between frida.toml and the environment; exit non-zero on drift
+        #[arg(long, conflicts_with_all = ["update_map", "no_project", "recreate_venv"])]
+        check: bool,
+
+        /// After syncing, delete cached servers and assets no registered project uses
+        #[arg(long, conflicts_with_all = ["no_project", "check"])]
+        prune: bool,
+
+        /// Run the agent's package manager install when its dependencies are out of date
+        #[arg(long, conflicts_with_all = ["no_project", "check"])]
+        agent_install: bool,
     },
 
     /// Manage TypeScript agent scaffold/build
@@ -298,9 +1169,91 @@ pub enum Commands {
         #[command(subcommand)]
         command: AgentCommands,
     },
+
+    /// Generate TypeScript hook stubs into the agent source tree
+    Codegen {
+        /// Java class and methods to hook, e.g. com.example.Crypto!encrypt,decrypt (repeatable)
+        #[arg(long, value_name = "CLASS!METHODS")]
+        java: Vec<String>,
+
+        /// Native module and exports/offsets to hook, e.g. libfoo.so!sub_1234 (repeatable)
+        #[arg(long, value_name = "MODULE!SYMBOLS")]
+        native: Vec<String>,
+
+        /// Output module name under <agent src>/hooks/ (default: derived from the first target)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Overwrite an existing hooks file
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Inspect the Frida → frida-tools/objection version map
+    Map {
+        #[command(subcommand)]
+        command: MapCommands,
+    },
+
+    /// Manage standalone hook scripts outside the agent bundle
+    Script {
+        #[command(subcommand)]
+        command: ScriptCommands,
+    },
+
+    /// Inspect and trim the frida-server download cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    /// Manage the global registry of frida-mgr projects
+    Projects {
+        #[command(subcommand)]
+        command: ProjectsCommands,
+    },
+
+    /// Export or import a portable project bundle for offline machines or colleagues
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommands,
+    },
+
+    /// Helpers for CI pipelines
+    Ci {
+        #[command(subcommand)]
+        command: CiCommands,
+    },
+
+    /// Manage the adb binary frida-mgr uses
+    Adb {
+        #[command(subcommand)]
+        command: AdbCommands,
+    },
+
+    /// Pin the device this project's commands use when --device is not given
+    Device {
+        #[command(subcommand)]
+        command: DeviceCommands,
+    },
+}
+
+/// Process exit code a command asks `main` to terminate with; passthrough commands
+/// forward the wrapped tool's status instead of exiting from library code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitStatus(pub i32);
+
+impl ExitStatus {
+    pub const SUCCESS: Self = Self(0);
+
+    pub fn code(self) -> i32 {
+        self.0
+    }
 }
 
-pub async fn run(cli: Cli) -> crate::core::error::Result<()> {
+pub async fn run(cli: Cli) -> crate::core::error::Result<ExitStatus> {
+    let discover = !cli.no_discover;
+
     match cli.command {
         Commands::Init {
             frida,
@@ -311,6 +1264,8 @@ pub async fn run(cli: Cli) -> crate::core::error::Result<()> {
             local_server_path,
             frida_tools,
             objection,
+            all_arches,
+            local,
         } => {
             commands::init::execute(
                 frida,
@@ -321,40 +1276,294 @@ pub async fn run(cli: Cli) -> crate::core::error::Result<()> {
                 local_server_path,
                 frida_tools,
                 objection,
+                all_arches,
+                local,
             )
-            .await
+            .await?
+        }
+
+        Commands::Migrate {
+            requirements,
+            servers,
+            python,
+            recreate_venv,
+        } => commands::migrate::execute(requirements, servers, python, recreate_venv).await?,
+
+        Commands::Install {
+            version,
+            platform,
+            arch,
+            all_arches,
+        } => {
+            commands::install::execute(
+                version,
+                platform.map(Into::into),
+                arch.into_iter().map(Into::into).collect(),
+                all_arches,
+            )
+            .await?
         }
 
-        Commands::Install { version } => commands::install::execute(version).await,
+        Commands::Uninstall {
+            version,
+            arch,
+            force,
+        } => commands::uninstall::execute(version, arch, force).await?,
+
+        Commands::List { installed } => commands::list::execute(installed).await?,
 
-        Commands::List { installed } => commands::list::execute(installed).await,
+        Commands::DiffVersions {
+            from,
+            to,
+            changelog,
+        } => commands::diff_versions::execute(from, to, changelog).await?,
 
-        Commands::Push { device, start } => commands::push::execute(device, start).await,
+        Commands::Assets { version } => commands::assets::execute(version).await?,
 
-        Commands::Start { device } => commands::start::execute(device).await,
+        Commands::Download {
+            asset,
+            version,
+            force,
+        } => commands::download::execute(asset, version, force).await?,
 
-        Commands::Stop { device } => commands::stop::execute(device).await,
+        Commands::Push {
+            group: Some(group),
+            start,
+            arch,
+            random_name,
+            ..
+        } => {
+            let arch: Option<ArchType> = arch.map(Into::into);
+            commands::group::for_each_member(&group, |member| {
+                commands::push::execute(Some(member), start, arch.clone(), random_name)
+            })
+            .await?
+        }
 
-        Commands::Status { device } => commands::status::execute(device).await,
+        Commands::Push {
+            device,
+            group: None,
+            start,
+            arch,
+            random_name,
+        } => commands::push::execute(device, start, arch.map(Into::into), random_name).await?,
+
+        Commands::Provision { device, forward } => {
+            commands::provision::execute(device, forward).await?
+        }
+
+        Commands::Forward { command } => match command {
+            ForwardCommands::Setup { device, port } => {
+                commands::forward::setup(device, port).await?
+            }
+            ForwardCommands::Remove { device, port } => {
+                commands::forward::remove(device, port).await?
+            }
+            ForwardCommands::List => commands::forward::list().await?,
+        },
+
+        Commands::Start {
+            group: Some(group), ..
+        } => {
+            // Without --foreground/--watchdog/--stop-on-exit, start always reports success.
+            commands::group::for_each_member(&group, |member| async move {
+                commands::start::execute(
+                    Some(member),
+                    false,
+                    false,
+                    OutputFormat::default(),
+                    false,
+                    Vec::new(),
+                    discover,
+                )
+                .await
+                .map(|_| ())
+            })
+            .await?
+        }
+
+        Commands::Start {
+            device,
+            group: None,
+            foreground,
+            watchdog,
+            output,
+            stop_on_exit,
+            command,
+        } => {
+            return commands::start::execute(
+                device,
+                foreground,
+                watchdog,
+                output,
+                stop_on_exit,
+                command,
+                discover,
+            )
+            .await
+        }
+
+        Commands::Watchdog {
+            device,
+            interval,
+            output,
+        } => commands::watchdog::execute(device, interval, output).await?,
+
+        Commands::Stop {
+            group: Some(group), ..
+        } => {
+            commands::group::for_each_member(&group, |member| commands::stop::execute(Some(member)))
+                .await?
+        }
+        Commands::Stop {
+            device,
+            group: None,
+        } => commands::stop::execute(device).await?,
+        Commands::Restart {
+            group: Some(group), ..
+        } => {
+            commands::group::for_each_member(&group, |member| {
+                commands::restart::execute(Some(member))
+            })
+            .await?
+        }
+        Commands::Restart {
+            device,
+            group: None,
+        } => commands::restart::execute(device).await?,
+        Commands::Logs {
+            device,
+            lines,
+            follow,
+            clear,
+        } => commands::logs::execute(device, lines, follow, clear).await?,
+        Commands::Logcat {
+            package,
+            device,
+            output,
+        } => commands::logcat::execute(package, device, output).await?,
+        Commands::Remove { device, dry_run } => commands::remove::execute(device, dry_run).await?,
+
+        Commands::Status {
+            group: Some(group), ..
+        } => {
+            commands::group::for_each_member(&group, |member| {
+                commands::status::execute(Some(member))
+            })
+            .await?
+        }
+        Commands::Status {
+            device,
+            group: None,
+        } => commands::status::execute(device).await?,
+
+        Commands::Info => commands::info::execute().await?,
+
+        Commands::Devices {
+            discover,
+            watch,
+            output,
+            long,
+            state,
+            transport,
+        } => {
+            let filter = commands::devices::DeviceFilter {
+                state,
+                transport: transport.map(Into::into),
+            };
+            commands::devices::execute(discover, watch, output, long, filter).await?
+        }
 
-        Commands::Devices => commands::devices::execute().await,
+        Commands::Tcpip {
+            device,
+            port,
+            alias,
+        } => commands::tcpip::execute(device, port, alias).await?,
+
+        Commands::Connect {
+            address,
+            pair,
+            code,
+            alias,
+            no_remember,
+        } => commands::connect::connect(address, pair, code, alias, !no_remember).await?,
+
+        Commands::Disconnect { address, forget } => {
+            commands::connect::disconnect(address, forget).await?
+        }
 
-        Commands::Doctor => commands::doctor::execute().await,
+        Commands::Doctor { fix } => commands::doctor::execute(fix).await?,
 
-        Commands::Run { command, args } => commands::run::execute(command, args).await,
+        Commands::Run {
+            device,
+            command,
+            args,
+        } => return commands::run::execute(device, command, args, discover).await,
 
-        Commands::Frida { args } => commands::frida::execute(args).await,
+        Commands::Frida { log_output, args } => {
+            return commands::frida::execute(args, log_output, discover).await
+        }
 
-        Commands::Objection { args } => commands::objection::execute(args).await,
+        Commands::Objection { args } => return commands::objection::execute(args, discover).await,
 
         Commands::Top {
             device,
             agent,
             agent_tool,
             scripts,
+            log_output,
+            watch,
+            server32,
+            follow,
+            output,
+            restart_server,
+            ensure_server,
+            auto_push,
+            stop_on_exit,
+            prefer,
+            user,
             args,
         } => {
-            commands::top::execute(device, agent, agent_tool.map(Into::into), scripts, args).await
+            return commands::top::execute(
+                device,
+                agent,
+                agent_tool.map(Into::into),
+                scripts,
+                args,
+                log_output,
+                watch,
+                server32,
+                follow,
+                output,
+                restart_server,
+                ensure_server,
+                auto_push,
+                stop_on_exit,
+                prefer,
+                user,
+            )
+            .await
+        }
+
+        Commands::RunScript {
+            device,
+            scripts,
+            watch,
+            log_output,
+            args,
+        } => return commands::run_script::execute(device, scripts, watch, args, log_output).await,
+
+        Commands::RunApp { name, device } => return commands::run_app::execute(name, device).await,
+
+        Commands::Console {
+            device,
+            agent,
+            agent_tool,
+            args,
+        } => commands::console::execute(device, agent, agent_tool.map(Into::into), args).await?,
+
+        Commands::DetectProtections { device, json } => {
+            commands::detect_protections::execute(device, json).await?
         }
 
         Commands::Spawn {
@@ -362,38 +1571,187 @@ pub async fn run(cli: Cli) -> crate::core::error::Result<()> {
             agent,
             agent_tool,
             scripts,
+            log_output,
+            pause,
+            no_pause,
+            run_as,
+            server32,
+            restart_server,
+            ensure_server,
+            auto_push,
+            stop_on_exit,
+            user,
+            args,
+        } => {
+            return commands::spawn::execute(
+                device,
+                agent,
+                agent_tool.map(Into::into),
+                scripts,
+                args,
+                log_output,
+                match (pause, no_pause) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                run_as,
+                server32,
+                restart_server,
+                ensure_server,
+                auto_push,
+                stop_on_exit,
+                user,
+            )
+            .await
+        }
+
+        Commands::Ephemeral { command } => match command {
+            EphemeralCommands::Spawn {
+                package,
+                device,
+                scripts,
+                args,
+            } => return commands::ephemeral::spawn(device, package, scripts, args).await,
+        },
+
+        Commands::ObjectionFg {
+            device,
+            ensure_server,
+            user,
             args,
-        } => commands::spawn::execute(device, agent, agent_tool.map(Into::into), scripts, args)
-            .await,
+        } => return commands::objection_fg::execute(device, ensure_server, user, args).await,
 
-        Commands::ObjectionFg { device, args } => {
-            commands::objection_fg::execute(device, args).await
+        Commands::Ps {
+            device,
+            apps,
+            json,
+            args,
+        } => return commands::ps::execute(device, apps, json, args, discover).await,
+
+        Commands::Trace { args } => {
+            return commands::run::execute(None, "frida-trace".to_string(), args, discover).await
         }
 
-        Commands::Ps { args } => commands::run::execute("frida-ps".to_string(), args).await,
+        Commands::Apk { args } => return commands::apk::execute(args, discover).await,
+
+        Commands::Decompile {
+            package,
+            device,
+            refresh,
+            jadx_args,
+        } => commands::decompile::execute(package, device, refresh, jadx_args).await?,
+
+        Commands::Targets {
+            package,
+            device,
+            all,
+        } => commands::targets::execute(package, device, all).await?,
 
-        Commands::Trace { args } => commands::run::execute("frida-trace".to_string(), args).await,
+        Commands::Intents {
+            package,
+            device,
+            launch,
+        } => commands::intents::execute(package, device, launch).await?,
 
-        Commands::Shell => commands::shell::execute().await,
+        Commands::Shell => return commands::shell::execute(discover).await,
 
-        Commands::Uv { args } => commands::uv::execute(args).await,
+        Commands::Uv { args } => return commands::uv::execute(args).await,
 
-        Commands::Pip { args } => commands::pip::execute(args).await,
+        Commands::Pip { args } => return commands::pip::execute(args).await,
 
         Commands::Sync {
             update_map,
             prerelease,
             no_project,
             recreate_venv,
-        } => commands::sync::execute(update_map, prerelease, no_project, recreate_venv).await,
+            check,
+            prune,
+            agent_install,
+        } => {
+            if check {
+                return commands::sync::check().await;
+            }
+            commands::sync::execute(
+                update_map,
+                prerelease,
+                no_project,
+                recreate_venv,
+                prune,
+                agent_install,
+            )
+            .await?
+        }
 
         Commands::Agent { command } => match command {
             AgentCommands::Init { dir, tool, force } => {
-                commands::agent::init(dir, tool.map(Into::into), force).await
+                commands::agent::init(dir, tool.map(Into::into), force).await?
             }
             AgentCommands::Build { dir, tool } => {
-                commands::agent::build(dir, tool.map(Into::into)).await
+                commands::agent::build(dir, tool.map(Into::into)).await?
+            }
+        },
+
+        Commands::Codegen {
+            java,
+            native,
+            name,
+            force,
+        } => commands::codegen::execute(java, native, name, force).await?,
+
+        Commands::Map { command } => match command {
+            MapCommands::Verify { prune } => return commands::map::verify(prune).await,
+        },
+
+        Commands::Script { command } => match command {
+            ScriptCommands::New { name, js, force } => {
+                commands::script::new_script(name, js, force).await?
+            }
+        },
+
+        Commands::Cache { command } => match command {
+            CacheCommands::Size => commands::cache::size().await?,
+            CacheCommands::List => commands::cache::list().await?,
+            CacheCommands::Prune {
+                keep,
+                older_than,
+                dry_run,
+            } => commands::cache::prune(keep, older_than, dry_run).await?,
+            CacheCommands::Clear { yes } => commands::cache::clear(yes).await?,
+        },
+
+        Commands::Projects { command } => match command {
+            ProjectsCommands::List => commands::projects::list().await?,
+            ProjectsCommands::Open { name } => commands::projects::open(name).await?,
+            ProjectsCommands::Prune => commands::projects::prune().await?,
+        },
+
+        Commands::Bundle { command } => match command {
+            BundleCommands::Export { output } => commands::bundle::export(output).await?,
+            BundleCommands::Import {
+                archive,
+                dir,
+                force,
+            } => commands::bundle::import(archive, dir, force).await?,
+        },
+
+        Commands::Ci { command } => match command {
+            CiCommands::CachePaths { json } => commands::ci::cache_paths(json).await?,
+        },
+
+        Commands::Adb { command } => match command {
+            AdbCommands::Install { force } => commands::adb::install(force).await?,
+        },
+
+        Commands::Device { command } => match command {
+            DeviceCommands::Use { device } => commands::device::pin(device).await?,
+            DeviceCommands::Show => commands::device::show().await?,
+            DeviceCommands::Clear => commands::device::clear().await?,
+            DeviceCommands::Cleanup { device, yes } => {
+                commands::device::cleanup(device, yes).await?
             }
         },
     }
+
+    Ok(ExitStatus::SUCCESS)
 }
@@ -0,0 +1,63 @@
+use crate::core::{Event, EventBus};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Bars {
+    /// Shared by downloads that overlap so their bars stack instead of overwriting each
+    /// other; a new one starts once they have all finished.
+    multi: MultiProgress,
+    active: HashMap<String, ProgressBar>,
+}
+
+/// Draw download progress bars on the terminal for events on `bus`.
+pub fn render_downloads(bus: &EventBus) {
+    let bars: Mutex<Bars> = Mutex::new(Bars::default());
+    bus.on_event(move |event| {
+        let Ok(mut bars) = bars.lock() else {
+            return;
+        };
+        match event {
+            Event::DownloadStarted { url, total_bytes } => {
+                if bars.active.is_empty() {
+                    bars.multi = MultiProgress::new();
+                }
+                let pb = bars
+                    .multi
+                    .add(download_progress_bar(total_bytes.unwrap_or(0), url));
+                bars.active.insert(url.clone(), pb);
+            }
+            Event::DownloadProgress {
+                url, downloaded, ..
+            } => {
+                if let Some(pb) = bars.active.get(url) {
+                    pb.set_position(*downloaded);
+                }
+            }
+            Event::DownloadFinished { url, ok } => {
+                if let Some(pb) = bars.active.remove(url) {
+                    if *ok {
+                        pb.finish_with_message("Download complete");
+                    } else {
+                        pb.abandon();
+                    }
+                }
+            }
+            _ => {}
+        }
+    });
+}
+
+fn download_progress_bar(total_size: u64, url: &str) -> ProgressBar {
+    let pb = ProgressBar::new(total_size);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} {prefix} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+            .expect("Invalid progress bar template")
+            .progress_chars("#>-"),
+    );
+    // The file name tells concurrent downloads apart.
+    pb.set_prefix(url.rsplit('/').next().unwrap_or(url).to_string());
+    pb
+}
@@ -0,0 +1,61 @@
+use crate::core::{ensure_dir_exists, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::fs;
+
+/// Friendly names for adb serials, e.g. the `host:port` endpoint recorded by `frida-mgr tcpip`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DeviceAliases {
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl DeviceAliases {
+    pub async fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).await?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            ensure_dir_exists(parent).await?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).await?;
+        Ok(())
+    }
+
+    /// Point `alias` at `serial`. Returns true if the file needs saving.
+    pub fn set(&mut self, alias: &str, serial: &str) -> bool {
+        self.aliases.insert(alias.to_string(), serial.to_string()) != Some(serial.to_string())
+    }
+
+    /// The serial behind `name`, or `name` itself when it is not an alias.
+    pub fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map(String::as_str).unwrap_or(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn aliases_roundtrip_and_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("device-aliases.toml");
+
+        let mut aliases = DeviceAliases::default();
+        assert!(aliases.set("pixel", "192.168.1.20:5555"));
+        assert!(!aliases.set("pixel", "192.168.1.20:5555"));
+        aliases.save(&path).await.unwrap();
+
+        let loaded = DeviceAliases::load_or_default(&path).await.unwrap();
+        assert_eq!(loaded.resolve("pixel"), "192.168.1.20:5555");
+        assert_eq!(loaded.resolve("emulator-5554"), "emulator-5554");
+    }
+}
@@ -74,6 +74,14 @@ impl GlobalConfigManager {
         self.get_cache_dir().join("servers")
     }
 
+    pub fn get_http_cache_dir(&self) -> PathBuf {
+        self.get_cache_dir().join("http")
+    }
+
+    pub fn get_assets_cache_dir(&self) -> PathBuf {
+        self.get_cache_dir().join("assets")
+    }
+
     pub fn get_version_map_path(&self) -> PathBuf {
         self.config_dir.join("version-map.toml")
     }
@@ -81,6 +89,18 @@ impl GlobalConfigManager {
     pub fn get_version_overrides_path(&self) -> PathBuf {
         self.config_dir.join("version-overrides.toml")
     }
+
+    pub fn get_project_registry_path(&self) -> PathBuf {
+        self.config_dir.join("projects.toml")
+    }
+
+    pub fn get_device_aliases_path(&self) -> PathBuf {
+        self.config_dir.join("device-aliases.toml")
+    }
+
+    pub fn get_server_names_path(&self) -> PathBuf {
+        self.config_dir.join("server-names.toml")
+    }
 }
 
 impl Default for GlobalConfigManager {
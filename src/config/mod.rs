@@ -1,21 +1,28 @@
+pub mod aliases;
 pub mod global;
 pub mod overrides;
 pub mod project;
+pub mod registry;
 pub mod schema;
+pub mod server_names;
 pub mod validation;
 pub mod version_map;
 
 use crate::core::error::Result;
 
+pub use aliases::DeviceAliases;
 pub use global::GlobalConfigManager;
 pub use overrides::VersionOverrides;
 pub use project::ProjectConfigManager;
+pub use registry::{register_project, ProjectRegistry};
 pub use schema::{
-    AgentBuildTool, AndroidServerSource, ArchType, GlobalConfig, LocalServerConfig, ProjectConfig,
-    DEFAULT_ANDROID_SERVER_NAME,
+    AgentBuildTool, AndroidServerConfig, AndroidServerMode, AndroidServerSource, AppProfile,
+    ArchType, GlobalConfig, LocalServerConfig, ProjectConfig, ProjectPlatform, ScriptsConfig,
+    SelinuxPolicy, ServerLogConfig, DEFAULT_ANDROID_SERVER_NAME, SERVER_LOG_DISABLED,
 };
+pub use server_names::{random_server_name, ServerNames};
 pub use validation::{validate_android_server_name, validate_project_config};
-pub use version_map::VersionMapping;
+pub use version_map::{MappingIssue, MappingProblem, VersionMapping};
 
 #[derive(Debug, Clone)]
 pub struct AndroidServerTarget {
@@ -23,6 +30,58 @@ pub struct AndroidServerTarget {
     pub process_name: String,
 }
 
+impl AndroidServerTarget {
+    /// Directory the server binary lives in on the device.
+    pub fn dir(&self) -> &str {
+        match self.remote_path.rsplit_once('/') {
+            Some(("", _)) => "/",
+            Some((dir, _)) => dir,
+            None => ".",
+        }
+    }
+
+    /// The same server placed in `dir` instead.
+    pub fn in_dir(&self, dir: &str) -> Self {
+        Self {
+            remote_path: format!("{}/{}", dir.trim_end_matches('/'), self.process_name),
+            process_name: self.process_name.clone(),
+        }
+    }
+}
+
+/// `target` followed by the same server in each of `fallback_dirs`, skipping duplicates.
+pub fn android_server_candidates(
+    target: AndroidServerTarget,
+    fallback_dirs: &[String],
+) -> Vec<AndroidServerTarget> {
+    let mut candidates = vec![target];
+    for dir in fallback_dirs {
+        let candidate = candidates[0].in_dir(dir);
+        if !candidates
+            .iter()
+            .any(|c| c.remote_path == candidate.remote_path)
+        {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// `resolve_android_server_target` for `device_id`, where a randomized name pushed to the
+/// device (see `ServerNames`) takes the place of `server_name_override`.
+pub async fn resolve_device_server_target(
+    default_push_path: &str,
+    server_name_override: Option<&str>,
+    device_id: &str,
+) -> Result<AndroidServerTarget> {
+    let names =
+        ServerNames::load_or_default(&GlobalConfigManager::new()?.get_server_names_path()).await?;
+    resolve_android_server_target(
+        default_push_path,
+        names.get(device_id).or(server_name_override),
+    )
+}
+
 pub fn resolve_android_server_target(
     default_push_path: &str,
     server_name_override: Option<&str>,
@@ -72,3 +131,22 @@ pub fn resolve_android_server_target(
         process_name,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_candidates_follow_fallback_dirs() {
+        let target =
+            resolve_android_server_target("/data/local/tmp/frida-server", Some("fs")).unwrap();
+        assert_eq!(target.dir(), "/data/local/tmp");
+
+        let candidates = android_server_candidates(
+            target,
+            &["/data/local/tmp/".to_string(), "/data/adb/".to_string()],
+        );
+        let paths: Vec<_> = candidates.iter().map(|c| c.remote_path.as_str()).collect();
+        assert_eq!(paths, ["/data/local/tmp/fs", "/data/adb/fs"]);
+    }
+}
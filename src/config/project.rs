@@ -86,12 +86,41 @@ impl ProjectConfigManager {
             current = current.parent()?;
         }
     }
+
+    /// Directory a venv command should treat as the project: the nearest ancestor holding
+    /// frida.toml (so subdirectories work like cargo/git), or `start_dir` itself when
+    /// discovery is disabled or no project is found.
+    pub fn discover_project_dir(start_dir: &Path, discover: bool) -> PathBuf {
+        if discover {
+            if let Some(root) = Self::find_project_root(start_dir) {
+                return root;
+            }
+        }
+        start_dir.to_path_buf()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn discovers_project_root_from_subdirectory() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("scripts").join("hooks");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join(PROJECT_CONFIG_FILE), "").unwrap();
+
+        assert_eq!(
+            ProjectConfigManager::discover_project_dir(&nested, true),
+            dir.path()
+        );
+        assert_eq!(
+            ProjectConfigManager::discover_project_dir(&nested, false),
+            nested
+        );
+    }
+
     #[tokio::test]
     async fn local_server_requires_tools_version() {
         let dir = tempfile::tempdir().unwrap();
@@ -192,4 +221,149 @@ root_command = "su"
         let config = mgr.load().await.unwrap();
         assert_eq!(config.frida.tools_version, None);
     }
+
+    #[tokio::test]
+    async fn local_server_paths_select_by_arch() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ProjectConfigManager::new(dir.path());
+
+        let toml = r#"
+[project]
+name = "t"
+
+[python]
+version = "3.11"
+
+[frida]
+version = "16.6.6"
+tools_version = "13.3.0"
+
+[android.server]
+source = "local"
+
+[android.server.local]
+paths = { arm64 = "./bin/frida-server-arm64", x86_64 = "./bin/frida-server-x86_64" }
+"#;
+
+        tokio::fs::write(mgr.config_path(), toml).await.unwrap();
+
+        let config = mgr.load().await.unwrap();
+        let local = config.android.server.local.unwrap();
+        assert_eq!(
+            local.path_for(&crate::config::ArchType::X8664),
+            Some("./bin/frida-server-x86_64")
+        );
+        assert_eq!(local.path_for(&crate::config::ArchType::Arm), None);
+
+        let bad = toml.replace("x86_64 =", "mips =");
+        tokio::fs::write(mgr.config_path(), bad).await.unwrap();
+        let err = mgr.load().await.unwrap_err().to_string();
+        assert!(
+            err.contains("unknown arch 'mips'"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn server_log_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ProjectConfigManager::new(dir.path());
+
+        let toml = r#"
+[project]
+name = "t"
+
+[python]
+version = "3.11"
+
+[frida]
+version = "16.6.6"
+
+[android.log]
+path = "/data/local/tmp/fs.log"
+max_size_kb = 0
+"#;
+
+        tokio::fs::write(mgr.config_path(), toml).await.unwrap();
+        let config = mgr.load().await.unwrap();
+        assert_eq!(
+            config.android.log.path_for("/data/local/tmp/fs"),
+            "/data/local/tmp/fs.log"
+        );
+        assert_eq!(config.android.log.max_size_kb, 0);
+
+        let disabled = toml.replace("max_size_kb = 0", "enabled = false");
+        tokio::fs::write(mgr.config_path(), disabled).await.unwrap();
+        let config = mgr.load().await.unwrap();
+        assert_eq!(
+            config.android.log.path_for("/data/local/tmp/fs"),
+            crate::config::SERVER_LOG_DISABLED
+        );
+        assert_eq!(config.android.log.file_for("/data/local/tmp/fs"), None);
+
+        let relative = toml.replace("/data/local/tmp/fs.log", "fs.log");
+        tokio::fs::write(mgr.config_path(), relative).await.unwrap();
+        assert!(mgr.load().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn server_repo_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ProjectConfigManager::new(dir.path());
+
+        let toml = r#"
+[project]
+name = "t"
+
+[python]
+version = "3.11"
+
+[frida]
+version = "16.6.6"
+
+[android.server]
+repo = "owner/frida-fork"
+asset = "fork-server-{version}-android-{arch}.gz"
+"#;
+
+        tokio::fs::write(mgr.config_path(), toml).await.unwrap();
+        let config = mgr.load().await.unwrap();
+        assert_eq!(
+            config.android.server.repo.as_deref(),
+            Some("owner/frida-fork")
+        );
+
+        let bad_repo = toml.replace("owner/frida-fork", "frida-fork");
+        tokio::fs::write(mgr.config_path(), bad_repo).await.unwrap();
+        assert!(mgr.load().await.is_err());
+
+        let no_arch = toml.replace("{arch}", "arm64");
+        tokio::fs::write(mgr.config_path(), no_arch).await.unwrap();
+        assert!(mgr.load().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn local_platform_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ProjectConfigManager::new(dir.path());
+
+        let mut config = ProjectConfig::default();
+        mgr.save(&config).await.unwrap();
+        let saved = tokio::fs::read_to_string(mgr.config_path()).await.unwrap();
+        assert!(!saved.contains("platform"), "{}", saved);
+        assert!(config.downloads_android_server());
+
+        config.project.platform = crate::config::ProjectPlatform::Local;
+        mgr.save(&config).await.unwrap();
+        let saved = tokio::fs::read_to_string(mgr.config_path()).await.unwrap();
+        assert!(saved.contains("platform = \"local\""), "{}", saved);
+
+        let loaded = mgr.load().await.unwrap();
+        assert_eq!(
+            loaded.project.platform,
+            crate::config::ProjectPlatform::Local
+        );
+        assert!(!loaded.downloads_android_server());
+    }
 }
@@ -0,0 +1,172 @@
+use crate::config::{ProjectConfig, ProjectConfigManager};
+use crate::core::{ensure_dir_exists, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Projects known to frida-mgr, recorded whenever a project is initialized or synced.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProjectRegistry {
+    #[serde(default)]
+    pub projects: Vec<String>,
+}
+
+impl ProjectRegistry {
+    pub async fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).await?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            ensure_dir_exists(parent).await?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).await?;
+        Ok(())
+    }
+
+    /// Record a project directory. Returns true if the registry changed.
+    pub fn register(&mut self, project_dir: &Path) -> bool {
+        let path = project_dir.to_string_lossy().to_string();
+        if self.projects.contains(&path) {
+            return false;
+        }
+        self.projects.push(path);
+        self.projects.sort();
+        true
+    }
+
+    /// Drop entries whose directory no longer contains a frida.toml. Returns the removed paths.
+    pub fn prune_missing(&mut self) -> Vec<String> {
+        let (kept, removed): (Vec<String>, Vec<String>) = self
+            .projects
+            .drain(..)
+            .partition(|p| ProjectConfigManager::new(Path::new(p)).exists());
+        self.projects = kept;
+        removed
+    }
+
+    pub fn project_dirs(&self) -> Vec<PathBuf> {
+        self.projects.iter().map(PathBuf::from).collect()
+    }
+
+    /// Configs of registered projects that still have a readable frida.toml.
+    pub async fn project_configs(&self) -> Vec<ProjectConfig> {
+        let mut configs = Vec::new();
+        for dir in self.project_dirs() {
+            if let Ok(config) = ProjectConfigManager::new(&dir).load().await {
+                configs.push(config);
+            }
+        }
+        configs
+    }
+
+    /// Frida versions referenced by registered projects that still have a readable frida.toml.
+    pub async fn referenced_frida_versions(&self) -> HashSet<String> {
+        self.project_configs()
+            .await
+            .into_iter()
+            .map(|config| config.frida.version)
+            .collect()
+    }
+}
+
+/// Best-effort registration of a project in the global registry file: an unreadable or
+/// unwritable registry only prints a warning, since it merely narrows cache pruning.
+pub async fn register_project(registry_path: &Path, project_dir: &Path) {
+    if let Err(e) = try_register_project(registry_path, project_dir).await {
+        eprintln!(
+            "{} Could not record the project in {}: {}",
+            "⚠".yellow().bold(),
+            registry_path.display(),
+            e
+        );
+    }
+}
+
+async fn try_register_project(registry_path: &Path, project_dir: &Path) -> Result<()> {
+    let mut registry = ProjectRegistry::load_or_default(registry_path).await?;
+    if registry.register(project_dir) {
+        registry.save(registry_path).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_is_idempotent() {
+        let mut registry = ProjectRegistry::default();
+        assert!(registry.register(Path::new("/work/a")));
+        assert!(!registry.register(Path::new("/work/a")));
+        assert!(registry.register(Path::new("/work/b")));
+        assert_eq!(registry.projects, vec!["/work/a", "/work/b"]);
+    }
+
+    #[tokio::test]
+    async fn register_tolerates_a_corrupt_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry_path = dir.path().join("projects.toml");
+        tokio::fs::write(&registry_path, "projects = [")
+            .await
+            .unwrap();
+
+        register_project(&registry_path, dir.path()).await;
+        assert_eq!(
+            tokio::fs::read_to_string(&registry_path).await.unwrap(),
+            "projects = ["
+        );
+    }
+
+    #[tokio::test]
+    async fn roundtrip_and_referenced_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("proj");
+        tokio::fs::create_dir_all(&project_dir).await.unwrap();
+        tokio::fs::write(
+            project_dir.join("frida.toml"),
+            r#"
+[project]
+name = "t"
+
+[python]
+version = "3.11"
+
+[frida]
+version = "16.5.2"
+
+[android]
+arch = "arm64"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let registry_path = dir.path().join("projects.toml");
+        register_project(&registry_path, &project_dir).await;
+        register_project(&registry_path, &dir.path().join("gone")).await;
+
+        let registry = ProjectRegistry::load_or_default(&registry_path)
+            .await
+            .unwrap();
+        assert_eq!(registry.projects.len(), 2);
+
+        let versions = registry.referenced_frida_versions().await;
+        assert_eq!(versions.len(), 1);
+        assert!(versions.contains("16.5.2"));
+
+        let mut registry = registry;
+        let removed = registry.prune_missing();
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].ends_with("gone"));
+        assert_eq!(registry.project_dirs(), vec![project_dir]);
+    }
+}
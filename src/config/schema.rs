@@ -1,21 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub const DEFAULT_ANDROID_SERVER_NAME: &str = "frida-server";
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum AgentBuildTool {
+    #[default]
     FridaCompile,
     Esbuild,
 }
 
-impl Default for AgentBuildTool {
-    fn default() -> Self {
-        Self::FridaCompile
-    }
-}
-
 impl AgentBuildTool {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -38,6 +33,9 @@ pub struct AgentConfig {
     pub out: String,
     #[serde(default)]
     pub tool: AgentBuildTool,
+    /// Emit source maps and translate agent stack traces in `top`/`spawn` back to the sources.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sourcemap: bool,
 }
 
 impl Default for AgentConfig {
@@ -47,6 +45,7 @@ impl Default for AgentConfig {
             entry: default_agent_entry(),
             out: default_agent_out(),
             tool: AgentBuildTool::default(),
+            sourcemap: false,
         }
     }
 }
@@ -57,6 +56,32 @@ impl AgentConfig {
             && self.entry == default_agent_entry()
             && self.out == default_agent_out()
             && self.tool == AgentBuildTool::default()
+            && !self.sourcemap
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScriptsConfig {
+    /// Directories, relative to project root, searched for `-l <name>` scripts.
+    #[serde(default = "default_script_paths")]
+    pub paths: Vec<String>,
+    /// Named standalone scripts (`frida-mgr script new`), path relative to project root.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub registry: BTreeMap<String, String>,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            paths: default_script_paths(),
+            registry: BTreeMap::new(),
+        }
+    }
+}
+
+impl ScriptsConfig {
+    fn is_default(&self) -> bool {
+        self.paths == default_script_paths() && self.registry.is_empty()
     }
 }
 
@@ -70,8 +95,32 @@ pub struct ProjectConfig {
     pub android: AndroidConfig,
     #[serde(default, skip_serializing_if = "AgentConfig::is_default")]
     pub agent: AgentConfig,
+    #[serde(default, skip_serializing_if = "ScriptsConfig::is_default")]
+    pub scripts: ScriptsConfig,
     #[serde(default)]
     pub environment: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub apps: BTreeMap<String, AppProfile>,
+}
+
+/// A named target app (`[apps.<name>]`), applied by `frida-mgr run-app <name>`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppProfile {
+    pub package: String,
+    /// Scripts to load: paths relative to the project, or bare names looked up in `scripts.paths`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<String>,
+    /// Spawn the app instead of attaching to the running process.
+    #[serde(default)]
+    pub spawn: bool,
+    /// Build and load the project agent (default: when the agent entry exists).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Extra frida arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -79,6 +128,24 @@ pub struct ProjectMeta {
     pub name: String,
     #[serde(default)]
     pub description: String,
+    /// What the project instruments: an Android device (default) or processes on this host.
+    #[serde(default, skip_serializing_if = "ProjectPlatform::is_default")]
+    pub platform: ProjectPlatform,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectPlatform {
+    #[default]
+    Android,
+    /// Attach to and spawn host processes with frida's local device; adb is never used.
+    Local,
+}
+
+impl ProjectPlatform {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -93,6 +160,12 @@ pub struct FridaConfig {
     pub version: String,
     #[serde(default)]
     pub tools_version: Option<String>,
+    /// Always tee frida/top/spawn output into `logs/` (same as passing `--log-output`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub log_output: bool,
+    /// Let `frida-mgr spawn` resume the app right after spawning (override with `--pause`).
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub spawn_no_pause: bool,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -107,55 +180,201 @@ pub struct AndroidConfig {
     pub arch: ArchType,
     #[serde(default)]
     pub server_name: Option<String>,
+    /// Push frida-server under a fresh random name every time (see `ServerNames`), for apps
+    /// that scan `/proc` for `frida-server`.
+    #[serde(default)]
+    pub randomize_server_name: bool,
     #[serde(default = "default_port")]
     pub server_port: u16,
     #[serde(default)]
     pub auto_start: bool,
+    /// `top`/`spawn` start frida-server on demand and stop it when the session ends; a
+    /// randomized server is also deleted from the device.
+    #[serde(default)]
+    pub stop_on_exit: bool,
+    /// `adb forward` `server_port` to this machine whenever the server is started, so
+    /// tools can connect with `-H 127.0.0.1:<server_port>`.
+    #[serde(default)]
+    pub auto_forward: bool,
+    /// `run-as` runs frida-server as `run_as_package` instead of root, for debuggable apps
+    /// on unrooted devices.
+    #[serde(default, skip_serializing_if = "AndroidServerMode::is_root")]
+    pub mode: AndroidServerMode,
+    /// Debuggable app whose data dir hosts frida-server in `run-as` mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as_package: Option<String>,
     #[serde(default = "default_root_command")]
     pub root_command: String,
+    /// `permissive` runs `setenforce 0` through `root_command` before starting
+    /// frida-server when the device's SELinux is enforcing.
+    #[serde(default, skip_serializing_if = "SelinuxPolicy::is_keep")]
+    pub selinux: SelinuxPolicy,
+    /// Device serial or alias pinned with `frida-mgr device use`; commands run without
+    /// `--device` use it instead of the first connected device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
     #[serde(default, skip_serializing_if = "AndroidServerConfig::is_default")]
     pub server: AndroidServerConfig,
+    #[serde(default, skip_serializing_if = "ServerLogConfig::is_default")]
+    pub log: ServerLogConfig,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+impl AndroidConfig {
+    /// The app frida-server runs as in `run-as` mode; `None` in root mode.
+    pub fn run_as(&self) -> Option<&str> {
+        match self.mode {
+            AndroidServerMode::Root => None,
+            AndroidServerMode::RunAs => self.run_as_package.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct AndroidServerConfig {
     #[serde(default)]
     pub source: AndroidServerSource,
     #[serde(default)]
     pub local: Option<LocalServerConfig>,
+    /// GitHub `owner/repo` to download builds from instead of `frida/frida` (e.g. an
+    /// anti-detection fork).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Release asset name in `repo`, with `{version}` and `{arch}` placeholders
+    /// (default: `frida-server-{version}-android-{arch}.xz`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+}
+
+impl AndroidServerConfig {
+    fn is_default(&self) -> bool {
+        self.source == AndroidServerSource::Download
+            && self.local.is_none()
+            && self.repo.is_none()
+            && self.asset.is_none()
+    }
+}
+
+/// Where frida-server's output goes on the device (`[android.log]`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServerLogConfig {
+    /// `false` sends the output to /dev/null.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Log file on the device (default: `<server path>.log`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Before each start, a log larger than this moves to `<path>.1`; 0 truncates it instead.
+    #[serde(default = "default_log_max_size_kb")]
+    pub max_size_kb: u64,
 }
 
-impl Default for AndroidServerConfig {
+impl Default for ServerLogConfig {
     fn default() -> Self {
         Self {
-            source: AndroidServerSource::default(),
-            local: None,
+            enabled: true,
+            path: None,
+            max_size_kb: default_log_max_size_kb(),
         }
     }
 }
 
-impl AndroidServerConfig {
+impl ServerLogConfig {
     fn is_default(&self) -> bool {
-        self.source == AndroidServerSource::Download && self.local.is_none()
+        self.enabled && self.path.is_none() && self.max_size_kb == default_log_max_size_kb()
+    }
+
+    /// The log file for the server at `server_path`, or /dev/null when logging is disabled.
+    pub fn path_for(&self, server_path: &str) -> String {
+        if !self.enabled {
+            return SERVER_LOG_DISABLED.to_string();
+        }
+        self.path
+            .clone()
+            .unwrap_or_else(|| format!("{}.log", server_path))
+    }
+
+    /// The log file the server at `server_path` writes, or `None` when logging is
+    /// disabled (and there is no file to clean up).
+    pub fn file_for(&self, server_path: &str) -> Option<String> {
+        Some(self.path_for(server_path)).filter(|path| path != SERVER_LOG_DISABLED)
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+/// Log target used when `android.log.enabled = false`.
+pub const SERVER_LOG_DISABLED: &str = "/dev/null";
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum AndroidServerSource {
+    #[default]
     Download,
     Local,
+    /// Downloaded from the releases of `android.server.repo` (Florida, hluda, ...) using the
+    /// `android.server.asset` name template.
+    Custom,
 }
 
-impl Default for AndroidServerSource {
-    fn default() -> Self {
-        Self::Download
+/// Whose privileges frida-server runs with (`android.mode`).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AndroidServerMode {
+    /// Started through `root_command`, listening on every interface.
+    #[default]
+    Root,
+    /// Started with `run-as <run_as_package>` from the app's data dir, listening on the
+    /// device's loopback and reached through `adb forward`.
+    RunAs,
+}
+
+impl AndroidServerMode {
+    fn is_root(&self) -> bool {
+        *self == AndroidServerMode::Root
+    }
+}
+
+/// What `start` does about SELinux on the device (`android.selinux`).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SelinuxPolicy {
+    /// Leave the device's SELinux mode alone.
+    #[default]
+    Keep,
+    /// Switch an enforcing device to permissive before starting frida-server.
+    Permissive,
+}
+
+impl SelinuxPolicy {
+    fn is_keep(&self) -> bool {
+        *self == SelinuxPolicy::Keep
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LocalServerConfig {
+    /// Binary used for any device arch without an entry in `paths`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub path: String,
+    /// Per-arch binaries keyed by `arm`/`arm64`/`x86`/`x86_64`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<String, String>,
+}
+
+impl LocalServerConfig {
+    /// The binary configured for `arch`, falling back to `path`.
+    pub fn path_for(&self, arch: &ArchType) -> Option<&str> {
+        self.paths
+            .get(arch.to_str())
+            .map(String::as_str)
+            .or_else(|| (!self.path.is_empty()).then_some(self.path.as_str()))
+    }
+
+    /// Every configured binary path.
+    pub fn all_paths(&self) -> impl Iterator<Item = &str> {
+        (!self.path.is_empty())
+            .then_some(self.path.as_str())
+            .into_iter()
+            .chain(self.paths.values().map(String::as_str))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -170,6 +389,14 @@ pub enum ArchType {
 }
 
 impl ArchType {
+    /// Concrete Android architectures frida-server is published for.
+    pub const ANDROID: [ArchType; 4] = [
+        ArchType::Arm,
+        ArchType::Arm64,
+        ArchType::X86,
+        ArchType::X8664,
+    ];
+
     pub fn to_str(&self) -> &str {
         match self {
             ArchType::Auto => "auto",
@@ -203,6 +430,10 @@ fn default_port() -> u16 {
     27042
 }
 
+fn default_log_max_size_kb() -> u64 {
+    1024
+}
+
 fn default_root_command() -> String {
     "su".to_string()
 }
@@ -219,12 +450,26 @@ fn default_agent_out() -> String {
     "dist/agent.js".to_string()
 }
 
+fn default_script_paths() -> Vec<String> {
+    vec!["scripts".to_string()]
+}
+
+impl ProjectConfig {
+    /// Whether frida-server builds are fetched from GitHub for this project: Android
+    /// projects with `android.server.source = "download"`.
+    pub fn downloads_android_server(&self) -> bool {
+        self.project.platform == ProjectPlatform::Android
+            && self.android.server.source != AndroidServerSource::Local
+    }
+}
+
 impl Default for ProjectConfig {
     fn default() -> Self {
         Self {
             project: ProjectMeta {
                 name: "frida-project".to_string(),
                 description: String::new(),
+                platform: ProjectPlatform::default(),
             },
             python: PythonConfig {
                 version: "3.11".to_string(),
@@ -233,18 +478,30 @@ impl Default for ProjectConfig {
             frida: FridaConfig {
                 version: "16.6.6".to_string(),
                 tools_version: None,
+                log_output: false,
+                spawn_no_pause: true,
             },
             objection: ObjectionConfig { version: None },
             android: AndroidConfig {
                 arch: default_arch(),
                 server_name: Some(default_server_name()),
+                randomize_server_name: false,
                 server_port: default_port(),
                 auto_start: false,
+                stop_on_exit: false,
+                auto_forward: false,
+                mode: AndroidServerMode::Root,
+                run_as_package: None,
                 root_command: default_root_command(),
+                selinux: SelinuxPolicy::Keep,
+                device: None,
                 server: AndroidServerConfig::default(),
+                log: ServerLogConfig::default(),
             },
             agent: AgentConfig::default(),
+            scripts: ScriptsConfig::default(),
             environment: HashMap::new(),
+            apps: BTreeMap::new(),
         }
     }
 }
@@ -256,6 +513,11 @@ pub struct GlobalConfig {
     pub android: GlobalAndroidConfig,
     pub network: NetworkConfig,
     pub defaults: DefaultsConfig,
+    #[serde(default, skip_serializing_if = "DevicesConfig::is_default")]
+    pub devices: DevicesConfig,
+    /// Send a desktop notification when long-running steps finish.
+    #[serde(default)]
+    pub notifications: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -278,6 +540,14 @@ pub struct GlobalAndroidConfig {
     pub adb_path: String,
     #[serde(default = "default_push_path")]
     pub default_push_path: String,
+    /// Directories tried in order when the directory of `default_push_path` is not
+    /// writable or is mounted noexec on a device.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub push_path_fallbacks: Vec<String>,
+    /// `host:port` endpoints remembered by `frida-mgr connect`; adb is reconnected to them
+    /// when a command needs a device and none is attached.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wireless_devices: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -286,8 +556,47 @@ pub struct NetworkConfig {
     pub timeout_seconds: u64,
     #[serde(default = "default_retries")]
     pub max_retries: u32,
+    /// Where github.com downloads and release pages are fetched first: `github` (direct),
+    /// `ghproxy`, or a URL template (see `core::mirror::Mirror`), falling back to github.com.
     #[serde(default = "default_mirror")]
     pub mirror: String,
+    /// Proxy URL for all HTTP traffic (http://, https://, socks5://). Falls back to
+    /// HTTPS_PROXY/HTTP_PROXY/ALL_PROXY when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Concurrent ranged requests per asset download (1 = single stream).
+    #[serde(default = "default_parallel_chunks")]
+    pub parallel_chunks: usize,
+    /// Alternate bases tried in place of https://github.com when a release asset
+    /// download fails (e.g. a GitHub proxy or a self-hosted mirror of the release tree).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_hosts: Vec<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: default_timeout(),
+            max_retries: default_retries(),
+            mirror: default_mirror(),
+            proxy: None,
+            parallel_chunks: default_parallel_chunks(),
+            fallback_hosts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DevicesConfig {
+    /// Named sets of device serials or aliases, targeted with `--group <name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl DevicesConfig {
+    fn is_default(&self) -> bool {
+        self.groups.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -304,6 +613,10 @@ fn default_true() -> bool {
     true
 }
 
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
 fn default_adb_path() -> String {
     "adb".to_string()
 }
@@ -324,6 +637,10 @@ fn default_mirror() -> String {
     "github".to_string()
 }
 
+fn default_parallel_chunks() -> usize {
+    1
+}
+
 impl Default for GlobalConfig {
     fn default() -> Self {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -342,16 +659,16 @@ impl Default for GlobalConfig {
             android: GlobalAndroidConfig {
                 adb_path: default_adb_path(),
                 default_push_path: default_push_path(),
+                push_path_fallbacks: Vec::new(),
+                wireless_devices: Vec::new(),
             },
-            network: NetworkConfig {
-                timeout_seconds: default_timeout(),
-                max_retries: default_retries(),
-                mirror: default_mirror(),
-            },
+            network: NetworkConfig::default(),
             defaults: DefaultsConfig {
                 python_version: "3.11".to_string(),
                 frida_version: "16.6.6".to_string(),
             },
+            devices: DevicesConfig::default(),
+            notifications: false,
         }
     }
 }
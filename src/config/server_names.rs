@@ -0,0 +1,86 @@
+use crate::core::{ensure_dir_exists, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use tokio::fs;
+
+/// The randomized frida-server name last pushed to each device (`push --random-name` or
+/// `android.randomize_server_name`), so later commands look for that process instead of
+/// `android.server_name`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ServerNames {
+    #[serde(default)]
+    pub devices: BTreeMap<String, String>,
+}
+
+impl ServerNames {
+    pub async fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).await?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            ensure_dir_exists(parent).await?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).await?;
+        Ok(())
+    }
+
+    /// The randomized name active on `device_id`, if any.
+    pub fn get(&self, device_id: &str) -> Option<&str> {
+        self.devices.get(device_id).map(String::as_str)
+    }
+
+    /// Record `name` as active on `device_id`, or with `None` go back to the configured
+    /// name. Returns true if the file needs saving.
+    pub fn set(&mut self, device_id: &str, name: Option<&str>) -> bool {
+        match name {
+            Some(name) => {
+                self.devices
+                    .insert(device_id.to_string(), name.to_string())
+                    .as_deref()
+                    != Some(name)
+            }
+            None => self.devices.remove(device_id).is_some(),
+        }
+    }
+}
+
+/// A fresh server name: eight hex characters, which nothing on the device ties to frida.
+pub fn random_server_name() -> String {
+    format!("{:08x}", RandomState::new().build_hasher().finish() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn names_roundtrip_per_device() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server-names.toml");
+
+        let mut names = ServerNames::default();
+        assert!(names.set("emulator-5554", Some("1a2b3c4d")));
+        assert!(!names.set("emulator-5554", Some("1a2b3c4d")));
+        assert!(names.set("R58M123", Some("deadbeef")));
+        assert!(names.set("R58M123", None));
+        assert!(!names.set("R58M123", None));
+        names.save(&path).await.unwrap();
+
+        let loaded = ServerNames::load_or_default(&path).await.unwrap();
+        assert_eq!(loaded.get("emulator-5554"), Some("1a2b3c4d"));
+        assert_eq!(loaded.get("R58M123"), None);
+
+        let name = random_server_name();
+        assert_eq!(name.len(), 8);
+        assert!(name.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
@@ -1,7 +1,10 @@
-use crate::config::schema::{AndroidServerSource, ProjectConfig};
+use crate::config::schema::{AndroidServerMode, AndroidServerSource, ProjectConfig};
 use crate::core::error::{FridaMgrError, Result};
 use semver::Version;
 
+/// Keys accepted in `android.server.local.paths`.
+const LOCAL_SERVER_ARCHES: &[&str] = &["arm", "arm64", "x86", "x86_64"];
+
 pub fn validate_android_server_name(name: &str) -> Result<()> {
     if name.is_empty() {
         return Err(FridaMgrError::Config(
@@ -88,6 +91,21 @@ pub fn validate_project_config(config: &ProjectConfig) -> Result<()> {
         ));
     }
 
+    if config.android.mode == AndroidServerMode::RunAs && config.android.run_as_package.is_none() {
+        return Err(FridaMgrError::Config(
+            "android.mode = \"run-as\" requires android.run_as_package".to_string(),
+        ));
+    }
+
+    if let Some(path) = config.android.log.path.as_deref() {
+        if !path.starts_with('/') || path.contains('\'') {
+            return Err(FridaMgrError::Config(format!(
+                "android.log.path must be an absolute device path without quotes, got '{}'",
+                path
+            )));
+        }
+    }
+
     if config.agent.dir.trim().is_empty() {
         return Err(FridaMgrError::Config("agent.dir cannot be empty".to_string()));
     }
@@ -100,6 +118,24 @@ pub fn validate_project_config(config: &ProjectConfig) -> Result<()> {
         return Err(FridaMgrError::Config("agent.out cannot be empty".to_string()));
     }
 
+    for (name, app) in &config.apps {
+        if app.package.trim().is_empty() {
+            return Err(FridaMgrError::Config(format!(
+                "apps.{}.package cannot be empty",
+                name
+            )));
+        }
+    }
+
+    if config.android.server.source == AndroidServerSource::Custom
+        && config.android.server.repo.is_none()
+    {
+        return Err(FridaMgrError::Config(
+            "android.server.repo is required when android.server.source = \"custom\" (e.g. \"Ylarod/Florida\")"
+                .to_string(),
+        ));
+    }
+
     if config.android.server.source == AndroidServerSource::Local {
         let tools_version_ok = config
             .frida
@@ -120,9 +156,56 @@ pub fn validate_project_config(config: &ProjectConfig) -> Result<()> {
             )
         })?;
 
-        if local.path.trim().is_empty() {
+        if local.path.trim().is_empty() && local.paths.is_empty() {
             return Err(FridaMgrError::Config(
-                "android.server.local.path cannot be empty".to_string(),
+                "android.server.local.path or android.server.local.paths is required".to_string(),
+            ));
+        }
+
+        for (arch, path) in &local.paths {
+            if !LOCAL_SERVER_ARCHES.contains(&arch.as_str()) {
+                return Err(FridaMgrError::Config(format!(
+                    "android.server.local.paths: unknown arch '{}' (expected one of {})",
+                    arch,
+                    LOCAL_SERVER_ARCHES.join(", ")
+                )));
+            }
+            if path.trim().is_empty() {
+                return Err(FridaMgrError::Config(format!(
+                    "android.server.local.paths.{} cannot be empty",
+                    arch
+                )));
+            }
+        }
+    }
+
+    if let Some(repo) = config.android.server.repo.as_deref() {
+        let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+            [owner, name].iter().all(|part| {
+                !part.is_empty()
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+            })
+        });
+        if !valid {
+            return Err(FridaMgrError::Config(format!(
+                "android.server.repo must be a GitHub \"owner/repo\", got '{}'",
+                repo
+            )));
+        }
+    }
+
+    if let Some(asset) = config.android.server.asset.as_deref() {
+        if config.android.server.repo.is_none() {
+            return Err(FridaMgrError::Config(
+                "android.server.asset requires android.server.repo".to_string(),
+            ));
+        }
+        if !asset.contains("{arch}") || asset.contains('/') {
+            return Err(FridaMgrError::Config(
+                "android.server.asset must be a file name containing {arch} (and usually {version})"
+                    .to_string(),
             ));
         }
     }
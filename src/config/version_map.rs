@@ -42,6 +42,50 @@ pub struct ObjectionVersionResolution {
     pub mapped_from_frida: String,
 }
 
+/// Why a mapping would break `install`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingIssue {
+    /// The pinned release is not (or no longer) on PyPI.
+    Missing {
+        package: &'static str,
+        version: String,
+    },
+    /// The pinned release's `requires_dist` excludes the mapped Frida version.
+    Incompatible {
+        package: &'static str,
+        version: String,
+        requires: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for MappingIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappingIssue::Missing { package, version } => {
+                write!(f, "{} {} is not on PyPI", package, version)
+            }
+            MappingIssue::Incompatible {
+                package,
+                version,
+                requires,
+            } => write!(
+                f,
+                "{} {} requires {}",
+                package,
+                version,
+                requires.join(", ")
+            ),
+        }
+    }
+}
+
+/// A broken entry found by [`VersionMapping::verify_against_pypi`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingProblem {
+    pub frida: String,
+    pub issue: MappingIssue,
+}
+
 impl VersionMapping {
     pub fn builtin() -> Self {
         let mut mappings = HashMap::new();
@@ -197,12 +241,76 @@ impl VersionMapping {
         versions
     }
 
-    pub async fn build_from_github_releases(include_prerelease: bool) -> Result<Self> {
-        let http = HttpClient::new();
+    /// Check every mapping against PyPI: the pinned frida-tools/objection releases must
+    /// exist and their `requires_dist` must accept the mapped Frida version.
+    pub async fn verify_against_pypi(&self, http: &HttpClient) -> Result<Vec<MappingProblem>> {
+        // "package==version" -> None when missing on PyPI, else its requires_dist.
+        let mut cache: HashMap<String, Option<Option<Vec<String>>>> = HashMap::new();
+        let mut problems = Vec::new();
+
+        for frida in self.list_versions() {
+            let Some(info) = self.mappings.get(&frida) else {
+                continue;
+            };
+            let mut pins = vec![("frida-tools", info.tools.clone())];
+            if let Some(objection) = &info.objection {
+                pins.push(("objection", objection.clone()));
+            }
+
+            for (package, version) in pins {
+                let key = format!("{}=={}", package, version);
+                let requires = match cache.get(&key) {
+                    Some(v) => v.clone(),
+                    None => {
+                        let url = format!("https://pypi.org/pypi/{}/{}/json", package, version);
+                        let v = if http.url_exists(&url).await? {
+                            Some(fetch_pypi_requires_dist(http, package, &version).await?)
+                        } else {
+                            None
+                        };
+                        cache.insert(key, v.clone());
+                        v
+                    }
+                };
+
+                let issue = match requires {
+                    None => Some(MappingIssue::Missing { package, version }),
+                    Some(requires) => {
+                        requires_issue(package, &version, requires.as_deref(), &frida)
+                    }
+                };
+                if let Some(issue) = issue {
+                    problems.push(MappingProblem {
+                        frida: frida.clone(),
+                        issue,
+                    });
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Drop mappings and any aliases pointing at them; `latest`/`stable`/`lts` are
+    /// re-derived from what remains.
+    pub fn remove_versions(&mut self, versions: &[String]) {
+        for version in versions {
+            self.mappings.remove(version);
+        }
+        self.aliases
+            .retain(|_, target| self.mappings.contains_key(target));
+        for (alias, target) in build_default_aliases(&self.mappings) {
+            self.aliases.entry(alias).or_insert(target);
+        }
+    }
 
+    pub async fn build_from_github_releases(
+        http: &HttpClient,
+        include_prerelease: bool,
+    ) -> Result<Self> {
         // Prefer Atom (no auth, 1 request), but in some environments it may return HTML.
         // Fallback to parsing the Releases HTML page (polite pagination).
-        let frida = fetch_repo_releases(&http, "frida", "frida", include_prerelease).await?;
+        let frida = fetch_repo_releases(http, "frida", "frida", include_prerelease).await?;
 
         // Prefer PyPI as the source-of-truth for installable Python package versions.
         // (GitHub tags don't always correspond 1:1 with PyPI releases, and dependencies can change.)
@@ -210,11 +318,11 @@ impl VersionMapping {
         // If PyPI is unavailable, fall back to GitHub timestamps, but avoid pinning far-future
         // versions to reduce incompatibility risk.
         let (tools_by_date, tools_from_pypi) =
-            match fetch_pypi_releases(&http, "frida-tools", include_prerelease).await {
+            match fetch_pypi_releases(http, "frida-tools", include_prerelease).await {
                 Ok(v) => (v, true),
                 Err(_) => {
                     sleep(Duration::from_millis(200)).await;
-                    let v = fetch_repo_releases(&http, "frida", "frida-tools", include_prerelease)
+                    let v = fetch_repo_releases(http, "frida", "frida-tools", include_prerelease)
                         .await?
                         .into_iter()
                         .map(|r| PypiRelease {
@@ -230,7 +338,7 @@ impl VersionMapping {
         // but we filter out versions that don't exist on PyPI to avoid non-installable pins.
         sleep(Duration::from_millis(200)).await;
         let mut objection_by_date =
-            fetch_repo_releases(&http, "sensepost", "objection", include_prerelease).await?;
+            fetch_repo_releases(http, "sensepost", "objection", include_prerelease).await?;
         objection_by_date.sort_by_key(|r| r.published_at);
         let mut objection_exists_cache: HashMap<String, Option<bool>> = HashMap::new();
         let mut tools_requires_cache: HashMap<String, Option<Vec<String>>> = HashMap::new();
@@ -240,7 +348,7 @@ impl VersionMapping {
         for fr in frida {
             let tools_release = if tools_from_pypi {
                 select_compatible_tools_release_for_frida(
-                    &http,
+                    http,
                     &tools_by_date,
                     &mut tools_requires_cache,
                     &fr.version,
@@ -253,7 +361,7 @@ impl VersionMapping {
 
             if let Some(tools_release) = tools_release {
                 let objection_release = select_objection_release_for_frida(
-                    &http,
+                    http,
                     &objection_by_date,
                     &mut objection_exists_cache,
                     fr.published_at,
@@ -327,6 +435,43 @@ mod tests {
         assert_eq!(created.mappings.len(), loaded.mappings.len());
     }
 
+    #[test]
+    fn test_requires_issue_reports_frida_bounds() {
+        let reqs = vec![
+            "frida>=17.0.0".to_string(),
+            "frida-tools>=13.0.0".to_string(),
+            "colorama".to_string(),
+        ];
+        assert_eq!(
+            requires_issue("objection", "1.12.0", Some(&reqs), "16.6.6"),
+            Some(MappingIssue::Incompatible {
+                package: "objection",
+                version: "1.12.0".to_string(),
+                requires: vec!["frida>=17.0.0".to_string()],
+            })
+        );
+        assert_eq!(
+            requires_issue("objection", "1.12.0", Some(&reqs), "17.1.0"),
+            None
+        );
+        assert_eq!(
+            requires_issue("frida-tools", "13.3.0", None, "16.6.6"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_versions_rederives_aliases() {
+        let mut mapping = VersionMapping::builtin();
+        let latest = mapping.resolve_alias("latest");
+        mapping.remove_versions(std::slice::from_ref(&latest));
+
+        assert!(!mapping.mappings.contains_key(&latest));
+        let new_latest = mapping.resolve_alias("latest");
+        assert_ne!(new_latest, latest);
+        assert!(mapping.mappings.contains_key(&new_latest));
+    }
+
     #[test]
     fn test_find_nearest_by_date() {
         let tools = vec![
@@ -731,7 +876,7 @@ async fn fetch_repo_releases(
     // If HTML fails but Atom succeeded, fall back to the partial Atom result.
     match fetch_html_releases(http, owner, repo, include_prerelease, MAX_HTML_PAGES).await {
         Ok(html) => all.extend(html),
-        Err(e) if !all.is_empty() => return Ok(dedup_releases(all)),
+        Err(_) if !all.is_empty() => return Ok(dedup_releases(all)),
         Err(e) => return Err(e),
     }
 
@@ -936,10 +1081,10 @@ fn extract_tag_from_title(title: &str) -> Option<&str> {
 }
 
 #[cfg(test)]
-fn find_nearest_by_date<'a>(
-    sorted_by_date: &'a [NormalizedRelease],
+fn find_nearest_by_date(
+    sorted_by_date: &[NormalizedRelease],
     target: DateTime<Utc>,
-) -> Option<&'a NormalizedRelease> {
+) -> Option<&NormalizedRelease> {
     if sorted_by_date.is_empty() {
         return None;
     }
@@ -971,10 +1116,10 @@ fn find_nearest_by_date<'a>(
 }
 
 #[cfg(test)]
-fn find_next_on_or_after_date<'a>(
-    sorted_by_date: &'a [PypiRelease],
+fn find_next_on_or_after_date(
+    sorted_by_date: &[PypiRelease],
     target: DateTime<Utc>,
-) -> Option<&'a PypiRelease> {
+) -> Option<&PypiRelease> {
     if sorted_by_date.is_empty() {
         return None;
     }
@@ -987,10 +1132,10 @@ fn find_next_on_or_after_date<'a>(
     sorted_by_date.get(idx)
 }
 
-fn find_next_on_or_after_date_github<'a>(
-    sorted_by_date: &'a [NormalizedRelease],
+fn find_next_on_or_after_date_github(
+    sorted_by_date: &[NormalizedRelease],
     target: DateTime<Utc>,
-) -> Option<&'a NormalizedRelease> {
+) -> Option<&NormalizedRelease> {
     if sorted_by_date.is_empty() {
         return None;
     }
@@ -1015,10 +1160,7 @@ async fn pypi_version_exists_cached(
     }
 
     let url = format!("https://pypi.org/pypi/{}/{}/json", package, version);
-    let exists = match http.url_exists(&url).await {
-        Ok(v) => Some(v),
-        Err(_) => None,
-    };
+    let exists = http.url_exists(&url).await.ok();
     cache.insert(key, exists);
     exists
 }
@@ -1062,10 +1204,10 @@ async fn select_objection_release_for_frida(
         })
 }
 
-fn select_release_near_future_or_previous<'a>(
-    sorted_by_date: &'a [PypiRelease],
+fn select_release_near_future_or_previous(
+    sorted_by_date: &[PypiRelease],
     target: DateTime<Utc>,
-) -> Option<&'a PypiRelease> {
+) -> Option<&PypiRelease> {
     const MAX_FORWARD_LOOKAHEAD_DAYS: i64 = 21;
 
     if sorted_by_date.is_empty() {
@@ -1164,7 +1306,7 @@ async fn fetch_pypi_releases(
 async fn fetch_pypi_requires_dist(
     http: &HttpClient,
     package: &str,
-    version: &semver::Version,
+    version: &str,
 ) -> Result<Option<Vec<String>>> {
     #[derive(Debug, Deserialize)]
     struct PypiVersionInfo {
@@ -1310,7 +1452,7 @@ async fn select_compatible_tools_release_for_frida(
         let requires = match requires_cache.get(&key) {
             Some(v) => v.clone(),
             None => {
-                let v = fetch_pypi_requires_dist(http, "frida-tools", &cand.version).await?;
+                let v = fetch_pypi_requires_dist(http, "frida-tools", &key).await?;
                 requires_cache.insert(key.clone(), v.clone());
                 v
             }
@@ -1326,7 +1468,7 @@ async fn select_compatible_tools_release_for_frida(
         let requires = match requires_cache.get(&key) {
             Some(v) => v.clone(),
             None => {
-                let v = fetch_pypi_requires_dist(http, "frida-tools", &cand.version).await?;
+                let v = fetch_pypi_requires_dist(http, "frida-tools", &key).await?;
                 requires_cache.insert(key.clone(), v.clone());
                 v
             }
@@ -1344,6 +1486,35 @@ async fn select_compatible_tools_release_for_frida(
     Ok(fallback.cloned())
 }
 
+/// `Incompatible` when `requires_dist` pins Frida outside `frida`.
+fn requires_issue(
+    package: &'static str,
+    version: &str,
+    requires_dist: Option<&[String]>,
+    frida: &str,
+) -> Option<MappingIssue> {
+    let frida_version = semver::Version::parse(frida).ok()?;
+    if tools_compatible_with_frida(requires_dist, &frida_version) {
+        return None;
+    }
+
+    let requires = requires_dist
+        .unwrap_or_default()
+        .iter()
+        .map(|r| r.split(';').next().unwrap_or(r).trim())
+        .filter(|r| {
+            r.strip_prefix("frida")
+                .is_some_and(|rest| !rest.starts_with(['-', '_']))
+        })
+        .map(str::to_string)
+        .collect();
+    Some(MappingIssue::Incompatible {
+        package,
+        version: version.to_string(),
+        requires,
+    })
+}
+
 fn build_default_aliases(mappings: &HashMap<String, VersionInfo>) -> HashMap<String, String> {
     let mut parsed: Vec<semver::Version> = mappings
         .keys()
@@ -0,0 +1,163 @@
+use crate::core::error::Result;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// Progress of a long-running operation (download, push, or a named step such as
+/// installing the Python packages).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    DownloadStarted {
+        url: String,
+        total_bytes: Option<u64>,
+    },
+    DownloadProgress {
+        url: String,
+        downloaded: u64,
+        total_bytes: Option<u64>,
+    },
+    DownloadFinished {
+        url: String,
+        ok: bool,
+    },
+    PushStarted {
+        device: String,
+        local: PathBuf,
+        remote: String,
+    },
+    PushFinished {
+        device: String,
+        remote: String,
+        ok: bool,
+    },
+    StepStarted {
+        step: String,
+    },
+    StepFinished {
+        step: String,
+        ok: bool,
+    },
+}
+
+type Handler = Arc<dyn Fn(&Event) + Send + Sync>;
+
+#[derive(Default)]
+struct Subscribers {
+    handlers: Vec<Handler>,
+    channels: Vec<UnboundedSender<Event>>,
+}
+
+/// Fans events out to in-process handlers and channel subscribers. Cloning shares the
+/// subscriber list.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Subscribers>>,
+}
+
+static GLOBAL: OnceLock<EventBus> = OnceLock::new();
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bus frida-mgr's own operations report to unless given another one.
+    pub fn global() -> &'static EventBus {
+        GLOBAL.get_or_init(EventBus::new)
+    }
+
+    /// Receive every later event; dropping the receiver ends the subscription.
+    pub fn subscribe(&self) -> UnboundedReceiver<Event> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.lock().channels.push(tx);
+        rx
+    }
+
+    /// Run `handler` inline for every later event, e.g. to draw progress bars in step with
+    /// other terminal output.
+    pub fn on_event(&self, handler: impl Fn(&Event) + Send + Sync + 'static) {
+        self.lock().handlers.push(Arc::new(handler));
+    }
+
+    pub fn emit(&self, event: Event) {
+        let handlers = {
+            let mut subscribers = self.lock();
+            subscribers
+                .channels
+                .retain(|tx| tx.send(event.clone()).is_ok());
+            subscribers.handlers.clone()
+        };
+        // Called outside the lock so a handler may emit or subscribe itself.
+        for handler in handlers {
+            handler(&event);
+        }
+    }
+
+    /// Run `operation` between `StepStarted` and `StepFinished` events.
+    pub async fn step<T>(
+        &self,
+        step: impl Into<String>,
+        operation: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let step = step.into();
+        self.emit(Event::StepStarted { step: step.clone() });
+        let result = operation.await;
+        self.emit(Event::StepFinished {
+            step,
+            ok: result.is_ok(),
+        });
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Subscribers> {
+        // Subscribers hold no invariants a panicking handler could break.
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn events_reach_handlers_and_channels() {
+        let bus = EventBus::new();
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&seen);
+        bus.on_event(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut rx = bus.subscribe();
+
+        let value = bus.step("build", async { Ok(7) }).await.unwrap();
+        assert_eq!(value, 7);
+        assert_eq!(seen.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            rx.recv().await,
+            Some(Event::StepStarted {
+                step: "build".to_string()
+            })
+        );
+        assert_eq!(
+            rx.recv().await,
+            Some(Event::StepFinished {
+                step: "build".to_string(),
+                ok: true
+            })
+        );
+    }
+
+    #[test]
+    fn dropped_subscribers_are_pruned() {
+        let bus = EventBus::new();
+        drop(bus.subscribe());
+        bus.emit(Event::StepStarted {
+            step: "x".to_string(),
+        });
+        assert!(bus.lock().channels.is_empty());
+    }
+}
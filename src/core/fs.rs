@@ -1,12 +1,23 @@
 use crate::core::error::Result;
+use crate::core::interrupt;
+use md5::Md5;
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
 pub async fn compute_sha256(path: &Path) -> Result<String> {
+    compute_digest::<Sha256>(path).await
+}
+
+/// MD5 of `path`, for comparing with `md5sum` on devices whose shell lacks `sha256sum`.
+pub async fn compute_md5(path: &Path) -> Result<String> {
+    compute_digest::<Md5>(path).await
+}
+
+async fn compute_digest<D: Digest>(path: &Path) -> Result<String> {
     let mut file = File::open(path).await?;
-    let mut hasher = Sha256::new();
+    let mut hasher = D::new();
     let mut buffer = vec![0; 8192];
 
     loop {
@@ -17,8 +28,18 @@ pub async fn compute_sha256(path: &Path) -> Result<String> {
         hasher.update(&buffer[..n]);
     }
 
-    let hash = hasher.finalize();
-    Ok(format!("{:x}", hash))
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// `<path>.part`, where a file is written before being renamed into place.
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
 }
 
 pub async fn ensure_dir_exists(path: &Path) -> Result<()> {
@@ -29,23 +50,68 @@ pub async fn ensure_dir_exists(path: &Path) -> Result<()> {
 }
 
 pub async fn decompress_xz(input: &Path, output: &Path) -> Result<()> {
+    decompress_with(input, output, |file| {
+        Box::new(xz2::read::XzDecoder::new(file))
+    })
+    .await
+}
+
+pub async fn decompress_gz(input: &Path, output: &Path) -> Result<()> {
+    decompress_with(input, output, |file| {
+        Box::new(flate2::read::GzDecoder::new(file))
+    })
+    .await
+}
+
+async fn decompress_with(
+    input: &Path,
+    output: &Path,
+    decoder: fn(std::io::BufReader<std::fs::File>) -> Box<dyn std::io::Read>,
+) -> Result<()> {
     use std::io::BufReader;
     use tokio::task;
-    use xz2::read::XzDecoder;
 
     let input = input.to_path_buf();
     let output = output.to_path_buf();
+    // Decompress beside the target and rename, so an interrupted run never leaves a
+    // truncated binary where the cache expects a complete one.
+    let part = part_path(&output);
+    let _guard = interrupt::remove_on_interrupt(&part);
+
+    let result = task::spawn_blocking({
+        let part = part.clone();
+        move || {
+            let file = std::fs::File::open(&input)?;
+            let mut decoder = decoder(BufReader::new(file));
+            let mut output_file = std::fs::File::create(&part)?;
+            std::io::copy(&mut decoder, &mut output_file)?;
+            Ok::<_, std::io::Error>(())
+        }
+    })
+    .await
+    .map_err(std::io::Error::other)
+    .and_then(|r| r);
+
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&part).await;
+        return Err(e.into());
+    }
+    tokio::fs::rename(&part, &output).await?;
+    Ok(())
+}
 
-    task::spawn_blocking(move || {
+/// Unpack a zip archive into `dest`, keeping the archive's directory layout.
+pub async fn extract_zip(input: &Path, dest: &Path) -> Result<()> {
+    let input = input.to_path_buf();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
         let file = std::fs::File::open(&input)?;
-        let buf_reader = BufReader::new(file);
-        let mut decoder = XzDecoder::new(buf_reader);
-        let mut output_file = std::fs::File::create(&output)?;
-        std::io::copy(&mut decoder, &mut output_file)?;
-        Ok::<_, std::io::Error>(())
+        let mut archive = zip::ZipArchive::new(file).map_err(std::io::Error::other)?;
+        archive.extract(&dest).map_err(std::io::Error::other)
     })
     .await
-    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))??;
+    .map_err(std::io::Error::other)??;
 
     Ok(())
 }
@@ -64,3 +130,85 @@ pub async fn make_executable(path: &Path) -> Result<()> {
 pub async fn make_executable(_path: &Path) -> Result<()> {
     Ok(())
 }
+
+/// Total size in bytes of all regular files below `path` (0 if it doesn't exist).
+pub async fn dir_size(path: &Path) -> Result<u64> {
+    let path = path.to_path_buf();
+    let size = tokio::task::spawn_blocking(move || dir_size_blocking(&path))
+        .await
+        .map_err(std::io::Error::other)??;
+    Ok(size)
+}
+
+fn dir_size_blocking(path: &Path) -> std::io::Result<u64> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += dir_size_blocking(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_size_picks_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(10 * 1024 * 1024 * 1024), "10.0 GB");
+    }
+
+    #[tokio::test]
+    async fn dir_size_sums_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::create_dir_all(dir.path().join("a/b"))
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("a/one"), vec![0u8; 10])
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("a/b/two"), vec![0u8; 5])
+            .await
+            .unwrap();
+        assert_eq!(dir_size(dir.path()).await.unwrap(), 15);
+        assert_eq!(dir_size(&dir.path().join("missing")).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_decompression_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("frida-server.xz");
+        let output = dir.path().join("frida-server");
+        tokio::fs::write(&input, b"not xz").await.unwrap();
+
+        assert!(decompress_xz(&input, &output).await.is_err());
+        assert!(!output.exists());
+        assert!(!part_path(&output).exists());
+        assert_eq!(part_path(&output), dir.path().join("frida-server.part"));
+    }
+}
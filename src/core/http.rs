@@ -1,18 +1,88 @@
+use crate::config::schema::NetworkConfig;
 use crate::core::error::{FridaMgrError, Result};
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
-use reqwest::Client;
-use std::path::Path;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use crate::core::events::{Event, EventBus};
+use crate::core::fs::{ensure_dir_exists, part_path};
+use crate::core::interrupt;
+use crate::core::mirror::Mirror;
+use colored::Colorize;
+use futures::StreamExt;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, CONTENT_RANGE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, RANGE,
+};
+use reqwest::{Client, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio::time::{sleep, Duration};
 
+/// Disk-backed cache of text responses keyed by URL, revalidated with ETag/Last-Modified.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedResponse {
+    url: String,
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    body: String,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        let key = Sha256::digest(url.as_bytes());
+        self.dir.join(format!("{:x}.json", key))
+    }
+
+    async fn load(&self, url: &str) -> Option<CachedResponse> {
+        let content = tokio::fs::read_to_string(self.entry_path(url)).await.ok()?;
+        let entry: CachedResponse = serde_json::from_str(&content).ok()?;
+        // Guard against (unlikely) hash collisions.
+        (entry.url == url).then_some(entry)
+    }
+
+    async fn store(&self, entry: &CachedResponse) -> Result<()> {
+        ensure_dir_exists(&self.dir).await?;
+        let content = serde_json::to_string(entry).map_err(|e| FridaMgrError::Other(e.into()))?;
+        tokio::fs::write(self.entry_path(&entry.url), content).await?;
+        Ok(())
+    }
+}
+
+/// Assets smaller than this are always fetched with a single request.
+const MIN_PARALLEL_DOWNLOAD_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Clone)]
 pub struct HttpClient {
     client: Client,
+    cache: Option<HttpCache>,
+    max_attempts: usize,
+    parallel_chunks: usize,
+    events: EventBus,
+    /// Tried before github.com for GitHub URLs (`network.mirror`).
+    mirror: Option<Mirror>,
 }
 
 impl HttpClient {
     pub fn new() -> Self {
+        Self::from_network_config(&NetworkConfig::default()).expect("Failed to create HTTP client")
+    }
+
+    /// Client honoring the `[network]` section of the global config
+    /// (timeout, retries, proxy, mirror and parallel download chunks).
+    pub fn from_network_config(network: &NetworkConfig) -> Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(
             ACCEPT,
@@ -21,75 +91,313 @@ impl HttpClient {
             ),
         );
 
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .user_agent(format!("frida-mgr/{}", env!("CARGO_PKG_VERSION")))
             .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(300))
+            .timeout(Duration::from_secs(network.timeout_seconds.max(1)));
+
+        for proxy in resolve_proxies(network.proxy.as_deref(), |key| std::env::var(key).ok())? {
+            builder = builder.proxy(proxy);
+        }
+
+        let client = builder
             .build()
-            .expect("Failed to create HTTP client");
+            .map_err(|e| FridaMgrError::Config(format!("Failed to create HTTP client: {}", e)))?;
 
-        Self { client }
+        Ok(Self {
+            client,
+            cache: None,
+            // The first attempt is not a retry.
+            max_attempts: network.max_retries as usize + 1,
+            parallel_chunks: network.parallel_chunks.max(1),
+            events: EventBus::global().clone(),
+            mirror: Mirror::parse(&network.mirror)?,
+        })
     }
 
-    pub async fn download_file(&self, url: &str, dest: &Path) -> Result<()> {
-        let response =
-            self.client.get(url).send().await.map_err(|e| {
-                FridaMgrError::Download(format!("Failed to download {}: {}", url, e))
-            })?;
+    /// Report download progress to `events` instead of the global bus.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
 
-        if !response.status().is_success() {
-            return Err(FridaMgrError::Download(format!(
-                "HTTP error {}: {}",
-                response.status(),
-                url
-            )));
+    /// Cache text responses under `dir` and send conditional requests on later fetches.
+    pub fn with_response_cache(mut self, dir: PathBuf) -> Self {
+        self.cache = Some(HttpCache::new(dir));
+        self
+    }
+
+    /// GET with retries on connection errors, 429 and 5xx. `range` is an inclusive byte range.
+    async fn send_with_retry(&self, url: &str, range: Option<(u64, u64)>) -> Result<Response> {
+        let mut attempt = 0usize;
+        let mut backoff = Duration::from_millis(500);
+
+        loop {
+            attempt += 1;
+            let mut request = self.client.get(url);
+            if let Some((start, end)) = range {
+                request = request.header(RANGE, format!("bytes={}-{}", start, end));
+            }
+
+            match request.send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(resp),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let retryable = status.as_u16() == 429 || status.is_server_error();
+                    if retryable && attempt < self.max_attempts {
+                        sleep(backoff).await;
+                        backoff = (backoff * 2).min(Duration::from_secs(8));
+                        continue;
+                    }
+                    return Err(FridaMgrError::Download(format!(
+                        "HTTP error {}: {}",
+                        status, url
+                    )));
+                }
+                Err(e) => {
+                    if attempt < self.max_attempts {
+                        sleep(backoff).await;
+                        backoff = (backoff * 2).min(Duration::from_secs(8));
+                        continue;
+                    }
+                    return Err(FridaMgrError::Download(format!(
+                        "Failed to download {}: {}",
+                        url, e
+                    )));
+                }
+            }
         }
+    }
 
-        let total_size = response.content_length().unwrap_or(0);
+    /// `url` through the configured mirror, when it applies to `url`.
+    fn mirrored(&self, url: &str) -> Option<String> {
+        self.mirror.as_ref()?.rewrite(url)
+    }
 
-        let pb = ProgressBar::new(total_size);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                .expect("Invalid progress bar template")
-                .progress_chars("#>-"),
+    fn mirror_failed(mirrored: &str, error: &FridaMgrError) {
+        eprintln!(
+            "{} Mirror {} failed ({}); falling back to github.com",
+            "⚠".yellow().bold(),
+            mirrored.yellow(),
+            error
         );
+    }
+
+    /// Download `url` to `dest`, reporting `Download*` events along the way. Data lands in
+    /// `<dest>.part` first, so `dest` only ever appears complete. GitHub URLs go through
+    /// `network.mirror` first.
+    pub async fn download_file(&self, url: &str, dest: &Path) -> Result<()> {
+        if let Some(mirrored) = self.mirrored(url) {
+            match self.download_file_from(&mirrored, dest).await {
+                Ok(()) => return Ok(()),
+                Err(e) => Self::mirror_failed(&mirrored, &e),
+            }
+        }
+        self.download_file_from(url, dest).await
+    }
+
+    async fn download_file_from(&self, url: &str, dest: &Path) -> Result<()> {
+        let part = part_path(dest);
+        let _guard = interrupt::remove_on_interrupt(&part);
+        let mut result = self.download_to(url, &part).await;
+        if result.is_ok() {
+            result = tokio::fs::rename(&part, dest).await.map_err(Into::into);
+        } else {
+            let _ = tokio::fs::remove_file(&part).await;
+        }
+        self.events.emit(Event::DownloadFinished {
+            url: url.to_string(),
+            ok: result.is_ok(),
+        });
+        result
+    }
+
+    async fn download_to(&self, url: &str, dest: &Path) -> Result<()> {
+        if self.parallel_chunks > 1 {
+            if let Some(total) = self.probe_range_support(url).await {
+                if total >= MIN_PARALLEL_DOWNLOAD_BYTES {
+                    return self.download_ranged(url, dest, total).await;
+                }
+            }
+        }
+
+        self.download_single(url, dest).await
+    }
+
+    fn report_progress(&self, url: &str, downloaded: u64, total_bytes: Option<u64>) {
+        self.events.emit(Event::DownloadProgress {
+            url: url.to_string(),
+            downloaded,
+            total_bytes,
+        });
+    }
+
+    async fn download_single(&self, url: &str, dest: &Path) -> Result<()> {
+        // Retry establishing the response; a stream that breaks midway is reported as-is.
+        let response = self.send_with_retry(url, None).await?;
+
+        let total_bytes = response.content_length();
+        self.events.emit(Event::DownloadStarted {
+            url: url.to_string(),
+            total_bytes,
+        });
 
         let mut file = File::create(dest).await?;
         let mut downloaded: u64 = 0;
         let mut stream = response.bytes_stream();
 
-        use futures::StreamExt;
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.map_err(|e| FridaMgrError::Download(e.to_string()))?;
             file.write_all(&chunk).await?;
             downloaded += chunk.len() as u64;
-            pb.set_position(downloaded);
+            self.report_progress(url, downloaded, total_bytes);
+        }
+
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Total size of the resource if the host honors byte ranges.
+    async fn probe_range_support(&self, url: &str) -> Option<u64> {
+        let response = self.send_with_retry(url, Some((0, 0))).await.ok()?;
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return None;
+        }
+        response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_content_range_total)
+    }
+
+    async fn download_ranged(&self, url: &str, dest: &Path, total: u64) -> Result<()> {
+        let ranges = split_ranges(total, self.parallel_chunks);
+        self.events.emit(Event::DownloadStarted {
+            url: url.to_string(),
+            total_bytes: Some(total),
+        });
+        let downloaded = AtomicU64::new(0);
+
+        let file = File::create(dest).await?;
+        file.set_len(total).await?;
+        drop(file);
+
+        futures::future::try_join_all(
+            ranges.iter().map(|&(start, end)| {
+                self.download_range(url, dest, (start, end), total, &downloaded)
+            }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn download_range(
+        &self,
+        url: &str,
+        dest: &Path,
+        (start, end): (u64, u64),
+        total: u64,
+        downloaded: &AtomicU64,
+    ) -> Result<()> {
+        let response = self.send_with_retry(url, Some((start, end))).await?;
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(FridaMgrError::Download(format!(
+                "Host stopped honoring range requests: {}",
+                url
+            )));
         }
 
-        pb.finish_with_message("Download complete");
+        let mut file = OpenOptions::new().write(true).open(dest).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+
+        let expected = end - start + 1;
+        let mut written: u64 = 0;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| FridaMgrError::Download(e.to_string()))?;
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+            let so_far =
+                downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+            self.report_progress(url, so_far, Some(total));
+        }
         file.flush().await?;
 
+        if written != expected {
+            return Err(FridaMgrError::Download(format!(
+                "Incomplete chunk {}-{} from {} ({} of {} bytes)",
+                start, end, url, written, expected
+            )));
+        }
         Ok(())
     }
 
     pub async fn fetch_text(&self, url: &str) -> Result<String> {
-        self.fetch_text_with_retry(url, 3).await
+        self.fetch_text_with_retry(url, self.max_attempts).await
     }
 
+    /// GET `url` as text, making up to `max_attempts` attempts. GitHub URLs go through
+    /// `network.mirror` first.
     pub async fn fetch_text_with_retry(&self, url: &str, max_attempts: usize) -> Result<String> {
+        if let Some(mirrored) = self.mirrored(url) {
+            match self.fetch_text_from(&mirrored, max_attempts).await {
+                Ok(body) => return Ok(body),
+                Err(e) => Self::mirror_failed(&mirrored, &e),
+            }
+        }
+        self.fetch_text_from(url, max_attempts).await
+    }
+
+    async fn fetch_text_from(&self, url: &str, max_attempts: usize) -> Result<String> {
         let mut attempt = 0usize;
         let mut backoff = Duration::from_millis(500);
 
+        let cached = match &self.cache {
+            Some(cache) => cache.load(url).await,
+            None => None,
+        };
+
         loop {
             attempt += 1;
-            let response = self.client.get(url).send().await;
+            let mut request = self.client.get(url);
+            if let Some(entry) = &cached {
+                if let Some(etag) = entry.etag.as_deref() {
+                    request = request.header(IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = entry.last_modified.as_deref() {
+                    request = request.header(IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+            let response = request.send().await;
 
             match response {
                 Ok(resp) => {
                     let status = resp.status();
+                    if status == StatusCode::NOT_MODIFIED {
+                        if let Some(entry) = cached {
+                            return Ok(entry.body);
+                        }
+                    }
                     if status.is_success() {
-                        return Ok(resp.text().await?);
+                        let etag = header_string(resp.headers(), ETAG);
+                        let last_modified = header_string(resp.headers(), LAST_MODIFIED);
+                        let body = resp.text().await?;
+                        if let Some(cache) = &self.cache {
+                            if etag.is_some() || last_modified.is_some() {
+                                let entry = CachedResponse {
+                                    url: url.to_string(),
+                                    etag,
+                                    last_modified,
+                                    body,
+                                };
+                                // A failed cache write should never fail the fetch itself.
+                                let _ = cache.store(&entry).await;
+                                return Ok(entry.body);
+                            }
+                        }
+                        return Ok(body);
                     }
 
                     // Retry on 429 / 5xx to be polite with transient failures or rate limiting.
@@ -158,8 +466,191 @@ impl HttpClient {
     }
 }
 
+/// Split `0..total` into at most `chunks` contiguous inclusive byte ranges.
+fn split_ranges(total: u64, chunks: usize) -> Vec<(u64, u64)> {
+    if total == 0 {
+        return Vec::new();
+    }
+    let chunks = (chunks.max(1) as u64).min(total);
+    let size = total.div_ceil(chunks);
+    (0..chunks)
+        .map(|i| i * size)
+        .take_while(|&start| start < total)
+        .map(|start| (start, (start + size).min(total) - 1))
+        .collect()
+}
+
+/// Total length from a `Content-Range: bytes 0-0/12345` header.
+fn parse_content_range_total(value: &str) -> Option<u64> {
+    let (_, total) = value.trim().rsplit_once('/')?;
+    total.trim().parse().ok()
+}
+
+/// Explicit proxies for the client. `network.proxy` wins over the environment; otherwise
+/// `HTTPS_PROXY`/`HTTP_PROXY` are handled by reqwest itself and `ALL_PROXY` is added here.
+/// Any explicit proxy turns reqwest's own environment lookup off, so alongside `ALL_PROXY`
+/// the scheme-specific variables are added too, ahead of it.
+fn resolve_proxies(
+    configured: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<reqwest::Proxy>> {
+    let no_proxy = || reqwest::NoProxy::from_env();
+    let invalid = |url: &str, e: reqwest::Error| {
+        FridaMgrError::Config(format!("Invalid proxy URL '{}': {}", url, e))
+    };
+
+    if let Some(url) = configured.map(str::trim).filter(|s| !s.is_empty()) {
+        let proxy = reqwest::Proxy::all(url).map_err(|e| invalid(url, e))?;
+        return Ok(vec![proxy.no_proxy(no_proxy())]);
+    }
+
+    let all_proxy = env("ALL_PROXY")
+        .or_else(|| env("all_proxy"))
+        .filter(|s| !s.trim().is_empty());
+    let Some(all_proxy) = all_proxy else {
+        return Ok(Vec::new());
+    };
+
+    let mut proxies = Vec::new();
+    let scheme_env = |key: &str| {
+        env(key)
+            .or_else(|| env(&key.to_lowercase()))
+            .filter(|s| !s.trim().is_empty())
+    };
+    if let Some(url) = scheme_env("HTTPS_PROXY") {
+        let proxy = reqwest::Proxy::https(url.trim()).map_err(|e| invalid(&url, e))?;
+        proxies.push(proxy.no_proxy(no_proxy()));
+    }
+    if let Some(url) = scheme_env("HTTP_PROXY") {
+        let proxy = reqwest::Proxy::http(url.trim()).map_err(|e| invalid(&url, e))?;
+        proxies.push(proxy.no_proxy(no_proxy()));
+    }
+    let proxy = reqwest::Proxy::all(all_proxy.trim()).map_err(|e| invalid(&all_proxy, e))?;
+    proxies.push(proxy.no_proxy(no_proxy()));
+    Ok(proxies)
+}
+
+fn header_string(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+}
+
 impl Default for HttpClient {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_ranges_covers_whole_file() {
+        assert_eq!(split_ranges(10, 3), vec![(0, 3), (4, 7), (8, 9)]);
+        assert_eq!(split_ranges(2, 4), vec![(0, 0), (1, 1)]);
+        assert_eq!(split_ranges(5, 1), vec![(0, 4)]);
+        assert!(split_ranges(0, 4).is_empty());
+    }
+
+    #[test]
+    fn parses_content_range_total() {
+        assert_eq!(
+            parse_content_range_total("bytes 0-0/52428800"),
+            Some(52428800)
+        );
+        assert_eq!(parse_content_range_total("bytes 0-0/*"), None);
+    }
+
+    #[test]
+    fn configured_proxy_takes_precedence() {
+        let proxies = resolve_proxies(Some("http://proxy.local:3128"), |_| {
+            Some("socks5://ignored:1080".to_string())
+        })
+        .unwrap();
+        assert_eq!(proxies.len(), 1);
+    }
+
+    #[test]
+    fn all_proxy_env_is_used_when_not_configured() {
+        let proxies = resolve_proxies(None, |key| {
+            (key == "ALL_PROXY").then(|| "socks5h://127.0.0.1:1080".to_string())
+        })
+        .unwrap();
+        assert_eq!(proxies.len(), 1);
+
+        let proxies = resolve_proxies(None, |_| None).unwrap();
+        assert!(proxies.is_empty());
+    }
+
+    #[test]
+    fn scheme_proxies_stay_in_effect_next_to_all_proxy() {
+        let proxies = resolve_proxies(None, |key| match key {
+            "all_proxy" => Some("socks5h://127.0.0.1:1080".to_string()),
+            "https_proxy" => Some("http://proxy.local:3128".to_string()),
+            "HTTP_PROXY" => Some(" ".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(proxies.len(), 2);
+
+        let err = resolve_proxies(None, |key| match key {
+            "ALL_PROXY" => Some("socks5h://127.0.0.1:1080".to_string()),
+            "HTTPS_PROXY" => Some("::not a url::".to_string()),
+            _ => None,
+        })
+        .unwrap_err();
+        assert!(matches!(err, FridaMgrError::Config(_)));
+    }
+
+    #[test]
+    fn invalid_proxy_is_a_config_error() {
+        let err = resolve_proxies(Some("::not a url::"), |_| None).unwrap_err();
+        assert!(matches!(err, FridaMgrError::Config(_)));
+    }
+
+    #[test]
+    fn max_retries_counts_retries_after_the_first_attempt() {
+        let attempts = |max_retries| {
+            let network = NetworkConfig {
+                max_retries,
+                ..NetworkConfig::default()
+            };
+            HttpClient::from_network_config(&network)
+                .unwrap()
+                .max_attempts
+        };
+        assert_eq!(attempts(0), 1);
+        assert_eq!(attempts(1), 2);
+        assert_eq!(attempts(3), 4);
+    }
+
+    #[tokio::test]
+    async fn cache_roundtrip_is_keyed_by_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HttpCache::new(dir.path().join("http"));
+        let url = "https://pypi.org/pypi/frida-tools/json";
+
+        assert!(cache.load(url).await.is_none());
+
+        cache
+            .store(&CachedResponse {
+                url: url.to_string(),
+                etag: Some("\"abc\"".to_string()),
+                last_modified: None,
+                body: "{}".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let entry = cache.load(url).await.unwrap();
+        assert_eq!(entry.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(entry.body, "{}");
+        assert!(cache
+            .load("https://pypi.org/pypi/objection/json")
+            .await
+            .is_none());
+    }
+}
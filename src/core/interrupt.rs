@@ -0,0 +1,196 @@
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Exit code for a command stopped by Ctrl-C (128 + SIGINT).
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Something to undo if the command is interrupted before it completes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cleanup {
+    RemoveFile(PathBuf),
+    KillProcess(u32),
+    RemoveForward {
+        adb_path: String,
+        device_id: String,
+        spec: String,
+    },
+    RunCommand {
+        program: String,
+        args: Vec<String>,
+    },
+}
+
+struct Registry {
+    next_id: u64,
+    pending: BTreeMap<u64, Cleanup>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    next_id: 0,
+    pending: BTreeMap::new(),
+});
+static DEFERRALS: AtomicUsize = AtomicUsize::new(0);
+
+fn registry() -> MutexGuard<'static, Registry> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn register(cleanup: Cleanup) -> u64 {
+    let mut registry = registry();
+    registry.next_id += 1;
+    let id = registry.next_id;
+    registry.pending.insert(id, cleanup);
+    id
+}
+
+/// Keeps a cleanup registered until dropped, i.e. until the guarded work finished or
+/// failed on its own.
+#[must_use = "the cleanup is unregistered as soon as the guard is dropped"]
+pub struct CleanupGuard {
+    id: u64,
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        registry().pending.remove(&self.id);
+    }
+}
+
+/// Delete `path` (a partial download or a half-written cache file) if interrupted.
+pub fn remove_on_interrupt(path: &Path) -> CleanupGuard {
+    CleanupGuard {
+        id: register(Cleanup::RemoveFile(path.to_path_buf())),
+    }
+}
+
+/// Kill a spawned child (adb, uv, a bundler) if interrupted while it runs.
+pub fn kill_on_interrupt(pid: u32) -> CleanupGuard {
+    CleanupGuard {
+        id: register(Cleanup::KillProcess(pid)),
+    }
+}
+
+/// Remove an adb forward set up by this command if it is interrupted at any later point.
+pub fn remove_forward_on_interrupt(adb_path: &str, device_id: &str, spec: &str) {
+    register(Cleanup::RemoveForward {
+        adb_path: adb_path.to_string(),
+        device_id: device_id.to_string(),
+        spec: spec.to_string(),
+    });
+}
+
+/// Run `program` (e.g. an adb shell command undoing device-side changes) if interrupted.
+pub fn run_on_interrupt(program: &str, args: &[String]) -> CleanupGuard {
+    CleanupGuard {
+        id: register(Cleanup::RunCommand {
+            program: program.to_string(),
+            args: args.to_vec(),
+        }),
+    }
+}
+
+/// Marks Ctrl-C as handled elsewhere while alive: by an interactive child sharing the
+/// terminal, or by a command waiting on `ctrl_c`.
+pub struct Deferral(());
+
+impl Drop for Deferral {
+    fn drop(&mut self) {
+        DEFERRALS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn defer() -> Deferral {
+    DEFERRALS.fetch_add(1, Ordering::SeqCst);
+    Deferral(())
+}
+
+/// `tokio::signal::ctrl_c` for commands that stop gracefully on Ctrl-C; the global handler
+/// stands down while this is awaited.
+pub async fn ctrl_c() -> std::io::Result<()> {
+    let _deferral = defer();
+    tokio::signal::ctrl_c().await
+}
+
+/// On Ctrl-C, undo whatever the running command registered and exit, unless the
+/// interrupt is currently someone else's to handle.
+pub fn install_handler() {
+    tokio::spawn(async {
+        loop {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            if DEFERRALS.load(Ordering::SeqCst) > 0 {
+                continue;
+            }
+            run_cleanups();
+            eprintln!();
+            eprintln!("{} Interrupted", "✗".red().bold());
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+    });
+}
+
+fn run_cleanups() {
+    let pending = std::mem::take(&mut registry().pending);
+    // Children first so nothing recreates a file after it is removed.
+    let (kills, rest): (Vec<_>, Vec<_>) = pending
+        .into_values()
+        .partition(|c| matches!(c, Cleanup::KillProcess(_)));
+    for cleanup in kills.into_iter().chain(rest) {
+        match cleanup {
+            Cleanup::KillProcess(pid) => kill_process(pid),
+            Cleanup::RemoveFile(path) => {
+                let _ = std::fs::remove_file(&path);
+            }
+            Cleanup::RemoveForward {
+                adb_path,
+                device_id,
+                spec,
+            } => {
+                let _ = std::process::Command::new(adb_path)
+                    .args(["-s", &device_id, "forward", "--remove", &spec])
+                    .output();
+            }
+            Cleanup::RunCommand { program, args } => {
+                let _ = std::process::Command::new(program).args(args).output();
+            }
+        }
+    }
+}
+
+fn kill_process(pid: u32) {
+    let pid = pid.to_string();
+    let mut command = if cfg!(windows) {
+        let mut command = std::process::Command::new("taskkill");
+        command.args(["/PID", &pid, "/T", "/F"]);
+        command
+    } else {
+        let mut command = std::process::Command::new("kill");
+        command.args(["-KILL", &pid]);
+        command
+    };
+    let _ = command.output();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_unregister_on_drop() {
+        let path = PathBuf::from("/tmp/frida-mgr-interrupt-test.part");
+        let guard = remove_on_interrupt(&path);
+        assert!(registry()
+            .pending
+            .get(&guard.id)
+            .is_some_and(|c| *c == Cleanup::RemoveFile(path.clone())));
+        let id = guard.id;
+        drop(guard);
+        assert!(!registry().pending.contains_key(&id));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::{ensure_dir_exists, HttpClient};
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::fs;
+
+/// Base URL of the canonical release host; asset paths are relative to it.
+pub const PRIMARY_ASSET_HOST: &str = "https://github.com";
+
+/// File under the cache dir recording per-host download health.
+pub const HOST_HEALTH_FILE: &str = "hosts.toml";
+
+/// `network.mirror` preset that proxies any github.com URL.
+const GHPROXY_TEMPLATE: &str = "https://ghproxy.net/{url}";
+
+/// Where github.com downloads and pages are fetched first (`network.mirror`): `github`
+/// (no mirror), `ghproxy`, or a URL template. Templates may use `{url}` (the full GitHub
+/// URL), `{path}` (the part after `https://github.com/`), or for release downloads
+/// `{owner}`, `{repo}`, `{tag}` and `{asset}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mirror {
+    template: String,
+}
+
+impl Mirror {
+    /// The mirror configured by `value`; `None` for `github` (or an empty value).
+    pub fn parse(value: &str) -> Result<Option<Self>> {
+        let value = value.trim();
+        let template = match value.to_ascii_lowercase().as_str() {
+            "" | "github" => return Ok(None),
+            "ghproxy" => GHPROXY_TEMPLATE.to_string(),
+            "jsdelivr" => {
+                return Err(FridaMgrError::Config(
+                    "network.mirror = \"jsdelivr\" is not supported: jsDelivr serves repository files, not GitHub release assets; use \"ghproxy\" or a URL template".to_string(),
+                ))
+            }
+            _ => value.to_string(),
+        };
+        let placeholders = ["{url}", "{path}", "{asset}"];
+        if !(template.starts_with("http://") || template.starts_with("https://"))
+            || !placeholders.iter().any(|p| template.contains(p))
+        {
+            return Err(FridaMgrError::Config(format!(
+                "Invalid network.mirror '{}': expected github, ghproxy, or an http(s) URL template with {{url}}, {{path}} or {{owner}}/{{repo}}/{{tag}}/{{asset}}",
+                value
+            )));
+        }
+        Ok(Some(Self { template }))
+    }
+
+    /// `url` through the mirror; `None` for URLs outside github.com, and for non-release
+    /// URLs when the template only knows release downloads.
+    pub fn rewrite(&self, url: &str) -> Option<String> {
+        let path = url
+            .strip_prefix(PRIMARY_ASSET_HOST)?
+            .strip_prefix('/')
+            .filter(|path| !path.is_empty())?;
+        let mut mirrored = self.template.replace("{url}", url).replace("{path}", path);
+        if ["{owner}", "{repo}", "{tag}", "{asset}"]
+            .iter()
+            .any(|p| mirrored.contains(p))
+        {
+            let parts: Vec<&str> = path.splitn(6, '/').collect();
+            let [owner, repo, "releases", "download", tag, asset] = parts.as_slice() else {
+                return None;
+            };
+            mirrored = mirrored
+                .replace("{owner}", owner)
+                .replace("{repo}", repo)
+                .replace("{tag}", tag)
+                .replace("{asset}", asset);
+        }
+        Some(mirrored)
+    }
+}
+
+/// Outcome of the most recent download attempt against a host.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HostHealth {
+    pub ok: bool,
+    /// Wall time of the last successful download, in milliseconds.
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Unix timestamp (seconds) of the last attempt.
+    pub checked_at: i64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct HostHealthStore {
+    #[serde(default)]
+    pub hosts: BTreeMap<String, HostHealth>,
+}
+
+impl HostHealthStore {
+    pub async fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).await?;
+        // Health data only affects ordering; a corrupt file just resets it.
+        Ok(toml::from_str(&content).unwrap_or_default())
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            ensure_dir_exists(parent).await?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).await?;
+        Ok(())
+    }
+}
+
+/// Order hosts so that working ones come first (fastest first), untried hosts keep
+/// their configured order, and hosts that failed last time are tried last.
+pub fn order_hosts(hosts: &[String], health: &HostHealthStore) -> Vec<String> {
+    let mut ranked: Vec<(usize, u64, usize, &String)> = hosts
+        .iter()
+        .enumerate()
+        .map(|(idx, host)| match health.hosts.get(host) {
+            Some(h) if h.ok => (0, h.latency_ms, idx, host),
+            None => (1, 0, idx, host),
+            Some(_) => (2, 0, idx, host),
+        })
+        .collect();
+    ranked.sort();
+    ranked
+        .into_iter()
+        .map(|(_, _, _, host)| host.clone())
+        .collect()
+}
+
+/// The primary release host followed by configured `network.fallback_hosts`.
+#[derive(Debug, Clone)]
+pub struct AssetHosts {
+    hosts: Vec<String>,
+    health_path: PathBuf,
+}
+
+impl AssetHosts {
+    pub fn new(fallbacks: &[String], health_path: PathBuf) -> Self {
+        let mut hosts = vec![PRIMARY_ASSET_HOST.to_string()];
+        for base in fallbacks {
+            let base = base.trim().trim_end_matches('/').to_string();
+            if !base.is_empty() && !hosts.contains(&base) {
+                hosts.push(base);
+            }
+        }
+        Self { hosts, health_path }
+    }
+
+    async fn record(&self, host: &str, latency: Option<Duration>) {
+        let Ok(mut store) = HostHealthStore::load_or_default(&self.health_path).await else {
+            return;
+        };
+        store.hosts.insert(
+            host.to_string(),
+            HostHealth {
+                ok: latency.is_some(),
+                latency_ms: latency.map(|d| d.as_millis() as u64).unwrap_or(0),
+                checked_at: Utc::now().timestamp(),
+            },
+        );
+        // Best-effort: losing a health sample only affects host ordering.
+        let _ = store.save(&self.health_path).await;
+    }
+
+    /// Download `path` (relative to the host base, e.g. `/frida/frida/releases/download/...`)
+    /// trying each host in health order until one succeeds.
+    pub async fn download(&self, http: &HttpClient, path: &str, dest: &Path) -> Result<()> {
+        let store = HostHealthStore::load_or_default(&self.health_path)
+            .await
+            .unwrap_or_default();
+        let ordered = order_hosts(&self.hosts, &store);
+
+        let mut last_error = None;
+        for (idx, host) in ordered.iter().enumerate() {
+            let url = format!("{}{}", host, path);
+            let started = Instant::now();
+            match http.download_file(&url, dest).await {
+                Ok(()) => {
+                    self.record(host, Some(started.elapsed())).await;
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.record(host, None).await;
+                    if idx + 1 < ordered.len() {
+                        println!(
+                            "{} {} failed: {}; trying next host",
+                            "⚠".yellow().bold(),
+                            host.yellow(),
+                            e
+                        );
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        let _ = fs::remove_file(dest).await;
+        Err(last_error
+            .unwrap_or_else(|| FridaMgrError::Download(format!("No hosts available for {}", path))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(ok: bool, latency_ms: u64) -> HostHealth {
+        HostHealth {
+            ok,
+            latency_ms,
+            checked_at: 0,
+        }
+    }
+
+    #[test]
+    fn fallbacks_follow_primary_and_are_deduplicated() {
+        let hosts = AssetHosts::new(
+            &[
+                "https://mirror.example/gh/".to_string(),
+                PRIMARY_ASSET_HOST.to_string(),
+                " ".to_string(),
+            ],
+            PathBuf::from("hosts.toml"),
+        );
+        assert_eq!(
+            hosts.hosts,
+            vec![PRIMARY_ASSET_HOST, "https://mirror.example/gh"]
+        );
+    }
+
+    #[test]
+    fn healthy_fast_hosts_first_failed_last() {
+        let hosts: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let mut store = HostHealthStore::default();
+        store.hosts.insert("a".to_string(), health(false, 0));
+        store.hosts.insert("c".to_string(), health(true, 900));
+        store.hosts.insert("d".to_string(), health(true, 100));
+
+        assert_eq!(order_hosts(&hosts, &store), vec!["d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn mirrors_rewrite_github_urls() {
+        let release =
+            "https://github.com/frida/frida/releases/download/17.5.0/frida-server-17.5.0-android-arm64.xz";
+        let feed = "https://github.com/frida/frida/releases.atom";
+
+        assert_eq!(Mirror::parse("github").unwrap(), None);
+        assert_eq!(Mirror::parse(" ").unwrap(), None);
+        let ghproxy = Mirror::parse("ghproxy").unwrap().unwrap();
+        assert_eq!(
+            ghproxy.rewrite(feed).as_deref(),
+            Some("https://ghproxy.net/https://github.com/frida/frida/releases.atom")
+        );
+        assert_eq!(
+            ghproxy.rewrite("https://api.github.com/repos/frida/frida/releases"),
+            None
+        );
+
+        let custom = Mirror::parse(
+            "https://mirror.example.com/{owner}/{repo}/releases/download/{tag}/{asset}",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            custom.rewrite(release).as_deref(),
+            Some("https://mirror.example.com/frida/frida/releases/download/17.5.0/frida-server-17.5.0-android-arm64.xz")
+        );
+        assert_eq!(custom.rewrite(feed), None);
+
+        let by_path = Mirror::parse("https://gh.example.com/{path}")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            by_path.rewrite(feed).as_deref(),
+            Some("https://gh.example.com/frida/frida/releases.atom")
+        );
+
+        assert!(Mirror::parse("jsdelivr").is_err());
+        assert!(Mirror::parse("mirror.example.com").is_err());
+        assert!(Mirror::parse("https://mirror.example.com/").is_err());
+    }
+
+    #[test]
+    fn untried_hosts_keep_configured_order() {
+        let hosts: Vec<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            order_hosts(&hosts, &HostHealthStore::default()),
+            vec!["a", "b"]
+        );
+    }
+}
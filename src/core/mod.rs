@@ -1,11 +1,22 @@
 pub mod error;
+pub mod events;
 pub mod fs;
 pub mod http;
+pub mod interrupt;
+pub mod mirror;
+pub mod notify;
 pub mod path;
 pub mod process;
+pub mod shell;
 
 pub use error::{FridaMgrError, Result};
-pub use fs::{compute_sha256, decompress_xz, ensure_dir_exists, make_executable};
+pub use events::{Event, EventBus};
+pub use fs::{
+    compute_md5, compute_sha256, decompress_gz, decompress_xz, dir_size, ensure_dir_exists,
+    extract_zip, format_size, make_executable, part_path,
+};
 pub use http::HttpClient;
+pub use mirror::AssetHosts;
+pub use notify::notify;
 pub use path::resolve_path;
-pub use process::ProcessExecutor;
+pub use process::{CommandRunner, ProcessExecutor, ScriptedRunner};
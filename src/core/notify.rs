@@ -0,0 +1,20 @@
+use notify_rust::Notification;
+
+/// Best-effort desktop notification; does nothing unless `notifications = true`
+/// in the global config. Failures (no notification daemon, headless session) are ignored.
+pub async fn notify(enabled: bool, summary: &str, body: &str) {
+    if !enabled {
+        return;
+    }
+    let summary = summary.to_string();
+    let body = body.to_string();
+    let _ = tokio::task::spawn_blocking(move || {
+        Notification::new()
+            .appname("frida-mgr")
+            .summary(&summary)
+            .body(&body)
+            .show()
+            .map(|_| ())
+    })
+    .await;
+}
@@ -1,10 +1,88 @@
 use crate::core::error::{FridaMgrError, Result};
-use std::path::Path;
-use std::process::Output;
+use crate::core::interrupt;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Output, Stdio};
+use std::sync::Mutex;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// Runs non-interactive external commands (adb, uv) to completion. `ProcessExecutor` spawns
+/// real processes; tests and library consumers can substitute their own, e.g. a
+/// `ScriptedRunner` replaying a recorded adb transcript.
+pub trait CommandRunner: Send + Sync {
+    /// Run `program` with `args`, capturing stdout and stderr.
+    fn run<'a>(&'a self, program: &'a str, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output>>;
+
+    /// Whether `program` can be run at all.
+    fn exists(&self, program: &str) -> bool;
+
+    /// Run `program` with `args`, handing each line of stdout and stderr to `on_line` as
+    /// it is printed. The default buffers through `run` and replays the lines afterwards.
+    fn run_streaming<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [&'a str],
+        on_line: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<ExitStatus>> {
+        Box::pin(async move {
+            let output = self.run(program, args).await?;
+            let mut stdout = output.stdout;
+            stdout.extend_from_slice(&output.stderr);
+            stdout.push(b'\n');
+            for line in drain_lines(&mut stdout) {
+                on_line(&line);
+            }
+            Ok(output.status)
+        })
+    }
+}
+
+impl dyn CommandRunner {
+    /// Whether the command exited successfully.
+    pub async fn status(&self, program: &str, args: &[&str]) -> Result<bool> {
+        Ok(self.run(program, args).await?.status.success())
+    }
+
+    /// Trimmed stdout of a successful command; a failure carries its stderr.
+    pub async fn stdout(&self, program: &str, args: &[&str]) -> Result<String> {
+        let output = self.run(program, args).await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(FridaMgrError::CommandFailed(format!(
+                "{} failed: {}",
+                program, stderr
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
 
 pub struct ProcessExecutor;
 
+impl CommandRunner for ProcessExecutor {
+    fn run<'a>(&'a self, program: &'a str, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output>> {
+        Box::pin(Self::execute(program, args, None))
+    }
+
+    fn exists(&self, program: &str) -> bool {
+        Self::check_command_exists(program)
+    }
+
+    fn run_streaming<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [&'a str],
+        on_line: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<ExitStatus>> {
+        Box::pin(Self::execute_streaming(program, args, on_line))
+    }
+}
+
 impl ProcessExecutor {
     pub async fn execute(cmd: &str, args: &[&str], env: Option<&[(&str, &str)]>) -> Result<Output> {
         let mut command = Command::new(cmd);
@@ -16,14 +94,24 @@ impl ProcessExecutor {
             }
         }
 
-        let output = command
-            .output()
+        let output = Self::output(&mut command)
             .await
             .map_err(|e| FridaMgrError::CommandFailed(format!("{}: {}", cmd, e)))?;
 
         Ok(output)
     }
 
+    /// `command.output()`, killing the child if frida-mgr is interrupted meanwhile.
+    pub async fn output(command: &mut Command) -> std::io::Result<Output> {
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let _guard = child.id().map(interrupt::kill_on_interrupt);
+        child.wait_with_output().await
+    }
+
     pub async fn execute_with_status(cmd: &str, args: &[&str]) -> Result<bool> {
         let output = Self::execute(cmd, args, None).await?;
         Ok(output.status.success())
@@ -43,15 +131,232 @@ impl ProcessExecutor {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
+    /// Run `cmd` with `args`, calling `on_line` for every line of stdout and stderr as it
+    /// arrives (carriage returns count as line ends, so progress counters come through too).
+    pub async fn execute_streaming(
+        cmd: &str,
+        args: &[&str],
+        on_line: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<ExitStatus> {
+        let failed = |e: std::io::Error| FridaMgrError::CommandFailed(format!("{}: {}", cmd, e));
+        let mut child = Command::new(cmd)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(failed)?;
+        let _guard = child.id().map(interrupt::kill_on_interrupt);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_lines(stdout, tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_lines(stderr, tx));
+        }
+        while let Some(line) = rx.recv().await {
+            on_line(&line);
+        }
+
+        child.wait().await.map_err(failed)
+    }
+
     pub fn check_command_exists(cmd: &str) -> bool {
-        std::process::Command::new("which")
-            .arg(cmd)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        Self::find_command(cmd).is_some()
+    }
+
+    /// Where `cmd` resolves to: itself when it contains a path separator, otherwise the
+    /// first match on `PATH` (trying each `PATHEXT` extension on Windows). Lookups are
+    /// remembered for the life of the process.
+    pub fn find_command(cmd: &str) -> Option<PathBuf> {
+        static LOOKUPS: Lazy<Mutex<HashMap<String, Option<PathBuf>>>> =
+            Lazy::new(|| Mutex::new(HashMap::new()));
+
+        if let Some(found) = LOOKUPS.lock().unwrap().get(cmd) {
+            return found.clone();
+        }
+        let path = env::var_os("PATH").unwrap_or_default();
+        let extensions = executable_extensions(env::var("PATHEXT").ok().as_deref());
+        let found = search_path(cmd, env::split_paths(&path), &extensions);
+        LOOKUPS
+            .lock()
+            .unwrap()
+            .insert(cmd.to_string(), found.clone());
+        found
+    }
+}
+
+async fn forward_lines<R>(mut reader: R, tx: mpsc::UnboundedSender<String>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => pending.extend_from_slice(&chunk[..n]),
+        }
+        for line in drain_lines(&mut pending) {
+            if tx.send(line).is_err() {
+                return;
+            }
+        }
+    }
+    pending.push(b'\n');
+    for line in drain_lines(&mut pending) {
+        let _ = tx.send(line);
+    }
+}
+
+/// Take every complete line (ended by `\n` or `\r`) off the front of `buf`, skipping blank
+/// ones; a trailing partial line stays in `buf`.
+fn drain_lines(buf: &mut Vec<u8>) -> Vec<String> {
+    let Some(end) = buf.iter().rposition(|b| matches!(b, b'\n' | b'\r')) else {
+        return Vec::new();
+    };
+    let complete: Vec<u8> = buf.drain(..=end).collect();
+    complete
+        .split(|b| matches!(b, b'\n' | b'\r'))
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect()
+}
+
+/// Suffixes to try after a command name: `PATHEXT` on Windows, nothing elsewhere.
+fn executable_extensions(pathext: Option<&str>) -> Vec<String> {
+    if !cfg!(windows) {
+        return vec![String::new()];
+    }
+    pathext
+        .unwrap_or(".COM;.EXE;.BAT;.CMD")
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| ext.to_ascii_lowercase())
+        .collect()
+}
+
+fn search_path<I>(cmd: &str, dirs: I, extensions: &[String]) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = PathBuf>,
+{
+    // A name that already carries an extension (`adb.exe`) is also tried as given.
+    let mut extensions = extensions.to_vec();
+    if Path::new(cmd).extension().is_some() && !extensions.iter().any(String::is_empty) {
+        extensions.insert(0, String::new());
+    }
+    let candidates = |base: PathBuf| {
+        extensions
+            .iter()
+            .map(move |ext| PathBuf::from(format!("{}{}", base.display(), ext)))
+            .find(|candidate| is_executable(candidate))
+    };
+
+    if cmd.contains('/') || cmd.contains(std::path::MAIN_SEPARATOR) {
+        return candidates(PathBuf::from(cmd));
+    }
+    dirs.into_iter()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .find_map(|dir| candidates(dir.join(cmd)))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// A `CommandRunner` that replays a recorded transcript: each call must match the next
+/// expected command line exactly and gets its recorded exit code and stdout.
+#[derive(Default)]
+pub struct ScriptedRunner {
+    steps: Mutex<VecDeque<ScriptedStep>>,
+}
+
+struct ScriptedStep {
+    command: String,
+    code: i32,
+    stdout: String,
+}
+
+impl ScriptedRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect `command` (program and arguments joined by spaces) next.
+    pub fn expect(self, command: &str, code: i32, stdout: &str) -> Self {
+        self.lock().push_back(ScriptedStep {
+            command: command.to_string(),
+            code,
+            stdout: stdout.to_string(),
+        });
+        self
+    }
+
+    /// Expected commands that were never run.
+    pub fn remaining(&self) -> Vec<String> {
+        self.lock().iter().map(|s| s.command.clone()).collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ScriptedStep>> {
+        self.steps
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CommandRunner for ScriptedRunner {
+    fn run<'a>(&'a self, program: &'a str, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output>> {
+        let command = std::iter::once(program)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let step = self.lock().pop_front();
+        Box::pin(async move {
+            match step {
+                Some(step) if step.command == command => Ok(Output {
+                    status: exit_status(step.code),
+                    stdout: step.stdout.into_bytes(),
+                    stderr: Vec::new(),
+                }),
+                Some(step) => Err(FridaMgrError::CommandFailed(format!(
+                    "unexpected command `{}` (transcript expects `{}`)",
+                    command, step.command
+                ))),
+                None => Err(FridaMgrError::CommandFailed(format!(
+                    "unexpected command `{}` (transcript exhausted)",
+                    command
+                ))),
+            }
+        })
+    }
+
+    fn exists(&self, _program: &str) -> bool {
+        true
     }
 }
 
+#[cfg(unix)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw((code & 0xff) << 8)
+}
+
+#[cfg(windows)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(code as u32)
+}
+
 pub async fn ensure_dir_exists(path: &Path) -> Result<()> {
     if !path.exists() {
         tokio::fs::create_dir_all(path).await?;
@@ -66,3 +371,66 @@ pub async fn copy_file(from: &Path, to: &Path) -> Result<()> {
     tokio::fs::copy(from, to).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_output_on_newlines_and_carriage_returns() {
+        let mut buf =
+            b"[ 10%] /data/app/base.apk\r[ 55%] /data/app/base.apk\r\n\nfinal: 1 file pulled\npart"
+                .to_vec();
+        assert_eq!(
+            drain_lines(&mut buf),
+            vec![
+                "[ 10%] /data/app/base.apk",
+                "[ 55%] /data/app/base.apk",
+                "final: 1 file pulled",
+            ]
+        );
+        assert_eq!(buf, b"part");
+        assert!(drain_lines(&mut buf).is_empty());
+    }
+
+    #[cfg(unix)]
+    fn make_file(path: &Path, mode: u32) {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::write(path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_the_first_executable_on_path() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        make_file(&first.path().join("adb"), 0o644);
+        make_file(&second.path().join("adb"), 0o755);
+        let dirs = || {
+            vec![
+                PathBuf::new(),
+                first.path().to_path_buf(),
+                second.path().to_path_buf(),
+            ]
+        };
+        let extensions = executable_extensions(None);
+
+        assert_eq!(
+            search_path("adb", dirs(), &extensions),
+            Some(second.path().join("adb"))
+        );
+        assert_eq!(search_path("jadx", dirs(), &extensions), None);
+
+        let direct = second.path().join("adb");
+        assert_eq!(
+            search_path(direct.to_str().unwrap(), Vec::new(), &extensions),
+            Some(direct)
+        );
+        let skipped = first.path().join("adb");
+        assert_eq!(
+            search_path(skipped.to_str().unwrap(), Vec::new(), &extensions),
+            None
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Quoting for command lines handed to `sh`, on the host (`script -c`) or on a device
+//! (`adb shell`).
+
+/// Quote `word` as a single `sh` word: left bare when it only contains characters the
+/// shell never interprets, otherwise wrapped in single quotes (`'` becomes `'\''`).
+pub fn quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// A simple command with every word quoted.
+pub fn command<I, S>(words: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    words
+        .into_iter()
+        .map(|word| quote(word.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(
+            quote("/data/local/tmp/frida-server"),
+            "/data/local/tmp/frida-server"
+        );
+        assert_eq!(quote("0.0.0.0:27042"), "0.0.0.0:27042");
+        assert_eq!(quote(""), "''");
+        assert_eq!(quote("/sdcard/my dir/fs"), "'/sdcard/my dir/fs'");
+        assert_eq!(quote("$HOME;rm"), "'$HOME;rm'");
+        assert_eq!(quote("it's"), "'it'\\''s'");
+    }
+}
@@ -1,3 +1,9 @@
+pub mod protections;
+pub mod release;
 pub mod server;
 
-pub use server::ServerDownloader;
+pub use release::{
+    fetch_release_assets, fetch_release_notes, fetch_repo_release_assets, find_asset,
+    published_sha256, releases_between, ReleaseAsset, ReleaseNotes,
+};
+pub use server::{ServerDistribution, ServerDownloader, ServerPlatform};
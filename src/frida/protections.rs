@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the line carrying the findings JSON in the script's console output.
+pub const FINDINGS_MARKER: &str = "FRIDA_MGR_FINDINGS ";
+
+/// Read-only reconnaissance script: only looks classes/modules up and scans memory, never
+/// hooks or patches anything.
+pub const PROTECTIONS_SCRIPT: &str = r#"'use strict';
+
+const findings = {
+  root_detection: [],
+  ssl_pinning: [],
+  packers: [],
+  frida_detection: [],
+};
+
+function add(list, value) {
+  if (list.indexOf(value) === -1) list.push(value);
+}
+
+const ROOT_CLASSES = {
+  'com.scottyab.rootbeer.RootBeer': 'RootBeer',
+  'com.kimchangyoun.rootbeerFresh.RootBeer': 'RootBeerFresh',
+  'com.google.android.gms.safetynet.SafetyNetClient': 'SafetyNet attestation',
+  'com.google.android.play.core.integrity.IntegrityManager': 'Play Integrity',
+  'com.guardsquare.dexguard.runtime.detection.RootDetector': 'DexGuard root detector',
+  'com.devadvance.rootcloak2.RootCloak': 'RootCloak check',
+};
+
+const PINNING_CLASSES = {
+  'okhttp3.CertificatePinner': 'OkHttp3 CertificatePinner',
+  'com.squareup.okhttp.CertificatePinner': 'OkHttp2 CertificatePinner',
+  'com.datatheorem.android.trustkit.TrustKit': 'TrustKit',
+  'appcelerator.https.PinningTrustManager': 'Appcelerator PinningTrustManager',
+  'io.netty.handler.ssl.util.FingerprintTrustManagerFactory': 'Netty fingerprint pinning',
+  'com.wultra.android.sslpinning.CertStore': 'Wultra SSL pinning',
+};
+
+const PACKER_CLASSES = {
+  'com.stub.StubApp': '360 Jiagu',
+  'com.tencent.StubShell.TxAppEntry': 'Tencent Legu',
+  'com.secneo.apkwrapper.ApplicationWrapper': 'Bangcle/SecNeo',
+  's.h.e.l.l.S': 'Ijiami',
+  'com.baidu.protect.StubApplication': 'Baidu protect',
+  'com.shell.SuperApplication': 'Ijiami (SuperApplication)',
+};
+
+const PACKER_MODULES = {
+  'libjiagu': '360 Jiagu',
+  'libshell': 'Tencent Legu',
+  'libsecexe': 'Bangcle',
+  'libSecShell': 'Bangcle',
+  'libDexHelper': 'SecNeo',
+  'libexec': 'Ijiami',
+  'libbaiduprotect': 'Baidu protect',
+  'libmobisec': 'Alibaba',
+  'libnqshield': 'NQ Shield',
+  'libAPKProtect': 'APKProtect',
+};
+
+const FRIDA_STRINGS = ['frida', 'gum-js-loop', 'gmain', 'linjector', '27042', 'gdbus'];
+
+function hexPattern(text) {
+  return Array.prototype.map
+    .call(text, (c) => ('0' + c.charCodeAt(0).toString(16)).slice(-2))
+    .join(' ');
+}
+
+function checkClasses(table, list) {
+  Object.keys(table).forEach((name) => {
+    try {
+      Java.use(name);
+      add(list, table[name] + ' (' + name + ')');
+    } catch (e) {
+      // not present
+    }
+  });
+}
+
+function appModules() {
+  return Process.enumerateModules().filter((m) => m.path.indexOf('/data/app/') === 0);
+}
+
+function checkNative() {
+  Process.enumerateModules().forEach((m) => {
+    Object.keys(PACKER_MODULES).forEach((prefix) => {
+      if (m.name.indexOf(prefix) === 0) add(findings.packers, PACKER_MODULES[prefix] + ' (' + m.name + ')');
+    });
+  });
+
+  appModules().forEach((m) => {
+    FRIDA_STRINGS.forEach((text) => {
+      const pattern = hexPattern(text);
+      m.enumerateRanges('r--').some((range) => {
+        try {
+          if (Memory.scanSync(range.base, range.size, pattern).length > 0) {
+            add(findings.frida_detection, '"' + text + '" in ' + m.name);
+            return true;
+          }
+        } catch (e) {
+          // unreadable range
+        }
+        return false;
+      });
+    });
+  });
+}
+
+function checkJava() {
+  checkClasses(ROOT_CLASSES, findings.root_detection);
+  checkClasses(PINNING_CLASSES, findings.ssl_pinning);
+  checkClasses(PACKER_CLASSES, findings.packers);
+
+  Java.enumerateLoadedClassesSync().forEach((name) => {
+    if (/^(android|androidx|java|javax|com\.android|sun|dalvik|org\.apache)\./.test(name)) return;
+    if (/TrustManager|Pinning|Pinner/.test(name)) add(findings.ssl_pinning, 'custom ' + name);
+    if (/[Rr]oot(ed)?(Check|Detect)|RootBeer|Magisk|Xposed|Substrate/.test(name)) {
+      add(findings.root_detection, 'custom ' + name);
+    }
+    if (/[Ff]rida|AntiHook|HookDetect/.test(name)) add(findings.frida_detection, 'class ' + name);
+  });
+}
+
+try {
+  checkNative();
+} catch (e) {
+  console.error('native checks failed: ' + e);
+}
+
+function report() {
+  console.log('FRIDA_MGR_FINDINGS ' + JSON.stringify(findings));
+}
+
+if (typeof Java !== 'undefined' && Java.available) {
+  Java.perform(() => {
+    try {
+      checkJava();
+    } catch (e) {
+      console.error('java checks failed: ' + e);
+    }
+    report();
+  });
+} else {
+  report();
+}
+"#;
+
+/// What `detect-protections` found, as printed by the script plus suggested next steps.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProtectionFindings {
+    #[serde(default)]
+    pub root_detection: Vec<String>,
+    #[serde(default)]
+    pub ssl_pinning: Vec<String>,
+    #[serde(default)]
+    pub packers: Vec<String>,
+    #[serde(default)]
+    pub frida_detection: Vec<String>,
+    #[serde(default)]
+    pub suggestions: Vec<String>,
+}
+
+impl ProtectionFindings {
+    /// Parse the findings line from frida's output and fill in `suggestions`.
+    pub fn from_output(output: &str) -> Option<Self> {
+        let json = output
+            .lines()
+            .find_map(|line| line.split_once(FINDINGS_MARKER).map(|(_, json)| json))?;
+        let mut findings: Self = serde_json::from_str(json.trim()).ok()?;
+        findings.suggestions = findings.suggest();
+        Some(findings)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root_detection.is_empty()
+            && self.ssl_pinning.is_empty()
+            && self.packers.is_empty()
+            && self.frida_detection.is_empty()
+    }
+
+    fn suggest(&self) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        if !self.packers.is_empty() {
+            suggestions.push(
+                "packed: spawn with --pause and hook after the real Application is loaded"
+                    .to_string(),
+            );
+        }
+        if !self.frida_detection.is_empty() {
+            suggestions.push(
+                "anti-frida: rename the server (android.server_name) and load an anti-detection script first"
+                    .to_string(),
+            );
+        }
+        if !self.root_detection.is_empty() {
+            suggestions.push("root detection: load a root-bypass script".to_string());
+        }
+        if !self.ssl_pinning.is_empty() {
+            suggestions.push("SSL pinning: load an SSL unpinning script".to_string());
+        }
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_findings_line() {
+        let output = "\
+     ____
+Attaching...
+FRIDA_MGR_FINDINGS {\"root_detection\":[\"RootBeer (com.scottyab.rootbeer.RootBeer)\"],\"ssl_pinning\":[\"OkHttp3 CertificatePinner (okhttp3.CertificatePinner)\"],\"packers\":[],\"frida_detection\":[]}
+";
+        let findings = ProtectionFindings::from_output(output).unwrap();
+        assert_eq!(findings.root_detection.len(), 1);
+        assert_eq!(findings.ssl_pinning.len(), 1);
+        assert!(!findings.is_empty());
+        assert_eq!(findings.suggestions.len(), 2);
+
+        assert_eq!(ProtectionFindings::from_output("Failed to attach"), None);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::HttpClient;
+use serde::Deserialize;
+
+/// One downloadable file attached to a GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub size: u64,
+    pub browser_download_url: String,
+    /// Content digest published by GitHub (e.g. `sha256:<hex>`); absent on older releases.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+impl ReleaseAsset {
+    /// Expected SHA-256 (lowercase hex) when GitHub published one.
+    pub fn sha256(&self) -> Option<String> {
+        self.digest
+            .as_deref()
+            .and_then(|d| d.strip_prefix("sha256:"))
+            .map(str::to_ascii_lowercase)
+    }
+
+    /// Path of this asset relative to the release host, for `AssetHosts::download`.
+    pub fn host_path(&self, version: &str) -> String {
+        format!("/frida/frida/releases/download/{}/{}", version, self.name)
+    }
+}
+
+/// Pick an asset by exact name, or by a substring that matches exactly one asset.
+pub fn find_asset<'a>(assets: &'a [ReleaseAsset], query: &str) -> Result<&'a ReleaseAsset> {
+    if let Some(asset) = assets.iter().find(|a| a.name == query) {
+        return Ok(asset);
+    }
+
+    let matches: Vec<&ReleaseAsset> = assets.iter().filter(|a| a.name.contains(query)).collect();
+    match matches.as_slice() {
+        [asset] => Ok(asset),
+        [] => Err(FridaMgrError::FileNotFound(format!(
+            "release asset matching '{}'. Run 'frida-mgr assets <version>' to list them.",
+            query
+        ))),
+        many => Err(FridaMgrError::Config(format!(
+            "'{}' matches {} assets: {}",
+            query,
+            many.len(),
+            many.iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+/// Checksum files some forks attach to their releases, in `sha256sum` format.
+const CHECKSUM_ASSETS: [&str; 3] = ["SHA256SUMS", "SHA256SUMS.txt", "sha256sums.txt"];
+
+/// List the assets published for a Frida release tag via the GitHub API.
+pub async fn fetch_release_assets(http: &HttpClient, version: &str) -> Result<Vec<ReleaseAsset>> {
+    fetch_repo_release_assets(http, "frida/frida", version).await
+}
+
+/// List the assets of release `tag` in GitHub repository `repo` (`owner/name`).
+pub async fn fetch_repo_release_assets(
+    http: &HttpClient,
+    repo: &str,
+    tag: &str,
+) -> Result<Vec<ReleaseAsset>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/releases/tags/{}",
+        repo, tag
+    );
+    let release: GithubRelease = http.fetch_json(&url).await.map_err(|e| {
+        FridaMgrError::Download(format!(
+            "Failed to fetch release assets for {} {}: {}",
+            repo, tag, e
+        ))
+    })?;
+
+    let mut assets = release.assets;
+    assets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(assets)
+}
+
+/// The published SHA-256 of asset `name` among `assets`: GitHub's own digest, else the
+/// entry in a `SHA256SUMS` file attached to the release. `None` when neither exists.
+pub async fn published_sha256(
+    http: &HttpClient,
+    assets: &[ReleaseAsset],
+    name: &str,
+) -> Result<Option<String>> {
+    if let Some(sha256) = assets
+        .iter()
+        .find(|a| a.name == name)
+        .and_then(ReleaseAsset::sha256)
+    {
+        return Ok(Some(sha256));
+    }
+    let Some(sums) = assets
+        .iter()
+        .find(|a| CHECKSUM_ASSETS.contains(&a.name.as_str()))
+    else {
+        return Ok(None);
+    };
+    let text = http.fetch_text(&sums.browser_download_url).await?;
+    Ok(parse_sha256sums(&text, name))
+}
+
+/// The digest listed for `name` in `sha256sum` output (`<hex>  <name>`, or `<hex> *<name>`
+/// in binary mode).
+pub fn parse_sha256sums(text: &str, name: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let (digest, file) = line.trim().split_once(char::is_whitespace)?;
+        let file = file.trim_start().trim_start_matches('*');
+        (file == name && digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| digest.to_ascii_lowercase())
+    })
+}
+
+/// Release notes for one Frida tag, as listed by the GitHub releases API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseNotes {
+    pub tag_name: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// Most recent Frida releases with their notes (one page of the GitHub API, newest first).
+pub async fn fetch_release_notes(http: &HttpClient) -> Result<Vec<ReleaseNotes>> {
+    let url = "https://api.github.com/repos/frida/frida/releases?per_page=100";
+    http.fetch_json(url)
+        .await
+        .map_err(|e| FridaMgrError::Download(format!("Failed to fetch Frida release notes: {}", e)))
+}
+
+/// Releases after `from` up to and including `to`, oldest first. Tags that are not
+/// semver are skipped.
+pub fn releases_between<'a>(
+    releases: &'a [ReleaseNotes],
+    from: &str,
+    to: &str,
+) -> Vec<&'a ReleaseNotes> {
+    let (Ok(from), Ok(to)) = (semver::Version::parse(from), semver::Version::parse(to)) else {
+        return Vec::new();
+    };
+    let (low, high) = if from <= to { (from, to) } else { (to, from) };
+
+    let mut between: Vec<(semver::Version, &ReleaseNotes)> = releases
+        .iter()
+        .filter_map(|r| {
+            semver::Version::parse(&r.tag_name)
+                .ok()
+                .map(|version| (version, r))
+        })
+        .filter(|(version, _)| *version > low && *version <= high)
+        .collect();
+    between.sort_by(|a, b| a.0.cmp(&b.0));
+    between.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(name: &str, digest: Option<&str>) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            size: 1,
+            browser_download_url: String::new(),
+            digest: digest.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn finds_exact_or_unique_substring() {
+        let assets = vec![
+            asset("frida-core-devkit-16.6.6-linux-x86_64.tar.xz", None),
+            asset("frida-gum-devkit-16.6.6-linux-x86_64.tar.xz", None),
+            asset("frida-clr-16.6.6-windows-x86_64.dll.xz", None),
+        ];
+        assert_eq!(find_asset(&assets, "clr").unwrap().name, assets[2].name);
+        assert_eq!(
+            find_asset(&assets, &assets[0].name).unwrap().name,
+            assets[0].name
+        );
+        assert!(find_asset(&assets, "devkit").is_err());
+        assert!(find_asset(&assets, "qml").is_err());
+    }
+
+    #[test]
+    fn selects_releases_between_versions() {
+        let notes: Vec<ReleaseNotes> =
+            ["16.6.6", "16.6.5", "16.5.9", "16.5.2", "16.5.1", "nightly"]
+                .iter()
+                .map(|tag| ReleaseNotes {
+                    tag_name: tag.to_string(),
+                    published_at: None,
+                    body: None,
+                })
+                .collect();
+
+        let tags = |from, to| {
+            releases_between(&notes, from, to)
+                .iter()
+                .map(|r| r.tag_name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tags("16.5.2", "16.6.5"), vec!["16.5.9", "16.6.5"]);
+        assert_eq!(tags("16.6.5", "16.5.2"), vec!["16.5.9", "16.6.5"]);
+        assert!(tags("16.6.6", "16.6.6").is_empty());
+    }
+
+    #[test]
+    fn parses_sha256_digest() {
+        assert_eq!(
+            asset("a", Some("sha256:ABCD")).sha256().as_deref(),
+            Some("abcd")
+        );
+        assert!(asset("a", Some("md5:00")).sha256().is_none());
+        assert!(asset("a", None).sha256().is_none());
+    }
+
+    #[test]
+    fn reads_digests_from_sha256sums() {
+        let digest = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        let sums = format!(
+            "{}  hluda-server-16.1.4-android-arm64.gz\n{} *hluda-server-16.1.4-android-x86.gz\n",
+            "0".repeat(64),
+            digest
+        );
+        assert_eq!(
+            parse_sha256sums(&sums, "hluda-server-16.1.4-android-x86.gz"),
+            Some(digest.to_ascii_lowercase())
+        );
+        assert_eq!(
+            parse_sha256sums(&sums, "hluda-server-16.1.4-android-arm64.gz"),
+            Some("0".repeat(64))
+        );
+        assert_eq!(
+            parse_sha256sums(&sums, "hluda-server-16.1.4-android-arm.gz"),
+            None
+        );
+        assert_eq!(parse_sha256sums("not a digest  x.gz", "x.gz"), None);
+    }
+}
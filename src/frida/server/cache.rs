@@ -0,0 +1,545 @@
+use crate::config::schema::CacheConfig;
+use crate::config::{GlobalConfigManager, ProjectRegistry};
+use crate::core::error::Result;
+use crate::core::{compute_sha256, dir_size, ensure_dir_exists, format_size, make_executable};
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const CACHE_INDEX_FILE: &str = "index.toml";
+pub const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CacheIndex {
+    #[serde(default)]
+    entries: BTreeMap<String, CacheIndexEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CacheIndexEntry {
+    /// Unix timestamp (seconds) of the last download or use of this entry.
+    last_access: i64,
+}
+
+/// Where the SHA-256 of a cached binary is kept (`<binary>.sha256`), so later uses can
+/// tell a corrupted cache entry from a good one.
+pub fn digest_path(binary: &Path) -> PathBuf {
+    let mut path = binary.as_os_str().to_owned();
+    path.push(".sha256");
+    PathBuf::from(path)
+}
+
+/// A cached frida-server for one version/arch pair.
+#[derive(Debug, Clone)]
+pub struct CachedServer {
+    pub version: String,
+    pub arch: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub last_access: i64,
+}
+
+/// Access tracking and LRU eviction for `<cache>/servers/<version>/<arch>/`.
+pub struct ServerCache {
+    servers_dir: PathBuf,
+}
+
+impl ServerCache {
+    pub fn new(cache_dir: &Path) -> Self {
+        Self {
+            servers_dir: cache_dir.join("servers"),
+        }
+    }
+
+    /// A cache rooted at `servers_dir` itself, such as a fork's `forks/<owner>/<repo>/`.
+    pub fn at(servers_dir: PathBuf) -> Self {
+        Self { servers_dir }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.servers_dir.join(CACHE_INDEX_FILE)
+    }
+
+    fn index_key(version: &str, arch: &str) -> String {
+        format!("{}/{}", version, arch)
+    }
+
+    async fn load_index(&self) -> Result<CacheIndex> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(CacheIndex::default());
+        }
+        let content = fs::read_to_string(&path).await?;
+        // A corrupt index only loses access times; never block a download on it.
+        Ok(toml::from_str(&content).unwrap_or_default())
+    }
+
+    async fn save_index(&self, index: &CacheIndex) -> Result<()> {
+        ensure_dir_exists(&self.servers_dir).await?;
+        let content = toml::to_string_pretty(index)?;
+        fs::write(self.index_path(), content).await?;
+        Ok(())
+    }
+
+    /// Mark a version/arch as used right now.
+    pub async fn touch(&self, version: &str, arch: &str) -> Result<()> {
+        let mut index = self.load_index().await?;
+        index.entries.insert(
+            Self::index_key(version, arch),
+            CacheIndexEntry {
+                last_access: Utc::now().timestamp(),
+            },
+        );
+        self.save_index(&index).await
+    }
+
+    /// Copy an existing frida-server binary into the cache, replacing any cached build for
+    /// the same version/arch.
+    pub async fn import(&self, version: &str, arch: &str, source: &Path) -> Result<PathBuf> {
+        let dest_dir = self.servers_dir.join(version).join(arch);
+        ensure_dir_exists(&dest_dir).await?;
+        let dest = dest_dir.join("frida-server");
+        fs::copy(source, &dest).await?;
+        make_executable(&dest).await?;
+        fs::write(digest_path(&dest), compute_sha256(&dest).await?).await?;
+        self.touch(version, arch).await?;
+        Ok(dest)
+    }
+
+    /// All cached version/arch entries with their on-disk size and last access time.
+    pub async fn entries(&self) -> Result<Vec<CachedServer>> {
+        if !self.servers_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let index = self.load_index().await?;
+        let mut out = Vec::new();
+
+        let mut versions = fs::read_dir(&self.servers_dir).await?;
+        while let Some(version_entry) = versions.next_entry().await? {
+            if !version_entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let Some(version) = version_entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+
+            let mut arches = fs::read_dir(version_entry.path()).await?;
+            while let Some(arch_entry) = arches.next_entry().await? {
+                if !arch_entry.file_type().await?.is_dir() {
+                    continue;
+                }
+                let Some(arch) = arch_entry.file_name().to_str().map(str::to_string) else {
+                    continue;
+                };
+
+                let path = arch_entry.path();
+                let size_bytes = dir_size(&path).await?;
+                let last_access = match index.entries.get(&Self::index_key(&version, &arch)) {
+                    Some(entry) => entry.last_access,
+                    None => modified_timestamp(&path).await,
+                };
+
+                out.push(CachedServer {
+                    version: version.clone(),
+                    arch,
+                    path,
+                    size_bytes,
+                    last_access,
+                });
+            }
+        }
+
+        out.sort_by(|a, b| a.version.cmp(&b.version).then(a.arch.cmp(&b.arch)));
+        Ok(out)
+    }
+
+    /// Delete a cached entry (and its version directory once empty).
+    pub async fn remove(&self, entry: &CachedServer) -> Result<()> {
+        if entry.path.exists() {
+            fs::remove_dir_all(&entry.path).await?;
+        }
+
+        if let Some(version_dir) = entry.path.parent() {
+            let mut remaining = fs::read_dir(version_dir).await?;
+            if remaining.next_entry().await?.is_none() {
+                fs::remove_dir(version_dir).await?;
+            }
+        }
+
+        let mut index = self.load_index().await?;
+        if index
+            .entries
+            .remove(&Self::index_key(&entry.version, &entry.arch))
+            .is_some()
+        {
+            self.save_index(&index).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Indices of entries to evict (oldest first) so the remaining total is <= `max_bytes`.
+pub fn select_lru_evictions(
+    entries: &[CachedServer],
+    max_bytes: u64,
+    protected: &HashSet<String>,
+) -> Vec<usize> {
+    let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    if total <= max_bytes {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<usize> = (0..entries.len())
+        .filter(|&i| !protected.contains(&entries[i].version))
+        .collect();
+    candidates.sort_by_key(|&i| entries[i].last_access);
+
+    let mut selected = Vec::new();
+    for idx in candidates {
+        if total <= max_bytes {
+            break;
+        }
+        total = total.saturating_sub(entries[idx].size_bytes);
+        selected.push(idx);
+    }
+    selected
+}
+
+/// Indices of entries `cache prune` deletes: those outside the `keep` most recently used
+/// versions and last used before `cutoff` (a Unix timestamp), whichever criteria are set.
+/// Entries whose version is in `protected` are never selected.
+pub fn select_prunable(
+    entries: &[CachedServer],
+    keep: Option<usize>,
+    cutoff: Option<i64>,
+    protected: &HashSet<String>,
+) -> Vec<usize> {
+    let mut recent: Vec<(&str, i64)> = Vec::new();
+    for entry in entries {
+        match recent.iter_mut().find(|(v, _)| *v == entry.version) {
+            Some((_, last)) => *last = (*last).max(entry.last_access),
+            None => recent.push((&entry.version, entry.last_access)),
+        }
+    }
+    recent.sort_by_key(|&(_, last)| std::cmp::Reverse(last));
+    let kept: HashSet<&str> = recent
+        .iter()
+        .take(keep.unwrap_or(usize::MAX))
+        .map(|&(v, _)| v)
+        .collect();
+
+    (0..entries.len())
+        .filter(|&i| {
+            let entry = &entries[i];
+            !protected.contains(&entry.version)
+                && (keep.is_none() || !kept.contains(entry.version.as_str()))
+                && cutoff.is_none_or(|cutoff| entry.last_access < cutoff)
+        })
+        .collect()
+}
+
+/// Every server cache root under `cache_dir`: upstream `servers/` plus each fork's
+/// `forks/<owner>/<repo>/`.
+pub async fn server_cache_dirs(cache_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![cache_dir.join("servers")];
+    let forks_dir = cache_dir.join("forks");
+    if !forks_dir.exists() {
+        return Ok(dirs);
+    }
+    let mut owners = fs::read_dir(&forks_dir).await?;
+    while let Some(owner) = owners.next_entry().await? {
+        if !owner.file_type().await?.is_dir() {
+            continue;
+        }
+        let mut repos = fs::read_dir(owner.path()).await?;
+        while let Some(repo) = repos.next_entry().await? {
+            if repo.file_type().await?.is_dir() {
+                dirs.push(repo.path());
+            }
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Every cached build under `cache_dir`, upstream and forks, with the cache root it
+/// lives in.
+pub async fn cached_servers(cache_dir: &Path) -> Result<Vec<(PathBuf, CachedServer)>> {
+    let mut builds = Vec::new();
+    for servers_dir in server_cache_dirs(cache_dir).await? {
+        for entry in ServerCache::at(servers_dir.clone()).entries().await? {
+            builds.push((servers_dir.clone(), entry));
+        }
+    }
+    Ok(builds)
+}
+
+/// Evict least-recently-used builds, upstream and forks alike, until together they fit
+/// in `max_bytes`. Entries whose version is in `protected` are never evicted.
+pub async fn evict_to_fit(
+    cache_dir: &Path,
+    max_bytes: u64,
+    protected: &HashSet<String>,
+) -> Result<Vec<CachedServer>> {
+    let builds = cached_servers(cache_dir).await?;
+    let entries: Vec<CachedServer> = builds.iter().map(|(_, entry)| entry.clone()).collect();
+    let mut evicted = Vec::new();
+    for idx in select_lru_evictions(&entries, max_bytes, protected) {
+        let (servers_dir, entry) = &builds[idx];
+        ServerCache::at(servers_dir.clone()).remove(entry).await?;
+        evicted.push(entry.clone());
+    }
+    Ok(evicted)
+}
+
+/// Remove every cached build whose (cache root, version) pair is not in `referenced`,
+/// dropping fork directories left empty. Returns the removed entries.
+pub async fn prune_unreferenced(
+    cache_dir: &Path,
+    referenced: &HashSet<(PathBuf, String)>,
+) -> Result<Vec<CachedServer>> {
+    let mut removed = Vec::new();
+    for servers_dir in server_cache_dirs(cache_dir).await? {
+        let cache = ServerCache::at(servers_dir.clone());
+        let entries = cache.entries().await?;
+        let stale: Vec<CachedServer> = entries
+            .iter()
+            .filter(|e| !referenced.contains(&(servers_dir.clone(), e.version.clone())))
+            .cloned()
+            .collect();
+        for entry in &stale {
+            cache.remove(entry).await?;
+        }
+
+        if stale.len() == entries.len() && servers_dir.starts_with(cache_dir.join("forks")) {
+            fs::remove_dir_all(&servers_dir).await?;
+            if let Some(owner_dir) = servers_dir.parent() {
+                if fs::read_dir(owner_dir).await?.next_entry().await?.is_none() {
+                    fs::remove_dir(owner_dir).await?;
+                }
+            }
+        }
+        removed.extend(stale);
+    }
+    Ok(removed)
+}
+
+async fn modified_timestamp(path: &Path) -> i64 {
+    fs::metadata(path)
+        .await
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Enforce `cache.max_size_gb` after a download when `cache.auto_clean` is enabled.
+/// Versions referenced by registered projects are kept regardless of age.
+pub async fn auto_clean(
+    global_mgr: &GlobalConfigManager,
+    cache_config: &CacheConfig,
+) -> Result<()> {
+    if !cache_config.auto_clean || cache_config.max_size_gb == 0 {
+        return Ok(());
+    }
+
+    let registry =
+        ProjectRegistry::load_or_default(&global_mgr.get_project_registry_path()).await?;
+    let protected = registry.referenced_frida_versions().await;
+
+    let evicted = evict_to_fit(
+        &global_mgr.get_cache_dir(),
+        cache_config.max_size_gb * BYTES_PER_GB,
+        &protected,
+    )
+    .await?;
+
+    if !evicted.is_empty() {
+        let reclaimed: u64 = evicted.iter().map(|e| e.size_bytes).sum();
+        println!(
+            "{} Cache exceeded {} GB; evicted {} least recently used frida-server build(s) ({})",
+            "ℹ".blue().bold(),
+            cache_config.max_size_gb,
+            evicted.len().to_string().cyan(),
+            format_size(reclaimed).yellow()
+        );
+        for entry in &evicted {
+            println!("  - {}", entry.path.display().to_string().dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(version: &str, size_bytes: u64, last_access: i64) -> CachedServer {
+        CachedServer {
+            version: version.to_string(),
+            arch: "arm64".to_string(),
+            path: PathBuf::from(version),
+            size_bytes,
+            last_access,
+        }
+    }
+
+    #[test]
+    fn nothing_evicted_under_limit() {
+        let entries = vec![entry("16.0.0", 10, 1), entry("16.1.0", 10, 2)];
+        assert!(select_lru_evictions(&entries, 20, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn evicts_oldest_first_until_fit() {
+        let entries = vec![
+            entry("16.0.0", 10, 3),
+            entry("16.1.0", 10, 1),
+            entry("16.2.0", 10, 2),
+        ];
+        assert_eq!(
+            select_lru_evictions(&entries, 15, &HashSet::new()),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn protected_versions_are_skipped() {
+        let entries = vec![entry("16.0.0", 10, 1), entry("16.1.0", 10, 2)];
+        let protected: HashSet<String> = ["16.0.0".to_string()].into_iter().collect();
+        assert_eq!(select_lru_evictions(&entries, 10, &protected), vec![1]);
+    }
+
+    #[test]
+    fn prune_keeps_recent_versions_and_respects_cutoff() {
+        let mut entries = vec![
+            entry("16.0.0", 10, 100),
+            entry("16.1.0", 10, 300),
+            entry("16.2.0", 10, 200),
+            entry("16.3.0", 10, 50),
+        ];
+        entries.push(CachedServer {
+            arch: "x86_64".to_string(),
+            ..entry("16.0.0", 10, 400)
+        });
+        let none = HashSet::new();
+
+        // 16.0.0 was used most recently through its x86_64 build.
+        assert_eq!(select_prunable(&entries, Some(2), None, &none), vec![2, 3]);
+        assert_eq!(
+            select_prunable(&entries, None, Some(150), &none),
+            vec![0, 3]
+        );
+        assert_eq!(
+            select_prunable(&entries, Some(1), Some(250), &none),
+            vec![2, 3]
+        );
+
+        let protected: HashSet<String> = ["16.3.0".to_string()].into_iter().collect();
+        assert_eq!(
+            select_prunable(&entries, Some(2), None, &protected),
+            vec![2]
+        );
+    }
+
+    #[tokio::test]
+    async fn touch_and_remove_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ServerCache::new(dir.path());
+        let arch_dir = dir.path().join("servers/16.6.6/arm64");
+        tokio::fs::create_dir_all(&arch_dir).await.unwrap();
+        tokio::fs::write(arch_dir.join("frida-server"), vec![0u8; 4])
+            .await
+            .unwrap();
+        cache.touch("16.6.6", "arm64").await.unwrap();
+
+        let entries = cache.entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].size_bytes, 4);
+        assert!(entries[0].last_access > 0);
+
+        cache.remove(&entries[0]).await.unwrap();
+        assert!(!dir.path().join("servers/16.6.6").exists());
+        assert!(cache.entries().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn evicts_fork_builds_under_the_shared_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        for (build, last_access) in [
+            ("servers/16.5.2", 3),
+            ("forks/acme/stealth/16.0.0", 1),
+            ("forks/acme/stealth/16.5.2", 2),
+        ] {
+            let arch_dir = dir.path().join(build).join("arm64");
+            tokio::fs::create_dir_all(&arch_dir).await.unwrap();
+            tokio::fs::write(arch_dir.join("frida-server"), vec![0u8; 4])
+                .await
+                .unwrap();
+            let (root, version) = build.rsplit_once('/').unwrap();
+            let cache = ServerCache::at(dir.path().join(root));
+            let mut index = cache.load_index().await.unwrap();
+            index.entries.insert(
+                ServerCache::index_key(version, "arm64"),
+                CacheIndexEntry { last_access },
+            );
+            cache.save_index(&index).await.unwrap();
+        }
+
+        let evicted = evict_to_fit(dir.path(), 8, &HashSet::new()).await.unwrap();
+        assert_eq!(evicted.len(), 1);
+        assert!(!dir.path().join("forks/acme/stealth/16.0.0").exists());
+        assert!(dir.path().join("forks/acme/stealth/16.5.2").exists());
+        assert!(dir.path().join("servers/16.5.2").exists());
+    }
+
+    #[tokio::test]
+    async fn prunes_builds_no_project_references() {
+        let dir = tempfile::tempdir().unwrap();
+        for build in [
+            "servers/16.5.2/arm64",
+            "servers/16.0.0/arm64",
+            "forks/acme/stealth/16.5.2/arm64",
+        ] {
+            let arch_dir = dir.path().join(build);
+            tokio::fs::create_dir_all(&arch_dir).await.unwrap();
+            tokio::fs::write(arch_dir.join("frida-server"), vec![0u8; 4])
+                .await
+                .unwrap();
+        }
+
+        let referenced: HashSet<(PathBuf, String)> =
+            [(dir.path().join("servers"), "16.5.2".to_string())]
+                .into_iter()
+                .collect();
+        let mut removed: Vec<String> = prune_unreferenced(dir.path(), &referenced)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| {
+                e.path
+                    .strip_prefix(dir.path())
+                    .unwrap()
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        removed.sort();
+
+        assert_eq!(
+            removed,
+            ["forks/acme/stealth/16.5.2/arm64", "servers/16.0.0/arm64"]
+        );
+        assert!(dir
+            .path()
+            .join("servers/16.5.2/arm64/frida-server")
+            .exists());
+        assert!(!dir.path().join("forks/acme").exists());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::config::AndroidServerConfig;
+use std::path::{Path, PathBuf};
+
+/// Repository official frida-server builds are published in.
+pub const UPSTREAM_REPO: &str = "frida/frida";
+
+/// Asset name pattern of upstream Android builds, also assumed for forks that do not set
+/// `android.server.asset`.
+pub const DEFAULT_ASSET_PATTERN: &str = "frida-server-{version}-android-{arch}.xz";
+
+/// Where Android frida-server builds are downloaded from: upstream or a fork's GitHub
+/// releases (`android.server.repo` / `android.server.asset`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerDistribution {
+    pub repo: String,
+    pub asset_pattern: String,
+}
+
+impl Default for ServerDistribution {
+    fn default() -> Self {
+        Self {
+            repo: UPSTREAM_REPO.to_string(),
+            asset_pattern: DEFAULT_ASSET_PATTERN.to_string(),
+        }
+    }
+}
+
+impl ServerDistribution {
+    pub fn from_config(config: &AndroidServerConfig) -> Self {
+        match config.repo.as_deref() {
+            Some(repo) if repo != UPSTREAM_REPO => Self {
+                repo: repo.to_string(),
+                asset_pattern: config
+                    .asset
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ASSET_PATTERN.to_string()),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    pub fn is_upstream(&self) -> bool {
+        self.repo == UPSTREAM_REPO
+    }
+
+    pub fn asset_name(&self, version: &str, asset_arch: &str) -> String {
+        self.asset_pattern
+            .replace("{version}", version)
+            .replace("{arch}", asset_arch)
+    }
+
+    /// Path of a release asset relative to the release host, for `AssetHosts::download`.
+    pub fn host_path(&self, version: &str, asset_name: &str) -> String {
+        format!(
+            "/{}/releases/download/{}/{}",
+            self.repo, version, asset_name
+        )
+    }
+
+    /// Root of the `<version>/<arch>/` tree builds are cached in. Forks get their own
+    /// namespace under `forks/<owner>/<repo>/` so they never shadow upstream builds.
+    pub fn servers_dir(&self, cache_dir: &Path) -> PathBuf {
+        if self.is_upstream() {
+            return cache_dir.join("servers");
+        }
+        self.repo
+            .split('/')
+            .fold(cache_dir.join("forks"), |dir, part| dir.join(part))
+    }
+
+    /// Human-readable origin for `status` and download messages.
+    pub fn describe(&self) -> String {
+        if self.is_upstream() {
+            format!("{} (upstream)", self.repo)
+        } else {
+            format!("{} ({})", self.repo, self.asset_pattern)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fork(asset: Option<&str>) -> ServerDistribution {
+        ServerDistribution::from_config(&AndroidServerConfig {
+            repo: Some("hzzheyang/strongR-frida-android".to_string()),
+            asset: asset.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn upstream_keeps_the_existing_layout() {
+        let upstream = ServerDistribution::from_config(&AndroidServerConfig::default());
+        assert!(upstream.is_upstream());
+        assert_eq!(
+            upstream.asset_name("16.6.6", "arm64"),
+            "frida-server-16.6.6-android-arm64.xz"
+        );
+        assert_eq!(
+            upstream.host_path("16.6.6", "frida-server-16.6.6-android-arm64.xz"),
+            "/frida/frida/releases/download/16.6.6/frida-server-16.6.6-android-arm64.xz"
+        );
+        assert_eq!(
+            upstream.servers_dir(Path::new("/cache")),
+            PathBuf::from("/cache/servers")
+        );
+    }
+
+    #[test]
+    fn forks_use_their_repo_pattern_and_namespace() {
+        let build = fork(Some("hluda-server-{version}-android-{arch}.gz"));
+        assert!(!build.is_upstream());
+        assert_eq!(
+            build.asset_name("16.5.9", "x86_64"),
+            "hluda-server-16.5.9-android-x86_64.gz"
+        );
+        assert_eq!(
+            build.host_path("16.5.9", "a.gz"),
+            "/hzzheyang/strongR-frida-android/releases/download/16.5.9/a.gz"
+        );
+        assert_eq!(
+            build.servers_dir(Path::new("/cache")),
+            PathBuf::from("/cache/forks/hzzheyang/strongR-frida-android")
+        );
+        assert_eq!(fork(None).asset_pattern, DEFAULT_ASSET_PATTERN);
+    }
+
+    #[test]
+    fn custom_source_reads_repo_and_asset_from_toml() {
+        let config: AndroidServerConfig = toml::from_str(
+            r#"
+source = "custom"
+repo = "Ylarod/Florida"
+asset = "florida-server-{version}-android-{arch}.gz"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.source, crate::config::AndroidServerSource::Custom);
+        let build = ServerDistribution::from_config(&config);
+        assert_eq!(
+            build.asset_name("16.2.1", "arm64"),
+            "florida-server-16.2.1-android-arm64.gz"
+        );
+        assert_eq!(
+            build.servers_dir(Path::new("/cache")),
+            PathBuf::from("/cache/forks/Ylarod/Florida")
+        );
+    }
+}
@@ -1,66 +1,471 @@
+use crate::config::schema::NetworkConfig;
 use crate::config::ArchType;
-use crate::core::error::Result;
-use crate::core::{decompress_xz, ensure_dir_exists, make_executable, HttpClient};
+use crate::core::error::{FridaMgrError, Result};
+use crate::core::mirror::HOST_HEALTH_FILE;
+use crate::core::{
+    compute_sha256, decompress_gz, decompress_xz, ensure_dir_exists, interrupt, make_executable,
+    notify, part_path, AssetHosts, EventBus, HttpClient,
+};
+use crate::frida::release::{fetch_repo_release_assets, published_sha256, ReleaseAsset};
+use crate::frida::server::cache::{digest_path, ServerCache};
+use crate::frida::server::distribution::ServerDistribution;
+use crate::frida::server::platform::ServerPlatform;
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tokio::sync::OnceCell;
+
+/// The assets of the release being fetched from, looked up at most once however many
+/// arches are downloaded; `None` when the lookup failed.
+type ReleaseAssets = OnceCell<Option<Vec<ReleaseAsset>>>;
+
+/// How `fetch_for` provided a binary.
+#[derive(Clone, Copy, PartialEq)]
+enum Fetched {
+    Cached,
+    /// Downloaded and checked against the release's published checksum.
+    Verified,
+    /// Downloaded without a published checksum to check against.
+    Unverified,
+}
 
 pub struct ServerDownloader {
     cache_dir: PathBuf,
     http_client: HttpClient,
+    hosts: AssetHosts,
+    notifications: bool,
+    distribution: ServerDistribution,
 }
 
 impl ServerDownloader {
     pub fn new(cache_dir: PathBuf) -> Self {
+        let hosts = AssetHosts::new(&[], cache_dir.join(HOST_HEALTH_FILE));
         Self {
             cache_dir,
             http_client: HttpClient::new(),
+            hosts,
+            notifications: false,
+            distribution: ServerDistribution::default(),
+        }
+    }
+
+    pub fn with_network(cache_dir: PathBuf, network: &NetworkConfig) -> Result<Self> {
+        let hosts = AssetHosts::new(&network.fallback_hosts, cache_dir.join(HOST_HEALTH_FILE));
+        Ok(Self {
+            cache_dir,
+            http_client: HttpClient::from_network_config(network)?,
+            hosts,
+            notifications: false,
+            distribution: ServerDistribution::default(),
+        })
+    }
+
+    /// Send a desktop notification once a download finishes.
+    pub fn with_notifications(mut self, enabled: bool) -> Self {
+        self.notifications = enabled;
+        self
+    }
+
+    /// Fetch Android builds from `distribution` (a fork's releases) instead of upstream.
+    pub fn with_distribution(mut self, distribution: ServerDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// The distribution `platform` builds come from; forks only publish Android servers.
+    fn distribution_for(&self, platform: ServerPlatform) -> ServerDistribution {
+        if platform == ServerPlatform::Android {
+            self.distribution.clone()
+        } else {
+            ServerDistribution::default()
+        }
+    }
+
+    async fn record_access(&self, distribution: &ServerDistribution, version: &str, arch: &str) {
+        // `ServerCache` only tracks upstream builds. Access times only steer eviction
+        // order; failing to record one is harmless.
+        if distribution.is_upstream() {
+            let _ = ServerCache::new(&self.cache_dir).touch(version, arch).await;
         }
     }
 
     pub async fn download(&self, version: &str, arch: &ArchType) -> Result<PathBuf> {
-        let arch_str = self.get_arch_string(arch);
-        let cache_path = self.get_cache_path(version, &arch_str);
+        self.download_for(version, ServerPlatform::Android, arch)
+            .await
+    }
+
+    /// Download (or reuse) the frida-server build for any supported platform.
+    pub async fn download_for(
+        &self,
+        version: &str,
+        platform: ServerPlatform,
+        arch: &ArchType,
+    ) -> Result<PathBuf> {
+        self.fetch_for(version, platform, arch, &ReleaseAssets::new(), false)
+            .await
+            .map(|(cache_path, _)| cache_path)
+    }
+
+    /// Download (or reuse) the frida-server builds for several architectures concurrently,
+    /// each cached under its own arch directory. Every download runs to completion; the
+    /// first failure is returned once they have all finished.
+    pub async fn download_all(
+        &self,
+        version: &str,
+        platform: ServerPlatform,
+        arches: &[ArchType],
+    ) -> Result<Vec<PathBuf>> {
+        // Arches sharing a cache directory (`auto` and `arm64`) are fetched once.
+        let mut cache_keys = Vec::new();
+        let mut targets = Vec::new();
+        for arch in arches {
+            let cache_key = platform.cache_key(platform.asset_arch(arch)?);
+            if !cache_keys.contains(&cache_key) {
+                cache_keys.push(cache_key);
+                targets.push(arch);
+            }
+        }
+        println!(
+            "{} Fetching frida-server {} for {}...",
+            "↓".blue().bold(),
+            version.cyan(),
+            cache_keys.join(", ").yellow()
+        );
+
+        let releases = ReleaseAssets::new();
+        let results = futures::future::join_all(
+            targets
+                .into_iter()
+                .map(|arch| self.fetch_for(version, platform, arch, &releases, true)),
+        )
+        .await;
+
+        let distribution = self.distribution_for(platform);
+        let mut paths = Vec::new();
+        let mut downloaded = Vec::new();
+        let mut first_error = None;
+        for (cache_key, result) in cache_keys.iter().zip(results) {
+            match result {
+                Ok((cache_path, fetched)) => {
+                    println!(
+                        "  {} {} ({})",
+                        "✓".green().bold(),
+                        cache_key.yellow(),
+                        match fetched {
+                            Fetched::Cached => "cached",
+                            Fetched::Verified => "downloaded, checksum verified",
+                            Fetched::Unverified => {
+                                "downloaded, no published checksum; verification skipped"
+                            }
+                        }
+                    );
+                    // One at a time: concurrent writes to the cache index would race.
+                    self.record_access(&distribution, version, cache_key).await;
+                    if fetched != Fetched::Cached {
+                        downloaded.push(cache_key.as_str());
+                    }
+                    paths.push(cache_path);
+                }
+                Err(e) => {
+                    println!("  {} {}: {}", "✗".red().bold(), cache_key.yellow(), e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if !downloaded.is_empty() {
+            notify(
+                self.notifications,
+                "frida-server downloaded",
+                &format!(
+                    "frida-server {} for {} is ready",
+                    version,
+                    downloaded.join(", ")
+                ),
+            )
+            .await;
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(paths),
+        }
+    }
+
+    /// The cached binary for `arch`, downloaded first when it is missing or fails its
+    /// recorded checksum, and how it was obtained. A `batch` fetch runs alongside others
+    /// sharing `releases`, so it stays quiet and leaves access times, the notification and
+    /// the verification notice to `download_all`.
+    async fn fetch_for(
+        &self,
+        version: &str,
+        platform: ServerPlatform,
+        arch: &ArchType,
+        releases: &ReleaseAssets,
+        batch: bool,
+    ) -> Result<(PathBuf, Fetched)> {
+        let asset_arch = platform.asset_arch(arch)?;
+        let cache_key = platform.cache_key(asset_arch);
+        let distribution = self.distribution_for(platform);
+        let cache_path = self.get_cache_path(&distribution, version, &cache_key, platform);
+        let origin = if distribution.is_upstream() {
+            String::new()
+        } else {
+            format!(" from {}", distribution.repo)
+        };
 
         // Check if already cached
         if cache_path.exists() {
+            if is_intact(&cache_path).await? {
+                if !batch {
+                    println!(
+                        "{} Using cached frida-server {} for {}{}",
+                        "✓".green().bold(),
+                        version.cyan(),
+                        cache_key.yellow(),
+                        origin
+                    );
+                    self.record_access(&distribution, version, &cache_key).await;
+                }
+                return Ok((cache_path, Fetched::Cached));
+            }
             println!(
-                "{} Using cached frida-server {} for {}",
-                "✓".green().bold(),
+                "{} Cached frida-server {} for {} failed checksum verification; downloading again",
+                "⚠".yellow().bold(),
                 version.cyan(),
-                arch_str.yellow()
+                cache_key.yellow()
             );
-            return Ok(cache_path);
+            tokio::fs::remove_file(&cache_path).await?;
         }
 
+        if !batch {
+            println!(
+                "{} Downloading frida-server {} for {}{}...",
+                "↓".blue().bold(),
+                version.cyan(),
+                cache_key.yellow(),
+                origin
+            );
+        }
+
+        let asset_name = if distribution.is_upstream() {
+            platform.asset_name(version, asset_arch)
+        } else {
+            distribution.asset_name(version, asset_arch)
+        };
+        let asset_path = distribution.host_path(version, &asset_name);
+        let expected_sha256 = self
+            .published_digest(&distribution, version, &asset_name, releases, batch)
+            .await;
+        let fetched = if expected_sha256.is_some() {
+            Fetched::Verified
+        } else {
+            Fetched::Unverified
+        };
+        EventBus::global()
+            .step(
+                format!("download frida-server {} ({})", version, cache_key),
+                self.fetch_server(&asset_path, &cache_path, expected_sha256.as_deref(), batch),
+            )
+            .await?;
+        if batch {
+            return Ok((cache_path, fetched));
+        }
+        self.record_access(&distribution, version, &cache_key).await;
+
         println!(
-            "{} Downloading frida-server {} for {}...",
-            "↓".blue().bold(),
-            version.cyan(),
-            arch_str.yellow()
+            "{} frida-server {} downloaded and cached",
+            "✓".green().bold(),
+            version.cyan()
         );
+        notify(
+            self.notifications,
+            "frida-server downloaded",
+            &format!("frida-server {} for {} is ready", version, cache_key),
+        )
+        .await;
 
-        ensure_dir_exists(cache_path.parent().unwrap()).await?;
+        Ok((cache_path, fetched))
+    }
+
+    /// The SHA-256 the release publishes for `asset_name`, looking the release up through
+    /// `releases`. A release without one, or an unreachable GitHub API, only skips
+    /// verification (`quiet` leaves out the notice for the former).
+    async fn published_digest(
+        &self,
+        distribution: &ServerDistribution,
+        version: &str,
+        asset_name: &str,
+        releases: &ReleaseAssets,
+        quiet: bool,
+    ) -> Option<String> {
+        let assets = releases
+            .get_or_init(|| async {
+                match fetch_repo_release_assets(&self.http_client, &distribution.repo, version)
+                    .await
+                {
+                    Ok(assets) => Some(assets),
+                    Err(e) => {
+                        eprintln!(
+                            "{} Could not look up the checksums of frida-server {} ({}); skipping verification",
+                            "⚠".yellow().bold(),
+                            version,
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .await
+            .as_deref()?;
+        match published_sha256(&self.http_client, assets, asset_name).await {
+            Ok(Some(digest)) => Some(digest),
+            Ok(None) => {
+                if quiet {
+                    return None;
+                }
+                println!(
+                    "{} No published checksum for {}; skipping verification",
+                    "ℹ".blue().bold(),
+                    asset_name
+                );
+                None
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} Could not look up the checksum for {} ({}); skipping verification",
+                    "⚠".yellow().bold(),
+                    asset_name,
+                    e
+                );
+                None
+            }
+        }
+    }
 
-        let url = self.get_download_url(version, &arch_str);
-        let compressed_path = cache_path.with_extension("xz");
+    /// Download `asset_path`, check it against `expected_sha256`, unpack it to `cache_path`
+    /// and record the binary's own digest next to it for `is_intact`. `quiet` skips the
+    /// progress lines.
+    async fn fetch_server(
+        &self,
+        asset_path: &str,
+        cache_path: &Path,
+        expected_sha256: Option<&str>,
+        quiet: bool,
+    ) -> Result<()> {
+        ensure_dir_exists(cache_path.parent().unwrap()).await?;
+        // Upstream ships .xz; forks may also publish .gz or the bare binary.
+        let extension = if asset_path.ends_with(".gz") {
+            "gz"
+        } else if asset_path.ends_with(".xz") {
+            "xz"
+        } else {
+            "download"
+        };
+        let compressed_path = cache_path.with_extension(extension);
+        let _guard = interrupt::remove_on_interrupt(&compressed_path);
 
-        // Download compressed file
-        self.http_client
-            .download_file(&url, &compressed_path)
+        // Download compressed file, falling back to mirrors if the primary host fails
+        self.hosts
+            .download(&self.http_client, asset_path, &compressed_path)
             .await?;
 
+        if let Some(expected) = expected_sha256 {
+            let actual = compute_sha256(&compressed_path).await?;
+            if actual != expected {
+                tokio::fs::remove_file(&compressed_path).await?;
+                return Err(FridaMgrError::ChecksumMismatch(format!(
+                    "{} (expected sha256 {}, got {})",
+                    asset_path.rsplit('/').next().unwrap_or(asset_path),
+                    expected,
+                    actual
+                )));
+            }
+            if !quiet {
+                println!("{} Checksum verified", "✓".green().bold());
+            }
+        }
+
         // Decompress
-        println!("{} Decompressing...", "⚙".blue().bold());
-        decompress_xz(&compressed_path, &cache_path).await?;
+        if !quiet && extension != "download" {
+            println!("{} Decompressing...", "⚙".blue().bold());
+        }
+        match extension {
+            "gz" => decompress_gz(&compressed_path, cache_path).await?,
+            "xz" => decompress_xz(&compressed_path, cache_path).await?,
+            _ => {
+                // Like the decompressors, copy beside the target and rename so an
+                // interrupted copy never leaves a truncated binary in the cache.
+                let part = part_path(cache_path);
+                let _guard = interrupt::remove_on_interrupt(&part);
+                tokio::fs::copy(&compressed_path, &part).await?;
+                tokio::fs::rename(&part, cache_path).await?;
+            }
+        }
 
         // Make executable
-        make_executable(&cache_path).await?;
+        make_executable(cache_path).await?;
+        tokio::fs::write(digest_path(cache_path), compute_sha256(cache_path).await?).await?;
 
         // Clean up compressed file
         tokio::fs::remove_file(&compressed_path).await?;
+        Ok(())
+    }
+
+    /// Download (or reuse) the Android frida-gadget library, cached decompressed under
+    /// `assets/<version>/` and checked against the release's published checksum like
+    /// frida-server builds.
+    pub async fn download_gadget(&self, version: &str, arch: &ArchType) -> Result<PathBuf> {
+        let asset_arch = ServerPlatform::Android.asset_arch(arch)?;
+        let asset_name = format!("frida-gadget-{}-android-{}.so.xz", version, asset_arch);
+        let cache_path = self
+            .cache_dir
+            .join("assets")
+            .join(version)
+            .join(asset_name.trim_end_matches(".xz"));
+
+        if cache_path.exists() {
+            if is_intact(&cache_path).await? {
+                println!(
+                    "{} Using cached frida-gadget {} for {}",
+                    "✓".green().bold(),
+                    version.cyan(),
+                    asset_arch.yellow()
+                );
+                return Ok(cache_path);
+            }
+            println!(
+                "{} Cached frida-gadget {} for {} failed checksum verification; downloading again",
+                "⚠".yellow().bold(),
+                version.cyan(),
+                asset_arch.yellow()
+            );
+            tokio::fs::remove_file(&cache_path).await?;
+        }
 
         println!(
-            "{} frida-server {} downloaded and cached",
+            "{} Downloading frida-gadget {} for {}...",
+            "↓".blue().bold(),
+            version.cyan(),
+            asset_arch.yellow()
+        );
+        let expected_sha256 = self
+            .published_digest(
+                &ServerDistribution::default(),
+                version,
+                &asset_name,
+                &ReleaseAssets::new(),
+                false,
+            )
+            .await;
+        self.fetch_server(
+            &self.get_asset_path(version, &asset_name),
+            &cache_path,
+            expected_sha256.as_deref(),
+            false,
+        )
+        .await?;
+
+        println!(
+            "{} frida-gadget {} downloaded and cached",
             "✓".green().bold(),
             version.cyan()
         );
@@ -68,43 +473,62 @@ impl ServerDownloader {
         Ok(cache_path)
     }
 
-    fn get_download_url(&self, version: &str, arch: &str) -> String {
-        format!(
-            "https://github.com/frida/frida/releases/download/{}/frida-server-{}-android-{}.xz",
-            version, version, arch
-        )
+    fn get_asset_path(&self, version: &str, asset_name: &str) -> String {
+        format!("/frida/frida/releases/download/{}/{}", version, asset_name)
     }
 
-    fn get_cache_path(&self, version: &str, arch: &str) -> PathBuf {
-        self.cache_dir
-            .join("servers")
+    fn get_cache_path(
+        &self,
+        distribution: &ServerDistribution,
+        version: &str,
+        cache_key: &str,
+        platform: ServerPlatform,
+    ) -> PathBuf {
+        distribution
+            .servers_dir(&self.cache_dir)
             .join(version)
-            .join(arch)
-            .join("frida-server")
+            .join(cache_key)
+            .join(platform.binary_name())
     }
 
-    fn get_arch_string(&self, arch: &ArchType) -> String {
-        match arch {
-            ArchType::Arm => "arm",
-            ArchType::Arm64 => "arm64",
-            ArchType::X86 => "x86",
-            ArchType::X8664 => "x86_64",
-            ArchType::Auto => "arm64", // default
-        }
-        .to_string()
+    pub async fn get_cached(&self, version: &str, arch: &ArchType) -> Option<PathBuf> {
+        self.get_cached_for(version, ServerPlatform::Android, arch)
+            .await
     }
 
-    pub async fn get_cached(&self, version: &str, arch: &ArchType) -> Option<PathBuf> {
-        let arch_str = self.get_arch_string(arch);
-        let cache_path = self.get_cache_path(version, &arch_str);
+    pub async fn get_cached_for(
+        &self,
+        version: &str,
+        platform: ServerPlatform,
+        arch: &ArchType,
+    ) -> Option<PathBuf> {
+        let cache_key = platform.cache_key(platform.asset_arch(arch).ok()?);
+        let distribution = self.distribution_for(platform);
+        let cache_path = self.get_cache_path(&distribution, version, &cache_key, platform);
 
         if cache_path.exists() {
+            self.record_access(&distribution, version, &cache_key).await;
             Some(cache_path)
         } else {
             None
         }
     }
 
+    /// Whether a frida-server release asset is already unpacked in the cache.
+    pub fn is_asset_cached(&self, version: &str, asset_name: &str) -> bool {
+        match ServerPlatform::parse_asset_name(asset_name, version) {
+            Some((platform, asset_arch)) => self
+                .get_cache_path(
+                    &ServerDistribution::default(),
+                    version,
+                    &platform.cache_key(&asset_arch),
+                    platform,
+                )
+                .exists(),
+            None => false,
+        }
+    }
+
     pub async fn list_cached_versions(&self) -> Result<Vec<String>> {
         let servers_dir = self.cache_dir.join("servers");
 
@@ -127,3 +551,37 @@ impl ServerDownloader {
         Ok(versions)
     }
 }
+
+/// Whether a cached binary still matches the digest recorded when it was downloaded.
+/// Binaries cached before digests were recorded are trusted.
+async fn is_intact(cache_path: &Path) -> Result<bool> {
+    let Ok(recorded) = tokio::fs::read_to_string(digest_path(cache_path)).await else {
+        return Ok(true);
+    };
+    Ok(compute_sha256(cache_path).await? == recorded.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cached_binaries_are_checked_against_the_recorded_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("frida-server");
+        tokio::fs::write(&binary, b"frida-server").await.unwrap();
+        assert_eq!(digest_path(&binary), dir.path().join("frida-server.sha256"));
+
+        // Nothing recorded yet (cached by an older frida-mgr).
+        assert!(is_intact(&binary).await.unwrap());
+
+        let digest = compute_sha256(&binary).await.unwrap();
+        tokio::fs::write(digest_path(&binary), format!("{}\n", digest))
+            .await
+            .unwrap();
+        assert!(is_intact(&binary).await.unwrap());
+
+        tokio::fs::write(&binary, b"frida-serveR").await.unwrap();
+        assert!(!is_intact(&binary).await.unwrap());
+    }
+}
@@ -1,3 +1,9 @@
+pub mod cache;
+pub mod distribution;
 pub mod download;
+pub mod platform;
 
+pub use cache::{CachedServer, ServerCache};
+pub use distribution::ServerDistribution;
 pub use download::ServerDownloader;
+pub use platform::ServerPlatform;
@@ -0,0 +1,183 @@
+use crate::config::ArchType;
+use crate::core::error::{FridaMgrError, Result};
+
+/// Operating system a frida-server build targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServerPlatform {
+    #[default]
+    Android,
+    Linux,
+    Macos,
+    Windows,
+}
+
+impl ServerPlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServerPlatform::Android => "android",
+            ServerPlatform::Linux => "linux",
+            ServerPlatform::Macos => "macos",
+            ServerPlatform::Windows => "windows",
+        }
+    }
+
+    /// Arch suffix used in release asset names (e.g. `linux-armhf`, `macos-arm64`).
+    pub fn asset_arch(&self, arch: &ArchType) -> Result<&'static str> {
+        let name = match (self, arch) {
+            (ServerPlatform::Android, ArchType::Arm) => Some("arm"),
+            (ServerPlatform::Android, ArchType::Arm64 | ArchType::Auto) => Some("arm64"),
+            (ServerPlatform::Android, ArchType::X86) => Some("x86"),
+            (ServerPlatform::Android, ArchType::X8664) => Some("x86_64"),
+            (ServerPlatform::Linux, ArchType::Arm) => Some("armhf"),
+            (ServerPlatform::Linux, ArchType::Arm64) => Some("arm64"),
+            (ServerPlatform::Linux, ArchType::X86) => Some("x86"),
+            (ServerPlatform::Linux, ArchType::X8664) => Some("x86_64"),
+            (ServerPlatform::Macos, ArchType::Arm64) => Some("arm64"),
+            (ServerPlatform::Macos, ArchType::X8664) => Some("x86_64"),
+            (ServerPlatform::Windows, ArchType::X86) => Some("x86"),
+            (ServerPlatform::Windows, ArchType::X8664) => Some("x86_64"),
+            _ => None,
+        };
+        name.ok_or_else(|| {
+            FridaMgrError::Config(format!(
+                "frida-server is not published for {} {}",
+                self.as_str(),
+                arch.to_str()
+            ))
+        })
+    }
+
+    /// The architectures frida-server is published for on this platform.
+    pub fn published_arches(&self) -> Vec<ArchType> {
+        [
+            ArchType::Arm,
+            ArchType::Arm64,
+            ArchType::X86,
+            ArchType::X8664,
+        ]
+        .into_iter()
+        .filter(|arch| self.asset_arch(arch).is_ok())
+        .collect()
+    }
+
+    /// Directory name under `servers/<version>/`. Android keeps the bare arch so
+    /// existing caches stay valid; desktop builds are prefixed with the OS.
+    pub fn cache_key(&self, asset_arch: &str) -> String {
+        match self {
+            ServerPlatform::Android => asset_arch.to_string(),
+            _ => format!("{}-{}", self.as_str(), asset_arch),
+        }
+    }
+
+    pub fn binary_name(&self) -> &'static str {
+        match self {
+            ServerPlatform::Windows => "frida-server.exe",
+            _ => "frida-server",
+        }
+    }
+
+    /// Split a frida-server asset name back into its platform and arch suffix.
+    pub fn parse_asset_name(asset_name: &str, version: &str) -> Option<(Self, String)> {
+        let rest = asset_name
+            .strip_prefix(&format!("frida-server-{}-", version))?
+            .strip_suffix(".xz")?;
+        let rest = rest.strip_suffix(".exe").unwrap_or(rest);
+        let (os, arch) = rest.split_once('-')?;
+        let platform = match os {
+            "android" => ServerPlatform::Android,
+            "linux" => ServerPlatform::Linux,
+            "macos" => ServerPlatform::Macos,
+            "windows" => ServerPlatform::Windows,
+            _ => return None,
+        };
+        Some((platform, arch.to_string()))
+    }
+
+    /// Release asset file name, e.g. `frida-server-16.6.6-windows-x86_64.exe.xz`.
+    pub fn asset_name(&self, version: &str, asset_arch: &str) -> String {
+        let ext = match self {
+            ServerPlatform::Windows => ".exe",
+            _ => "",
+        };
+        format!(
+            "frida-server-{}-{}-{}{}.xz",
+            version,
+            self.as_str(),
+            asset_arch,
+            ext
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_published_arches() {
+        assert_eq!(
+            ServerPlatform::Android.published_arches(),
+            [
+                ArchType::Arm,
+                ArchType::Arm64,
+                ArchType::X86,
+                ArchType::X8664
+            ]
+        );
+        assert_eq!(
+            ServerPlatform::Macos.published_arches(),
+            [ArchType::Arm64, ArchType::X8664]
+        );
+    }
+
+    #[test]
+    fn android_layout_is_unchanged() {
+        let p = ServerPlatform::Android;
+        let arch = p.asset_arch(&ArchType::Arm64).unwrap();
+        assert_eq!(p.cache_key(arch), "arm64");
+        assert_eq!(
+            p.asset_name("16.6.6", arch),
+            "frida-server-16.6.6-android-arm64.xz"
+        );
+    }
+
+    #[test]
+    fn desktop_asset_names() {
+        let linux = ServerPlatform::Linux;
+        assert_eq!(linux.asset_arch(&ArchType::Arm).unwrap(), "armhf");
+        assert_eq!(linux.cache_key("x86_64"), "linux-x86_64");
+
+        let windows = ServerPlatform::Windows;
+        assert_eq!(
+            windows.asset_name("16.6.6", "x86_64"),
+            "frida-server-16.6.6-windows-x86_64.exe.xz"
+        );
+        assert_eq!(windows.binary_name(), "frida-server.exe");
+    }
+
+    #[test]
+    fn parses_server_asset_names() {
+        assert_eq!(
+            ServerPlatform::parse_asset_name("frida-server-16.6.6-windows-x86_64.exe.xz", "16.6.6"),
+            Some((ServerPlatform::Windows, "x86_64".to_string()))
+        );
+        assert_eq!(
+            ServerPlatform::parse_asset_name("frida-server-16.6.6-linux-arm64-musl.xz", "16.6.6"),
+            Some((ServerPlatform::Linux, "arm64-musl".to_string()))
+        );
+        assert!(ServerPlatform::parse_asset_name(
+            "frida-gadget-16.6.6-android-arm64.so.xz",
+            "16.6.6"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn unsupported_combinations_are_rejected() {
+        assert!(ServerPlatform::Macos.asset_arch(&ArchType::X86).is_err());
+        assert!(ServerPlatform::Windows
+            .asset_arch(&ArchType::Arm64)
+            .is_err());
+        assert!(ServerPlatform::Linux.asset_arch(&ArchType::Auto).is_err());
+    }
+}
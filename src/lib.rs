@@ -1,5 +1,6 @@
 pub mod agent;
 pub mod android;
+pub mod bundle;
 pub mod cli;
 pub mod config;
 pub mod core;
@@ -1,10 +1,15 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use colored::Colorize;
-use frida_mgr::cli::{run, Cli};
+use frida_mgr::cli::{progress, run, Cli};
+use frida_mgr::core::{interrupt, EventBus};
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[tokio::main]
 async fn main() {
+    // Answer shell completion requests (`COMPLETE=<shell> frida-mgr ...`) and exit.
+    CompleteEnv::with_factory(Cli::command).complete();
+
     // Initialize logging
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
@@ -17,9 +22,15 @@ async fn main() {
     // Parse CLI
     let cli = Cli::parse();
 
+    progress::render_downloads(EventBus::global());
+    interrupt::install_handler();
+
     // Run command
-    if let Err(e) = run(cli).await {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        std::process::exit(1);
+    match run(cli).await {
+        Ok(status) => std::process::exit(status.code()),
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
     }
 }
@@ -1,12 +1,26 @@
 use crate::core::error::{FridaMgrError, Result};
+use crate::core::{ensure_dir_exists, interrupt, shell, ProcessExecutor};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use tokio::process::Command;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+
+/// Project directory that receives `--log-output` transcripts.
+pub const RUN_LOG_DIR: &str = "logs";
+
+/// Serial of the device frida-mgr resolved, exported alongside `ANDROID_SERIAL` so wrapped
+/// scripts can tell the selection came from frida-mgr.
+pub const DEVICE_ENV: &str = "FRIDA_MGR_DEVICE";
 
 pub struct VenvExecutor {
     venv_path: PathBuf,
     project_dir: PathBuf,
+    work_dir: Option<PathBuf>,
+    device: Option<String>,
+    detached_stdout_to_stderr: bool,
 }
 
 pub struct CapturedOutput {
@@ -21,6 +35,40 @@ impl VenvExecutor {
         Self {
             venv_path,
             project_dir,
+            work_dir: None,
+            device: None,
+            detached_stdout_to_stderr: false,
+        }
+    }
+
+    /// Run commands from `dir` instead of the project root, so relative arguments keep
+    /// meaning what they meant where frida-mgr was invoked.
+    pub fn with_work_dir(mut self, dir: PathBuf) -> Self {
+        self.work_dir = Some(dir);
+        self
+    }
+
+    fn work_dir(&self) -> &Path {
+        self.work_dir.as_deref().unwrap_or(&self.project_dir)
+    }
+
+    /// Point adb and custom scripts run by the command at the device frida-mgr selected.
+    pub fn with_device(mut self, serial: Option<&str>) -> Self {
+        self.device = serial.map(str::to_string);
+        self
+    }
+
+    /// Send the stdout of `spawn_detached_stdin` commands to stderr, keeping frida-mgr's
+    /// stdout for `--output jsonl` events.
+    pub fn with_detached_stdout_to_stderr(mut self, enabled: bool) -> Self {
+        self.detached_stdout_to_stderr = enabled;
+        self
+    }
+
+    fn device_env(&self) -> Vec<(&'static str, &str)> {
+        match self.device.as_deref() {
+            Some(serial) => vec![("ANDROID_SERIAL", serial), (DEVICE_ENV, serial)],
+            None => Vec::new(),
         }
     }
 
@@ -67,11 +115,14 @@ impl VenvExecutor {
         let original_path = std::env::var("PATH").unwrap_or_default();
         let new_path = format!("{}:{}", bin_dir.display(), original_path);
 
+        // The command shares the terminal and decides for itself what Ctrl-C means.
+        let _deferral = interrupt::defer();
         let status = Command::new(&executable)
             .args(args)
             .env("VIRTUAL_ENV", &self.venv_path)
             .env("PATH", new_path)
-            .current_dir(&self.project_dir)
+            .envs(self.device_env())
+            .current_dir(self.work_dir())
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -84,8 +135,16 @@ impl VenvExecutor {
         Ok(status.code().unwrap_or(1))
     }
 
-    /// Run a command in the virtual environment and capture stdout/stderr.
-    pub async fn run_captured(&self, command: &str, args: &[String]) -> Result<CapturedOutput> {
+    /// Like `run_interactive`, but also copy everything the command prints into `log_path`.
+    ///
+    /// On Unix the command runs under `script(1)` so it still sees a terminal (frida's REPL
+    /// needs one); elsewhere output is teed through pipes and the command runs non-interactively.
+    pub async fn run_interactive_logged(
+        &self,
+        command: &str,
+        args: &[String],
+        log_path: &Path,
+    ) -> Result<i32> {
         if !self.venv_exists() {
             return Err(FridaMgrError::PythonEnv(
                 "Virtual environment not found. Run 'frida-mgr init' first.".to_string(),
@@ -101,21 +160,217 @@ impl VenvExecutor {
             )));
         }
 
+        if let Some(parent) = log_path.parent() {
+            ensure_dir_exists(parent).await?;
+        }
+        println!(
+            "{} Logging output to {}",
+            "ℹ".blue().bold(),
+            log_path.display().to_string().cyan()
+        );
+
         let bin_dir = self.get_venv_bin_dir();
         let original_path = std::env::var("PATH").unwrap_or_default();
         let new_path = format!("{}:{}", bin_dir.display(), original_path);
 
-        let output = Command::new(&executable)
-            .args(args)
+        let mut cmd = match pty_command(&executable, args, log_path) {
+            Some(mut cmd) => {
+                cmd.stdin(Stdio::inherit())
+                    .stdout(Stdio::inherit())
+                    .stderr(Stdio::inherit());
+                cmd
+            }
+            None => {
+                warn_no_pty(command);
+                let mut cmd = Command::new(&executable);
+                cmd.args(args)
+                    .stdin(Stdio::inherit())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+                cmd
+            }
+        };
+
+        let _deferral = interrupt::defer();
+        let mut child = cmd
             .env("VIRTUAL_ENV", &self.venv_path)
             .env("PATH", new_path)
-            .current_dir(&self.project_dir)
-            .output()
-            .await
+            .envs(self.device_env())
+            .current_dir(self.work_dir())
+            .spawn()
             .map_err(|e| {
                 FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
             })?;
 
+        if let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) {
+            let log = Mutex::new(tokio::fs::File::create(log_path).await?);
+            tokio::try_join!(
+                tee(stdout, tokio::io::stdout(), &log),
+                tee(stderr, tokio::io::stderr(), &log)
+            )?;
+        }
+
+        let status = child.wait().await.map_err(|e| {
+            FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
+        })?;
+
+        Ok(status.code().unwrap_or(1))
+    }
+
+    /// Like `run_interactive`, but pass every line the command prints through `translate`
+    /// before it reaches the terminal (and `log_path`, when given).
+    ///
+    /// On Unix the command runs under `script(1)` with its transcript discarded, so it still
+    /// sees a terminal while frida-mgr reads the output.
+    pub async fn run_interactive_translated(
+        &self,
+        command: &str,
+        args: &[String],
+        log_path: Option<&Path>,
+        translate: &(dyn Fn(&str) -> Option<String> + Sync),
+    ) -> Result<i32> {
+        if !self.venv_exists() {
+            return Err(FridaMgrError::PythonEnv(
+                "Virtual environment not found. Run 'frida-mgr init' first.".to_string(),
+            ));
+        }
+
+        let executable = self.get_executable_path(command);
+
+        if !executable.exists() {
+            return Err(FridaMgrError::PythonEnv(format!(
+                "Command '{}' not found in virtual environment. Is it installed?",
+                command
+            )));
+        }
+
+        let log = match log_path {
+            Some(log_path) => {
+                if let Some(parent) = log_path.parent() {
+                    ensure_dir_exists(parent).await?;
+                }
+                println!(
+                    "{} Logging output to {}",
+                    "ℹ".blue().bold(),
+                    log_path.display().to_string().cyan()
+                );
+                Some(Mutex::new(tokio::fs::File::create(log_path).await?))
+            }
+            None => None,
+        };
+
+        let bin_dir = self.get_venv_bin_dir();
+        let original_path = std::env::var("PATH").unwrap_or_default();
+        let new_path = format!("{}:{}", bin_dir.display(), original_path);
+
+        let mut cmd = pty_command(&executable, args, Path::new("/dev/null")).unwrap_or_else(|| {
+            warn_no_pty(command);
+            let mut cmd = Command::new(&executable);
+            cmd.args(args);
+            cmd
+        });
+
+        let _deferral = interrupt::defer();
+        let mut child = cmd
+            .env("VIRTUAL_ENV", &self.venv_path)
+            .env("PATH", new_path)
+            .envs(self.device_env())
+            .current_dir(self.work_dir())
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
+            })?;
+
+        if let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) {
+            tokio::try_join!(
+                tee_translated(stdout, tokio::io::stdout(), log.as_ref(), translate),
+                tee_translated(stderr, tokio::io::stderr(), log.as_ref(), translate)
+            )?;
+        }
+
+        let status = child.wait().await.map_err(|e| {
+            FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
+        })?;
+
+        Ok(status.code().unwrap_or(1))
+    }
+
+    /// Start a command in the virtual environment without waiting for it. Output goes to
+    /// the terminal, but stdin is a pipe so the caller keeps the keyboard.
+    pub fn spawn_detached_stdin(&self, command: &str, args: &[String]) -> Result<Child> {
+        if !self.venv_exists() {
+            return Err(FridaMgrError::PythonEnv(
+                "Virtual environment not found. Run 'frida-mgr init' first.".to_string(),
+            ));
+        }
+
+        let executable = self.get_executable_path(command);
+
+        if !executable.exists() {
+            return Err(FridaMgrError::PythonEnv(format!(
+                "Command '{}' not found in virtual environment. Is it installed?",
+                command
+            )));
+        }
+
+        let bin_dir = self.get_venv_bin_dir();
+        let original_path = std::env::var("PATH").unwrap_or_default();
+        let new_path = format!("{}:{}", bin_dir.display(), original_path);
+
+        Command::new(&executable)
+            .args(args)
+            .env("VIRTUAL_ENV", &self.venv_path)
+            .env("PATH", new_path)
+            .envs(self.device_env())
+            .current_dir(self.work_dir())
+            .stdin(Stdio::piped())
+            .stdout(if self.detached_stdout_to_stderr {
+                Stdio::from(std::io::stderr())
+            } else {
+                Stdio::inherit()
+            })
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
+            })
+    }
+
+    /// Run a command in the virtual environment and capture stdout/stderr.
+    pub async fn run_captured(&self, command: &str, args: &[String]) -> Result<CapturedOutput> {
+        if !self.venv_exists() {
+            return Err(FridaMgrError::PythonEnv(
+                "Virtual environment not found. Run 'frida-mgr init' first.".to_string(),
+            ));
+        }
+
+        let executable = self.get_executable_path(command);
+
+        if !executable.exists() {
+            return Err(FridaMgrError::PythonEnv(format!(
+                "Command '{}' not found in virtual environment. Is it installed?",
+                command
+            )));
+        }
+
+        let bin_dir = self.get_venv_bin_dir();
+        let original_path = std::env::var("PATH").unwrap_or_default();
+        let new_path = format!("{}:{}", bin_dir.display(), original_path);
+
+        let mut cmd = Command::new(&executable);
+        cmd.args(args)
+            .env("VIRTUAL_ENV", &self.venv_path)
+            .env("PATH", new_path)
+            .envs(self.device_env())
+            .current_dir(self.work_dir());
+        let output = ProcessExecutor::output(&mut cmd).await.map_err(|e| {
+            FridaMgrError::CommandFailed(format!("Failed to execute {}: {}", command, e))
+        })?;
+
         Ok(CapturedOutput {
             exit_code: output.status.code().unwrap_or(1),
             stdout: String::from_utf8_lossy(&output.stdout).to_string(),
@@ -142,6 +397,7 @@ impl VenvExecutor {
             std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string())
         };
 
+        let _deferral = interrupt::defer();
         println!("{} Entering virtual environment shell", "→".blue().bold());
         println!("  Type {} to exit", "exit".yellow());
         println!();
@@ -149,8 +405,9 @@ impl VenvExecutor {
         let status = Command::new(&shell)
             .env("VIRTUAL_ENV", &self.venv_path)
             .env("PATH", new_path)
+            .envs(self.device_env())
             .env("PS1", "(venv) $ ") // Custom prompt for bash/zsh
-            .current_dir(&self.project_dir)
+            .current_dir(self.work_dir())
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -199,10 +456,175 @@ impl VenvExecutor {
     }
 }
 
+/// Timestamped transcript path for one logged run, e.g. `logs/frida-20250101-120000.log`.
+pub fn run_log_path(project_dir: &Path, command: &str) -> PathBuf {
+    project_dir.join(RUN_LOG_DIR).join(format!(
+        "{}-{}.log",
+        command,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// `executable args` wrapped in `script(1)` so it runs attached to a pseudo-terminal while
+/// its output is copied to `transcript` and script's own stdout. `None` without `script`.
+fn pty_command(executable: &Path, args: &[String], transcript: &Path) -> Option<Command> {
+    if !cfg!(unix) || !ProcessExecutor::check_command_exists("script") {
+        return None;
+    }
+    let mut cmd = Command::new("script");
+    if cfg!(target_os = "macos") {
+        cmd.arg("-q")
+            .arg("-F")
+            .arg(transcript)
+            .arg(executable)
+            .args(args);
+    } else {
+        let executable = executable.to_string_lossy();
+        let line = shell::command(
+            std::iter::once(executable.as_ref()).chain(args.iter().map(String::as_str)),
+        );
+        cmd.args(["-q", "-f", "-e", "-c"]).arg(line).arg(transcript);
+    }
+    Some(cmd)
+}
+
+fn warn_no_pty(command: &str) {
+    eprintln!(
+        "{} No pty helper available; {} will not run attached to a terminal",
+        "⚠".yellow().bold(),
+        command
+    );
+}
+
+async fn tee<R, W>(mut reader: R, mut terminal: W, log: &Mutex<tokio::fs::File>) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut buf = vec![0u8; 8192];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        terminal.write_all(&buf[..n]).await?;
+        terminal.flush().await?;
+        log.lock().await.write_all(&buf[..n]).await?;
+    }
+    Ok(())
+}
+
+/// How long an unterminated line (a prompt, or a line still being written) is held back
+/// before it is shown untranslated.
+const PARTIAL_LINE_DELAY: Duration = Duration::from_millis(50);
+
+/// `tee`, but complete lines go through `translate` first.
+async fn tee_translated<R, W>(
+    mut reader: R,
+    mut terminal: W,
+    log: Option<&Mutex<tokio::fs::File>>,
+    translate: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut buf = vec![0u8; 8192];
+    let mut pending: Vec<u8> = Vec::new();
+    // The start of the pending line was already shown as-is.
+    let mut continued = false;
+    loop {
+        let read = if pending.is_empty() {
+            Some(reader.read(&mut buf).await?)
+        } else {
+            match tokio::time::timeout(PARTIAL_LINE_DELAY, reader.read(&mut buf)).await {
+                Ok(n) => Some(n?),
+                Err(_) => None,
+            }
+        };
+
+        let mut out = Vec::new();
+        match read {
+            Some(0) => {
+                out.append(&mut pending);
+            }
+            Some(n) => {
+                pending.extend_from_slice(&buf[..n]);
+                while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = pending.drain(..=end).collect();
+                    if continued {
+                        out.extend_from_slice(&line);
+                        continued = false;
+                    } else {
+                        out.extend_from_slice(&translate_line_bytes(&line, translate));
+                    }
+                }
+            }
+            None => {
+                out.append(&mut pending);
+                continued = true;
+            }
+        }
+
+        if !out.is_empty() {
+            terminal.write_all(&out).await?;
+            terminal.flush().await?;
+            if let Some(log) = log {
+                log.lock().await.write_all(&out).await?;
+            }
+        }
+        if read == Some(0) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// One output line (with its line ending) after `translate`.
+fn translate_line_bytes(
+    line: &[u8],
+    translate: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> Vec<u8> {
+    let text = String::from_utf8_lossy(line);
+    let body = text.trim_end_matches(['\r', '\n']);
+    match translate(body) {
+        Some(translated) => {
+            let mut out = translated.into_bytes();
+            out.extend_from_slice(text[body.len()..].as_bytes());
+            out
+        }
+        None => line.to_vec(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn translates_whole_lines_and_keeps_line_endings() {
+        let translate = |line: &str| {
+            line.strip_prefix("bundle")
+                .map(|rest| format!("src{}", rest))
+        };
+        assert_eq!(
+            translate_line_bytes(b"  at bundle.js:3\r\n", &translate),
+            b"  at bundle.js:3\r\n"
+        );
+        assert_eq!(
+            translate_line_bytes(b"bundle.js:3\r\n", &translate),
+            b"src.js:3\r\n"
+        );
+        assert_eq!(
+            translate_line_bytes(b"bundle.js:3", &translate),
+            b"src.js:3"
+        );
+    }
+
     #[test]
     fn test_venv_executor_creation() {
         let project_dir = PathBuf::from("/tmp/test");
@@ -225,4 +647,19 @@ mod tests {
         #[cfg(windows)]
         assert!(path.ends_with(".venv\\Scripts\\frida.exe"));
     }
+
+    #[test]
+    fn device_env_exports_selected_serial() {
+        let executor = VenvExecutor::new(PathBuf::from("/tmp/test"));
+        assert!(executor.device_env().is_empty());
+
+        let executor = executor.with_device(Some("emulator-5554"));
+        assert_eq!(
+            executor.device_env(),
+            vec![
+                ("ANDROID_SERIAL", "emulator-5554"),
+                (DEVICE_ENV, "emulator-5554")
+            ]
+        );
+    }
 }
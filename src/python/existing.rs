@@ -0,0 +1,123 @@
+use crate::core::error::{FridaMgrError, Result};
+use crate::python::pypi::python_satisfies;
+use crate::python::uv::{versions_compatible, UvManager};
+use std::path::Path;
+
+/// Python setup already present in a directory before `frida-mgr init` runs.
+#[derive(Debug, Default)]
+pub struct ExistingPythonEnv {
+    /// Interpreter version of an existing `.venv` (from `pyvenv.cfg`).
+    pub venv_python: Option<String>,
+    /// `project.requires-python` from an existing `pyproject.toml`.
+    pub requires_python: Option<String>,
+    pub has_pyproject: bool,
+}
+
+impl ExistingPythonEnv {
+    pub async fn detect(dir: &Path) -> Result<Self> {
+        let mut env = Self::default();
+
+        if dir.join(".venv").is_dir() {
+            env.venv_python = UvManager::new(dir.to_path_buf())
+                .get_venv_python_version()
+                .await?;
+        }
+
+        let pyproject = dir.join("pyproject.toml");
+        if pyproject.is_file() {
+            env.has_pyproject = true;
+            let content = tokio::fs::read_to_string(&pyproject).await?;
+            env.requires_python = requires_python(&content);
+        }
+
+        Ok(env)
+    }
+
+    /// Pick the Python version for the project: an explicit request must match the existing
+    /// `.venv`; otherwise the `.venv` interpreter wins over `fallback`. The result must
+    /// satisfy `requires-python` when a pyproject declares one.
+    pub fn choose_python(&self, requested: Option<&str>, fallback: &str) -> Result<String> {
+        let chosen = match (requested, self.venv_python.as_deref()) {
+            (Some(requested), Some(found)) => {
+                if !versions_compatible(requested, found) {
+                    return Err(FridaMgrError::PythonEnv(format!(
+                        "Existing .venv uses Python {}, but --python {} was requested. Omit --python to reuse it, or remove .venv first.",
+                        found, requested
+                    )));
+                }
+                requested.to_string()
+            }
+            (Some(requested), None) => requested.to_string(),
+            (None, Some(found)) => major_minor(found),
+            (None, None) => fallback.to_string(),
+        };
+
+        if let Some(spec) = self.requires_python.as_deref() {
+            if !python_satisfies(spec, &chosen) {
+                return Err(FridaMgrError::PythonEnv(format!(
+                    "pyproject.toml requires-python '{}' is not satisfied by Python {}; pass --python with a compatible version",
+                    spec, chosen
+                )));
+            }
+        }
+
+        Ok(chosen)
+    }
+}
+
+/// `project.requires-python` from pyproject.toml content, if declared.
+pub fn requires_python(pyproject: &str) -> Option<String> {
+    let value: toml::Value = toml::from_str(pyproject).ok()?;
+    value
+        .get("project")?
+        .get("requires-python")?
+        .as_str()
+        .map(|s| s.trim().to_string())
+}
+
+fn major_minor(version: &str) -> String {
+    version
+        .trim()
+        .split('.')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_requires_python() {
+        let content = r#"
+[project]
+name = "research"
+requires-python = ">=3.10"
+"#;
+        assert_eq!(requires_python(content).as_deref(), Some(">=3.10"));
+        assert!(requires_python("[tool.uv]\n").is_none());
+    }
+
+    #[test]
+    fn existing_venv_version_is_reused() {
+        let env = ExistingPythonEnv {
+            venv_python: Some("3.12.4".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(env.choose_python(None, "3.11").unwrap(), "3.12");
+        assert_eq!(env.choose_python(Some("3.12"), "3.11").unwrap(), "3.12");
+        assert!(env.choose_python(Some("3.11"), "3.11").is_err());
+    }
+
+    #[test]
+    fn requires_python_is_enforced() {
+        let env = ExistingPythonEnv {
+            requires_python: Some(">=3.12".to_string()),
+            has_pyproject: true,
+            ..Default::default()
+        };
+        assert!(env.choose_python(None, "3.11").is_err());
+        assert_eq!(env.choose_python(Some("3.13"), "3.11").unwrap(), "3.13");
+    }
+}
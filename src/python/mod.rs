@@ -1,7 +1,9 @@
 pub mod executor;
+pub mod existing;
 pub mod pypi;
 pub mod uv;
 
 pub use executor::VenvExecutor;
+pub use existing::ExistingPythonEnv;
 pub use pypi::PypiClient;
 pub use uv::UvManager;
@@ -9,9 +9,11 @@ pub struct PypiClient {
 
 impl PypiClient {
     pub fn new() -> Self {
-        Self {
-            http: HttpClient::new(),
-        }
+        Self::with_http(HttpClient::new())
+    }
+
+    pub fn with_http(http: HttpClient) -> Self {
+        Self { http }
     }
 
     pub async fn requires_python(&self, package: &str, version: &str) -> Result<Option<String>> {
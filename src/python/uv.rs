@@ -1,21 +1,32 @@
 use crate::core::error::{FridaMgrError, Result};
-use crate::core::ProcessExecutor;
+use crate::core::{interrupt, CommandRunner, EventBus, ProcessExecutor};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::Arc;
 use tokio::process::Command;
 
 pub struct UvManager {
     project_dir: PathBuf,
+    runner: Arc<dyn CommandRunner>,
 }
 
 impl UvManager {
     pub fn new(project_dir: PathBuf) -> Self {
-        Self { project_dir }
+        Self {
+            project_dir,
+            runner: Arc::new(ProcessExecutor),
+        }
+    }
+
+    /// Run uv's non-interactive commands through `runner` instead of spawning uv.
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
     }
 
-    pub fn check_installed() -> Result<()> {
-        if !ProcessExecutor::check_command_exists("uv") {
+    pub fn check_installed(&self) -> Result<()> {
+        if !self.runner.exists("uv") {
             return Err(FridaMgrError::PythonEnv(
                 "uv is not installed. Please install it first: https://github.com/astral-sh/uv"
                     .to_string(),
@@ -29,7 +40,7 @@ impl UvManager {
     }
 
     pub async fn ensure_venv(&self, python_version: &str, recreate: bool) -> Result<()> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let venv_path = self.project_dir.join(".venv");
 
@@ -70,16 +81,18 @@ impl UvManager {
             python_version.cyan()
         );
 
-        let success = ProcessExecutor::execute_with_status(
-            "uv",
-            &[
-                "venv",
-                "--python",
-                python_version,
-                venv_path.to_str().unwrap(),
-            ],
-        )
-        .await?;
+        let success = self
+            .runner
+            .status(
+                "uv",
+                &[
+                    "venv",
+                    "--python",
+                    python_version,
+                    venv_path.to_str().unwrap(),
+                ],
+            )
+            .await?;
 
         if !success {
             return Err(FridaMgrError::PythonEnv(format!(
@@ -102,7 +115,7 @@ impl UvManager {
             return Ok(());
         }
 
-        Self::check_installed()?;
+        self.check_installed()?;
         let python_path = self.get_python_path()?;
 
         println!(
@@ -121,7 +134,7 @@ impl UvManager {
         args.extend(packages.iter().cloned());
 
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = ProcessExecutor::execute("uv", &args_ref, None).await?;
+        let output = self.runner.run("uv", &args_ref).await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -149,7 +162,7 @@ impl UvManager {
         tools_version: Option<&str>,
         allow_tools_unpinned_fallback: bool,
     ) -> Result<()> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let python_path = self.get_python_path()?;
 
@@ -161,14 +174,19 @@ impl UvManager {
             tools_label.cyan()
         );
 
-        install_frida_packages(
-            &python_path,
-            frida_version,
-            tools_version,
-            false,
-            allow_tools_unpinned_fallback,
-        )
-        .await?;
+        EventBus::global()
+            .step(
+                format!("install frida {}", frida_version),
+                install_frida_packages(
+                    self.runner.as_ref(),
+                    &python_path,
+                    frida_version,
+                    tools_version,
+                    false,
+                    allow_tools_unpinned_fallback,
+                ),
+            )
+            .await?;
 
         println!(
             "{} Frida packages installed successfully",
@@ -184,7 +202,7 @@ impl UvManager {
         tools_version: Option<&str>,
         allow_tools_unpinned_fallback: bool,
     ) -> Result<()> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let python_path = self.get_python_path()?;
 
@@ -196,14 +214,19 @@ impl UvManager {
             tools_label.cyan()
         );
 
-        install_frida_packages(
-            &python_path,
-            frida_version,
-            tools_version,
-            true,
-            allow_tools_unpinned_fallback,
-        )
-        .await?;
+        EventBus::global()
+            .step(
+                format!("install frida {}", frida_version),
+                install_frida_packages(
+                    self.runner.as_ref(),
+                    &python_path,
+                    frida_version,
+                    tools_version,
+                    true,
+                    allow_tools_unpinned_fallback,
+                ),
+            )
+            .await?;
 
         println!("{} Frida packages upgraded", "✓".green().bold());
 
@@ -215,7 +238,7 @@ impl UvManager {
         objection_version: Option<&str>,
         allow_unpinned_fallback: bool,
     ) -> Result<()> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let python_path = self.get_python_path()?;
 
@@ -227,6 +250,7 @@ impl UvManager {
         );
 
         install_optional_pinned_package(
+            self.runner.as_ref(),
             &python_path,
             "objection",
             objection_version,
@@ -245,7 +269,7 @@ impl UvManager {
         objection_version: Option<&str>,
         allow_unpinned_fallback: bool,
     ) -> Result<()> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let python_path = self.get_python_path()?;
 
@@ -257,6 +281,7 @@ impl UvManager {
         );
 
         install_optional_pinned_package(
+            self.runner.as_ref(),
             &python_path,
             "objection",
             objection_version,
@@ -273,17 +298,19 @@ impl UvManager {
     pub async fn get_installed_version(&self, package: &str) -> Result<Option<String>> {
         let python_path = self.get_python_path()?;
 
-        let output = ProcessExecutor::execute_with_output(
-            "uv",
-            &[
-                "pip",
-                "show",
-                "--python",
-                python_path.to_str().unwrap(),
-                package,
-            ],
-        )
-        .await;
+        let output = self
+            .runner
+            .stdout(
+                "uv",
+                &[
+                    "pip",
+                    "show",
+                    "--python",
+                    python_path.to_str().unwrap(),
+                    package,
+                ],
+            )
+            .await;
 
         match output {
             Ok(output) => {
@@ -299,6 +326,60 @@ impl UvManager {
         }
     }
 
+    /// Exact pins of everything installed in the venv (`uv pip freeze`), usable as a
+    /// requirements lockfile.
+    pub async fn freeze(&self) -> Result<String> {
+        self.check_installed()?;
+        let python_path = self.get_python_path()?;
+
+        self.runner
+            .stdout(
+                "uv",
+                &["pip", "freeze", "--python", python_path.to_str().unwrap()],
+            )
+            .await
+    }
+
+    /// Install exactly the pins in a requirements lockfile (see `freeze`).
+    pub async fn install_requirements(&self, lockfile: &Path) -> Result<()> {
+        self.check_installed()?;
+        let python_path = self.get_python_path()?;
+
+        println!(
+            "{} Installing locked Python packages from {}",
+            "⚙".blue().bold(),
+            lockfile.display().to_string().yellow()
+        );
+
+        let output = self
+            .runner
+            .run(
+                "uv",
+                &[
+                    "pip",
+                    "sync",
+                    "--python",
+                    python_path.to_str().unwrap(),
+                    lockfile.to_str().unwrap(),
+                ],
+            )
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !stderr.is_empty() {
+                eprintln!("{}", stderr);
+            }
+            return Err(FridaMgrError::PythonEnv(
+                "Failed to install locked Python packages. See output above for details."
+                    .to_string(),
+            ));
+        }
+
+        println!("{} Locked Python packages installed", "✓".green().bold());
+        Ok(())
+    }
+
     fn get_python_path(&self) -> Result<PathBuf> {
         let venv_path = self.project_dir.join(".venv");
 
@@ -332,8 +413,9 @@ impl UvManager {
     }
 
     pub async fn run_uv_interactive(&self, args: &[String]) -> Result<i32> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
+        let _deferral = interrupt::defer();
         let status = Command::new("uv")
             .args(args)
             .current_dir(&self.project_dir)
@@ -348,7 +430,7 @@ impl UvManager {
     }
 
     pub async fn run_uv_pip_interactive(&self, args: &[String]) -> Result<i32> {
-        Self::check_installed()?;
+        self.check_installed()?;
 
         let mut uv_args: Vec<String> = vec!["pip".to_string()];
 
@@ -363,7 +445,7 @@ impl UvManager {
         self.run_uv_interactive(&uv_args).await
     }
 
-    async fn get_venv_python_version(&self) -> Result<Option<String>> {
+    pub async fn get_venv_python_version(&self) -> Result<Option<String>> {
         let cfg_path = self.get_venv_path().join("pyvenv.cfg");
         if !cfg_path.exists() {
             return Ok(None);
@@ -384,7 +466,8 @@ impl UvManager {
 }
 
 async fn install_frida_packages(
-    python_path: &PathBuf,
+    runner: &dyn CommandRunner,
+    python_path: &Path,
     frida_version: &str,
     tools_version: Option<&str>,
     upgrade: bool,
@@ -412,7 +495,7 @@ async fn install_frida_packages(
         }
 
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = ProcessExecutor::execute("uv", &args_ref, None).await?;
+        let output = runner.run("uv", &args_ref).await?;
 
         if output.status.success() {
             return Ok(());
@@ -464,7 +547,8 @@ async fn install_frida_packages(
 }
 
 async fn install_optional_pinned_package(
-    python_path: &PathBuf,
+    runner: &dyn CommandRunner,
+    python_path: &Path,
     package: &str,
     pinned_version: Option<&str>,
     upgrade: bool,
@@ -491,7 +575,7 @@ async fn install_optional_pinned_package(
         }
 
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = ProcessExecutor::execute("uv", &args_ref, None).await?;
+        let output = runner.run("uv", &args_ref).await?;
 
         if output.status.success() {
             return Ok(());
@@ -573,7 +657,7 @@ fn extract_version_parts(input: &str) -> Vec<u32> {
     parts
 }
 
-fn versions_compatible(requested: &str, found: &str) -> bool {
+pub fn versions_compatible(requested: &str, found: &str) -> bool {
     let req = extract_version_parts(requested);
     let got = extract_version_parts(found);
 